@@ -1,3 +1,5 @@
+mod tool;
+
 use proc_macro::TokenStream;
 use quote::{quote, ToTokens};
 use syn::{
@@ -124,7 +126,7 @@ pub fn byot(args: TokenStream, item: TokenStream) -> TokenStream {
 
     // Generate return type based on stream flag
     let return_type = if bounds_args.stream {
-        quote! { Result<::std::pin::Pin<Box<dyn ::futures::Stream<Item = Result<R, OpenAIError>> + Send>>, OpenAIError> }
+        quote! { Result<crate::runtime::BoxStream<'static, Result<R, OpenAIError>>, OpenAIError> }
     } else {
         quote! { Result<R, OpenAIError> }
     };
@@ -139,3 +141,21 @@ pub fn byot(args: TokenStream, item: TokenStream) -> TokenStream {
 
     expanded.into()
 }
+
+/// Turn an async function or a struct of parameters into a tool: see `async_openai::tools::tool`.
+#[proc_macro_attribute]
+pub fn tool(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as tool::ToolArgs);
+    let expanded = match parse_macro_input!(item as syn::Item) {
+        syn::Item::Fn(input) => tool::expand(args, input),
+        syn::Item::Struct(input) => tool::expand_struct(args, input),
+        item => Err(syn::Error::new_spanned(
+            item,
+            "#[tool] applies to async functions and structs",
+        )),
+    };
+
+    expanded
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
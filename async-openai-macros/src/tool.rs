@@ -0,0 +1,301 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token::Comma,
+    Attribute, Expr, ExprLit, Fields, FnArg, Ident, ItemFn, ItemStruct, Lit, LitStr, Meta, Pat,
+    Type,
+};
+
+// Parse attribute arguments like #[tool(name = "get_weather")]
+pub(crate) struct ToolArgs {
+    name: Option<String>,
+}
+
+impl Parse for ToolArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut name = None;
+        let vars = Punctuated::<syn::MetaNameValue, Comma>::parse_terminated(input)?;
+
+        for var in vars {
+            match var.path.get_ident().map(Ident::to_string).as_deref() {
+                Some("name") => name = Some(lit_str(&var.value)?.value()),
+                _ => return Err(syn::Error::new_spanned(var.path, "unknown tool argument")),
+            }
+        }
+
+        Ok(ToolArgs { name })
+    }
+}
+
+fn lit_str(expr: &Expr) -> syn::Result<LitStr> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) => Ok(lit.clone()),
+        _ => Err(syn::Error::new_spanned(expr, "expected a string literal")),
+    }
+}
+
+/// Lines of the doc comments of a function or a struct.
+fn doc_lines(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(nv) if nv.path.is_ident("doc") => lit_str(&nv.value).ok(),
+            _ => None,
+        })
+        .flat_map(|doc| {
+            doc.value()
+                .lines()
+                .map(|line| line.strip_prefix(' ').unwrap_or(line).to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Split doc comments into the function description and the descriptions of its arguments,
+/// listed under an `# Arguments` heading as in rustdoc:
+///
+/// ```text
+/// # Arguments
+///
+/// * `location` - The city and state, e.g. San Francisco, CA
+/// ```
+fn parse_docs(lines: &[String]) -> (String, Vec<(String, String)>) {
+    let mut description = vec![];
+    let mut arguments: Vec<(String, String)> = vec![];
+    let mut in_arguments = false;
+
+    for line in lines {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with('#') {
+            in_arguments = trimmed.trim_start_matches('#').trim() == "Arguments";
+            if in_arguments {
+                continue;
+            }
+        }
+
+        if !in_arguments {
+            description.push(line.as_str());
+            continue;
+        }
+
+        let item = trimmed
+            .strip_prefix('*')
+            .or_else(|| trimmed.strip_prefix('-'))
+            .map(str::trim_start);
+
+        match item.and_then(|item| item.strip_prefix('`')?.split_once('`')) {
+            Some((name, rest)) => {
+                let rest = rest.trim_start();
+                let rest = rest
+                    .strip_prefix('-')
+                    .or_else(|| rest.strip_prefix(':'))
+                    .unwrap_or(rest);
+                arguments.push((name.to_string(), rest.trim().to_string()));
+            }
+            // continuation of the previous argument description
+            None if !trimmed.is_empty() => {
+                if let Some((_, text)) = arguments.last_mut() {
+                    text.push(' ');
+                    text.push_str(trimmed);
+                }
+            }
+            None => {}
+        }
+    }
+
+    (description.join("\n").trim().to_string(), arguments)
+}
+
+/// `get_weather` -> `GetWeatherTool`
+fn tool_ident(fn_name: &Ident) -> Ident {
+    let camel: String = fn_name
+        .to_string()
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect();
+
+    format_ident!("{}Tool", camel, span = fn_name.span())
+}
+
+/// `GetWeather` -> `get_weather`
+fn snake_case(ident: &Ident) -> String {
+    let mut name = String::new();
+    for (i, c) in ident.to_string().chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                name.push('_');
+            }
+            name.extend(c.to_lowercase());
+        } else {
+            name.push(c);
+        }
+    }
+    name
+}
+
+fn description_tokens(description: String) -> TokenStream {
+    if description.is_empty() {
+        quote! { None }
+    } else {
+        quote! { Some(#description.into()) }
+    }
+}
+
+pub(crate) fn expand(args: ToolArgs, input: ItemFn) -> syn::Result<TokenStream> {
+    if input.sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(
+            input.sig.fn_token,
+            "#[tool] requires an async function",
+        ));
+    }
+
+    if !input.sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.sig.generics,
+            "#[tool] functions cannot be generic",
+        ));
+    }
+
+    let fn_name = &input.sig.ident;
+    let name = args.name.unwrap_or_else(|| fn_name.to_string());
+    let tool = tool_ident(fn_name);
+    let vis = &input.vis;
+
+    let (description, argument_docs) = parse_docs(&doc_lines(&input.attrs));
+    let description = description_tokens(description);
+
+    let mut fields: Vec<(Ident, Type)> = vec![];
+    for arg in &input.sig.inputs {
+        match arg {
+            FnArg::Receiver(receiver) => {
+                return Err(syn::Error::new_spanned(
+                    receiver,
+                    "#[tool] functions cannot take self",
+                ))
+            }
+            FnArg::Typed(pat_type) => match &*pat_type.pat {
+                Pat::Ident(pat_ident) => {
+                    fields.push((pat_ident.ident.clone(), (*pat_type.ty).clone()))
+                }
+                pat => {
+                    return Err(syn::Error::new_spanned(
+                        pat,
+                        "#[tool] arguments must be plain identifiers",
+                    ))
+                }
+            },
+        }
+    }
+
+    let field_defs = fields.iter().map(|(ident, ty)| {
+        let doc = argument_docs
+            .iter()
+            .find(|(name, _)| ident == name)
+            .map(|(_, doc)| quote! { #[doc = #doc] });
+        quote! { #doc #ident: #ty }
+    });
+    let field_names: Vec<&Ident> = fields.iter().map(|(ident, _)| ident).collect();
+
+    let tool_doc = LitStr::new(
+        &format!("Tool calling [`{fn_name}`], register it with `ToolSet::register`."),
+        Span::call_site(),
+    );
+
+    Ok(quote! {
+        #input
+
+        #[doc = #tool_doc]
+        #[derive(Debug, Clone, Copy, Default)]
+        #vis struct #tool;
+
+        const _: () = {
+            use ::async_openai::tools::__private::{schemars, serde};
+
+            #[derive(serde::Deserialize, schemars::JsonSchema)]
+            #[serde(crate = "::async_openai::tools::__private::serde")]
+            #[schemars(crate = "::async_openai::tools::__private::schemars")]
+            struct Arguments {
+                #(#field_defs),*
+            }
+
+            impl ::async_openai::tools::Tool for #tool {
+                fn definition(&self) -> ::async_openai::types::FunctionObject {
+                    ::async_openai::types::FunctionObject {
+                        name: #name.into(),
+                        description: #description,
+                        parameters: Some(::async_openai::tools::__private::parameters::<Arguments>()),
+                        strict: None,
+                    }
+                }
+
+                fn call<'a>(
+                    &'a self,
+                    arguments: &'a str,
+                ) -> ::async_openai::tools::__private::BoxFuture<
+                    'a,
+                    Result<String, ::async_openai::error::OpenAIError>,
+                > {
+                    Box::pin(async move {
+                        let Arguments { #(#field_names),* } =
+                            ::async_openai::tools::__private::arguments(#name, arguments)?;
+                        ::async_openai::tools::__private::output(#name, #fn_name(#(#field_names),*).await)
+                    })
+                }
+            }
+        };
+    })
+}
+
+/// `#[tool]` on a struct: its fields are the parameters of the function, deserialized from the
+/// arguments of the model, and it implements `ToolParameters`.
+pub(crate) fn expand_struct(args: ToolArgs, input: ItemStruct) -> syn::Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "#[tool] structs cannot be generic",
+        ));
+    }
+
+    if !matches!(input.fields, Fields::Named(_)) {
+        return Err(syn::Error::new_spanned(
+            &input.fields,
+            "#[tool] structs must have named fields",
+        ));
+    }
+
+    let ident = &input.ident;
+    let name = args.name.unwrap_or_else(|| snake_case(ident));
+    let (description, _) = parse_docs(&doc_lines(&input.attrs));
+    let description = description_tokens(description);
+
+    Ok(quote! {
+        #[derive(::async_openai::tools::__private::serde::Deserialize)]
+        #[derive(::async_openai::tools::__private::schemars::JsonSchema)]
+        #[serde(crate = "::async_openai::tools::__private::serde")]
+        #[schemars(crate = "::async_openai::tools::__private::schemars")]
+        #input
+
+        impl ::async_openai::tools::ToolParameters for #ident {
+            fn definition() -> ::async_openai::types::FunctionObject {
+                ::async_openai::types::FunctionObject {
+                    name: #name.into(),
+                    description: #description,
+                    parameters: Some(::async_openai::tools::__private::parameters::<Self>()),
+                    strict: None,
+                }
+            }
+        }
+    })
+}
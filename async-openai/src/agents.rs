@@ -0,0 +1,363 @@
+//! Agent loops driving the built-in tools of the [Responses](crate::Responses) API, and your
+//! own tools with an [Agent] (feature `tools`).
+//!
+//! [ComputerUseSession] runs the [computer use](https://platform.openai.com/docs/guides/tools-computer-use)
+//! loop: the actions requested by the model are performed by your [ComputerActions],
+//! and a screenshot of the result is sent back, until the model answers.
+//!
+//! ```no_run
+//! use async_openai::{
+//!     agents::{ComputerActions, ComputerUseSession},
+//!     types::{ComputerUsePreviewTool, CreateResponseArgs, Tool},
+//!     Client,
+//! };
+//! # use async_openai::{error::OpenAIError, types::Coordinate};
+//! # use bytes::Bytes;
+//! # struct Browser;
+//! # impl ComputerActions for Browser {
+//! #     async fn screenshot(&mut self) -> Result<Bytes, OpenAIError> { todo!() }
+//! #     async fn click(&mut self, x: i32, y: i32, button: &str) -> Result<(), OpenAIError> { todo!() }
+//! #     async fn double_click(&mut self, x: i32, y: i32) -> Result<(), OpenAIError> { todo!() }
+//! #     async fn scroll(&mut self, x: i32, y: i32, scroll_x: i32, scroll_y: i32) -> Result<(), OpenAIError> { todo!() }
+//! #     async fn type_text(&mut self, text: &str) -> Result<(), OpenAIError> { todo!() }
+//! #     async fn keypress(&mut self, keys: &[String]) -> Result<(), OpenAIError> { todo!() }
+//! #     async fn move_to(&mut self, x: i32, y: i32) -> Result<(), OpenAIError> { todo!() }
+//! #     async fn drag(&mut self, path: &[Coordinate]) -> Result<(), OpenAIError> { todo!() }
+//! # }
+//!
+//! # tokio_test::block_on(async {
+//! let client = Client::new();
+//!
+//! let request = CreateResponseArgs::default()
+//!     .model("computer-use-preview")
+//!     .input("Find the opening hours of the library on its website")
+//!     .tools([Tool::ComputerUsePreview(ComputerUsePreviewTool {
+//!         display_width: 1024,
+//!         display_height: 768,
+//!         environment: "browser".into(),
+//!     })])
+//!     .build()?;
+//!
+//! let mut session = ComputerUseSession::new(&client, Browser);
+//! let response = session.run(request).await?;
+//! println!("{}", response.output_text());
+//! # Ok::<(), OpenAIError>(())
+//! # });
+//! ```
+#[cfg(feature = "tools")]
+mod agent;
+
+use std::{future::Future, time::Duration};
+
+use base64::{engine::general_purpose, Engine as _};
+use bytes::Bytes;
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    runtime::{self, MaybeSend},
+    types::{
+        ComputerAction, ComputerCallOutput, ComputerScreenshot, ComputerToolCall, Coordinate,
+        CreateResponse, Input, InputMessage, InputRole, Item, Response, SafetyCheck, Truncation,
+    },
+    Client,
+};
+
+#[cfg(feature = "tools")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tools")))]
+pub use agent::{Agent, AgentEvent, AgentOutcome, AgentRun, AgentStopReason, Memory};
+
+/// Turns of a [ComputerUseSession] unless set with [ComputerUseSession::with_max_turns].
+const DEFAULT_MAX_TURNS: usize = 50;
+
+/// Duration of the `wait` action unless overridden by [ComputerActions::wait].
+const WAIT: Duration = Duration::from_secs(1);
+
+/// A computer controlled by the model, e.g. a browser or a virtual machine.
+///
+/// Coordinates are in pixels of the display given in the computer use tool.
+pub trait ComputerActions: MaybeSend {
+    /// A screenshot of the display, as PNG.
+    fn screenshot(&mut self) -> impl Future<Output = Result<Bytes, OpenAIError>> + MaybeSend;
+
+    /// Click with `button`: `left`, `right`, `wheel`, `back` or `forward`.
+    fn click(
+        &mut self,
+        x: i32,
+        y: i32,
+        button: &str,
+    ) -> impl Future<Output = Result<(), OpenAIError>> + MaybeSend;
+
+    fn double_click(
+        &mut self,
+        x: i32,
+        y: i32,
+    ) -> impl Future<Output = Result<(), OpenAIError>> + MaybeSend;
+
+    /// Scroll by `scroll_x` and `scroll_y` pixels, with the mouse at `x` and `y`.
+    fn scroll(
+        &mut self,
+        x: i32,
+        y: i32,
+        scroll_x: i32,
+        scroll_y: i32,
+    ) -> impl Future<Output = Result<(), OpenAIError>> + MaybeSend;
+
+    fn type_text(
+        &mut self,
+        text: &str,
+    ) -> impl Future<Output = Result<(), OpenAIError>> + MaybeSend;
+
+    /// Press the combination of `keys`, e.g. `["CTRL", "C"]`.
+    fn keypress(
+        &mut self,
+        keys: &[String],
+    ) -> impl Future<Output = Result<(), OpenAIError>> + MaybeSend;
+
+    fn move_to(
+        &mut self,
+        x: i32,
+        y: i32,
+    ) -> impl Future<Output = Result<(), OpenAIError>> + MaybeSend;
+
+    /// Drag the mouse along `path`, from its first to its last point.
+    fn drag(
+        &mut self,
+        path: &[Coordinate],
+    ) -> impl Future<Output = Result<(), OpenAIError>> + MaybeSend;
+
+    /// Wait for the display to change, e.g. for a page to load. Waits 1 second by default.
+    fn wait(&mut self) -> impl Future<Output = Result<(), OpenAIError>> + MaybeSend {
+        async {
+            runtime::sleep(WAIT).await;
+            Ok(())
+        }
+    }
+
+    /// Whether to proceed with an action despite the safety `checks` of the model,
+    /// e.g. after asking the user. The session stops when they are not acknowledged,
+    /// which is the default.
+    fn acknowledge_safety_checks(
+        &mut self,
+        _checks: &[SafetyCheck],
+    ) -> impl Future<Output = bool> + MaybeSend {
+        async { false }
+    }
+}
+
+/// Run a computer use request until the model answers, performing the actions of its
+/// computer calls with a [ComputerActions].
+pub struct ComputerUseSession<'c, C: Config, A: ComputerActions> {
+    client: &'c Client<C>,
+    computer: A,
+    max_turns: usize,
+}
+
+impl<'c, C: Config, A: ComputerActions> ComputerUseSession<'c, C, A> {
+    pub fn new(client: &'c Client<C>, computer: A) -> Self {
+        Self {
+            client,
+            computer,
+            max_turns: DEFAULT_MAX_TURNS,
+        }
+    }
+
+    /// Maximum number of responses created after the first one, 50 by default.
+    pub fn with_max_turns(mut self, max_turns: usize) -> Self {
+        self.max_turns = max_turns;
+        self
+    }
+
+    pub fn computer(&mut self) -> &mut A {
+        &mut self.computer
+    }
+
+    pub fn into_computer(self) -> A {
+        self.computer
+    }
+
+    /// Create a response for `request`, which must include the computer use tool, then
+    /// perform its computer calls and send their screenshots back, until a response has
+    /// no computer call.
+    ///
+    /// Turns are chained with `previous_response_id`, or by sending the whole conversation
+    /// back when `store` is false. `truncation` defaults to `auto`, as required by the tool.
+    ///
+    /// Returns the last response, with its computer calls not performed, when the safety
+    /// checks of a call are not acknowledged, or when the maximum number of turns is reached.
+    pub async fn run(&mut self, mut request: CreateResponse) -> Result<Response, OpenAIError> {
+        request.stream = None;
+        request.truncation.get_or_insert(Truncation::Auto);
+
+        let mut response = self.client.responses().create(request.clone()).await?;
+
+        for _ in 0..self.max_turns {
+            let calls: Vec<ComputerToolCall> = response
+                .output
+                .iter()
+                .filter_map(|item| match item {
+                    Item::ComputerCall(call) => Some(call.clone()),
+                    _ => None,
+                })
+                .collect();
+
+            if calls.is_empty() {
+                return Ok(response);
+            }
+
+            let mut outputs = Vec::with_capacity(calls.len());
+            for call in calls {
+                let checks = call.pending_safety_checks;
+                if !checks.is_empty() && !self.computer.acknowledge_safety_checks(&checks).await {
+                    return Ok(response);
+                }
+
+                self.perform(&call.action).await?;
+                let screenshot = self.computer.screenshot().await?;
+
+                outputs.push(Item::ComputerCallOutput(ComputerCallOutput {
+                    id: None,
+                    call_id: call.call_id,
+                    output: ComputerScreenshot {
+                        image_url: Some(format!(
+                            "data:image/png;base64,{}",
+                            general_purpose::STANDARD.encode(&screenshot)
+                        )),
+                        file_id: None,
+                    },
+                    acknowledged_safety_checks: (!checks.is_empty()).then_some(checks),
+                    status: None,
+                }));
+            }
+
+            follow_up(&mut request, response, outputs);
+            response = self.client.responses().create(request.clone()).await?;
+        }
+
+        Ok(response)
+    }
+
+    async fn perform(&mut self, action: &ComputerAction) -> Result<(), OpenAIError> {
+        let computer = &mut self.computer;
+        match action {
+            ComputerAction::Click { button, x, y } => computer.click(*x, *y, button).await,
+            ComputerAction::DoubleClick { x, y } => computer.double_click(*x, *y).await,
+            ComputerAction::Drag { path } => computer.drag(path).await,
+            ComputerAction::Keypress { keys } => computer.keypress(keys).await,
+            ComputerAction::Move { x, y } => computer.move_to(*x, *y).await,
+            // a screenshot is taken after every action
+            ComputerAction::Screenshot => Ok(()),
+            ComputerAction::Scroll {
+                x,
+                y,
+                scroll_x,
+                scroll_y,
+            } => computer.scroll(*x, *y, *scroll_x, *scroll_y).await,
+            ComputerAction::Type { text } => computer.type_text(text).await,
+            ComputerAction::Wait => computer.wait().await,
+        }
+    }
+}
+
+/// Make `request` the next turn after `response`, sending `outputs` to the model.
+///
+/// Turns are chained with `previous_response_id`, or by sending the whole conversation
+/// back when `store` is false.
+pub(crate) fn follow_up(request: &mut CreateResponse, response: Response, outputs: Vec<Item>) {
+    if request.store == Some(false) {
+        let mut items = match std::mem::take(&mut request.input) {
+            Input::Text(text) => vec![InputMessage::new(InputRole::User, text).into()],
+            Input::Items(items) => items,
+        };
+        items.extend(response.into_input_items());
+        items.extend(outputs);
+        request.input = Input::Items(items);
+    } else {
+        request.previous_response_id = Some(response.id);
+        request.input = Input::Items(outputs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records the actions performed.
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    impl ComputerActions for Recorder {
+        async fn screenshot(&mut self) -> Result<Bytes, OpenAIError> {
+            Ok(Bytes::new())
+        }
+
+        async fn click(&mut self, x: i32, y: i32, button: &str) -> Result<(), OpenAIError> {
+            self.0.push(format!("click {button} {x} {y}"));
+            Ok(())
+        }
+
+        async fn double_click(&mut self, x: i32, y: i32) -> Result<(), OpenAIError> {
+            self.0.push(format!("double_click {x} {y}"));
+            Ok(())
+        }
+
+        async fn scroll(
+            &mut self,
+            x: i32,
+            y: i32,
+            scroll_x: i32,
+            scroll_y: i32,
+        ) -> Result<(), OpenAIError> {
+            self.0.push(format!("scroll {x} {y} {scroll_x} {scroll_y}"));
+            Ok(())
+        }
+
+        async fn type_text(&mut self, text: &str) -> Result<(), OpenAIError> {
+            self.0.push(format!("type {text}"));
+            Ok(())
+        }
+
+        async fn keypress(&mut self, keys: &[String]) -> Result<(), OpenAIError> {
+            self.0.push(format!("keypress {}", keys.join("+")));
+            Ok(())
+        }
+
+        async fn move_to(&mut self, x: i32, y: i32) -> Result<(), OpenAIError> {
+            self.0.push(format!("move {x} {y}"));
+            Ok(())
+        }
+
+        async fn drag(&mut self, path: &[Coordinate]) -> Result<(), OpenAIError> {
+            self.0.push(format!("drag {}", path.len()));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn perform_actions() {
+        let client = Client::new();
+        let mut session = ComputerUseSession::new(&client, Recorder::default());
+
+        let actions: Vec<ComputerAction> = serde_json::from_value(serde_json::json!([
+            {"type": "click", "button": "left", "x": 1, "y": 2},
+            {"type": "scroll", "x": 1, "y": 2, "scroll_x": 0, "scroll_y": 100},
+            {"type": "keypress", "keys": ["CTRL", "C"]},
+            {"type": "screenshot"},
+            {"type": "type", "text": "hello"},
+        ]))
+        .unwrap();
+        for action in &actions {
+            session.perform(action).await.unwrap();
+        }
+
+        assert_eq!(
+            session.into_computer().0,
+            [
+                "click left 1 2",
+                "scroll 1 2 0 100",
+                "keypress CTRL+C",
+                "type hello"
+            ]
+        );
+    }
+}
@@ -0,0 +1,497 @@
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{Stream, StreamExt};
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    runtime::{BoxStream, MaybeSend, MaybeSync},
+    tools::{ToolCall, ToolSet},
+    types::{
+        CreateResponse, FunctionToolCall, Include, Input, InputMessage, InputRole, Item, Response,
+        ResponseAccumulator, ResponseStream, ResponseStreamEvent, Truncation,
+    },
+    Client,
+};
+
+/// Responses created by a run of an [Agent] unless set with [Agent::with_max_turns].
+const DEFAULT_MAX_TURNS: usize = 10;
+
+/// What an [Agent] remembers of the conversation from one run to the next.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Memory {
+    /// The conversation is stored by OpenAI, its responses chained with `previous_response_id`.
+    /// Items in the middle of the conversation are dropped when it exceeds the context window
+    /// of the model (`truncation: auto`).
+    #[default]
+    Stored,
+    /// The whole conversation is sent with each request, with `store` false.
+    Full,
+    /// Only the last runs of the conversation are sent with each request, with `store` false.
+    LastRuns(usize),
+    /// Nothing is remembered, each run starts a new conversation.
+    Stateless,
+}
+
+/// Why a run of an [Agent] ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentStopReason {
+    /// The last response has no function call.
+    Answered,
+    /// The run reached [Agent::with_max_turns] responses, the function calls of the last one
+    /// were not run.
+    MaxTurns,
+    /// The responses of the run used [Agent::with_max_tokens] tokens or more, the function calls
+    /// of the last one were not run.
+    MaxTokens,
+}
+
+/// The end of a run of an [Agent].
+#[derive(Debug, Clone)]
+pub struct AgentOutcome {
+    /// The last response of the run, see [Response::output_text].
+    pub response: Response,
+    pub reason: AgentStopReason,
+    /// Responses created during the run.
+    pub turns: usize,
+    /// Tokens used by the responses of the run.
+    pub total_tokens: u32,
+}
+
+/// Event of an [AgentRun].
+#[derive(Debug, Clone)]
+pub enum AgentEvent {
+    /// A delta of the text output of the model.
+    TextDelta(String),
+    /// A function call of the model, about to be run.
+    ToolCall(FunctionToolCall),
+    /// The output of a function call, sent back to the model.
+    ToolOutput { call_id: String, output: String },
+    /// A response of the model, one per turn.
+    ResponseCompleted(Response),
+    /// The run ended, this is the last event.
+    Finished(AgentOutcome),
+}
+
+/// A model with instructions and [ToolSet], answering the inputs of [Agent::run] with the
+/// [Responses](crate::Responses) API: the function calls of the model are run and their outputs
+/// sent back until it answers, remembering the conversation according to [Memory].
+///
+/// ```no_run
+/// use async_openai::{agents::{Agent, AgentEvent, Memory}, tools::ToolSet, Client};
+/// use futures::StreamExt;
+///
+/// # tokio_test::block_on(async {
+/// let client = Client::new();
+/// let mut agent = Agent::new(&client, "gpt-4.1")
+///     .with_instructions("You are a helpful assistant.")
+///     .with_tools(ToolSet::new())
+///     .with_memory(Memory::LastRuns(10))
+///     .with_max_tokens(50_000);
+///
+/// {
+///     let mut run = agent.run("What is the weather in Paris?");
+///     while let Some(event) = run.next().await {
+///         match event? {
+///             AgentEvent::TextDelta(delta) => print!("{delta}"),
+///             AgentEvent::ToolCall(call) => println!("{}({})", call.name, call.arguments),
+///             _ => {}
+///         }
+///     }
+/// }
+///
+/// let outcome = agent.run("And tomorrow?").finish().await?;
+/// println!("{}", outcome.response.output_text());
+/// # Ok::<(), async_openai::error::OpenAIError>(())
+/// # });
+/// ```
+pub struct Agent<'c, C: Config> {
+    client: &'c Client<C>,
+    model: String,
+    instructions: Option<String>,
+    tools: ToolSet,
+    memory: Memory,
+    max_turns: usize,
+    max_tokens: Option<u32>,
+    /// Items of the previous runs, one entry per run, when the conversation is not stored.
+    history: VecDeque<Vec<Item>>,
+    /// Last response of the conversation, when it is stored.
+    previous_response_id: Option<String>,
+    /// Outputs of the function calls not run by the previous run, when the conversation is
+    /// stored, sent with the next run.
+    unanswered: Vec<Item>,
+}
+
+impl<'c, C: Config> Agent<'c, C> {
+    pub fn new<S: Into<String>>(client: &'c Client<C>, model: S) -> Self {
+        Self {
+            client,
+            model: model.into(),
+            instructions: None,
+            tools: ToolSet::new(),
+            memory: Memory::default(),
+            max_turns: DEFAULT_MAX_TURNS,
+            max_tokens: None,
+            history: VecDeque::new(),
+            previous_response_id: None,
+            unanswered: vec![],
+        }
+    }
+
+    pub fn with_instructions<S: Into<String>>(mut self, instructions: S) -> Self {
+        self.instructions = Some(instructions.into());
+        self
+    }
+
+    /// Tools the model can call, their `tools` sent with each request.
+    pub fn with_tools(mut self, tools: ToolSet) -> Self {
+        self.tools = tools;
+        self
+    }
+
+    /// What is remembered from one run to the next, [Memory::Stored] by default.
+    pub fn with_memory(mut self, memory: Memory) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Maximum number of responses created by a run, 10 by default.
+    pub fn with_max_turns(mut self, max_turns: usize) -> Self {
+        self.max_turns = max_turns;
+        self
+    }
+
+    /// Budget of tokens of a run, input and output tokens of all its responses: no more
+    /// function calls are run once it is reached.
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    pub fn tools(&mut self) -> &mut ToolSet {
+        &mut self.tools
+    }
+
+    /// Forget the conversation, the next run starts a new one.
+    pub fn reset(&mut self) {
+        self.history.clear();
+        self.previous_response_id = None;
+        self.unanswered.clear();
+    }
+
+    /// Answer `input`, running the function calls of the model until it answers, or until
+    /// [Agent::with_max_turns] or [Agent::with_max_tokens] is reached.
+    ///
+    /// Responses are streamed: the [AgentRun] yields the text deltas, function calls and
+    /// responses of the model as they arrive, and ends with [AgentEvent::Finished], or
+    /// after the first error.
+    pub fn run(&mut self, input: &str) -> AgentRun<'_>
+    where
+        C: MaybeSend + MaybeSync,
+    {
+        let mut items = std::mem::take(&mut self.unanswered);
+        items.push(InputMessage::new(InputRole::User, input).into());
+
+        let state = RunState {
+            agent: self,
+            items,
+            sent: 0,
+            previous_response_id: None,
+            stream: None,
+            accumulator: ResponseAccumulator::new(),
+            pending: VecDeque::new(),
+            turns: 0,
+            total_tokens: 0,
+            done: false,
+        };
+
+        AgentRun {
+            events: Box::pin(futures::stream::unfold(state, RunState::next)),
+        }
+    }
+
+    /// Request of the next turn of a run, with the `items` of the run not sent yet when the
+    /// conversation is stored, or the whole conversation otherwise, its reasoning items
+    /// then sent back with their encrypted content.
+    fn request(&self, items: &[Item], previous_response_id: Option<String>) -> CreateResponse {
+        let stored = self.memory == Memory::Stored;
+        let input = match self.memory {
+            Memory::Stored | Memory::Stateless => items.to_vec(),
+            Memory::Full | Memory::LastRuns(_) => self
+                .history
+                .iter()
+                .flatten()
+                .chain(items)
+                .cloned()
+                .collect(),
+        };
+        let tools = self.tools.response_tools();
+
+        CreateResponse {
+            model: self.model.clone(),
+            input: Input::Items(input),
+            instructions: self.instructions.clone(),
+            tools: (!tools.is_empty()).then_some(tools),
+            store: Some(stored),
+            include: (!stored).then(|| vec![Include::ReasoningEncryptedContent]),
+            previous_response_id: previous_response_id.filter(|_| stored),
+            truncation: stored.then_some(Truncation::Auto),
+            stream: Some(true),
+            ..Default::default()
+        }
+    }
+
+    /// Remember the `items` of a run ending with `response`.
+    fn remember(&mut self, items: Vec<Item>, response: &Response, unanswered: Vec<Item>) {
+        match self.memory {
+            Memory::Stored => {
+                self.previous_response_id = Some(response.id.clone());
+                self.unanswered = unanswered;
+            }
+            Memory::Full | Memory::LastRuns(_) => {
+                self.history
+                    .push_back(items.into_iter().chain(unanswered).collect());
+                if let Memory::LastRuns(runs) = self.memory {
+                    while self.history.len() > runs {
+                        self.history.pop_front();
+                    }
+                }
+            }
+            Memory::Stateless => {}
+        }
+    }
+}
+
+/// Stream of the [AgentEvent]s of a run of an [Agent], see [Agent::run].
+pub struct AgentRun<'a> {
+    events: BoxStream<'a, Result<AgentEvent, OpenAIError>>,
+}
+
+impl std::fmt::Debug for AgentRun<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("AgentRun")
+    }
+}
+
+impl AgentRun<'_> {
+    /// Consume the events of the run and return its outcome.
+    pub async fn finish(mut self) -> Result<AgentOutcome, OpenAIError> {
+        while let Some(event) = self.events.next().await {
+            if let AgentEvent::Finished(outcome) = event? {
+                return Ok(outcome);
+            }
+        }
+
+        Err(OpenAIError::StreamError(
+            "agent run ended before it finished".into(),
+        ))
+    }
+}
+
+impl Stream for AgentRun<'_> {
+    type Item = Result<AgentEvent, OpenAIError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events.poll_next_unpin(cx)
+    }
+}
+
+/// State of an [AgentRun] between its events.
+struct RunState<'a, 'c, C: Config> {
+    agent: &'a mut Agent<'c, C>,
+    /// Items of the run: the input, the outputs of the model and of the function calls.
+    items: Vec<Item>,
+    /// Number of `items` sent, when the conversation is stored.
+    sent: usize,
+    previous_response_id: Option<String>,
+    stream: Option<ResponseStream>,
+    accumulator: ResponseAccumulator,
+    /// Events to yield before resuming the run.
+    pending: VecDeque<AgentEvent>,
+    turns: usize,
+    total_tokens: u32,
+    done: bool,
+}
+
+impl<'a, 'c, C: Config + MaybeSend + MaybeSync> RunState<'a, 'c, C> {
+    async fn next(mut self) -> Option<(Result<AgentEvent, OpenAIError>, Self)> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some((Ok(event), self));
+            }
+            if self.done {
+                return None;
+            }
+
+            let result = match &mut self.stream {
+                None => self.start_turn().await,
+                Some(stream) => match stream.next().await {
+                    Some(Ok(ResponseStreamEvent::Error(event))) => {
+                        Err(OpenAIError::StreamError(event.message))
+                    }
+                    Some(Ok(event)) => {
+                        if let ResponseStreamEvent::ResponseOutputTextDelta(delta) = &event {
+                            self.pending
+                                .push_back(AgentEvent::TextDelta(delta.delta.clone()));
+                        }
+                        self.accumulator.apply(&event);
+                        Ok(())
+                    }
+                    Some(Err(e)) => Err(e),
+                    None => self.end_turn().await,
+                },
+            };
+
+            if let Err(e) = result {
+                self.done = true;
+                return Some((Err(e), self));
+            }
+        }
+    }
+
+    /// Send the items of the run not sent yet, and stream the response.
+    async fn start_turn(&mut self) -> Result<(), OpenAIError> {
+        let previous_response_id = self
+            .previous_response_id
+            .clone()
+            .or_else(|| self.agent.previous_response_id.clone());
+        let request = self
+            .agent
+            .request(&self.items[self.sent..], previous_response_id);
+
+        self.stream = Some(self.agent.client.responses().create_stream(request).await?);
+        self.accumulator = ResponseAccumulator::new();
+        Ok(())
+    }
+
+    /// Run the function calls of the response once its stream ended, or finish the run.
+    async fn end_turn(&mut self) -> Result<(), OpenAIError> {
+        self.stream = None;
+        let response = match std::mem::take(&mut self.accumulator).into_response() {
+            Some(response) if !response.status.is_pending() => response,
+            _ => {
+                return Err(OpenAIError::StreamError(
+                    "stream ended before the response was completed".into(),
+                ))
+            }
+        };
+
+        self.turns += 1;
+        self.total_tokens += response
+            .usage
+            .as_ref()
+            .map_or(0, |usage| usage.total_tokens);
+        self.previous_response_id = Some(response.id.clone());
+        self.pending
+            .push_back(AgentEvent::ResponseCompleted(response.clone()));
+
+        let function_calls: Vec<FunctionToolCall> = response
+            .output
+            .iter()
+            .filter_map(|item| match item {
+                Item::FunctionCall(function_call) => Some(function_call.clone()),
+                _ => None,
+            })
+            .collect();
+        self.items.extend(response.clone().into_input_items());
+        if self.agent.memory == Memory::Stored {
+            // the stored response has the items sent so far and its output
+            self.sent = self.items.len();
+        }
+
+        let reason = if function_calls.is_empty() {
+            Some(AgentStopReason::Answered)
+        } else if self.turns >= self.agent.max_turns {
+            Some(AgentStopReason::MaxTurns)
+        } else if self
+            .agent
+            .max_tokens
+            .is_some_and(|max_tokens| self.total_tokens >= max_tokens)
+        {
+            Some(AgentStopReason::MaxTokens)
+        } else {
+            None
+        };
+
+        if let Some(reason) = reason {
+            // function calls must be answered before the conversation can go on
+            let unanswered = function_calls
+                .iter()
+                .map(|call| call.output("error: the run stopped before this call".into()))
+                .collect();
+            self.agent
+                .remember(std::mem::take(&mut self.items), &response, unanswered);
+            self.pending.push_back(AgentEvent::Finished(AgentOutcome {
+                response,
+                reason,
+                turns: self.turns,
+                total_tokens: self.total_tokens,
+            }));
+            self.done = true;
+            return Ok(());
+        }
+
+        self.pending
+            .extend(function_calls.iter().cloned().map(AgentEvent::ToolCall));
+        let outputs = self.agent.tools.dispatch_all(&function_calls).await;
+        for output in &outputs {
+            if let Item::FunctionCallOutput(output) = output {
+                self.pending.push_back(AgentEvent::ToolOutput {
+                    call_id: output.call_id.clone(),
+                    output: output.output.clone(),
+                });
+            }
+        }
+        self.items.extend(outputs);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_memory() {
+        let client = Client::new();
+        let mut agent = Agent::new(&client, "gpt-4.1").with_memory(Memory::LastRuns(1));
+        let response: Response = serde_json::from_value(serde_json::json!({
+            "id": "resp_1",
+            "object": "response",
+            "created_at": 1,
+            "status": "completed",
+            "model": "gpt-4.1",
+            "output": [],
+        }))
+        .unwrap();
+
+        let message = |text: &str| -> Item { InputMessage::new(InputRole::User, text).into() };
+        agent.remember(vec![message("first")], &response, vec![]);
+        agent.remember(vec![message("second")], &response, vec![]);
+
+        let request = agent.request(&[message("third")], Some("resp_1".into()));
+        assert_eq!(request.store, Some(false));
+        assert_eq!(
+            request.include,
+            Some(vec![Include::ReasoningEncryptedContent])
+        );
+        assert_eq!(request.previous_response_id, None);
+        assert_eq!(
+            request.input,
+            Input::Items(vec![message("second"), message("third")])
+        );
+
+        let mut agent = agent.with_memory(Memory::Stored);
+        agent.remember(vec![], &response, vec![]);
+        let request = agent.request(&[message("fourth")], agent.previous_response_id.clone());
+        assert_eq!(request.store, Some(true));
+        assert_eq!(request.include, None);
+        assert_eq!(request.previous_response_id.as_deref(), Some("resp_1"));
+        assert_eq!(request.input, Input::Items(vec![message("fourth")]));
+    }
+}
@@ -3,6 +3,7 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::Paginated,
     types::{
         AssistantObject, CreateAssistantRequest, DeleteAssistantResponse, ListAssistantsResponse,
         ModifyAssistantRequest,
@@ -67,4 +68,15 @@ impl<'c, C: Config> Assistants<'c, C> {
     {
         self.client.get_with_query("/assistants", &query).await
     }
+
+    /// Every page of [Assistants::list], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate<Q>(
+        &self,
+        query: &Q,
+    ) -> Result<Paginated<C, ListAssistantsResponse>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(self.client, "/assistants".into(), query)
+    }
 }
@@ -7,7 +7,7 @@ use crate::{
         CreateSpeechRequest, CreateSpeechResponse, CreateTranscriptionRequest,
         CreateTranscriptionResponseJson, CreateTranscriptionResponseVerboseJson,
         CreateTranslationRequest, CreateTranslationResponseJson,
-        CreateTranslationResponseVerboseJson,
+        CreateTranslationResponseVerboseJson, TranscriptionResponseStream,
     },
     Client,
 };
@@ -33,6 +33,14 @@ impl<'c, C: Config> Audio<'c, C> {
         &self,
         request: CreateTranscriptionRequest,
     ) -> Result<CreateTranscriptionResponseJson, OpenAIError> {
+        #[cfg(not(feature = "byot"))]
+        {
+            if request.stream == Some(true) {
+                return Err(OpenAIError::InvalidArgument(
+                    "When stream is true, use Audio::transcribe_stream".into(),
+                ));
+            }
+        }
         self.client
             .post_form("/audio/transcriptions", request)
             .await
@@ -53,7 +61,43 @@ impl<'c, C: Config> Audio<'c, C> {
             .await
     }
 
+    /// Transcribes audio into the input language, streaming the transcript as it is generated.
+    ///
+    /// Not supported by `whisper-1`.
+    ///
+    /// byot: You must ensure "stream: true" in the form built from `request`
+    #[crate::byot(
+        T0 = Clone,
+        R = serde::de::DeserializeOwned,
+        stream = "true",
+        where_clause = "R: crate::runtime::MaybeSend + 'static, reqwest::multipart::Form: crate::traits::AsyncTryFrom<T0, Error = OpenAIError>",
+    )]
+    #[allow(unused_mut)]
+    pub async fn transcribe_stream(
+        &self,
+        mut request: CreateTranscriptionRequest,
+    ) -> Result<TranscriptionResponseStream, OpenAIError> {
+        #[cfg(not(feature = "byot"))]
+        {
+            if request.stream == Some(false) {
+                return Err(OpenAIError::InvalidArgument(
+                    "When stream is false, use Audio::transcribe".into(),
+                ));
+            }
+
+            request.stream = Some(true);
+        }
+        self.client
+            .post_form_stream("/audio/transcriptions", request)
+            .await
+    }
+
     /// Transcribes audio into the input language.
+    #[crate::byot(
+        T0 = Clone,
+        R = From<bytes::Bytes>,
+        where_clause =  "reqwest::multipart::Form: crate::traits::AsyncTryFrom<T0, Error = OpenAIError>",
+    )]
     pub async fn transcribe_raw(
         &self,
         request: CreateTranscriptionRequest,
@@ -90,6 +134,11 @@ impl<'c, C: Config> Audio<'c, C> {
     }
 
     /// Transcribes audio into the input language.
+    #[crate::byot(
+        T0 = Clone,
+        R = From<bytes::Bytes>,
+        where_clause =  "reqwest::multipart::Form: crate::traits::AsyncTryFrom<T0, Error = OpenAIError>",
+    )]
     pub async fn translate_raw(
         &self,
         request: CreateTranslationRequest,
@@ -100,12 +149,11 @@ impl<'c, C: Config> Audio<'c, C> {
     }
 
     /// Generates audio from the input text.
+    #[crate::byot(T0 = serde::Serialize, R = From<bytes::Bytes>)]
     pub async fn speech(
         &self,
         request: CreateSpeechRequest,
     ) -> Result<CreateSpeechResponse, OpenAIError> {
-        let bytes = self.client.post_raw("/audio/speech", request).await?;
-
-        Ok(CreateSpeechResponse { bytes })
+        self.client.post_raw("/audio/speech", request).await
     }
 }
@@ -1,6 +1,8 @@
 use serde::Serialize;
 
-use crate::{config::Config, error::OpenAIError, types::ListAuditLogsResponse, Client};
+use crate::{
+    config::Config, error::OpenAIError, pagination::Paginated, types::ListAuditLogsResponse, Client,
+};
 
 /// Logs of user actions and configuration changes within this organization.
 /// To log events, you must activate logging in the [Organization Settings](https://platform.openai.com/settings/organization/general).
@@ -24,4 +26,12 @@ impl<'c, C: Config> AuditLogs<'c, C> {
             .get_with_query("/organization/audit_logs", &query)
             .await
     }
+
+    /// Every page of [AuditLogs::get], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate<Q>(&self, query: &Q) -> Result<Paginated<C, ListAuditLogsResponse>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(self.client, "/organization/audit_logs".into(), query)
+    }
 }
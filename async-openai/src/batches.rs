@@ -3,6 +3,7 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::Paginated,
     types::{Batch, BatchRequest, ListBatchesResponse},
     Client,
 };
@@ -34,6 +35,14 @@ impl<'c, C: Config> Batches<'c, C> {
         self.client.get_with_query("/batches", &query).await
     }
 
+    /// Every page of [Batches::list], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate<Q>(&self, query: &Q) -> Result<Paginated<C, ListBatchesResponse>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(self.client, "/batches".into(), query)
+    }
+
     /// Retrieves a batch.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, batch_id: &str) -> Result<Batch, OpenAIError> {
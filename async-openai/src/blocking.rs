@@ -0,0 +1,450 @@
+//! Blocking client, with feature `blocking`, for programs which are not async, e.g. CLI tools
+//! and build scripts.
+//!
+//! [Client] drives an async [crate::Client] on its own single threaded tokio runtime, with
+//! blocking versions of the methods of its API groups. Streams are iterators. Realtime, the
+//! nested groups, e.g. the messages and runs of threads or the files of vector stores, and
+//! pagination have no blocking methods: call them with [Client::block_on]:
+//!
+//! ```no_run
+//! use async_openai::{
+//!     blocking::Client,
+//!     types::{ChatCompletionRequestUserMessage, CreateChatCompletionRequestArgs},
+//! };
+//!
+//! let client = Client::new();
+//!
+//! let request = CreateChatCompletionRequestArgs::default()
+//!     .model("gpt-4o-mini")
+//!     .messages([ChatCompletionRequestUserMessage::from("Hello!").into()])
+//!     .build()?;
+//! let response = client.chat().create(request)?;
+//!
+//! let vector_store = client.vector_stores().retrieve("vs_abc123")?;
+//! let files = client.block_on(async {
+//!     let files = client.as_async().vector_stores().files(&vector_store.id);
+//!     files.list(&[("limit", 5)]).await
+//! })?;
+//! # Ok::<(), async_openai::error::OpenAIError>(())
+//! ```
+//!
+//! Blocking methods panic when called within an async runtime, use [crate::Client] there instead.
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use bytes::Bytes;
+use futures::StreamExt;
+use serde::Serialize;
+use tokio::runtime::Runtime;
+
+use crate::{
+    config::{Config, OpenAIConfig},
+    error::OpenAIError,
+    types::{
+        AddUploadPartRequest, AssistantObject, AssistantStreamEvent, Batch, BatchRequest,
+        ChatCompletionList, ChatCompletionMessageList, CompleteUploadRequest, Conversation,
+        CreateAssistantRequest, CreateBase64EmbeddingResponse, CreateChatCompletionRequest,
+        CreateChatCompletionResponse, CreateChatCompletionStreamResponse, CreateCompletionRequest,
+        CreateCompletionResponse, CreateConversationRequest, CreateEmbeddingRequest,
+        CreateEmbeddingResponse, CreateFileRequest, CreateFineTuningJobRequest,
+        CreateImageEditRequest, CreateImageRequest, CreateImageVariationRequest,
+        CreateModerationRequest, CreateModerationResponse, CreateResponse, CreateSpeechRequest,
+        CreateSpeechResponse, CreateThreadAndRunRequest, CreateThreadRequest,
+        CreateTranscriptionRequest, CreateTranscriptionResponseJson,
+        CreateTranscriptionResponseStreamEvent, CreateTranscriptionResponseVerboseJson,
+        CreateTranslationRequest, CreateTranslationResponseJson,
+        CreateTranslationResponseVerboseJson, CreateUploadRequest, CreateVectorStoreRequest,
+        DeleteAssistantResponse, DeleteChatCompletionResponse, DeleteConversationResponse,
+        DeleteFileResponse, DeleteModelResponse, DeleteResponse, DeleteThreadResponse,
+        DeleteVectorStoreResponse, FineTuningJob, ImagesResponse, Invite, InviteDeleteResponse,
+        InviteListResponse, InviteRequest, ListAssistantsResponse, ListAuditLogsResponse,
+        ListBatchesResponse, ListFilesResponse, ListFineTuningJobCheckpointsResponse,
+        ListFineTuningJobEventsResponse, ListModelResponse, ListPaginatedFineTuningJobsResponse,
+        ListVectorStoresResponse, Model, ModifyAssistantRequest, ModifyThreadRequest, OpenAIFile,
+        Project, ProjectCreateRequest, ProjectListResponse, ProjectUpdateRequest, Response,
+        ResponseItemList, ResponseStreamEvent, RunObject, ThreadObject,
+        UpdateChatCompletionRequest, UpdateConversationRequest, UpdateVectorStoreRequest, Upload,
+        UploadPart, User, UserDeleteResponse, UserListResponse, UserRoleUpdateRequest,
+        VectorStoreObject, VectorStoreSearchRequest, VectorStoreSearchResultsPage,
+    },
+};
+
+/// Blocking client, see the [module](self) documentation.
+///
+/// Cloning is cheap: clones share the async client and the runtime.
+#[derive(Debug, Clone)]
+pub struct Client<C: Config> {
+    client: crate::Client<C>,
+    runtime: Arc<Runtime>,
+}
+
+impl Client<OpenAIConfig> {
+    /// Client with default [OpenAIConfig]
+    pub fn new() -> Self {
+        Self::from_async(crate::Client::new())
+    }
+}
+
+impl Default for Client<OpenAIConfig> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Config> Client<C> {
+    /// Create client with [OpenAIConfig] or [crate::config::AzureConfig]
+    pub fn with_config(config: C) -> Self {
+        Self::from_async(crate::Client::with_config(config))
+    }
+
+    /// Blocking client of `client`, e.g. configured with its `with_*` methods.
+    ///
+    /// Panics if the runtime cannot be created.
+    pub fn from_async(client: crate::Client<C>) -> Self {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to build tokio runtime of blocking client");
+
+        Self {
+            client,
+            runtime: Arc::new(runtime),
+        }
+    }
+
+    /// The async client, to call APIs without blocking methods with [Client::block_on].
+    pub fn as_async(&self) -> &crate::Client<C> {
+        &self.client
+    }
+
+    /// Run `future` to completion on the runtime of the client.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// To call [Models] group related APIs using this client.
+    pub fn models(&self) -> Models<C> {
+        Models::new(self)
+    }
+
+    /// To call [Completions] group related APIs using this client.
+    pub fn completions(&self) -> Completions<C> {
+        Completions::new(self)
+    }
+
+    /// To call [Chat] group related APIs using this client.
+    pub fn chat(&self) -> Chat<C> {
+        Chat::new(self)
+    }
+
+    /// To call [Images] group related APIs using this client.
+    pub fn images(&self) -> Images<C> {
+        Images::new(self)
+    }
+
+    /// To call [Moderations] group related APIs using this client.
+    pub fn moderations(&self) -> Moderations<C> {
+        Moderations::new(self)
+    }
+
+    /// To call [Files] group related APIs using this client.
+    pub fn files(&self) -> Files<C> {
+        Files::new(self)
+    }
+
+    /// To call [Uploads] group related APIs using this client.
+    pub fn uploads(&self) -> Uploads<C> {
+        Uploads::new(self)
+    }
+
+    /// To call [FineTuning] group related APIs using this client.
+    pub fn fine_tuning(&self) -> FineTuning<C> {
+        FineTuning::new(self)
+    }
+
+    /// To call [Embeddings] group related APIs using this client.
+    pub fn embeddings(&self) -> Embeddings<C> {
+        Embeddings::new(self)
+    }
+
+    /// To call [Audio] group related APIs using this client.
+    pub fn audio(&self) -> Audio<C> {
+        Audio::new(self)
+    }
+
+    /// To call [Assistants] group related APIs using this client.
+    pub fn assistants(&self) -> Assistants<C> {
+        Assistants::new(self)
+    }
+
+    /// To call [Threads] group related APIs using this client.
+    pub fn threads(&self) -> Threads<C> {
+        Threads::new(self)
+    }
+
+    /// To call [VectorStores] group related APIs using this client.
+    pub fn vector_stores(&self) -> VectorStores<C> {
+        VectorStores::new(self)
+    }
+
+    /// To call [Batches] group related APIs using this client.
+    pub fn batches(&self) -> Batches<C> {
+        Batches::new(self)
+    }
+
+    /// To call [AuditLogs] group related APIs using this client.
+    pub fn audit_logs(&self) -> AuditLogs<C> {
+        AuditLogs::new(self)
+    }
+
+    /// To call [Invites] group related APIs using this client.
+    pub fn invites(&self) -> Invites<C> {
+        Invites::new(self)
+    }
+
+    /// To call [Users] group related APIs using this client.
+    pub fn users(&self) -> Users<C> {
+        Users::new(self)
+    }
+
+    /// To call [Projects] group related APIs using this client.
+    pub fn projects(&self) -> Projects<C> {
+        Projects::new(self)
+    }
+
+    /// To call [Responses] group related APIs using this client.
+    pub fn responses(&self) -> Responses<C> {
+        Responses::new(self)
+    }
+
+    /// To call [Conversations] group related APIs using this client.
+    pub fn conversations(&self) -> Conversations<C> {
+        Conversations::new(self)
+    }
+}
+
+/// Blocking iterator over the items of a stream, e.g. of [Chat::create_stream].
+pub struct StreamIter<'c, T> {
+    stream: Pin<Box<dyn futures::Stream<Item = Result<T, OpenAIError>> + Send>>,
+    runtime: &'c Runtime,
+}
+
+impl<T> Iterator for StreamIter<'_, T> {
+    type Item = Result<T, OpenAIError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+impl<T> std::fmt::Debug for StreamIter<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamIter").finish_non_exhaustive()
+    }
+}
+
+/// Declare the blocking version of an API group, with its methods returning a response,
+/// and `stream` methods returning a [StreamIter] of items.
+macro_rules! blocking_group {
+    (
+        $group:ident {
+            $(fn $name:ident(&self $(, $arg:ident: $ty:ty)*) -> $ret:ty;)*
+            $(stream fn $stream_name:ident(&self $(, $stream_arg:ident: $stream_ty:ty)*) -> $item:ty;)*
+        }
+    ) => {
+        #[doc = concat!("Blocking [", stringify!($group), "](crate::", stringify!($group), ").")]
+        pub struct $group<'c, C: Config> {
+            group: crate::$group<'c, C>,
+            runtime: &'c Runtime,
+        }
+
+        impl<'c, C: Config> $group<'c, C> {
+            fn new(client: &'c Client<C>) -> Self {
+                Self {
+                    group: crate::$group::new(&client.client),
+                    runtime: &client.runtime,
+                }
+            }
+
+            $(
+                #[doc = concat!("Blocking [", stringify!($group), "::", stringify!($name), "](crate::", stringify!($group), "::", stringify!($name), ").")]
+                pub fn $name(&self $(, $arg: $ty)*) -> Result<$ret, OpenAIError> {
+                    self.runtime.block_on(self.group.$name($($arg),*))
+                }
+            )*
+
+            $(
+                #[doc = concat!("Blocking [", stringify!($group), "::", stringify!($stream_name), "](crate::", stringify!($group), "::", stringify!($stream_name), "), its items read as the iterator is advanced.")]
+                pub fn $stream_name(&self $(, $stream_arg: $stream_ty)*) -> Result<StreamIter<'c, $item>, OpenAIError> {
+                    let stream = self.runtime.block_on(self.group.$stream_name($($stream_arg),*))?;
+                    Ok(StreamIter {
+                        stream,
+                        runtime: self.runtime,
+                    })
+                }
+            )*
+        }
+    };
+}
+
+blocking_group!(Models {
+    fn list(&self) -> ListModelResponse;
+    fn retrieve(&self, id: &str) -> Model;
+    fn delete(&self, model: &str) -> DeleteModelResponse;
+});
+
+blocking_group!(Completions {
+    fn create(&self, request: CreateCompletionRequest) -> CreateCompletionResponse;
+    stream fn create_stream(&self, request: CreateCompletionRequest) -> CreateCompletionResponse;
+});
+
+blocking_group!(Chat {
+    fn create(&self, request: CreateChatCompletionRequest) -> CreateChatCompletionResponse;
+    fn list(&self, query: &(impl Serialize + ?Sized)) -> ChatCompletionList;
+    fn retrieve(&self, completion_id: &str) -> CreateChatCompletionResponse;
+    fn messages(&self, completion_id: &str, query: &(impl Serialize + ?Sized)) -> ChatCompletionMessageList;
+    fn update(&self, completion_id: &str, request: UpdateChatCompletionRequest) -> CreateChatCompletionResponse;
+    fn delete(&self, completion_id: &str) -> DeleteChatCompletionResponse;
+    stream fn create_stream(&self, request: CreateChatCompletionRequest) -> CreateChatCompletionStreamResponse;
+});
+
+blocking_group!(Images {
+    fn create(&self, request: CreateImageRequest) -> ImagesResponse;
+    fn create_edit(&self, request: CreateImageEditRequest) -> ImagesResponse;
+    fn create_variation(&self, request: CreateImageVariationRequest) -> ImagesResponse;
+});
+
+blocking_group!(Moderations {
+    fn create(&self, request: CreateModerationRequest) -> CreateModerationResponse;
+});
+
+blocking_group!(Files {
+    fn create(&self, request: CreateFileRequest) -> OpenAIFile;
+    fn list(&self, query: &(impl Serialize + ?Sized)) -> ListFilesResponse;
+    fn retrieve(&self, file_id: &str) -> OpenAIFile;
+    fn delete(&self, file_id: &str) -> DeleteFileResponse;
+    fn content(&self, file_id: &str) -> Bytes;
+});
+
+blocking_group!(FineTuning {
+    fn create(&self, request: CreateFineTuningJobRequest) -> FineTuningJob;
+    fn list_paginated(&self, query: &(impl Serialize + ?Sized)) -> ListPaginatedFineTuningJobsResponse;
+    fn retrieve(&self, fine_tuning_job_id: &str) -> FineTuningJob;
+    fn cancel(&self, fine_tuning_job_id: &str) -> FineTuningJob;
+    fn list_events(&self, fine_tuning_job_id: &str, query: &(impl Serialize + ?Sized)) -> ListFineTuningJobEventsResponse;
+    fn list_checkpoints(&self, fine_tuning_job_id: &str, query: &(impl Serialize + ?Sized)) -> ListFineTuningJobCheckpointsResponse;
+});
+
+blocking_group!(Embeddings {
+    fn create(&self, request: CreateEmbeddingRequest) -> CreateEmbeddingResponse;
+    fn create_base64(&self, request: CreateEmbeddingRequest) -> CreateBase64EmbeddingResponse;
+});
+
+blocking_group!(Audio {
+    fn transcribe(&self, request: CreateTranscriptionRequest) -> CreateTranscriptionResponseJson;
+    fn transcribe_verbose_json(&self, request: CreateTranscriptionRequest) -> CreateTranscriptionResponseVerboseJson;
+    fn transcribe_raw(&self, request: CreateTranscriptionRequest) -> Bytes;
+    fn translate(&self, request: CreateTranslationRequest) -> CreateTranslationResponseJson;
+    fn translate_verbose_json(&self, request: CreateTranslationRequest) -> CreateTranslationResponseVerboseJson;
+    fn translate_raw(&self, request: CreateTranslationRequest) -> Bytes;
+    fn speech(&self, request: CreateSpeechRequest) -> CreateSpeechResponse;
+    stream fn transcribe_stream(&self, request: CreateTranscriptionRequest) -> CreateTranscriptionResponseStreamEvent;
+});
+
+blocking_group!(Batches {
+    fn create(&self, request: BatchRequest) -> Batch;
+    fn list(&self, query: &(impl Serialize + ?Sized)) -> ListBatchesResponse;
+    fn retrieve(&self, batch_id: &str) -> Batch;
+    fn cancel(&self, batch_id: &str) -> Batch;
+});
+
+blocking_group!(Responses {
+    fn create(&self, request: CreateResponse) -> Response;
+    fn retrieve(&self, response_id: &str, query: &(impl Serialize + ?Sized)) -> Response;
+    fn delete(&self, response_id: &str) -> DeleteResponse;
+    fn cancel(&self, response_id: &str) -> Response;
+    fn list_input_items(&self, response_id: &str, query: &(impl Serialize + ?Sized)) -> ResponseItemList;
+    stream fn create_stream(&self, request: CreateResponse) -> ResponseStreamEvent;
+});
+
+blocking_group!(Uploads {
+    fn create(&self, request: CreateUploadRequest) -> Upload;
+    fn add_part(&self, upload_id: &str, request: AddUploadPartRequest) -> UploadPart;
+    fn complete(&self, upload_id: &str, request: CompleteUploadRequest) -> Upload;
+    fn cancel(&self, upload_id: &str) -> Upload;
+});
+
+blocking_group!(Assistants {
+    fn create(&self, request: CreateAssistantRequest) -> AssistantObject;
+    fn retrieve(&self, assistant_id: &str) -> AssistantObject;
+    fn update(&self, assistant_id: &str, request: ModifyAssistantRequest) -> AssistantObject;
+    fn delete(&self, assistant_id: &str) -> DeleteAssistantResponse;
+    fn list(&self, query: &(impl Serialize + ?Sized)) -> ListAssistantsResponse;
+});
+
+blocking_group!(Threads {
+    fn create_and_run(&self, request: CreateThreadAndRunRequest) -> RunObject;
+    fn create(&self, request: CreateThreadRequest) -> ThreadObject;
+    fn retrieve(&self, thread_id: &str) -> ThreadObject;
+    fn update(&self, thread_id: &str, request: ModifyThreadRequest) -> ThreadObject;
+    fn delete(&self, thread_id: &str) -> DeleteThreadResponse;
+    stream fn create_and_run_stream(&self, request: CreateThreadAndRunRequest) -> AssistantStreamEvent;
+});
+
+blocking_group!(VectorStores {
+    fn create(&self, request: CreateVectorStoreRequest) -> VectorStoreObject;
+    fn retrieve(&self, vector_store_id: &str) -> VectorStoreObject;
+    fn list(&self, query: &(impl Serialize + ?Sized)) -> ListVectorStoresResponse;
+    fn delete(&self, vector_store_id: &str) -> DeleteVectorStoreResponse;
+    fn update(&self, vector_store_id: &str, request: UpdateVectorStoreRequest) -> VectorStoreObject;
+    fn search(&self, vector_store_id: &str, request: VectorStoreSearchRequest) -> VectorStoreSearchResultsPage;
+});
+
+blocking_group!(Conversations {
+    fn create(&self, request: CreateConversationRequest) -> Conversation;
+    fn retrieve(&self, conversation_id: &str) -> Conversation;
+    fn update(&self, conversation_id: &str, request: UpdateConversationRequest) -> Conversation;
+    fn delete(&self, conversation_id: &str) -> DeleteConversationResponse;
+});
+
+blocking_group!(AuditLogs {
+    fn get(&self, query: &(impl Serialize + ?Sized)) -> ListAuditLogsResponse;
+});
+
+blocking_group!(Invites {
+    fn list(&self, query: &(impl Serialize + ?Sized)) -> InviteListResponse;
+    fn retrieve(&self, invite_id: &str) -> Invite;
+    fn create(&self, request: InviteRequest) -> Invite;
+    fn delete(&self, invite_id: &str) -> InviteDeleteResponse;
+});
+
+blocking_group!(Users {
+    fn list(&self, query: &(impl Serialize + ?Sized)) -> UserListResponse;
+    fn modify(&self, user_id: &str, request: UserRoleUpdateRequest) -> User;
+    fn retrieve(&self, user_id: &str) -> User;
+    fn delete(&self, user_id: &str) -> UserDeleteResponse;
+});
+
+blocking_group!(Projects {
+    fn list(&self, query: &(impl Serialize + ?Sized)) -> ProjectListResponse;
+    fn create(&self, request: ProjectCreateRequest) -> Project;
+    fn retrieve(&self, project_id: String) -> Project;
+    fn modify(&self, project_id: String, request: ProjectUpdateRequest) -> Project;
+    fn archive(&self, project_id: String) -> Project;
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_on_runtime_of_client() {
+        let client = Client::new();
+        let clone = client.clone();
+        let slept = clone.block_on(async {
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+            true
+        });
+        assert!(slept);
+        assert!(Arc::ptr_eq(&client.runtime, &clone.runtime));
+    }
+}
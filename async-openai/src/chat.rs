@@ -1,8 +1,13 @@
+use serde::Serialize;
+
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::Paginated,
     types::{
-        ChatCompletionResponseStream, CreateChatCompletionRequest, CreateChatCompletionResponse,
+        ChatCompletionList, ChatCompletionMessageList, ChatCompletionResponseStream,
+        CreateChatCompletionRequest, CreateChatCompletionResponse, DeleteChatCompletionResponse,
+        UpdateChatCompletionRequest,
     },
     Client,
 };
@@ -51,10 +56,48 @@ impl<'c, C: Config> Chat<'c, C> {
                     "When stream is true, use Chat::create_stream".into(),
                 ));
             }
+            crate::types::validate::stream_options(
+                Some(request.stream.unwrap_or(false)),
+                request.stream_options.is_some(),
+            )?;
         }
         self.client.post("/chat/completions", request).await
     }
 
+    /// Creates a model response for the given chat conversation, with `response_format` set to
+    /// [ResponseFormat::json_schema_for](crate::types::ResponseFormat::json_schema_for) of `T`,
+    /// and deserializes the message of the first choice into `T`.
+    ///
+    /// Fails with [OpenAIError::Refusal] when the model refuses to answer, and with
+    /// [OpenAIError::Deserialization] when the message is not a `T`, e.g. when it is truncated
+    /// because it reached `max_completion_tokens`.
+    #[cfg(feature = "schemars")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+    pub async fn create_parsed<T>(
+        &self,
+        mut request: CreateChatCompletionRequest,
+    ) -> Result<T, OpenAIError>
+    where
+        T: schemars::JsonSchema + serde::de::DeserializeOwned,
+    {
+        request.response_format = Some(crate::types::ResponseFormat::json_schema_for::<T>()?);
+
+        let response = self.create(request).await?;
+        let message = response
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message);
+        if let Some(refusal) = message.as_ref().and_then(|message| message.refusal.clone()) {
+            return Err(OpenAIError::Refusal(refusal));
+        }
+
+        let content = message
+            .and_then(|message| message.content)
+            .unwrap_or_default();
+        crate::error::deserialize(content.as_bytes())
+    }
+
     /// Creates a completion for the chat message
     ///
     /// partial message deltas will be sent, like in ChatGPT. Tokens will be sent as data-only [server-sent events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events/Using_server-sent_events#Event_stream_format) as they become available, with the stream terminated by a `data: [DONE]` message.
@@ -66,7 +109,7 @@ impl<'c, C: Config> Chat<'c, C> {
         T0 = serde::Serialize,
         R = serde::de::DeserializeOwned,
         stream = "true",
-        where_clause = "R: std::marker::Send + 'static"
+        where_clause = "R: crate::runtime::MaybeSend + 'static"
     )]
     #[allow(unused_mut)]
     pub async fn create_stream(
@@ -85,4 +128,93 @@ impl<'c, C: Config> Chat<'c, C> {
         }
         Ok(self.client.post_stream("/chat/completions", request).await)
     }
+
+    /// Lists the chat completions stored with `store: true`, paginated with the `after`,
+    /// `limit` and `order` query parameters, and filtered by `model` and `metadata`.
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn list<Q>(&self, query: &Q) -> Result<ChatCompletionList, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_with_query("/chat/completions", &query)
+            .await
+    }
+
+    /// Every page of [Chat::list], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate<Q>(&self, query: &Q) -> Result<Paginated<C, ChatCompletionList>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(self.client, "/chat/completions".into(), query)
+    }
+
+    /// Retrieves a chat completion stored with `store: true`.
+    #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
+    pub async fn retrieve(
+        &self,
+        completion_id: &str,
+    ) -> Result<CreateChatCompletionResponse, OpenAIError> {
+        self.client
+            .get(&format!("/chat/completions/{completion_id}"))
+            .await
+    }
+
+    /// Returns the messages of a stored chat completion, paginated with the `after`, `limit`
+    /// and `order` query parameters.
+    #[crate::byot(T0 = std::fmt::Display, T1 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn messages<Q>(
+        &self,
+        completion_id: &str,
+        query: &Q,
+    ) -> Result<ChatCompletionMessageList, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_with_query(
+                &format!("/chat/completions/{completion_id}/messages"),
+                &query,
+            )
+            .await
+    }
+
+    /// Every page of [Chat::messages], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate_messages<Q>(
+        &self,
+        completion_id: &str,
+        query: &Q,
+    ) -> Result<Paginated<C, ChatCompletionMessageList>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(
+            self.client,
+            format!("/chat/completions/{completion_id}/messages"),
+            query,
+        )
+    }
+
+    /// Updates the metadata of a stored chat completion.
+    #[crate::byot(T0 = std::fmt::Display, T1 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn update(
+        &self,
+        completion_id: &str,
+        request: UpdateChatCompletionRequest,
+    ) -> Result<CreateChatCompletionResponse, OpenAIError> {
+        self.client
+            .post(&format!("/chat/completions/{completion_id}"), request)
+            .await
+    }
+
+    /// Deletes a stored chat completion.
+    #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
+    pub async fn delete(
+        &self,
+        completion_id: &str,
+    ) -> Result<DeleteChatCompletionResponse, OpenAIError> {
+        self.client
+            .delete(&format!("/chat/completions/{completion_id}"))
+            .await
+    }
 }
@@ -1,29 +1,68 @@
-use std::pin::Pin;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
 
 use bytes::Bytes;
-use futures::{stream::StreamExt, Stream};
-use reqwest::multipart::Form;
-use reqwest_eventsource::{Event, EventSource, RequestBuilderExt};
+use reqwest::{header::HeaderMap, multipart::Form};
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
-    config::{Config, OpenAIConfig},
-    error::{map_deserialization_error, ApiError, OpenAIError, WrappedError},
+    concurrent::{request_model, RateLimiter, Throttle},
+    config::{Config, OpenAIConfig, OPENAI_ORGANIZATION_HEADER, OPENAI_PROJECT_HEADER},
+    error::{deserialize, map_error_response, ApiErrorType, OpenAIError},
     file::Files,
     image::Images,
+    json_stream::{self, ListItemsParser},
+    middleware::{self, Middleware, ResponseParts},
     moderation::Moderations,
-    traits::AsyncTryFrom,
-    Assistants, Audio, AuditLogs, Batches, Chat, Completions, Embeddings, FineTuning, Invites,
-    Models, Projects, Threads, Uploads, Users, VectorStores,
+    retry::{self, is_transient, RetryBackoff},
+    runtime::{self, BoxStream, MaybeSend},
+    single_flight::{Flight, RequestKey, SingleFlight},
+    sse::{Event, EventSource},
+    traits::{AsyncTryFrom, UsageObserver},
+    transport::{HttpResponse, HttpTransport},
+    types::{RateLimitHeaders, ResponseMetadata, TokenUsage},
+    Assistants, Audio, AuditLogs, Batches, Chat, Completions, Conversations, Embeddings,
+    FineTuning, Invites, Models, Projects, Responses, Threads, Uploads, Users, VectorStores,
 };
 
-#[derive(Debug, Clone, Default)]
+#[cfg(feature = "tracing-spans")]
+use crate::spans;
+
 /// Client is a container for config, backoff and http_client
 /// used to make API calls.
+///
+/// Cloning a client is cheap: clones share the same configuration, [reqwest::Client] and its
+/// connection pool, single flight and usage observer, so a client can be cloned per request
+/// or per task instead of being wrapped in an `Arc`. Each request retries with its own copy of
+/// the backoff, so clones don't slow each other down.
+///
+/// `with_*` methods called on a clone only change that clone.
+#[derive(Debug, Clone, Default)]
 pub struct Client<C: Config> {
+    inner: Arc<ClientInner<C>>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ClientInner<C: Config> {
     http_client: reqwest::Client,
+    transport: Option<Arc<dyn HttpTransport>>,
     config: C,
     backoff: backoff::ExponentialBackoff,
+    max_retries: Option<u32>,
+    single_flight: Option<Arc<SingleFlight>>,
+    throttle: Option<Arc<Throttle>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    usage_observer: Option<Arc<dyn UsageObserver>>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    stream_idle_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    extra_query: Vec<(String, String)>,
+    extra_headers: HeaderMap,
 }
 
 impl Client<OpenAIConfig> {
@@ -41,32 +80,256 @@ impl<C: Config> Client<C> {
         backoff: backoff::ExponentialBackoff,
     ) -> Self {
         Self {
-            http_client,
-            config,
-            backoff,
+            inner: Arc::new(ClientInner {
+                http_client,
+                transport: None,
+                config,
+                backoff,
+                max_retries: None,
+                single_flight: None,
+                throttle: None,
+                rate_limiter: None,
+                usage_observer: None,
+                middlewares: vec![],
+                stream_idle_timeout: None,
+                timeout: None,
+                extra_query: vec![],
+                extra_headers: HeaderMap::new(),
+            }),
         }
     }
 
     /// Create client with [OpenAIConfig] or [crate::config::AzureConfig]
     pub fn with_config(config: C) -> Self {
-        Self {
-            http_client: reqwest::Client::new(),
-            config,
-            backoff: Default::default(),
-        }
+        Self::build(reqwest::Client::new(), config, Default::default())
+    }
+
+    /// The shared state, copied first if other clones use it.
+    fn inner_mut(&mut self) -> &mut ClientInner<C> {
+        Arc::make_mut(&mut self.inner)
     }
 
     /// Provide your own [client] to make HTTP requests with.
     ///
     /// [client]: reqwest::Client
     pub fn with_http_client(mut self, http_client: reqwest::Client) -> Self {
-        self.http_client = http_client;
+        self.inner_mut().http_client = http_client;
+        self
+    }
+
+    /// Send all requests, including streams, over the Unix domain socket at `path`, e.g. to a
+    /// local llama.cpp or vLLM server, without TCP and TLS. The host of the API base of the
+    /// config is only used for the `Host` header:
+    ///
+    /// ```
+    /// # use async_openai::{config::OpenAIConfig, Client};
+    /// let config = OpenAIConfig::new().with_api_base("http://localhost/v1");
+    /// let client = Client::with_config(config).with_unix_socket("/run/llama/llama.sock");
+    /// ```
+    ///
+    /// This replaces the [reqwest::Client] of the client, see [Client::with_http_client] to
+    /// configure it further with [reqwest::ClientBuilder::unix_socket].
+    ///
+    /// Panics if the [reqwest::Client] cannot be built, like [reqwest::Client::new].
+    #[cfg(unix)]
+    pub fn with_unix_socket<P: Into<std::path::PathBuf>>(self, path: P) -> Self {
+        let http_client = reqwest::Client::builder()
+            .unix_socket(path.into())
+            .build()
+            .expect("failed to build reqwest client for unix socket");
+        self.with_http_client(http_client)
+    }
+
+    /// Send requests with your own [HttpTransport] instead of the [reqwest::Client] of the
+    /// client, e.g. built on another HTTP stack. Requests are still built with the latter.
+    pub fn with_transport<T: HttpTransport + 'static>(mut self, transport: T) -> Self {
+        self.inner_mut().transport = Some(Arc::new(transport));
         self
     }
 
-    /// Exponential backoff for retrying [rate limited](https://platform.openai.com/docs/guides/rate-limits) requests.
+    /// The transport to send requests with.
+    fn transport(&self) -> Arc<dyn HttpTransport> {
+        match &self.inner.transport {
+            Some(transport) => transport.clone(),
+            None => Arc::new(self.inner.http_client.clone()),
+        }
+    }
+
+    /// Exponential backoff for retrying [rate limited](https://platform.openai.com/docs/guides/rate-limits) requests,
+    /// requests failed with a server error, and requests whose connection failed, e.g. was reset.
+    /// Its `initial_interval`, `multiplier` and `randomization_factor` set the base delay,
+    /// its growth and jitter, while a longer `Retry-After` header of the response takes precedence.
+    ///
+    /// Streaming requests are retried as well, as long as they fail before the first event is received.
     pub fn with_backoff(mut self, backoff: backoff::ExponentialBackoff) -> Self {
-        self.backoff = backoff;
+        self.inner_mut().backoff = backoff;
+        self
+    }
+
+    /// Give up after `max_retries` retries of a request, in addition to the `max_elapsed_time`
+    /// of the backoff, including when responses have a `Retry-After` header. Set to 0 to never retry.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.inner_mut().max_retries = Some(max_retries);
+        self
+    }
+
+    /// Backoff of a new request.
+    fn backoff(&self) -> RetryBackoff {
+        RetryBackoff::new(self.inner.backoff.clone(), self.inner.max_retries)
+    }
+
+    /// Coalesce identical concurrent requests into a single HTTP call and share its response.
+    ///
+    /// Requests are identical when method, url, headers and body match as sent, i.e. after
+    /// [Config::route] and the middlewares adjusted them; multipart uploads and
+    /// streaming requests are never coalesced. Note that callers of a coalesced sampling request
+    /// (e.g. a chat completion) all receive the same generated output.
+    ///
+    /// Only successful responses are shared: when the leading request fails, callers waiting
+    /// on it make their own request.
+    pub fn with_single_flight(mut self, enabled: bool) -> Self {
+        self.inner_mut().single_flight = enabled.then(|| Arc::new(SingleFlight::default()));
+        self
+    }
+
+    /// Pause requests as set by `throttle` from the rate limit headers of responses.
+    pub(crate) fn with_throttle(mut self, throttle: Arc<Throttle>) -> Self {
+        self.inner_mut().throttle = Some(throttle);
+        self
+    }
+
+    /// Pause requests for a model when the `x-ratelimit-remaining-*` headers of a response for
+    /// that model show the organization is about to be rate limited, until the limit resets,
+    /// and after a rate limited response, for its `Retry-After`.
+    ///
+    /// Clones of the client share the pauses. The headers of the last response for a model are
+    /// available with [Client::rate_limit].
+    pub fn with_rate_limiter(mut self, enabled: bool) -> Self {
+        self.inner_mut().rate_limiter = enabled.then(|| Arc::new(RateLimiter::default()));
+        self
+    }
+
+    /// The [rate limits](https://platform.openai.com/docs/guides/rate-limits) of `model` from
+    /// the `x-ratelimit-*` headers of the last response to a request for it, when
+    /// [Client::with_rate_limiter] is enabled. Requests without model, e.g. uploads,
+    /// are counted under an empty `model`.
+    pub fn rate_limit(&self, model: &str) -> Option<RateLimitHeaders> {
+        self.inner.rate_limiter.as_ref()?.rate_limit(model)
+    }
+
+    /// Report the token usage of every completed generation call to `observer`,
+    /// see [UsageObserver].
+    pub fn with_usage_observer<O: UsageObserver + 'static>(mut self, observer: O) -> Self {
+        self.inner_mut().usage_observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Invoke the hooks of `middleware` around every HTTP request, including retries and
+    /// the connection of streams, after those of middlewares registered before it.
+    pub fn with_middleware<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+        self.inner_mut().middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// Fail streams with [OpenAIError::StreamError] when no server-sent event is received
+    /// within `timeout`, instead of waiting on a stalled connection forever.
+    pub fn with_stream_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.inner_mut().stream_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Fail requests with [OpenAIError::Reqwest] when they are not completed within `timeout`,
+    /// from when the request starts connecting until the response body has been read.
+    /// Each retry has its own timeout.
+    ///
+    /// This includes the whole body of streams, for which [Client::with_stream_idle_timeout]
+    /// is usually a better fit. Set on a clone of the client when it only applies to some calls:
+    ///
+    /// ```
+    /// # use async_openai::Client;
+    /// # use std::time::Duration;
+    /// let client = Client::new();
+    /// let embeddings = client.clone().with_timeout(Duration::from_secs(10));
+    /// let reasoning = client
+    ///     .clone()
+    ///     .with_timeout(Duration::from_secs(600))
+    ///     .with_stream_idle_timeout(Duration::from_secs(120));
+    /// ```
+    ///
+    /// The connect timeout is a setting of the [reqwest::Client], see [Client::with_http_client].
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.inner_mut().timeout = Some(timeout);
+        self
+    }
+
+    /// Query parameters added to every request, after those of the config.
+    ///
+    /// Useful together with `_byot` methods to pass options not (yet) modeled by this library,
+    /// on a clone of the client when they only apply to some calls:
+    ///
+    /// ```
+    /// # use async_openai::Client;
+    /// let client = Client::new();
+    /// let with_results = client.clone().with_query([("include[]", "file_search_call.results")]);
+    /// ```
+    ///
+    /// Repeated keys are sent as repeated parameters.
+    pub fn with_query<K, V>(mut self, query: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.inner_mut()
+            .extra_query
+            .extend(query.into_iter().map(|(k, v)| (k.into(), v.into())));
+        self
+    }
+
+    /// Headers added to every request, e.g. an `OpenAI-Beta` header for an experimental endpoint.
+    /// These replace headers of the same name set by the config.
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.inner_mut().extra_headers.extend(headers);
+        self
+    }
+
+    /// Send requests with an `OpenAI-Organization` header of `org_id`, instead of the one of the
+    /// config, e.g. on a clone of the client per tenant to attribute usage to its organization:
+    ///
+    /// ```
+    /// # use async_openai::Client;
+    /// let client = Client::new();
+    /// let tenant = client
+    ///     .clone()
+    ///     .with_organization("org-tenant")
+    ///     .with_project("proj_tenant");
+    /// ```
+    ///
+    /// When `org_id` is not a valid header value, it is ignored with a warning.
+    pub fn with_organization<S: AsRef<str>>(mut self, org_id: S) -> Self {
+        match reqwest::header::HeaderValue::from_str(org_id.as_ref()) {
+            Ok(value) => {
+                self.inner_mut()
+                    .extra_headers
+                    .insert(OPENAI_ORGANIZATION_HEADER, value);
+            }
+            Err(e) => tracing::warn!("ignoring invalid OpenAI-Organization header: {e}"),
+        }
+        self
+    }
+
+    /// Send requests with an `OpenAI-Project` header of `project_id`, instead of the one of the
+    /// config, see [Client::with_organization].
+    ///
+    /// When `project_id` is not a valid header value, it is ignored with a warning.
+    pub fn with_project<S: AsRef<str>>(mut self, project_id: S) -> Self {
+        match reqwest::header::HeaderValue::from_str(project_id.as_ref()) {
+            Ok(value) => {
+                self.inner_mut()
+                    .extra_headers
+                    .insert(OPENAI_PROJECT_HEADER, value);
+            }
+            Err(e) => tracing::warn!("ignoring invalid OpenAI-Project header: {e}"),
+        }
         self
     }
 
@@ -137,6 +400,23 @@ impl<C: Config> Client<C> {
         VectorStores::new(self)
     }
 
+    /// To call [Responses] group related APIs using this client.
+    pub fn responses(&self) -> Responses<C> {
+        Responses::new(self)
+    }
+
+    /// To call [Conversations] group related APIs using this client.
+    pub fn conversations(&self) -> Conversations<C> {
+        Conversations::new(self)
+    }
+
+    /// To call [Realtime](crate::Realtime) group related APIs using this client.
+    #[cfg(feature = "realtime")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "realtime")))]
+    pub fn realtime(&self) -> crate::Realtime<C> {
+        crate::Realtime::new(self)
+    }
+
     /// To call [Batches] group related APIs using this client.
     pub fn batches(&self) -> Batches<C> {
         Batches::new(self)
@@ -163,7 +443,152 @@ impl<C: Config> Client<C> {
     }
 
     pub fn config(&self) -> &C {
-        &self.config
+        &self.inner.config
+    }
+
+    /// Make a request to an arbitrary `path`, such as an endpoint without typed support yet,
+    /// and deserialize the response body.
+    ///
+    /// `body` is sent as JSON unless it serializes to `null`, so pass `()` for requests without body.
+    /// The request goes through the same headers, query, retries and error handling as typed calls.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use async_openai::Client;
+    /// use reqwest::Method;
+    /// use serde_json::{json, Value};
+    ///
+    /// let client = Client::new();
+    ///
+    /// let models: Value = client.request(Method::GET, "/models", ()).await?;
+    /// let completion: Value = client
+    ///     .request(Method::POST, "/chat/completions", json!({
+    ///         "model": "gpt-4o",
+    ///         "messages": [{"role": "user", "content": "Hello!"}]
+    ///     }))
+    ///     .await?;
+    /// # Ok::<(), async_openai::error::OpenAIError>(())
+    /// # });
+    /// ```
+    pub async fn request<R>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: impl Serialize,
+    ) -> Result<R, OpenAIError>
+    where
+        R: DeserializeOwned,
+    {
+        let body =
+            serde_json::to_value(body).map_err(|e| OpenAIError::InvalidArgument(e.to_string()))?;
+        let body = (!body.is_null()).then(|| json_body(&body)).transpose()?;
+
+        let request_maker = || async {
+            let request = self.request_builder(method.clone(), path).build()?;
+            Ok(match &body {
+                Some(body) => with_json_body(request, body),
+                None => request,
+            })
+        };
+
+        self.execute(request_maker).await
+    }
+
+    /// Streaming variant of [Client::request]: make a request to an arbitrary `path`
+    /// and deserialize each data-only server-sent event until a \[DONE\] is received from server.
+    ///
+    /// You must ensure `body` asks the endpoint for a stream, e.g. `"stream": true`.
+    pub async fn request_stream<R>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: impl Serialize,
+    ) -> Result<BoxStream<'static, Result<R, OpenAIError>>, OpenAIError>
+    where
+        R: DeserializeOwned + MaybeSend + 'static,
+    {
+        let body =
+            serde_json::to_value(body).map_err(|e| OpenAIError::InvalidArgument(e.to_string()))?;
+
+        self.refresh_before_stream().await?;
+        let event_source = self.event_source(self.raw_request_builder(method, path, &body));
+
+        Ok(stream(event_source, self.stream_settings()).await)
+    }
+
+    fn event_source(&self, request: reqwest::RequestBuilder) -> EventSource {
+        // requests with a streaming body, e.g. multipart, can't be cloned and have no model
+        let request = match request.try_clone().map(|r| r.build_split()) {
+            Some((client, Ok(mut routed))) => {
+                self.inner.config.route(&mut routed);
+                reqwest::RequestBuilder::from_parts(client, routed)
+            }
+            _ => request,
+        };
+
+        let mut event_source = EventSource::new(request)
+            .with_transport(self.transport())
+            .with_backoff(self.backoff())
+            .with_middlewares(self.inner.middlewares.clone());
+        if let Some(credentials) = self.inner.config.credentials() {
+            event_source = event_source.with_credentials(credentials);
+        }
+        match &self.inner.rate_limiter {
+            Some(rate_limiter) => event_source.with_rate_limiter(rate_limiter.clone()),
+            None => event_source,
+        }
+    }
+
+    /// Refresh the config before setting up an [EventSource], unless it has
+    /// [Credentials](crate::config::Credentials), refreshed before each connection instead.
+    async fn refresh_before_stream(&self) -> Result<(), OpenAIError> {
+        match self.inner.config.credentials() {
+            Some(_) => Ok(()),
+            None => self.inner.config.refresh().await,
+        }
+    }
+
+    fn stream_settings(&self) -> StreamSettings {
+        StreamSettings {
+            usage_observer: self.inner.usage_observer.clone(),
+            idle_timeout: self.inner.stream_idle_timeout,
+            #[cfg(feature = "tracing-spans")]
+            span: spans::request_span(),
+        }
+    }
+
+    /// Request to {path} with the query and headers of the config,
+    /// followed by those of [Client::with_query] and [Client::with_headers], and the timeout
+    /// of [Client::with_timeout].
+    fn request_builder(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let request_builder = self
+            .inner
+            .http_client
+            .request(method, self.inner.config.url(path))
+            .query(&self.inner.config.query())
+            .headers(self.inner.config.headers())
+            .query(&self.inner.extra_query)
+            .headers(self.inner.extra_headers.clone());
+
+        match self.inner.timeout {
+            Some(timeout) => request_builder.timeout(timeout),
+            None => request_builder,
+        }
+    }
+
+    fn raw_request_builder(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: &serde_json::Value,
+    ) -> reqwest::RequestBuilder {
+        let request_builder = self.request_builder(method, path);
+
+        if body.is_null() {
+            request_builder
+        } else {
+            request_builder.json(body)
+        }
     }
 
     /// Make a GET request to {path} and deserialize the response body
@@ -171,14 +596,8 @@ impl<C: Config> Client<C> {
     where
         O: DeserializeOwned,
     {
-        let request_maker = || async {
-            Ok(self
-                .http_client
-                .get(self.config.url(path))
-                .query(&self.config.query())
-                .headers(self.config.headers())
-                .build()?)
-        };
+        let request_maker =
+            || async { Ok(self.request_builder(reqwest::Method::GET, path).build()?) };
 
         self.execute(request_maker).await
     }
@@ -191,11 +610,8 @@ impl<C: Config> Client<C> {
     {
         let request_maker = || async {
             Ok(self
-                .http_client
-                .get(self.config.url(path))
-                .query(&self.config.query())
+                .request_builder(reqwest::Method::GET, path)
                 .query(query)
-                .headers(self.config.headers())
                 .build()?)
         };
 
@@ -209,10 +625,7 @@ impl<C: Config> Client<C> {
     {
         let request_maker = || async {
             Ok(self
-                .http_client
-                .delete(self.config.url(path))
-                .query(&self.config.query())
-                .headers(self.config.headers())
+                .request_builder(reqwest::Method::DELETE, path)
                 .build()?)
         };
 
@@ -220,35 +633,29 @@ impl<C: Config> Client<C> {
     }
 
     /// Make a GET request to {path} and return the response body
-    pub(crate) async fn get_raw(&self, path: &str) -> Result<Bytes, OpenAIError> {
-        let request_maker = || async {
-            Ok(self
-                .http_client
-                .get(self.config.url(path))
-                .query(&self.config.query())
-                .headers(self.config.headers())
-                .build()?)
-        };
+    pub(crate) async fn get_raw<O>(&self, path: &str) -> Result<O, OpenAIError>
+    where
+        O: From<Bytes>,
+    {
+        let request_maker =
+            || async { Ok(self.request_builder(reqwest::Method::GET, path).build()?) };
 
-        self.execute_raw(request_maker).await
+        self.execute_raw(request_maker).await.map(O::from)
     }
 
     /// Make a POST request to {path} and return the response body
-    pub(crate) async fn post_raw<I>(&self, path: &str, request: I) -> Result<Bytes, OpenAIError>
+    pub(crate) async fn post_raw<I, O>(&self, path: &str, request: I) -> Result<O, OpenAIError>
     where
         I: Serialize,
+        O: From<Bytes>,
     {
+        let body = json_body(&request)?;
         let request_maker = || async {
-            Ok(self
-                .http_client
-                .post(self.config.url(path))
-                .query(&self.config.query())
-                .headers(self.config.headers())
-                .json(&request)
-                .build()?)
+            let request = self.request_builder(reqwest::Method::POST, path).build()?;
+            Ok(with_json_body(request, &body))
         };
 
-        self.execute_raw(request_maker).await
+        self.execute_raw(request_maker).await.map(O::from)
     }
 
     /// Make a POST request to {path} and deserialize the response body
@@ -257,36 +664,30 @@ impl<C: Config> Client<C> {
         I: Serialize,
         O: DeserializeOwned,
     {
+        let body = json_body(&request)?;
         let request_maker = || async {
-            Ok(self
-                .http_client
-                .post(self.config.url(path))
-                .query(&self.config.query())
-                .headers(self.config.headers())
-                .json(&request)
-                .build()?)
+            let request = self.request_builder(reqwest::Method::POST, path).build()?;
+            Ok(with_json_body(request, &body))
         };
 
         self.execute(request_maker).await
     }
 
     /// POST a form at {path} and return the response body
-    pub(crate) async fn post_form_raw<F>(&self, path: &str, form: F) -> Result<Bytes, OpenAIError>
+    pub(crate) async fn post_form_raw<O, F>(&self, path: &str, form: F) -> Result<O, OpenAIError>
     where
+        O: From<Bytes>,
         Form: AsyncTryFrom<F, Error = OpenAIError>,
         F: Clone,
     {
         let request_maker = || async {
             Ok(self
-                .http_client
-                .post(self.config.url(path))
-                .query(&self.config.query())
-                .headers(self.config.headers())
+                .request_builder(reqwest::Method::POST, path)
                 .multipart(<Form as AsyncTryFrom<F>>::try_from(form.clone()).await?)
                 .build()?)
         };
 
-        self.execute_raw(request_maker).await
+        self.execute_raw(request_maker).await.map(O::from)
     }
 
     /// POST a form at {path} and deserialize the response body
@@ -298,10 +699,7 @@ impl<C: Config> Client<C> {
     {
         let request_maker = || async {
             Ok(self
-                .http_client
-                .post(self.config.url(path))
-                .query(&self.config.query())
-                .headers(self.config.headers())
+                .request_builder(reqwest::Method::POST, path)
                 .multipart(<Form as AsyncTryFrom<F>>::try_from(form.clone()).await?)
                 .build()?)
         };
@@ -309,70 +707,265 @@ impl<C: Config> Client<C> {
         self.execute(request_maker).await
     }
 
+    /// POST a form at {path} to receive SSE
+    ///
+    /// A multipart body can only be sent once, so the request is not retried.
+    pub(crate) async fn post_form_stream<O, F>(
+        &self,
+        path: &str,
+        form: F,
+    ) -> Result<BoxStream<'static, Result<O, OpenAIError>>, OpenAIError>
+    where
+        O: DeserializeOwned + MaybeSend + 'static,
+        Form: AsyncTryFrom<F, Error = OpenAIError>,
+    {
+        let form = <Form as AsyncTryFrom<F>>::try_from(form).await?;
+
+        self.inner.config.refresh().await?;
+        let event_source = EventSource::new(
+            self.request_builder(reqwest::Method::POST, path)
+                .multipart(form),
+        )
+        .with_transport(self.transport())
+        .with_middlewares(self.inner.middlewares.clone());
+
+        Ok(stream(event_source, self.stream_settings()).await)
+    }
+
     /// Execute a HTTP request and retry on rate limit
     ///
     /// request_maker serves one purpose: to be able to create request again
     /// to retry API call after getting rate limited. request_maker is async because
     /// reqwest::multipart::Form is created by async calls to read files for uploads.
+    ///
+    /// When [Client::with_single_flight] is enabled, identical concurrent requests share a single call.
     async fn execute_raw<M, Fut>(&self, request_maker: M) -> Result<Bytes, OpenAIError>
     where
         M: Fn() -> Fut,
         Fut: core::future::Future<Output = Result<reqwest::Request, OpenAIError>>,
     {
-        let client = self.http_client.clone();
-
-        backoff::future::retry(self.backoff.clone(), || async {
-            let request = request_maker().await.map_err(backoff::Error::Permanent)?;
-            let response = client
-                .execute(request)
-                .await
-                .map_err(OpenAIError::Reqwest)
-                .map_err(backoff::Error::Permanent)?;
-
-            let status = response.status();
-            let bytes = response
-                .bytes()
-                .await
-                .map_err(OpenAIError::Reqwest)
-                .map_err(backoff::Error::Permanent)?;
-
-            if status.is_server_error() {
-                // OpenAI does not guarantee server errors are returned as JSON so we cannot deserialize them.
-                let message: String = String::from_utf8_lossy(&bytes).into_owned();
-                tracing::warn!("Server error: {status} - {message}");
-                return Err(backoff::Error::Transient {
-                    err: OpenAIError::ApiError(ApiError { message, r#type: None, param: None, code: None }),
-                    retry_after: None,
-                });
+        let transport = self.transport();
+        #[cfg(feature = "tracing-spans")]
+        let started = runtime::Instant::now();
+        // The first attempt joins an identical request in flight, or leads it
+        // until the response is shared once retries are over.
+        let joining = AtomicBool::new(self.inner.single_flight.is_some());
+        let leader = Mutex::new(None);
+
+        let retry = retry::retry(self.backoff(), || async {
+            let request = self.prepare(&request_maker).await?;
+
+            if joining.swap(false, Ordering::Relaxed) {
+                if let Some(flight) = self.join_single_flight(&request) {
+                    match flight {
+                        Flight::Leader(flight) => *leader.lock().unwrap() = Some(flight),
+                        Flight::Follower(response) => {
+                            // otherwise the leading request failed or was dropped
+                            if let Ok(bytes) = response.await {
+                                return Ok(bytes);
+                            }
+                        }
+                    }
+                }
             }
 
-            // Deserialize response body from either error object or actual response object
+            let (method, url, response) = self.send(transport.as_ref(), request).await?;
+
+            let status = response.status;
+            let metadata = ResponseMetadata::from_response(status, &response.headers);
+            #[cfg(feature = "tracing-spans")]
+            spans::record_response(&metadata, started);
+            let observe_usage = method == reqwest::Method::POST;
+            let is_json = response
+                .headers
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|content_type| content_type.to_str().ok())
+                .is_some_and(|content_type| content_type.starts_with("application/json"));
+            let headers = response.headers.clone();
+            let bytes = response.bytes().await.map_err(transport_error)?;
+
+            let parts = ResponseParts {
+                method: &method,
+                url: &url,
+                status,
+                headers: &headers,
+                body: Some(&bytes),
+            };
+            middleware::on_response(&self.inner.middlewares, &parts).await;
+
             if !status.is_success() {
-                let wrapped_error: WrappedError = serde_json::from_slice(bytes.as_ref())
-                    .map_err(|e| map_deserialization_error(e, bytes.as_ref()))
-                    .map_err(backoff::Error::Permanent)?;
-
-                if status.as_u16() == 429
-                    // API returns 429 also when:
-                    // "You exceeded your current quota, please check your plan and billing details."
-                    && wrapped_error.error.r#type != Some("insufficient_quota".to_string())
-                {
-                    // Rate limited retry...
-                    tracing::warn!("Rate limited: {}", wrapped_error.error.message);
-                    return Err(backoff::Error::Transient {
-                        err: OpenAIError::ApiError(wrapped_error.error),
-                        retry_after: None,
-                    });
-                } else {
-                    return Err(backoff::Error::Permanent(OpenAIError::ApiError(
-                        wrapped_error.error,
-                    )));
+                return Err(error_response(status, metadata, &bytes));
+            }
+
+            let record_usage =
+                self.inner.usage_observer.is_some() || cfg!(feature = "tracing-spans");
+            if observe_usage && is_json && record_usage {
+                if let Some((model, usage)) = parse_usage(&bytes) {
+                    if let Some(usage_observer) = &self.inner.usage_observer {
+                        usage_observer.observe(model.as_deref().unwrap_or_default(), &usage);
+                    }
+                    #[cfg(feature = "tracing-spans")]
+                    spans::record_usage(&tracing::Span::current(), &usage);
                 }
             }
 
             Ok(bytes)
-        })
-        .await
+        });
+
+        #[cfg(feature = "tracing-spans")]
+        let retry = tracing::Instrument::instrument(retry, spans::request_span());
+
+        let bytes = retry.await?;
+        if let Some(leader) = leader.into_inner().unwrap() {
+            leader.complete(bytes.clone());
+        }
+
+        Ok(bytes)
+    }
+
+    /// Join the identical request in flight, if [Client::with_single_flight] is enabled.
+    fn join_single_flight(&self, request: &reqwest::Request) -> Option<Flight> {
+        let single_flight = self.inner.single_flight.as_ref()?;
+        Some(single_flight.join(RequestKey::new(request)?))
+    }
+
+    /// The request of `request_maker`, as sent: with the config refreshed, routed by the config
+    /// and adjusted by the request hooks of the middlewares.
+    async fn prepare<M, Fut>(
+        &self,
+        request_maker: &M,
+    ) -> Result<reqwest::Request, backoff::Error<OpenAIError>>
+    where
+        M: Fn() -> Fut,
+        Fut: core::future::Future<Output = Result<reqwest::Request, OpenAIError>>,
+    {
+        self.inner
+            .config
+            .refresh()
+            .await
+            .map_err(backoff::Error::Permanent)?;
+
+        let mut request = request_maker().await.map_err(backoff::Error::Permanent)?;
+        self.inner.config.route(&mut request);
+        middleware::on_request(&self.inner.middlewares, &mut request)
+            .await
+            .map_err(backoff::Error::Permanent)?;
+
+        Ok(request)
+    }
+
+    /// Send a prepared `request` once, after waiting for the throttle and the rate limiter
+    /// of its model. The rate limit headers of the response are observed, its body is not read.
+    async fn send(
+        &self,
+        transport: &dyn HttpTransport,
+        request: reqwest::Request,
+    ) -> Result<(reqwest::Method, reqwest::Url, HttpResponse), backoff::Error<OpenAIError>> {
+        if let Some(throttle) = &self.inner.throttle {
+            throttle.wait().await;
+        }
+
+        let (method, url) = (request.method().clone(), request.url().clone());
+        #[cfg(feature = "tracing-spans")]
+        spans::record_request(&request);
+
+        let rate_limiter = self
+            .inner
+            .rate_limiter
+            .as_ref()
+            .map(|rate_limiter| (rate_limiter, request_model(&request)));
+        if let Some((rate_limiter, model)) = &rate_limiter {
+            rate_limiter.wait(model).await;
+        }
+
+        let response = transport.execute(request).await.map_err(transport_error)?;
+
+        let metadata = ResponseMetadata::from_response(response.status, &response.headers);
+        if let Some(throttle) = &self.inner.throttle {
+            throttle.observe(&metadata);
+        }
+        if let Some((rate_limiter, model)) = &rate_limiter {
+            rate_limiter.observe(model, &metadata);
+        }
+
+        Ok((method, url, response))
+    }
+
+    /// Make a GET request to {path} with given Query and return the response body as it is received,
+    /// retrying on rate limit until the response starts.
+    pub(crate) async fn get_bytes_stream<Q>(
+        &self,
+        path: &str,
+        query: &Q,
+    ) -> Result<BoxStream<'static, Result<Bytes, OpenAIError>>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        let transport = self.transport();
+        #[cfg(feature = "tracing-spans")]
+        let started = runtime::Instant::now();
+        let request_maker = || async {
+            Ok(self
+                .request_builder(reqwest::Method::GET, path)
+                .query(query)
+                .build()?)
+        };
+
+        let retry = retry::retry(self.backoff(), || async {
+            let request = self.prepare(&request_maker).await?;
+            let (method, url, response) = self.send(transport.as_ref(), request).await?;
+
+            let status = response.status;
+            let metadata = ResponseMetadata::from_response(status, &response.headers);
+            #[cfg(feature = "tracing-spans")]
+            spans::record_response(&metadata, started);
+            if !status.is_success() {
+                let headers = response.headers.clone();
+                let bytes = response.bytes().await.map_err(transport_error)?;
+                let parts = ResponseParts {
+                    method: &method,
+                    url: &url,
+                    status,
+                    headers: &headers,
+                    body: Some(&bytes),
+                };
+                middleware::on_response(&self.inner.middlewares, &parts).await;
+                return Err(error_response(status, metadata, &bytes));
+            }
+
+            let parts = ResponseParts {
+                method: &method,
+                url: &url,
+                status,
+                headers: &response.headers,
+                body: None,
+            };
+            middleware::on_response(&self.inner.middlewares, &parts).await;
+
+            Ok(response)
+        });
+
+        #[cfg(feature = "tracing-spans")]
+        let retry = tracing::Instrument::instrument(retry, spans::request_span());
+
+        let response = retry.await?;
+
+        Ok(Box::pin(response.body))
+    }
+
+    /// Make a GET request to {path} with given Query and deserialize the elements
+    /// of the `data` array of the list in the response body, as they are received.
+    pub(crate) async fn get_list_stream<Q, O>(
+        &self,
+        path: &str,
+        query: &Q,
+    ) -> Result<BoxStream<'static, Result<O, OpenAIError>>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+        O: DeserializeOwned + MaybeSend + 'static,
+    {
+        let bytes = self.get_bytes_stream(path, query).await?;
+        Ok(json_stream::items(bytes, ListItemsParser::default()))
     }
 
     /// Execute a HTTP request and retry on rate limit
@@ -388,8 +981,7 @@ impl<C: Config> Client<C> {
     {
         let bytes = self.execute_raw(request_maker).await?;
 
-        let response: O = serde_json::from_slice(bytes.as_ref())
-            .map_err(|e| map_deserialization_error(e, bytes.as_ref()))?;
+        let response: O = deserialize(bytes.as_ref())?;
 
         Ok(response)
     }
@@ -399,43 +991,43 @@ impl<C: Config> Client<C> {
         &self,
         path: &str,
         request: I,
-    ) -> Pin<Box<dyn Stream<Item = Result<O, OpenAIError>> + Send>>
+    ) -> BoxStream<'static, Result<O, OpenAIError>>
     where
         I: Serialize,
-        O: DeserializeOwned + std::marker::Send + 'static,
+        O: DeserializeOwned + MaybeSend + 'static,
     {
-        let event_source = self
-            .http_client
-            .post(self.config.url(path))
-            .query(&self.config.query())
-            .headers(self.config.headers())
-            .json(&request)
-            .eventsource()
-            .unwrap();
+        if let Err(e) = self.refresh_before_stream().await {
+            return Box::pin(futures::stream::once(async { Err(e) }));
+        }
 
-        stream(event_source).await
+        let event_source = self.event_source(
+            self.request_builder(reqwest::Method::POST, path)
+                .json(&request),
+        );
+
+        stream(event_source, self.stream_settings()).await
     }
 
     pub(crate) async fn post_stream_mapped_raw_events<I, O>(
         &self,
         path: &str,
         request: I,
-        event_mapper: impl Fn(eventsource_stream::Event) -> Result<O, OpenAIError> + Send + 'static,
-    ) -> Pin<Box<dyn Stream<Item = Result<O, OpenAIError>> + Send>>
+        event_mapper: impl Fn(eventsource_stream::Event) -> Result<O, OpenAIError> + MaybeSend + 'static,
+    ) -> BoxStream<'static, Result<O, OpenAIError>>
     where
         I: Serialize,
-        O: DeserializeOwned + std::marker::Send + 'static,
+        O: DeserializeOwned + MaybeSend + 'static,
     {
-        let event_source = self
-            .http_client
-            .post(self.config.url(path))
-            .query(&self.config.query())
-            .headers(self.config.headers())
-            .json(&request)
-            .eventsource()
-            .unwrap();
+        if let Err(e) = self.refresh_before_stream().await {
+            return Box::pin(futures::stream::once(async { Err(e) }));
+        }
 
-        stream_mapped_raw_events(event_source, event_mapper).await
+        let event_source = self.event_source(
+            self.request_builder(reqwest::Method::POST, path)
+                .json(&request),
+        );
+
+        stream_mapped_raw_events(event_source, event_mapper, self.stream_settings()).await
     }
 
     /// Make HTTP GET request to receive SSE
@@ -443,114 +1035,234 @@ impl<C: Config> Client<C> {
         &self,
         path: &str,
         query: &Q,
-    ) -> Pin<Box<dyn Stream<Item = Result<O, OpenAIError>> + Send>>
+    ) -> BoxStream<'static, Result<O, OpenAIError>>
     where
         Q: Serialize + ?Sized,
-        O: DeserializeOwned + std::marker::Send + 'static,
+        O: DeserializeOwned + MaybeSend + 'static,
     {
-        let event_source = self
-            .http_client
-            .get(self.config.url(path))
-            .query(query)
-            .query(&self.config.query())
-            .headers(self.config.headers())
-            .eventsource()
-            .unwrap();
+        if let Err(e) = self.refresh_before_stream().await {
+            return Box::pin(futures::stream::once(async { Err(e) }));
+        }
+
+        let event_source = self.event_source(
+            self.request_builder(reqwest::Method::GET, path)
+                .query(query),
+        );
 
-        stream(event_source).await
+        stream(event_source, self.stream_settings()).await
     }
 }
 
+/// Client settings applied to each stream.
+#[derive(Clone)]
+pub(crate) struct StreamSettings {
+    usage_observer: Option<Arc<dyn UsageObserver>>,
+    idle_timeout: Option<Duration>,
+    #[cfg(feature = "tracing-spans")]
+    span: tracing::Span,
+}
+
+/// Wait for the next event of `event_source`, for at most the idle timeout of `settings` if set.
+async fn next_event(
+    event_source: &mut EventSource,
+    settings: &StreamSettings,
+) -> Option<Result<Event, OpenAIError>> {
+    let next = event_source.next();
+    #[cfg(feature = "tracing-spans")]
+    let next = tracing::Instrument::instrument(next, settings.span.clone());
+
+    let Some(idle_timeout) = settings.idle_timeout else {
+        return next.await;
+    };
+
+    runtime::timeout(idle_timeout, next)
+        .await
+        .unwrap_or_else(|| {
+            Some(Err(OpenAIError::StreamError(format!(
+                "no event received within {idle_timeout:?}"
+            ))))
+        })
+}
+
 /// Request which responds with SSE.
 /// [server-sent events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events/Using_server-sent_events#event_stream_format)
+///
+/// Events are read from the connection only as the returned stream is polled,
+/// so a slow consumer applies backpressure instead of buffering the whole response.
 pub(crate) async fn stream<O>(
-    mut event_source: EventSource,
-) -> Pin<Box<dyn Stream<Item = Result<O, OpenAIError>> + Send>>
+    event_source: EventSource,
+    settings: StreamSettings,
+) -> BoxStream<'static, Result<O, OpenAIError>>
 where
-    O: DeserializeOwned + std::marker::Send + 'static,
+    O: DeserializeOwned + MaybeSend + 'static,
 {
-    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
-
-    tokio::spawn(async move {
-        while let Some(ev) = event_source.next().await {
-            match ev {
-                Err(e) => {
-                    if let Err(_e) = tx.send(Err(OpenAIError::StreamError(e.to_string()))) {
-                        // rx dropped
-                        break;
-                    }
-                }
-                Ok(event) => match event {
-                    Event::Message(message) => {
-                        if message.data == "[DONE]" {
-                            break;
-                        }
+    let stream = futures::stream::unfold(Some((event_source, settings)), |state| async move {
+        let (mut event_source, settings) = state?;
 
-                        let response = match serde_json::from_str::<O>(&message.data) {
-                            Err(e) => Err(map_deserialization_error(e, message.data.as_bytes())),
-                            Ok(output) => Ok(output),
-                        };
+        let message = match next_event(&mut event_source, &settings).await? {
+            Ok(message) => message,
+            // the stream ends after an error
+            Err(e) => return Some((Err(e), None)),
+        };
 
-                        if let Err(_e) = tx.send(response) {
-                            // rx dropped
-                            break;
-                        }
-                    }
-                    Event::Open => continue,
-                },
-            }
+        if message.data == "[DONE]" {
+            return None;
         }
 
-        event_source.close();
+        observe_event(&settings, &message.data);
+
+        let response = deserialize::<O>(message.data.as_bytes());
+
+        Some((response, Some((event_source, settings))))
     });
 
-    Box::pin(tokio_stream::wrappers::UnboundedReceiverStream::new(rx))
+    Box::pin(stream)
 }
 
-pub(crate) async fn stream_mapped_raw_events<O>(
-    mut event_source: EventSource,
-    event_mapper: impl Fn(eventsource_stream::Event) -> Result<O, OpenAIError> + Send + 'static,
-) -> Pin<Box<dyn Stream<Item = Result<O, OpenAIError>> + Send>>
+/// Like [stream], with each event, including the final \[DONE\], converted by `event_mapper`.
+pub(crate) async fn stream_mapped_raw_events<O, F>(
+    event_source: EventSource,
+    event_mapper: F,
+    settings: StreamSettings,
+) -> BoxStream<'static, Result<O, OpenAIError>>
 where
-    O: DeserializeOwned + std::marker::Send + 'static,
+    O: DeserializeOwned + MaybeSend + 'static,
+    F: Fn(eventsource_stream::Event) -> Result<O, OpenAIError> + MaybeSend + 'static,
 {
-    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
-
-    tokio::spawn(async move {
-        while let Some(ev) = event_source.next().await {
-            match ev {
-                Err(e) => {
-                    if let Err(_e) = tx.send(Err(OpenAIError::StreamError(e.to_string()))) {
-                        // rx dropped
-                        break;
-                    }
-                }
-                Ok(event) => match event {
-                    Event::Message(message) => {
-                        let mut done = false;
+    let state = Some((event_source, event_mapper, settings));
 
-                        if message.data == "[DONE]" {
-                            done = true;
-                        }
+    let stream = futures::stream::unfold(state, |state| async move {
+        let (mut event_source, event_mapper, settings) = state?;
 
-                        let response = event_mapper(message);
+        let message = match next_event(&mut event_source, &settings).await? {
+            Ok(message) => message,
+            // the stream ends after an error
+            Err(e) => return Some((Err(e), None)),
+        };
 
-                        if let Err(_e) = tx.send(response) {
-                            // rx dropped
-                            break;
-                        }
+        let done = message.data == "[DONE]";
 
-                        if done {
-                            break;
-                        }
-                    }
-                    Event::Open => continue,
-                },
-            }
+        if !done {
+            observe_event(&settings, &message.data);
         }
 
-        event_source.close();
+        let response = event_mapper(message);
+
+        Some((
+            response,
+            (!done).then_some((event_source, event_mapper, settings)),
+        ))
     });
 
-    Box::pin(tokio_stream::wrappers::UnboundedReceiverStream::new(rx))
+    Box::pin(stream)
+}
+
+/// Error of a response with an unsuccessful status, transient for server errors
+/// and rate limits so that the request is retried.
+fn error_response(
+    status: reqwest::StatusCode,
+    metadata: ResponseMetadata,
+    bytes: &[u8],
+) -> backoff::Error<OpenAIError> {
+    let retry_after = metadata.retry_after;
+    // OpenAI does not guarantee server errors are returned as JSON,
+    // gateways in between may respond with HTML or plain text
+    let err = map_error_response(metadata, bytes);
+
+    if status.is_server_error() {
+        tracing::warn!("Server error: {err}");
+        return backoff::Error::Transient { err, retry_after };
+    }
+
+    let rate_limited = status.as_u16() == 429
+        // API returns 429 also when:
+        // "You exceeded your current quota, please check your plan and billing details."
+        && !matches!(
+            &err,
+            OpenAIError::ApiError(e) if e.r#type == Some(ApiErrorType::InsufficientQuota)
+        );
+
+    if rate_limited {
+        // Rate limited retry...
+        tracing::warn!("Rate limited: {err}");
+        return backoff::Error::Transient { err, retry_after };
+    }
+
+    backoff::Error::Permanent(err)
+}
+
+/// Error of a request whose transport failed, transient when its connection was e.g. reset
+/// so that the request is retried.
+fn transport_error(error: OpenAIError) -> backoff::Error<OpenAIError> {
+    match &error {
+        OpenAIError::Reqwest(e) if is_transient(e) => {
+            tracing::warn!("Connection error: {error}");
+            backoff::Error::transient(error)
+        }
+        _ => backoff::Error::Permanent(error),
+    }
+}
+
+/// Report the usage of an SSE event of a stream to the usage observer and span of `settings`.
+fn observe_event(settings: &StreamSettings, data: &str) {
+    #[cfg(feature = "tracing-spans")]
+    spans::record_chunk(&settings.span, data);
+
+    if settings.usage_observer.is_none() && !cfg!(feature = "tracing-spans") {
+        return;
+    }
+
+    let Some((model, usage)) = parse_usage(data.as_bytes()) else {
+        return;
+    };
+    if let Some(usage_observer) = &settings.usage_observer {
+        usage_observer.observe(model.as_deref().unwrap_or_default(), &usage);
+    }
+    #[cfg(feature = "tracing-spans")]
+    spans::record_usage(&settings.span, &usage);
+}
+
+/// Model and `usage` of a JSON response body or SSE event, if present.
+fn parse_usage(bytes: &[u8]) -> Option<(Option<String>, TokenUsage)> {
+    #[derive(serde::Deserialize)]
+    struct UsageEnvelope {
+        object: Option<String>,
+        model: Option<String>,
+        usage: Option<TokenUsage>,
+        /// The response of a Responses streaming event, e.g. `response.completed`
+        response: Option<Box<UsageEnvelope>>,
+    }
+
+    let mut envelope = serde_json::from_slice::<UsageEnvelope>(bytes).ok()?;
+
+    if let Some(response) = envelope.response.take() {
+        envelope = *response;
+    }
+
+    // Usage of run steps is already accounted for in the usage of their run
+    if envelope.object.as_deref() == Some("thread.run.step") {
+        return None;
+    }
+
+    Some((envelope.model, envelope.usage?))
+}
+
+/// Serialize a request body once, so that retries share it instead of serializing it again.
+fn json_body<I: Serialize + ?Sized>(body: &I) -> Result<Bytes, OpenAIError> {
+    serde_json::to_vec(body)
+        .map(Bytes::from)
+        .map_err(|e| OpenAIError::InvalidArgument(e.to_string()))
+}
+
+/// Set `body` as the JSON body of `request`, like [reqwest::RequestBuilder::json]
+/// but without copying the body.
+fn with_json_body(mut request: reqwest::Request, body: &Bytes) -> reqwest::Request {
+    request
+        .headers_mut()
+        .entry(reqwest::header::CONTENT_TYPE)
+        .or_insert(reqwest::header::HeaderValue::from_static(
+            "application/json",
+        ));
+    *request.body_mut() = Some(body.clone().into());
+    request
 }
@@ -38,6 +38,10 @@ impl<'c, C: Config> Completions<'c, C> {
                     "When stream is true, use Completion::create_stream".into(),
                 ));
             }
+            crate::types::validate::stream_options(
+                Some(request.stream.unwrap_or(false)),
+                request.stream_options.is_some(),
+            )?;
         }
         self.client.post("/completions", request).await
     }
@@ -55,7 +59,7 @@ impl<'c, C: Config> Completions<'c, C> {
         T0 = serde::Serialize,
         R = serde::de::DeserializeOwned,
         stream = "true",
-        where_clause = "R: std::marker::Send + 'static"
+        where_clause = "R: crate::runtime::MaybeSend + 'static"
     )]
     #[allow(unused_mut)]
     pub async fn create_stream(
@@ -69,6 +73,11 @@ impl<'c, C: Config> Completions<'c, C> {
                     "When stream is false, use Completion::create".into(),
                 ));
             }
+            if request.best_of.is_some_and(|best_of| best_of > 1) {
+                return Err(OpenAIError::InvalidArgument(
+                    "best_of greater than 1 cannot be streamed".into(),
+                ));
+            }
 
             request.stream = Some(true);
         }
@@ -0,0 +1,379 @@
+//! Bulk inference: many requests sent concurrently, within the rate limits of the organization.
+//!
+//! ```no_run
+//! use async_openai::{concurrent::map_concurrent, types::CreateEmbeddingRequestArgs, Client};
+//!
+//! # tokio_test::block_on(async {
+//! let client = Client::new();
+//! let documents = ["first document", "second document"];
+//!
+//! let requests = documents
+//!     .iter()
+//!     .map(|document| {
+//!         CreateEmbeddingRequestArgs::default()
+//!             .model("text-embedding-3-small")
+//!             .input(*document)
+//!             .build()
+//!     })
+//!     .collect::<Result<Vec<_>, _>>()?;
+//!
+//! // in the order of `documents`
+//! let embeddings = map_concurrent(&client, requests, 16).await;
+//! # Ok::<(), async_openai::error::OpenAIError>(())
+//! # });
+//! ```
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures::StreamExt;
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    retry,
+    runtime::{self, Instant},
+    types::{
+        CreateChatCompletionRequest, CreateChatCompletionResponse, CreateCompletionRequest,
+        CreateCompletionResponse, CreateEmbeddingRequest, CreateEmbeddingResponse, CreateResponse,
+        RateLimitHeaders, Response, ResponseMetadata,
+    },
+    Client,
+};
+
+/// Attempts of each request, when it fails with a connection error, or a rate limit or
+/// server error once the backoff of the client gave up.
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Delay before the second attempt of a request, doubled for each further attempt.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Pause after a rate limited response without `Retry-After`.
+const RATE_LIMITED_PAUSE: Duration = Duration::from_secs(1);
+
+/// A request which can be sent by [map_concurrent].
+pub trait ConcurrentRequest: Clone {
+    type Response;
+
+    fn send<C: Config>(
+        self,
+        client: &Client<C>,
+    ) -> impl Future<Output = Result<Self::Response, OpenAIError>>;
+}
+
+impl ConcurrentRequest for CreateChatCompletionRequest {
+    type Response = CreateChatCompletionResponse;
+
+    async fn send<C: Config>(self, client: &Client<C>) -> Result<Self::Response, OpenAIError> {
+        client.chat().create(self).await
+    }
+}
+
+impl ConcurrentRequest for CreateCompletionRequest {
+    type Response = CreateCompletionResponse;
+
+    async fn send<C: Config>(self, client: &Client<C>) -> Result<Self::Response, OpenAIError> {
+        client.completions().create(self).await
+    }
+}
+
+impl ConcurrentRequest for CreateEmbeddingRequest {
+    type Response = CreateEmbeddingResponse;
+
+    async fn send<C: Config>(self, client: &Client<C>) -> Result<Self::Response, OpenAIError> {
+        client.embeddings().create(self).await
+    }
+}
+
+impl ConcurrentRequest for CreateResponse {
+    type Response = Response;
+
+    async fn send<C: Config>(self, client: &Client<C>) -> Result<Self::Response, OpenAIError> {
+        client.responses().create(self).await
+    }
+}
+
+/// Send `requests` with at most `concurrency` of them in flight, and return their results
+/// in the order of `requests`.
+///
+/// All requests pause when the `x-ratelimit-remaining-*` headers of a response show the
+/// organization is about to be rate limited, until the limit resets, and after a rate
+/// limited response, for its `Retry-After`. A request failing with a connection error,
+/// or a rate limit or server error the client gave up retrying, is attempted up to 3 times.
+pub async fn map_concurrent<C, I>(
+    client: &Client<C>,
+    requests: I,
+    concurrency: usize,
+) -> Vec<Result<<I::Item as ConcurrentRequest>::Response, OpenAIError>>
+where
+    C: Config,
+    I: IntoIterator,
+    I::Item: ConcurrentRequest,
+{
+    let throttle = Arc::new(Throttle::default());
+    let client = client.clone().with_throttle(throttle.clone());
+
+    futures::stream::iter(requests)
+        .map(|request| send_with_retry(&client, &throttle, request))
+        .buffered(concurrency.max(1))
+        .collect()
+        .await
+}
+
+async fn send_with_retry<C: Config, R: ConcurrentRequest>(
+    client: &Client<C>,
+    throttle: &Throttle,
+    request: R,
+) -> Result<R::Response, OpenAIError> {
+    let mut delay = RETRY_DELAY;
+
+    for _ in 1..MAX_ATTEMPTS {
+        throttle.wait().await;
+        match request.clone().send(client).await {
+            Err(e) if is_transient(&e) => {
+                tracing::warn!("Retrying request: {e}");
+                runtime::sleep(delay).await;
+                delay *= 2;
+            }
+            result => return result,
+        }
+    }
+
+    throttle.wait().await;
+    request.send(client).await
+}
+
+fn is_transient(error: &OpenAIError) -> bool {
+    match error {
+        OpenAIError::Reqwest(e) => retry::is_transient(e),
+        OpenAIError::ApiError(e) => e
+            .metadata
+            .as_ref()
+            .is_some_and(|metadata| metadata.status == 429 || metadata.status >= 500),
+        OpenAIError::Http { status, .. } => *status == 429 || *status >= 500,
+        _ => false,
+    }
+}
+
+/// Pause shared by the requests of a client, set from the rate limit headers of responses,
+/// see [Client::with_throttle].
+#[derive(Debug, Default)]
+pub(crate) struct Throttle {
+    resume_at: Mutex<Option<Instant>>,
+}
+
+impl Throttle {
+    /// Wait for the end of the pause, if any.
+    pub(crate) async fn wait(&self) {
+        loop {
+            let resume_at = *self.resume_at.lock().unwrap();
+            match resume_at {
+                Some(resume_at) if resume_at > Instant::now() => {
+                    runtime::sleep_until(resume_at).await;
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Pause before rate limits are exceeded, or after they were.
+    pub(crate) fn observe(&self, metadata: &ResponseMetadata) {
+        if metadata.status == 429 {
+            self.pause(metadata.retry_after.unwrap_or(RATE_LIMITED_PAUSE));
+            return;
+        }
+
+        let rate_limit = &metadata.rate_limit;
+        let limits = [
+            (
+                rate_limit.remaining_requests,
+                rate_limit.limit_requests,
+                rate_limit.reset_requests,
+            ),
+            (
+                rate_limit.remaining_tokens,
+                rate_limit.limit_tokens,
+                rate_limit.reset_tokens,
+            ),
+        ];
+
+        for (remaining, limit, reset) in limits {
+            // less than 5% left
+            if let (Some(remaining), Some(limit), Some(reset)) = (remaining, limit, reset) {
+                if remaining * 20 < limit {
+                    self.pause(reset);
+                }
+            }
+        }
+    }
+
+    fn pause(&self, duration: Duration) {
+        let until = Instant::now() + duration;
+        let mut resume_at = self.resume_at.lock().unwrap();
+        if !resume_at.is_some_and(|resume_at| resume_at >= until) {
+            *resume_at = Some(until);
+        }
+    }
+}
+
+/// Pauses of the requests of a client per model, set from the rate limit headers of
+/// their responses, see [Client::with_rate_limiter].
+#[derive(Debug, Default)]
+pub(crate) struct RateLimiter {
+    models: Mutex<HashMap<String, ModelRateLimit>>,
+}
+
+#[derive(Debug, Default)]
+struct ModelRateLimit {
+    throttle: Arc<Throttle>,
+    rate_limit: Option<RateLimitHeaders>,
+}
+
+impl RateLimiter {
+    /// Rate limit headers of the last response to a request for `model`.
+    pub(crate) fn rate_limit(&self, model: &str) -> Option<RateLimitHeaders> {
+        let models = self.models.lock().unwrap();
+        models.get(model)?.rate_limit.clone()
+    }
+
+    /// Wait for the end of the pause of `model`, if any.
+    pub(crate) async fn wait(&self, model: &str) {
+        let throttle = self
+            .models
+            .lock()
+            .unwrap()
+            .entry(model.to_string())
+            .or_default()
+            .throttle
+            .clone();
+        throttle.wait().await;
+    }
+
+    /// Pause requests for `model` before its rate limits are exceeded, or after they were.
+    pub(crate) fn observe(&self, model: &str, metadata: &ResponseMetadata) {
+        let mut models = self.models.lock().unwrap();
+        let model = models.entry(model.to_string()).or_default();
+        model.throttle.observe(metadata);
+        if metadata.rate_limit != RateLimitHeaders::default() {
+            model.rate_limit = Some(metadata.rate_limit.clone());
+        }
+    }
+}
+
+/// The model of a JSON request body, by which rate limits are counted,
+/// empty for requests without model, e.g. multipart uploads.
+pub(crate) fn request_model(request: &reqwest::Request) -> String {
+    #[derive(serde::Deserialize)]
+    struct Body {
+        model: Option<String>,
+    }
+
+    request
+        .body()
+        .and_then(|body| body.as_bytes())
+        .and_then(|bytes| serde_json::from_slice::<Body>(bytes).ok())
+        .and_then(|body| body.model)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::ApiError, types::RateLimitHeaders};
+
+    fn metadata(remaining_requests: u64) -> ResponseMetadata {
+        ResponseMetadata {
+            status: 200,
+            rate_limit: RateLimitHeaders {
+                limit_requests: Some(100),
+                remaining_requests: Some(remaining_requests),
+                reset_requests: Some(Duration::from_secs(10)),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn transient_errors() {
+        let http = |status| OpenAIError::Http {
+            status,
+            body_snippet: "<html>Bad Gateway</html>".into(),
+            metadata: ResponseMetadata {
+                status,
+                ..Default::default()
+            },
+        };
+        let api = |status| {
+            OpenAIError::ApiError(ApiError {
+                message: "error".into(),
+                r#type: None,
+                param: None,
+                code: None,
+                metadata: Some(ResponseMetadata {
+                    status,
+                    ..Default::default()
+                }),
+            })
+        };
+
+        for status in [429, 500, 502, 503] {
+            assert!(is_transient(&http(status)), "{status}");
+            assert!(is_transient(&api(status)), "{status}");
+        }
+        for status in [400, 401, 404] {
+            assert!(!is_transient(&http(status)), "{status}");
+            assert!(!is_transient(&api(status)), "{status}");
+        }
+        assert!(!is_transient(&OpenAIError::InvalidArgument("model".into())));
+    }
+
+    #[test]
+    fn throttle_pauses_close_to_rate_limit() {
+        let throttle = Throttle::default();
+
+        throttle.observe(&metadata(50));
+        assert!(throttle.resume_at.lock().unwrap().is_none());
+
+        throttle.observe(&metadata(4));
+        let resume_at = throttle.resume_at.lock().unwrap().unwrap();
+        assert!(resume_at > Instant::now() + Duration::from_secs(9));
+
+        // a shorter pause does not end the current one
+        throttle.observe(&ResponseMetadata {
+            status: 429,
+            ..Default::default()
+        });
+        assert_eq!(*throttle.resume_at.lock().unwrap(), Some(resume_at));
+    }
+
+    #[test]
+    fn rate_limiter_per_model() {
+        let rate_limiter = RateLimiter::default();
+
+        rate_limiter.observe("gpt-4o", &metadata(4));
+        rate_limiter.observe("gpt-4o-mini", &metadata(50));
+
+        let paused = |model: &str| {
+            rate_limiter.models.lock().unwrap()[model]
+                .throttle
+                .resume_at
+                .lock()
+                .unwrap()
+                .is_some()
+        };
+        assert!(paused("gpt-4o"));
+        assert!(!paused("gpt-4o-mini"));
+        assert_eq!(
+            rate_limiter
+                .rate_limit("gpt-4o-mini")
+                .unwrap()
+                .remaining_requests,
+            Some(50)
+        );
+        assert!(rate_limiter.rate_limit("o3").is_none());
+    }
+}
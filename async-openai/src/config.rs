@@ -1,8 +1,21 @@
 //! Client configurations: [OpenAIConfig] for OpenAI, [AzureConfig] for Azure OpenAI Service.
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
 use reqwest::header::{HeaderMap, AUTHORIZATION};
 use secrecy::{ExposeSecret, SecretString};
 use serde::Deserialize;
 
+use crate::{
+    concurrent::request_model,
+    error::OpenAIError,
+    runtime::{BoxFuture, MaybeSend, MaybeSync, SystemTime},
+};
+
 /// Default v1 API base url
 pub const OPENAI_API_BASE: &str = "https://api.openai.com/v1";
 /// Organization header
@@ -13,6 +26,9 @@ pub const OPENAI_PROJECT_HEADER: &str = "OpenAI-Project";
 /// Calls to the Assistants API require that you pass a Beta header
 pub const OPENAI_BETA_HEADER: &str = "OpenAI-Beta";
 
+/// Tokens of a [TokenProvider] are refreshed this long before they expire.
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(5 * 60);
+
 /// [crate::Client] relies on this for every API call on OpenAI
 /// or Azure OpenAI service
 pub trait Config: Clone {
@@ -23,6 +39,20 @@ pub trait Config: Clone {
     fn api_base(&self) -> &str;
 
     fn api_key(&self) -> &SecretString;
+
+    /// Invoked before each request, e.g. to refresh credentials used by [Config::headers].
+    fn refresh(&self) -> impl Future<Output = Result<(), OpenAIError>> + MaybeSend {
+        async { Ok(()) }
+    }
+
+    /// Credentials which expire, refreshed by streams and realtime sessions before each
+    /// connection instead of [Config::refresh], as they reconnect long after they were set up.
+    fn credentials(&self) -> Option<Arc<dyn Credentials>> {
+        None
+    }
+
+    /// Adjust each request before it is sent, e.g. its url for the model of its body.
+    fn route(&self, _request: &mut reqwest::Request) {}
 }
 
 /// Configuration for OpenAI API
@@ -33,6 +63,9 @@ pub struct OpenAIConfig {
     api_key: SecretString,
     org_id: String,
     project_id: String,
+    /// Key of the API key provider, shared by clones.
+    #[serde(skip)]
+    provided_api_key: Option<Arc<ProvidedApiKey>>,
 }
 
 impl Default for OpenAIConfig {
@@ -44,6 +77,7 @@ impl Default for OpenAIConfig {
                 .into(),
             org_id: Default::default(),
             project_id: Default::default(),
+            provided_api_key: None,
         }
     }
 }
@@ -78,9 +112,97 @@ impl OpenAIConfig {
         self
     }
 
+    /// Request the API key from `api_key_provider` before each request, instead of using a
+    /// fixed one, e.g. to rotate keys or read them from a vault without recreating the client.
+    ///
+    /// ```
+    /// use async_openai::{config::OpenAIConfig, Client};
+    ///
+    /// let config = OpenAIConfig::new().with_api_key_provider(|| async {
+    ///     // e.g. from a cache of a secrets manager
+    ///     Ok(std::env::var("ROTATED_OPENAI_API_KEY")
+    ///         .unwrap_or_default()
+    ///         .into())
+    /// });
+    ///
+    /// let client = Client::with_config(config);
+    /// ```
+    ///
+    /// The provider is called for every request, so it should cache keys it fetches remotely.
+    /// Concurrent requests share a call, and streams call it before each connection.
+    pub fn with_api_key_provider<P: ApiKeyProvider + 'static>(
+        mut self,
+        api_key_provider: P,
+    ) -> Self {
+        self.provided_api_key = Some(Arc::new(ProvidedApiKey {
+            api_key_provider: Box::new(api_key_provider),
+            api_key: Default::default(),
+            requesting: Default::default(),
+        }));
+        self
+    }
+
+    /// Config of an OpenAI compatible provider at `api_base`, with the API key of the `env_var`
+    /// environment variable, if set.
+    fn provider(api_base: &str, env_var: &str) -> Self {
+        Self::new()
+            .with_api_base(api_base)
+            .with_api_key(std::env::var(env_var).unwrap_or_default())
+    }
+
+    /// [OpenRouter](https://openrouter.ai/docs), API key from the `OPENROUTER_API_KEY`
+    /// environment variable. Its optional `HTTP-Referer` and `X-Title` attribution headers
+    /// can be sent with [Client::with_headers](crate::Client::with_headers).
+    pub fn openrouter() -> Self {
+        Self::provider("https://openrouter.ai/api/v1", "OPENROUTER_API_KEY")
+    }
+
+    /// [Together AI](https://docs.together.ai), API key from the `TOGETHER_API_KEY`
+    /// environment variable.
+    pub fn together() -> Self {
+        Self::provider("https://api.together.xyz/v1", "TOGETHER_API_KEY")
+    }
+
+    /// [Groq](https://console.groq.com/docs/openai), API key from the `GROQ_API_KEY`
+    /// environment variable.
+    pub fn groq() -> Self {
+        Self::provider("https://api.groq.com/openai/v1", "GROQ_API_KEY")
+    }
+
+    /// [Mistral](https://docs.mistral.ai/api), API key from the `MISTRAL_API_KEY`
+    /// environment variable.
+    pub fn mistral() -> Self {
+        Self::provider("https://api.mistral.ai/v1", "MISTRAL_API_KEY")
+    }
+
+    /// [DeepSeek](https://api-docs.deepseek.com), API key from the `DEEPSEEK_API_KEY`
+    /// environment variable.
+    pub fn deepseek() -> Self {
+        Self::provider("https://api.deepseek.com/v1", "DEEPSEEK_API_KEY")
+    }
+
+    /// [Ollama](https://github.com/ollama/ollama/blob/main/docs/openai.md) running locally
+    /// on its default port. Ollama ignores the API key, which is set to `ollama`.
+    pub fn ollama() -> Self {
+        Self::new()
+            .with_api_base("http://localhost:11434/v1")
+            .with_api_key("ollama")
+    }
+
+    /// [vLLM](https://docs.vllm.ai/en/latest/serving/openai_compatible_server.html) serving at
+    /// `api_base`, e.g. `http://localhost:8000/v1`, with the API key of the `VLLM_API_KEY`
+    /// environment variable, if the server was started with `--api-key`.
+    pub fn vllm<S: AsRef<str>>(api_base: S) -> Self {
+        Self::provider(api_base.as_ref(), "VLLM_API_KEY")
+    }
+
     pub fn org_id(&self) -> &str {
         &self.org_id
     }
+
+    pub fn project_id(&self) -> &str {
+        &self.project_id
+    }
 }
 
 impl Config for OpenAIConfig {
@@ -100,9 +222,14 @@ impl Config for OpenAIConfig {
             );
         }
 
+        let provided_api_key = self
+            .provided_api_key
+            .as_ref()
+            .and_then(|provided_api_key| provided_api_key.api_key.lock().unwrap().clone());
+        let api_key = provided_api_key.as_ref().unwrap_or(&self.api_key);
         headers.insert(
             AUTHORIZATION,
-            format!("Bearer {}", self.api_key.expose_secret())
+            format!("Bearer {}", api_key.expose_secret())
                 .as_str()
                 .parse()
                 .unwrap(),
@@ -130,6 +257,176 @@ impl Config for OpenAIConfig {
     fn query(&self) -> Vec<(&str, &str)> {
         vec![]
     }
+
+    /// Request the API key of the API key provider, once for concurrent requests.
+    async fn refresh(&self) -> Result<(), OpenAIError> {
+        match &self.provided_api_key {
+            Some(provided_api_key) => provided_api_key.refresh().await,
+            None => Ok(()),
+        }
+    }
+
+    fn credentials(&self) -> Option<Arc<dyn Credentials>> {
+        self.provided_api_key
+            .clone()
+            .map(|provided_api_key| provided_api_key as Arc<dyn Credentials>)
+    }
+}
+
+/// Last key of an [ApiKeyProvider].
+#[derive(Debug)]
+struct ProvidedApiKey {
+    api_key_provider: Box<dyn ApiKeyProvider>,
+    api_key: Mutex<Option<SecretString>>,
+    /// Held while a key is requested, so that concurrent requests use it
+    /// instead of requesting their own.
+    requesting: futures::lock::Mutex<()>,
+}
+
+impl Credentials for ProvidedApiKey {
+    /// Request the API key, unless a request for it is in flight already.
+    fn refresh(&self) -> BoxFuture<'_, Result<(), OpenAIError>> {
+        Box::pin(async move {
+            let in_flight = self.requesting.try_lock().is_none();
+            let _requesting = self.requesting.lock().await;
+            if in_flight && self.api_key.lock().unwrap().is_some() {
+                return Ok(());
+            }
+
+            let api_key = self.api_key_provider.api_key().await?;
+            *self.api_key.lock().unwrap() = Some(api_key);
+
+            Ok(())
+        })
+    }
+
+    fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+
+        if let Some(api_key) = &*self.api_key.lock().unwrap() {
+            headers.insert(
+                AUTHORIZATION,
+                format!("Bearer {}", api_key.expose_secret())
+                    .as_str()
+                    .parse()
+                    .unwrap(),
+            );
+        }
+
+        headers
+    }
+}
+
+/// Provider of the API key of an [OpenAIConfig], called before each request.
+/// See [OpenAIConfig::with_api_key_provider].
+///
+/// Any `Fn() -> impl Future<Output = Result<SecretString, OpenAIError>>` closure is a provider.
+pub trait ApiKeyProvider: MaybeSend + MaybeSync {
+    fn api_key(&self) -> BoxFuture<'_, Result<SecretString, OpenAIError>>;
+}
+
+impl<F, Fut> ApiKeyProvider for F
+where
+    F: Fn() -> Fut + MaybeSend + MaybeSync,
+    Fut: Future<Output = Result<SecretString, OpenAIError>> + MaybeSend + 'static,
+{
+    fn api_key(&self) -> BoxFuture<'_, Result<SecretString, OpenAIError>> {
+        Box::pin(self())
+    }
+}
+
+impl std::fmt::Debug for dyn ApiKeyProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ApiKeyProvider")
+    }
+}
+
+/// A bearer token of a [TokenProvider].
+#[derive(Clone, Debug)]
+pub struct AccessToken {
+    pub token: SecretString,
+    /// [std::time::SystemTime], except on wasm where it is not implemented.
+    pub expires_at: SystemTime,
+}
+
+/// Provider of [Microsoft Entra ID](https://learn.microsoft.com/en-us/azure/ai-services/openai/how-to/managed-identity)
+/// bearer tokens, for the `https://cognitiveservices.azure.com/.default` scope,
+/// e.g. from a managed identity. See [AzureConfig::with_token_provider].
+///
+/// Any `Fn() -> impl Future<Output = Result<AccessToken, OpenAIError>>` closure is a provider.
+pub trait TokenProvider: MaybeSend + MaybeSync {
+    fn token(&self) -> BoxFuture<'_, Result<AccessToken, OpenAIError>>;
+}
+
+impl<F, Fut> TokenProvider for F
+where
+    F: Fn() -> Fut + MaybeSend + MaybeSync,
+    Fut: Future<Output = Result<AccessToken, OpenAIError>> + MaybeSend + 'static,
+{
+    fn token(&self) -> BoxFuture<'_, Result<AccessToken, OpenAIError>> {
+        Box::pin(self())
+    }
+}
+
+impl std::fmt::Debug for dyn TokenProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TokenProvider")
+    }
+}
+
+/// Expiring credentials of a [Config], shared by its clones, see [Config::credentials].
+pub trait Credentials: MaybeSend + MaybeSync {
+    /// Request new credentials when needed.
+    fn refresh(&self) -> BoxFuture<'_, Result<(), OpenAIError>>;
+
+    /// Headers carrying the current credentials, replacing those of [Config::headers].
+    fn headers(&self) -> HeaderMap;
+}
+
+/// Last token of a [TokenProvider].
+#[derive(Debug)]
+struct ProvidedToken {
+    token_provider: Box<dyn TokenProvider>,
+    token: Mutex<Option<AccessToken>>,
+    /// Held while a token is requested, so that concurrent requests wait for it
+    /// instead of requesting their own.
+    requesting: futures::lock::Mutex<()>,
+}
+
+impl Credentials for ProvidedToken {
+    /// Request a new token when the last one is about to expire.
+    fn refresh(&self) -> BoxFuture<'_, Result<(), OpenAIError>> {
+        Box::pin(async move {
+            let _requesting = self.requesting.lock().await;
+
+            let fresh =
+                self.token.lock().unwrap().as_ref().is_some_and(|token| {
+                    token.expires_at > SystemTime::now() + TOKEN_REFRESH_MARGIN
+                });
+            if !fresh {
+                let token = self.token_provider.token().await?;
+                *self.token.lock().unwrap() = Some(token);
+            }
+
+            Ok(())
+        })
+    }
+
+    fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+
+        if let Some(token) = &*self.token.lock().unwrap() {
+            headers.insert(
+                AUTHORIZATION,
+                format!("Bearer {}", token.token.expose_secret())
+                    .as_str()
+                    .parse()
+                    .unwrap(),
+            );
+        }
+
+        headers
+    }
 }
 
 /// Configuration for Azure OpenAI Service
@@ -140,6 +437,13 @@ pub struct AzureConfig {
     deployment_id: String,
     api_base: String,
     api_key: SecretString,
+    /// Deployments by model, see [AzureConfig::with_deployment].
+    deployments: HashMap<String, String>,
+    /// API versions by endpoint path, see [AzureConfig::with_endpoint_api_version].
+    api_versions: HashMap<String, String>,
+    /// Token of the token provider, shared by clones.
+    #[serde(skip)]
+    token: Option<Arc<ProvidedToken>>,
 }
 
 impl Default for AzureConfig {
@@ -151,6 +455,9 @@ impl Default for AzureConfig {
                 .into(),
             deployment_id: Default::default(),
             api_version: Default::default(),
+            deployments: Default::default(),
+            api_versions: Default::default(),
+            token: None,
         }
     }
 }
@@ -181,13 +488,72 @@ impl AzureConfig {
         self.api_base = api_base.into();
         self
     }
+
+    /// Send requests for `model` to `deployment` instead of the deployment set with
+    /// [AzureConfig::with_deployment_id], so that one client serves several models.
+    ///
+    /// The model is read from the `model` field of JSON request bodies.
+    pub fn with_deployment<M: Into<String>, D: Into<String>>(
+        mut self,
+        model: M,
+        deployment: D,
+    ) -> Self {
+        self.deployments.insert(model.into(), deployment.into());
+        self
+    }
+
+    /// Use `api_version` for the endpoint at `path`, e.g. `/chat/completions`, instead of the
+    /// version set with [AzureConfig::with_api_version].
+    pub fn with_endpoint_api_version<P: Into<String>, V: Into<String>>(
+        mut self,
+        path: P,
+        api_version: V,
+    ) -> Self {
+        self.api_versions.insert(path.into(), api_version.into());
+        self
+    }
+
+    /// Authenticate with bearer tokens of `token_provider`, e.g. of a managed identity,
+    /// instead of the API key. A new token is requested 5 minutes before the last one expires.
+    ///
+    /// ```
+    /// use async_openai::{config::{AccessToken, AzureConfig}, Client};
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// let config = AzureConfig::new()
+    ///     .with_api_base("https://my-resource-name.openai.azure.com")
+    ///     .with_api_version("2024-10-21")
+    ///     .with_deployment_id("deployment-id")
+    ///     .with_token_provider(|| async {
+    ///         // e.g. with the azure_identity crate
+    ///         Ok(AccessToken {
+    ///             token: "...".to_string().into(),
+    ///             expires_at: SystemTime::now() + Duration::from_secs(3600),
+    ///         })
+    ///     });
+    ///
+    /// let client = Client::with_config(config);
+    /// ```
+    pub fn with_token_provider<P: TokenProvider + 'static>(mut self, token_provider: P) -> Self {
+        self.token = Some(Arc::new(ProvidedToken {
+            token_provider: Box::new(token_provider),
+            token: Default::default(),
+            requesting: Default::default(),
+        }));
+        self
+    }
 }
 
 impl Config for AzureConfig {
     fn headers(&self) -> HeaderMap {
         let mut headers = HeaderMap::new();
 
-        headers.insert("api-key", self.api_key.expose_secret().parse().unwrap());
+        match &self.token {
+            Some(token) => headers.extend(token.headers()),
+            None => {
+                headers.insert("api-key", self.api_key.expose_secret().parse().unwrap());
+            }
+        }
 
         headers
     }
@@ -210,4 +576,207 @@ impl Config for AzureConfig {
     fn query(&self) -> Vec<(&str, &str)> {
         vec![("api-version", &self.api_version)]
     }
+
+    /// Replace the deployment and API version of the url of `request` when they are set
+    /// for its model and endpoint.
+    fn route(&self, request: &mut reqwest::Request) {
+        if self.deployments.is_empty() && self.api_versions.is_empty() {
+            return;
+        }
+
+        let prefix = format!("/openai/deployments/{}", self.deployment_id);
+        let Some(path) = request
+            .url()
+            .path()
+            .strip_prefix(&prefix)
+            .map(ToString::to_string)
+        else {
+            return;
+        };
+
+        if let Some(deployment) = self.deployments.get(&request_model(request)) {
+            let routed = format!("/openai/deployments/{deployment}{path}");
+            request.url_mut().set_path(&routed);
+        }
+
+        if let Some(api_version) = self.api_versions.get(&path) {
+            let query: Vec<(String, String)> = request
+                .url()
+                .query_pairs()
+                .filter(|(key, _)| key != "api-version")
+                .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                .collect();
+            request
+                .url_mut()
+                .query_pairs_mut()
+                .clear()
+                .append_pair("api-version", api_version)
+                .extend_pairs(query);
+        }
+    }
+
+    /// Request a new token of the token provider, if any, when the last one is about to expire.
+    async fn refresh(&self) -> Result<(), OpenAIError> {
+        match &self.token {
+            Some(token) => token.refresh().await,
+            None => Ok(()),
+        }
+    }
+
+    fn credentials(&self) -> Option<Arc<dyn Credentials>> {
+        self.token
+            .clone()
+            .map(|token| token as Arc<dyn Credentials>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn azure_deployment_routing() {
+        let config = AzureConfig::new()
+            .with_api_base("https://resource.openai.azure.com")
+            .with_api_version("2024-10-21")
+            .with_deployment_id("default")
+            .with_deployment("gpt-4o-mini", "mini")
+            .with_endpoint_api_version("/responses", "2025-03-01-preview");
+
+        let request = |path: &str, model: &str| {
+            let client = reqwest::Client::new();
+            let mut request = client
+                .post(config.url(path))
+                .query(&config.query())
+                .json(&serde_json::json!({"model": model}))
+                .build()
+                .unwrap();
+            config.route(&mut request);
+            request.url().to_string()
+        };
+
+        assert_eq!(
+            request("/chat/completions", "gpt-4o-mini"),
+            "https://resource.openai.azure.com/openai/deployments/mini/chat/completions?api-version=2024-10-21"
+        );
+        assert_eq!(
+            request("/chat/completions", "gpt-4o"),
+            "https://resource.openai.azure.com/openai/deployments/default/chat/completions?api-version=2024-10-21"
+        );
+        assert_eq!(
+            request("/responses", "gpt-4o-mini"),
+            "https://resource.openai.azure.com/openai/deployments/mini/responses?api-version=2025-03-01-preview"
+        );
+    }
+
+    #[test]
+    fn provider_presets() {
+        let config = OpenAIConfig::groq();
+        assert_eq!(
+            config.url("/chat/completions"),
+            "https://api.groq.com/openai/v1/chat/completions"
+        );
+
+        let config = OpenAIConfig::ollama();
+        assert_eq!(config.api_base(), "http://localhost:11434/v1");
+        assert_eq!(config.headers()[AUTHORIZATION], "Bearer ollama");
+
+        let config = OpenAIConfig::vllm("http://gpu-host:8000/v1");
+        assert_eq!(config.url("/models"), "http://gpu-host:8000/v1/models");
+    }
+
+    #[tokio::test]
+    async fn openai_api_key_provider() {
+        let keys = Arc::new(Mutex::new(vec!["second", "first"]));
+        let config = OpenAIConfig::new()
+            .with_api_key("fixed")
+            .with_api_key_provider(move || {
+                let api_key = keys.lock().unwrap().pop().unwrap();
+                async move { Ok(api_key.to_string().into()) }
+            });
+
+        assert_eq!(config.headers()[AUTHORIZATION], "Bearer fixed");
+
+        config.refresh().await.unwrap();
+        assert_eq!(config.headers()[AUTHORIZATION], "Bearer first");
+        config.clone().refresh().await.unwrap();
+        assert_eq!(config.headers()[AUTHORIZATION], "Bearer second");
+    }
+
+    #[tokio::test]
+    async fn openai_api_key_requested_once_by_concurrent_refreshes() {
+        let requested = Arc::new(Mutex::new(0));
+        let counter = requested.clone();
+        let config = OpenAIConfig::new().with_api_key_provider(move || {
+            let counter = counter.clone();
+            async move {
+                *counter.lock().unwrap() += 1;
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                Ok("provided".to_string().into())
+            }
+        });
+        let clone = config.clone();
+
+        let (first, second) = tokio::join!(config.refresh(), clone.refresh());
+        first.unwrap();
+        second.unwrap();
+        assert_eq!(*requested.lock().unwrap(), 1);
+        assert_eq!(config.headers()[AUTHORIZATION], "Bearer provided");
+
+        // later requests call the provider again
+        config.credentials().unwrap().refresh().await.unwrap();
+        assert_eq!(*requested.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn azure_token_provider() {
+        let requested = Arc::new(Mutex::new(0));
+        let counter = requested.clone();
+        let config = AzureConfig::new().with_token_provider(move || {
+            let counter = counter.clone();
+            async move {
+                *counter.lock().unwrap() += 1;
+                Ok(AccessToken {
+                    token: "token".to_string().into(),
+                    expires_at: SystemTime::now() + Duration::from_secs(3600),
+                })
+            }
+        });
+
+        assert!(config.headers().get(AUTHORIZATION).is_none());
+
+        config.refresh().await.unwrap();
+        config.clone().refresh().await.unwrap();
+        assert_eq!(*requested.lock().unwrap(), 1);
+        assert_eq!(config.headers()[AUTHORIZATION], "Bearer token");
+        assert!(config.headers().get("api-key").is_none());
+    }
+
+    #[tokio::test]
+    async fn azure_token_requested_once_by_concurrent_refreshes() {
+        let requested = Arc::new(Mutex::new(0));
+        let counter = requested.clone();
+        let config = AzureConfig::new().with_token_provider(move || {
+            let counter = counter.clone();
+            async move {
+                *counter.lock().unwrap() += 1;
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                Ok(AccessToken {
+                    token: "token".to_string().into(),
+                    expires_at: SystemTime::now() + Duration::from_secs(3600),
+                })
+            }
+        });
+        let clone = config.clone();
+
+        let (first, second) = tokio::join!(config.refresh(), clone.refresh());
+        first.unwrap();
+        second.unwrap();
+        assert_eq!(*requested.lock().unwrap(), 1);
+
+        let credentials = config.credentials().unwrap();
+        credentials.refresh().await.unwrap();
+        assert_eq!(*requested.lock().unwrap(), 1);
+        assert_eq!(credentials.headers()[AUTHORIZATION], "Bearer token");
+    }
 }
@@ -0,0 +1,94 @@
+use serde::Serialize;
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    pagination::Paginated,
+    types::{Conversation, ConversationItemList, CreateConversationItemsRequest, Item},
+    Client,
+};
+
+/// Represents the items of a [conversation](https://platform.openai.com/docs/api-reference/conversations).
+pub struct ConversationItems<'c, C: Config> {
+    /// The ID of the conversation the items belong to.
+    pub conversation_id: String,
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> ConversationItems<'c, C> {
+    pub fn new(client: &'c Client<C>, conversation_id: &str) -> Self {
+        Self {
+            client,
+            conversation_id: conversation_id.into(),
+        }
+    }
+
+    /// Add items to the conversation.
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn create(
+        &self,
+        request: CreateConversationItemsRequest,
+    ) -> Result<ConversationItemList, OpenAIError> {
+        self.client
+            .post(
+                &format!("/conversations/{}/items", self.conversation_id),
+                request,
+            )
+            .await
+    }
+
+    /// Retrieves an item of the conversation.
+    ///
+    /// Additional output data is included with the `include[]` query parameter,
+    /// e.g. `&[("include[]", Include::FileSearchCallResults)]`.
+    #[crate::byot(T0 = std::fmt::Display, T1 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn retrieve<Q>(&self, item_id: &str, query: &Q) -> Result<Item, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_with_query(
+                &format!("/conversations/{}/items/{item_id}", self.conversation_id),
+                &query,
+            )
+            .await
+    }
+
+    /// Returns the items of the conversation, paginated with the `after`, `limit` and
+    /// `order` query parameters.
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn list<Q>(&self, query: &Q) -> Result<ConversationItemList, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_with_query(
+                &format!("/conversations/{}/items", self.conversation_id),
+                &query,
+            )
+            .await
+    }
+
+    /// Every page of [ConversationItems::list], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate<Q>(&self, query: &Q) -> Result<Paginated<C, ConversationItemList>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(
+            self.client,
+            format!("/conversations/{}/items", self.conversation_id),
+            query,
+        )
+    }
+
+    /// Deletes an item of the conversation, returning the conversation.
+    #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
+    pub async fn delete(&self, item_id: &str) -> Result<Conversation, OpenAIError> {
+        self.client
+            .delete(&format!(
+                "/conversations/{}/items/{item_id}",
+                self.conversation_id
+            ))
+            .await
+    }
+}
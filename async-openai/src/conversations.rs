@@ -0,0 +1,68 @@
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    types::{
+        Conversation, CreateConversationRequest, DeleteConversationResponse,
+        UpdateConversationRequest,
+    },
+    Client, ConversationItems,
+};
+
+/// Create and manage conversations, which store the items of a [Responses](crate::Responses)
+/// conversation across sessions, devices or jobs.
+///
+/// Related guide: [Conversation state](https://platform.openai.com/docs/guides/conversation-state)
+pub struct Conversations<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Conversations<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// Call [ConversationItems] group API to manage the items of the [conversation_id] conversation.
+    pub fn items(&self, conversation_id: &str) -> ConversationItems<C> {
+        ConversationItems::new(self.client, conversation_id)
+    }
+
+    /// Create a conversation.
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn create(
+        &self,
+        request: CreateConversationRequest,
+    ) -> Result<Conversation, OpenAIError> {
+        self.client.post("/conversations", request).await
+    }
+
+    /// Retrieves a conversation.
+    #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
+    pub async fn retrieve(&self, conversation_id: &str) -> Result<Conversation, OpenAIError> {
+        self.client
+            .get(&format!("/conversations/{conversation_id}"))
+            .await
+    }
+
+    /// Updates the metadata of a conversation.
+    #[crate::byot(T0 = std::fmt::Display, T1 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn update(
+        &self,
+        conversation_id: &str,
+        request: UpdateConversationRequest,
+    ) -> Result<Conversation, OpenAIError> {
+        self.client
+            .post(&format!("/conversations/{conversation_id}"), request)
+            .await
+    }
+
+    /// Deletes a conversation. Its items are not deleted.
+    #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
+    pub async fn delete(
+        &self,
+        conversation_id: &str,
+    ) -> Result<DeleteConversationResponse, OpenAIError> {
+        self.client
+            .delete(&format!("/conversations/{conversation_id}"))
+            .await
+    }
+}
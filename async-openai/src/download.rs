@@ -1,11 +1,18 @@
+#[cfg(not(target_family = "wasm"))]
 use std::path::{Path, PathBuf};
 
 use base64::{engine::general_purpose, Engine as _};
+use bytes::Bytes;
+#[cfg(not(target_family = "wasm"))]
 use rand::{distributions::Alphanumeric, Rng};
+#[cfg(not(target_family = "wasm"))]
 use reqwest::Url;
+#[cfg(not(target_family = "wasm"))]
+use tokio::io::AsyncWriteExt;
 
 use crate::error::OpenAIError;
 
+#[cfg(not(target_family = "wasm"))]
 fn create_paths<P: AsRef<Path>>(url: &Url, base_dir: P) -> (PathBuf, PathBuf) {
     let mut dir = PathBuf::from(base_dir.as_ref());
     let mut path = dir.clone();
@@ -22,6 +29,7 @@ fn create_paths<P: AsRef<Path>>(url: &Url, base_dir: P) -> (PathBuf, PathBuf) {
     (dir, path)
 }
 
+#[cfg(not(target_family = "wasm"))]
 pub(crate) async fn download_url<P: AsRef<Path>>(
     url: &str,
     dir: P,
@@ -56,6 +64,20 @@ pub(crate) async fn download_url<P: AsRef<Path>>(
     Ok(file_path)
 }
 
+/// Length of the base64 chunks decoded at a time when saving an image, a multiple of 4.
+#[cfg(not(target_family = "wasm"))]
+const B64_CHUNK_LEN: usize = 64 * 1024;
+
+/// Decode `b64` into a single buffer of the decoded size.
+pub(crate) fn decode_b64(b64: &str) -> Result<Bytes, OpenAIError> {
+    general_purpose::STANDARD
+        .decode(b64)
+        .map(Bytes::from)
+        .map_err(|e| OpenAIError::InvalidArgument(format!("invalid base64 image: {e}")))
+}
+
+/// Decode `b64` into a new file in `dir`, chunk by chunk rather than in memory as a whole.
+#[cfg(not(target_family = "wasm"))]
 pub(crate) async fn save_b64<P: AsRef<Path>>(b64: &str, dir: P) -> Result<PathBuf, OpenAIError> {
     let filename: String = rand::thread_rng()
         .sample_iter(&Alphanumeric)
@@ -66,15 +88,24 @@ pub(crate) async fn save_b64<P: AsRef<Path>>(b64: &str, dir: P) -> Result<PathBu
     let filename = format!("{filename}.png");
 
     let path = PathBuf::from(dir.as_ref()).join(filename);
+    let save_error = |e: &dyn std::fmt::Display| {
+        OpenAIError::FileSaveError(format!("{e}, path: {}", path.display()))
+    };
 
-    tokio::fs::write(
-        path.as_path(),
-        general_purpose::STANDARD
-            .decode(b64)
-            .map_err(|e| OpenAIError::FileSaveError(e.to_string()))?,
-    )
-    .await
-    .map_err(|e| OpenAIError::FileSaveError(format!("{}, path: {}", e, path.display())))?;
+    let mut file = tokio::fs::File::create(path.as_path())
+        .await
+        .map_err(|e| save_error(&e))?;
+    let mut buffer = vec![0; B64_CHUNK_LEN / 4 * 3];
+
+    for chunk in b64.as_bytes().chunks(B64_CHUNK_LEN) {
+        let len = general_purpose::STANDARD
+            .decode_slice(chunk, &mut buffer)
+            .map_err(|e| OpenAIError::FileSaveError(e.to_string()))?;
+        file.write_all(&buffer[..len])
+            .await
+            .map_err(|e| save_error(&e))?;
+    }
+    file.flush().await.map_err(|e| save_error(&e))?;
 
     Ok(path)
 }
@@ -1,5 +1,7 @@
 //! Errors originating from API calls, parsing responses, and reading-or-writing to the file system.
-use serde::Deserialize;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::types::ResponseMetadata;
 
 #[derive(Debug, thiserror::Error)]
 pub enum OpenAIError {
@@ -10,35 +12,184 @@ pub enum OpenAIError {
     #[error("{0}")]
     ApiError(ApiError),
     /// Error when a response cannot be deserialized into a Rust type
+    #[deprecated(note = "deserialization errors are returned as OpenAIError::Deserialization")]
     #[error("failed to deserialize api response: {0}")]
     JSONDeserialize(serde_json::Error),
+    /// Error when a response cannot be deserialized into a Rust type
+    #[error("failed to deserialize api response at `{path}`: {source}")]
+    Deserialization {
+        source: serde_json::Error,
+        /// Path of the value which failed to deserialize, e.g. `choices[0].message.role`
+        path: String,
+        /// Response body, truncated to [MAX_BODY_SNIPPET_LEN] bytes
+        body: String,
+    },
     /// Error on the client side when saving file to file system
     #[error("failed to save file: {0}")]
     FileSaveError(String),
     /// Error on the client side when reading file from file system
     #[error("failed to read file: {0}")]
     FileReadError(String),
+    /// Error response which is not an OpenAI error object, e.g. the HTML page of a gateway
+    #[error("http status {status}: {body_snippet}{}", request_id_suffix(metadata))]
+    Http {
+        status: u16,
+        /// Response body, truncated to [MAX_BODY_SNIPPET_LEN] bytes
+        body_snippet: String,
+        /// `x-request-id`, `Retry-After` and rate limit headers of the response
+        metadata: ResponseMetadata,
+    },
     /// Error on SSE streaming
     #[error("stream failed: {0}")]
     StreamError(String),
+    /// Error when calling a tool of a [crate::tools::ToolSet]: unknown name,
+    /// arguments not matching its parameters, or failure of the tool itself
+    #[error("tool `{name}` failed: {message}")]
+    Tool { name: String, message: String },
+    /// The model refused to answer a request for [Structured Outputs](https://platform.openai.com/docs/guides/structured-outputs),
+    /// e.g. for safety reasons, with the given explanation
+    #[error("model refused: {0}")]
+    Refusal(String),
     /// Error from client side validation
     /// or when builder fails to build request before making API call
     #[error("invalid args: {0}")]
     InvalidArgument(String),
 }
 
+impl OpenAIError {
+    /// HTTP status, `x-request-id`, `Retry-After` and rate limit headers of the error response,
+    /// for [OpenAIError::ApiError] and [OpenAIError::Http].
+    pub fn metadata(&self) -> Option<&ResponseMetadata> {
+        match self {
+            OpenAIError::ApiError(e) => e.metadata.as_ref(),
+            OpenAIError::Http { metadata, .. } => Some(metadata),
+            _ => None,
+        }
+    }
+
+    /// The `x-request-id` of the error response, to include when reporting issues to OpenAI support.
+    pub fn request_id(&self) -> Option<&str> {
+        self.metadata()?.request_id.as_deref()
+    }
+}
+
+fn request_id_suffix(metadata: &ResponseMetadata) -> String {
+    match &metadata.request_id {
+        Some(request_id) => format!(" (request id: {request_id})"),
+        None => String::new(),
+    }
+}
+
 /// OpenAI API returns error object on failure
 #[derive(Debug, Deserialize, Clone)]
 pub struct ApiError {
     pub message: String,
-    pub r#type: Option<String>,
+    pub r#type: Option<ApiErrorType>,
     pub param: Option<String>,
-    pub code: Option<String>,
+    pub code: Option<ApiErrorCode>,
+    /// HTTP status, `x-request-id`, `Retry-After` and rate limit headers of the response
+    /// carrying this error. Not available for errors received as events of a stream.
+    #[serde(skip)]
+    pub metadata: Option<ResponseMetadata>,
+}
+
+/// The `type` of an [ApiError]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiErrorType {
+    InvalidRequestError,
+    AuthenticationError,
+    PermissionError,
+    NotFoundError,
+    RateLimitError,
+    /// Rate limited on requests per time unit
+    Requests,
+    /// Rate limited on tokens per time unit
+    Tokens,
+    InsufficientQuota,
+    ServerError,
+    /// Any type not known to this library
+    #[serde(untagged)]
+    Other(String),
+}
+
+/// The `code` of an [ApiError]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiErrorCode {
+    InvalidApiKey,
+    InsufficientQuota,
+    RateLimitExceeded,
+    ContextLengthExceeded,
+    ModelNotFound,
+    InvalidValue,
+    InvalidType,
+    MissingRequiredParameter,
+    UnknownParameter,
+    UnsupportedParameter,
+    UnsupportedValue,
+    ContentPolicyViolation,
+    StringAboveMaxLength,
+    InvalidImage,
+    ServerError,
+    /// Any code not known to this library
+    #[serde(untagged)]
+    Other(String),
+}
+
+/// Formats as the `snake_case` wire value
+impl std::fmt::Display for ApiErrorType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::InvalidRequestError => "invalid_request_error",
+                Self::AuthenticationError => "authentication_error",
+                Self::PermissionError => "permission_error",
+                Self::NotFoundError => "not_found_error",
+                Self::RateLimitError => "rate_limit_error",
+                Self::Requests => "requests",
+                Self::Tokens => "tokens",
+                Self::InsufficientQuota => "insufficient_quota",
+                Self::ServerError => "server_error",
+                Self::Other(other) => other,
+            }
+        )
+    }
+}
+
+/// Formats as the `snake_case` wire value
+impl std::fmt::Display for ApiErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::InvalidApiKey => "invalid_api_key",
+                Self::InsufficientQuota => "insufficient_quota",
+                Self::RateLimitExceeded => "rate_limit_exceeded",
+                Self::ContextLengthExceeded => "context_length_exceeded",
+                Self::ModelNotFound => "model_not_found",
+                Self::InvalidValue => "invalid_value",
+                Self::InvalidType => "invalid_type",
+                Self::MissingRequiredParameter => "missing_required_parameter",
+                Self::UnknownParameter => "unknown_parameter",
+                Self::UnsupportedParameter => "unsupported_parameter",
+                Self::UnsupportedValue => "unsupported_value",
+                Self::ContentPolicyViolation => "content_policy_violation",
+                Self::StringAboveMaxLength => "string_above_max_length",
+                Self::InvalidImage => "invalid_image",
+                Self::ServerError => "server_error",
+                Self::Other(other) => other,
+            }
+        )
+    }
 }
 
 impl std::fmt::Display for ApiError {
     /// If all fields are available, `ApiError` is formatted as:
-    /// `{type}: {message} (param: {param}) (code: {code})`
+    /// `{type}: {message} (param: {param}) (code: {code}) (request id: {request_id})`
     /// Otherwise, missing fields will be ignored.
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut parts = Vec::new();
@@ -57,6 +208,10 @@ impl std::fmt::Display for ApiError {
             parts.push(format!("(code: {code})"));
         }
 
+        if let Some(request_id) = self.metadata.as_ref().and_then(|m| m.request_id.as_ref()) {
+            parts.push(format!("(request id: {request_id})"));
+        }
+
         write!(f, "{}", parts.join(" "))
     }
 }
@@ -67,10 +222,134 @@ pub(crate) struct WrappedError {
     pub(crate) error: ApiError,
 }
 
-pub(crate) fn map_deserialization_error(e: serde_json::Error, bytes: &[u8]) -> OpenAIError {
-    tracing::error!(
-        "failed deserialization of: {}",
-        String::from_utf8_lossy(bytes)
-    );
-    OpenAIError::JSONDeserialize(e)
+/// Error for the body of a non-successful response: [OpenAIError::ApiError] when it is
+/// an OpenAI error object, [OpenAIError::Http] otherwise.
+pub(crate) fn map_error_response(metadata: ResponseMetadata, bytes: &[u8]) -> OpenAIError {
+    match serde_json::from_slice::<WrappedError>(bytes) {
+        Ok(mut wrapped_error) => {
+            wrapped_error.error.metadata = Some(metadata);
+            OpenAIError::ApiError(wrapped_error.error)
+        }
+        Err(_) => OpenAIError::Http {
+            status: metadata.status,
+            body_snippet: body_snippet(bytes),
+            metadata,
+        },
+    }
+}
+
+/// Maximum length of response bodies carried by errors
+pub const MAX_BODY_SNIPPET_LEN: usize = 2048;
+
+/// Deserialize a response body, on failure the error carries the path of the offending value
+/// and the (truncated) body.
+pub(crate) fn deserialize<O: DeserializeOwned>(bytes: &[u8]) -> Result<O, OpenAIError> {
+    // simd-json parses in place and its errors have no path,
+    // on failure the body is parsed again below for a detailed error
+    #[cfg(feature = "simd-json")]
+    {
+        if let Ok(output) = simd_json::serde::from_slice(&mut bytes.to_vec()) {
+            return Ok(output);
+        }
+    }
+
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+
+    let output = serde_path_to_error::deserialize(&mut deserializer).map_err(|e| {
+        let path = e.path().to_string();
+        map_deserialization_error(e.into_inner(), path, bytes)
+    })?;
+
+    // Trailing characters are an error, same as serde_json::from_slice
+    deserializer
+        .end()
+        .map_err(|e| map_deserialization_error(e, ".".into(), bytes))?;
+
+    Ok(output)
+}
+
+pub(crate) fn map_deserialization_error(
+    e: serde_json::Error,
+    path: String,
+    bytes: &[u8],
+) -> OpenAIError {
+    let body = body_snippet(bytes);
+    tracing::debug!("failed deserialization at `{path}` of: {body}");
+    OpenAIError::Deserialization {
+        source: e,
+        path,
+        body,
+    }
+}
+
+/// Lossy UTF-8 of `bytes`, truncated to [MAX_BODY_SNIPPET_LEN]
+pub(crate) fn body_snippet(bytes: &[u8]) -> String {
+    if bytes.len() <= MAX_BODY_SNIPPET_LEN {
+        return String::from_utf8_lossy(bytes).into_owned();
+    }
+
+    format!(
+        "{}... ({} bytes truncated)",
+        String::from_utf8_lossy(&bytes[..MAX_BODY_SNIPPET_LEN]),
+        bytes.len() - MAX_BODY_SNIPPET_LEN
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Chunk {
+        id: String,
+        choices: Vec<Choice>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Choice {
+        index: u32,
+    }
+
+    #[test]
+    fn deserialize_body() {
+        let chunk: Chunk = deserialize(br#"{"id": "1", "choices": [{"index": 0}]}"#).unwrap();
+        assert_eq!(
+            chunk,
+            Chunk {
+                id: "1".into(),
+                choices: vec![Choice { index: 0 }]
+            }
+        );
+
+        let error = deserialize::<Chunk>(br#"{"id": "1", "choices": [{"index": "0"}]}"#);
+        assert!(matches!(
+            error,
+            Err(OpenAIError::Deserialization { path, .. }) if path == "choices[0].index"
+        ));
+
+        assert!(deserialize::<Chunk>(br#"{"id": "1", "choices": []} x"#).is_err());
+    }
+
+    #[test]
+    fn request_id_of_error_responses() {
+        let metadata = ResponseMetadata {
+            status: 502,
+            request_id: Some("req_123".into()),
+            ..Default::default()
+        };
+
+        let error = map_error_response(metadata.clone(), b"<html>Bad Gateway</html>");
+        assert_eq!(error.request_id(), Some("req_123"));
+        assert_eq!(
+            error.to_string(),
+            "http status 502: <html>Bad Gateway</html> (request id: req_123)"
+        );
+
+        let error = map_error_response(
+            metadata,
+            br#"{"error": {"message": "Bad gateway", "type": "server_error", "param": null, "code": null}}"#,
+        );
+        assert_eq!(error.request_id(), Some("req_123"));
+        assert_eq!(error.metadata().unwrap().status, 502);
+    }
 }
@@ -1,10 +1,16 @@
 use bytes::Bytes;
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
     config::Config,
     error::OpenAIError,
-    types::{CreateFileRequest, DeleteFileResponse, ListFilesResponse, OpenAIFile},
+    json_stream::{self, JsonLinesParser},
+    pagination::Paginated,
+    runtime::{BoxStream, MaybeSend},
+    types::{
+        CreateFileRequest, DeleteFileResponse, FileContentStream, ListFilesResponse, OpenAIFile,
+        OpenAIFileStream,
+    },
     Client,
 };
 
@@ -45,6 +51,23 @@ impl<'c, C: Config> Files<'c, C> {
         self.client.get_with_query("/files", &query).await
     }
 
+    /// Every page of [Files::list], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate<Q>(&self, query: &Q) -> Result<Paginated<C, ListFilesResponse>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(self.client, "/files".into(), query)
+    }
+
+    /// Same as [Files::list], with the files deserialized one by one as the response is received,
+    /// instead of after the whole response.
+    pub async fn list_stream<Q>(&self, query: &Q) -> Result<OpenAIFileStream, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client.get_list_stream("/files", &query).await
+    }
+
     /// Returns information about a specific file.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, file_id: &str) -> Result<OpenAIFile, OpenAIError> {
@@ -60,11 +83,56 @@ impl<'c, C: Config> Files<'c, C> {
     }
 
     /// Returns the contents of the specified file
+    #[crate::byot(T0 = std::fmt::Display, R = From<bytes::Bytes>)]
     pub async fn content(&self, file_id: &str) -> Result<Bytes, OpenAIError> {
         self.client
             .get_raw(format!("/files/{file_id}/content").as_str())
             .await
     }
+
+    /// Returns the contents of the specified file in chunks as they are received,
+    /// to write large files to disk without holding them in memory.
+    pub async fn content_stream(&self, file_id: &str) -> Result<FileContentStream, OpenAIError> {
+        self.client
+            .get_bytes_stream(format!("/files/{file_id}/content").as_str(), &())
+            .await
+    }
+
+    /// Returns the contents of the specified [JSON Lines](https://jsonlines.org) file,
+    /// deserializing each line as it is received.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use async_openai::{types::BatchRequestOutput, Client};
+    /// use futures::StreamExt;
+    ///
+    /// let client = Client::new();
+    /// let batch = client.batches().retrieve("batch_abc123").await?;
+    ///
+    /// if let Some(output_file_id) = batch.output_file_id {
+    ///     let mut outputs = client
+    ///         .files()
+    ///         .content_jsonl::<BatchRequestOutput>(&output_file_id)
+    ///         .await?;
+    ///
+    ///     while let Some(output) = outputs.next().await {
+    ///         let output = output?;
+    ///         println!("{}: {:?}", output.custom_id, output.response);
+    ///     }
+    /// }
+    /// # Ok::<(), async_openai::error::OpenAIError>(())
+    /// # });
+    /// ```
+    pub async fn content_jsonl<T>(
+        &self,
+        file_id: &str,
+    ) -> Result<BoxStream<'static, Result<T, OpenAIError>>, OpenAIError>
+    where
+        T: DeserializeOwned + MaybeSend + 'static,
+    {
+        let bytes = self.content_stream(file_id).await?;
+        Ok(json_stream::items(bytes, JsonLinesParser::default()))
+    }
 }
 
 #[cfg(test)]
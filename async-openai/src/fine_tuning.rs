@@ -3,6 +3,7 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::Paginated,
     types::{
         CreateFineTuningJobRequest, FineTuningJob, ListFineTuningJobCheckpointsResponse,
         ListFineTuningJobEventsResponse, ListPaginatedFineTuningJobsResponse,
@@ -49,6 +50,17 @@ impl<'c, C: Config> FineTuning<'c, C> {
             .await
     }
 
+    /// Every page of [FineTuning::list_paginated], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate<Q>(
+        &self,
+        query: &Q,
+    ) -> Result<Paginated<C, ListPaginatedFineTuningJobsResponse>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(self.client, "/fine_tuning/jobs".into(), query)
+    }
+
     /// Gets info about the fine-tune job.
     ///
     /// [Learn more about Fine-tuning](https://platform.openai.com/docs/guides/fine-tuning)
@@ -88,6 +100,22 @@ impl<'c, C: Config> FineTuning<'c, C> {
             .await
     }
 
+    /// Every page of [FineTuning::list_events], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate_events<Q>(
+        &self,
+        fine_tuning_job_id: &str,
+        query: &Q,
+    ) -> Result<Paginated<C, ListFineTuningJobEventsResponse>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(
+            self.client,
+            format!("/fine_tuning/jobs/{fine_tuning_job_id}/events"),
+            query,
+        )
+    }
+
     #[crate::byot(T0 = std::fmt::Display, T1 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn list_checkpoints<Q>(
         &self,
@@ -104,4 +132,20 @@ impl<'c, C: Config> FineTuning<'c, C> {
             )
             .await
     }
+
+    /// Every page of [FineTuning::list_checkpoints], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate_checkpoints<Q>(
+        &self,
+        fine_tuning_job_id: &str,
+        query: &Q,
+    ) -> Result<Paginated<C, ListFineTuningJobCheckpointsResponse>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(
+            self.client,
+            format!("/fine_tuning/jobs/{fine_tuning_job_id}/checkpoints"),
+            query,
+        )
+    }
 }
@@ -0,0 +1,367 @@
+//! Conversation history kept within a token budget.
+//!
+//! ```no_run
+//! use async_openai::{
+//!     history::{ChatHistory, TruncationStrategy},
+//!     tokens::Tokenizer,
+//!     types::{
+//!         ChatCompletionRequestSystemMessage, ChatCompletionRequestUserMessage,
+//!         CreateChatCompletionRequestArgs,
+//!     },
+//!     Client,
+//! };
+//!
+//! # tokio_test::block_on(async {
+//! let client = Client::new();
+//! let mut history = ChatHistory::new(Tokenizer::for_model("gpt-4o")?, 8_000)
+//!     .with_strategy(TruncationStrategy::SummarizeOverflow {
+//!         model: "gpt-4o-mini".into(),
+//!     });
+//!
+//! history.push(ChatCompletionRequestSystemMessage::from("You are a helpful assistant."));
+//!
+//! loop {
+//!     # let user_input = String::new();
+//!     history.push(ChatCompletionRequestUserMessage::from(user_input));
+//!     history.summarize_overflow(&client).await?;
+//!
+//!     let request = CreateChatCompletionRequestArgs::default()
+//!         .model("gpt-4o")
+//!         .messages(history.to_messages())
+//!         .build()?;
+//!
+//!     let response = client.chat().create(request).await?;
+//!     # break;
+//! }
+//! # Ok::<(), async_openai::error::OpenAIError>(())
+//! # });
+//! ```
+//!
+//! For a single request, [fit_to_context] truncates its messages to the context window of
+//! its model.
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    tokens::{TokenCount, Tokenizer},
+    types::{
+        ChatCompletionRequestMessage, ChatCompletionRequestSystemMessage,
+        ChatCompletionRequestUserMessage, CreateChatCompletionRequest, ModelId,
+    },
+    Client,
+};
+
+/// Tokens priming the reply of the assistant, see [crate::tokens].
+const TOKENS_PER_REPLY: usize = 3;
+
+const SUMMARY_INSTRUCTIONS: &str = "Summarize the conversation below, given as one JSON message \
+per line, for an assistant which will continue it without seeing those messages. Keep facts, \
+decisions, preferences of the user and open questions. Be concise.";
+
+/// How [ChatHistory] fits the conversation in its budget.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TruncationStrategy {
+    /// Drop the oldest messages.
+    DropOldest,
+    /// Drop the oldest messages, except system and developer messages.
+    KeepSystem,
+    /// Replace the oldest messages, except system and developer messages, by a summary
+    /// generated by `model` with [ChatHistory::summarize_overflow].
+    SummarizeOverflow { model: String },
+}
+
+/// Messages of a conversation, of which the most recent fitting in a token budget
+/// make the `messages` of the next request.
+///
+/// All messages are kept in the history, truncation only applies to [ChatHistory::to_messages].
+/// Tool messages are never sent without the assistant message with their tool calls.
+#[derive(Debug, Clone)]
+pub struct ChatHistory {
+    messages: Vec<ChatCompletionRequestMessage>,
+    /// Number of leading non-system messages replaced by `summary`.
+    summarized: usize,
+    summary: Option<String>,
+    tokenizer: Tokenizer,
+    budget: usize,
+    strategy: TruncationStrategy,
+}
+
+impl ChatHistory {
+    /// Empty history keeping the prompt within `budget` tokens, with [TruncationStrategy::KeepSystem].
+    pub fn new(tokenizer: Tokenizer, budget: usize) -> Self {
+        Self {
+            messages: vec![],
+            summarized: 0,
+            summary: None,
+            tokenizer,
+            budget,
+            strategy: TruncationStrategy::KeepSystem,
+        }
+    }
+
+    /// Empty history for `model`, keeping the prompt within its context window
+    /// minus `max_output_tokens`. Fails for models without a known context window.
+    pub fn for_model<M: Into<ModelId>>(
+        model: M,
+        max_output_tokens: usize,
+    ) -> Result<Self, OpenAIError> {
+        let model = model.into();
+        let context_window = model.context_window().ok_or_else(|| {
+            OpenAIError::InvalidArgument(format!("unknown context window of {model}"))
+        })?;
+        let tokenizer = Tokenizer::for_model(model.as_str())?;
+
+        Ok(Self::new(
+            tokenizer,
+            (context_window as usize).saturating_sub(max_output_tokens),
+        ))
+    }
+
+    pub fn with_strategy(mut self, strategy: TruncationStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    pub fn push<M: Into<ChatCompletionRequestMessage>>(&mut self, message: M) {
+        self.messages.push(message.into());
+    }
+
+    /// Add the messages of a turn, e.g. the assistant message and the tool messages answering its tool calls.
+    pub fn extend<I>(&mut self, messages: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<ChatCompletionRequestMessage>,
+    {
+        self.messages.extend(messages.into_iter().map(Into::into));
+    }
+
+    /// All the messages of the conversation.
+    pub fn messages(&self) -> &[ChatCompletionRequestMessage] {
+        &self.messages
+    }
+
+    /// Summary of the oldest messages made by [ChatHistory::summarize_overflow].
+    pub fn summary(&self) -> Option<&str> {
+        self.summary.as_deref()
+    }
+
+    pub fn clear(&mut self) {
+        self.messages.clear();
+        self.summarized = 0;
+        self.summary = None;
+    }
+
+    /// Messages for the next request: system and developer messages depending on the strategy,
+    /// the summary if any, and the most recent messages fitting in the remaining budget.
+    ///
+    /// The last message is always included, even if it exceeds the budget on its own.
+    pub fn to_messages(&self) -> Vec<ChatCompletionRequestMessage> {
+        let kept = self.kept();
+        let summary = self.summary_message();
+
+        let mut messages = vec![];
+        let mut summary_inserted = summary.is_none();
+        let mut other = 0;
+
+        for (message, kept) in self.messages.iter().zip(kept) {
+            if !is_system(message) || self.strategy == TruncationStrategy::DropOldest {
+                other += 1;
+                // the summary replaces the first messages which are not pinned
+                if !summary_inserted && other > self.summarized {
+                    messages.extend(summary.clone());
+                    summary_inserted = true;
+                }
+            }
+            if kept {
+                messages.push(message.clone());
+            }
+        }
+
+        if !summary_inserted {
+            messages.extend(summary);
+        }
+
+        messages
+    }
+
+    /// Estimate of the prompt tokens of [ChatHistory::to_messages].
+    pub fn token_count(&self) -> usize {
+        self.to_messages().token_count(&self.tokenizer) + TOKENS_PER_REPLY
+    }
+
+    /// With [TruncationStrategy::SummarizeOverflow], summarize the messages which don't fit
+    /// in the budget anymore, together with the previous summary.
+    ///
+    /// Returns whether a summary was generated.
+    pub async fn summarize_overflow<C: Config>(
+        &mut self,
+        client: &Client<C>,
+    ) -> Result<bool, OpenAIError> {
+        let TruncationStrategy::SummarizeOverflow { model } = &self.strategy else {
+            return Ok(false);
+        };
+
+        let kept = self.kept();
+        let overflow: Vec<&ChatCompletionRequestMessage> = self
+            .messages
+            .iter()
+            .zip(&kept)
+            .filter(|(message, kept)| !**kept && !is_system(message))
+            .map(|(message, _)| message)
+            .skip(self.summarized)
+            .collect();
+
+        if overflow.is_empty() {
+            return Ok(false);
+        }
+
+        let mut transcript = vec![];
+        if let Some(summary) = self.summary_message() {
+            transcript.push(summary);
+        }
+        transcript.extend(overflow.iter().copied().cloned());
+
+        let transcript = transcript
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| OpenAIError::InvalidArgument(e.to_string()))?;
+
+        let request = CreateChatCompletionRequest {
+            model: model.clone(),
+            messages: vec![
+                ChatCompletionRequestSystemMessage::from(SUMMARY_INSTRUCTIONS).into(),
+                ChatCompletionRequestUserMessage::from(transcript.join("\n")).into(),
+            ],
+            ..Default::default()
+        };
+
+        let response = client.chat().create(request).await?;
+        let summary = response
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content)
+            .ok_or_else(|| {
+                OpenAIError::InvalidArgument("summary response has no content".into())
+            })?;
+
+        self.summarized += overflow.len();
+        self.summary = Some(summary);
+
+        Ok(true)
+    }
+
+    fn summary_message(&self) -> Option<ChatCompletionRequestMessage> {
+        self.summary.as_ref().map(|summary| {
+            ChatCompletionRequestSystemMessage::from(format!(
+                "Summary of the earlier conversation:\n{summary}"
+            ))
+            .into()
+        })
+    }
+
+    /// Which messages fit in the budget.
+    fn kept(&self) -> Vec<bool> {
+        let pin_system = self.strategy != TruncationStrategy::DropOldest;
+        let mut kept = vec![false; self.messages.len()];
+
+        let mut remaining = self.budget.saturating_sub(TOKENS_PER_REPLY);
+        if let Some(summary) = self.summary_message() {
+            remaining = remaining.saturating_sub(summary.token_count(&self.tokenizer));
+        }
+
+        if pin_system {
+            for (i, message) in self.messages.iter().enumerate() {
+                if is_system(message) {
+                    kept[i] = true;
+                    remaining = remaining.saturating_sub(message.token_count(&self.tokenizer));
+                }
+            }
+        }
+
+        // messages replaced by the summary are never sent
+        let summarized = self
+            .messages
+            .iter()
+            .enumerate()
+            .filter(|(_, message)| !(pin_system && is_system(message)))
+            .nth(self.summarized)
+            .map(|(i, _)| i)
+            .unwrap_or(self.messages.len());
+
+        // most recent messages, as long as they fit
+        let mut first = self.messages.len();
+        for (i, message) in self.messages.iter().enumerate().skip(summarized).rev() {
+            if pin_system && is_system(message) {
+                continue;
+            }
+
+            let tokens = message.token_count(&self.tokenizer);
+            if tokens > remaining && first != self.messages.len() {
+                break;
+            }
+            remaining = remaining.saturating_sub(tokens);
+            kept[i] = true;
+            first = i;
+        }
+
+        // tool messages answering a dropped assistant message would be rejected
+        for (i, message) in self.messages.iter().enumerate().skip(first) {
+            match message {
+                ChatCompletionRequestMessage::Tool(_) => kept[i] = false,
+                _ if pin_system && is_system(message) => {}
+                _ => break,
+            }
+        }
+
+        kept
+    }
+}
+
+/// Truncate the `messages` of `request`, the oldest first, or summarize them with
+/// [TruncationStrategy::SummarizeOverflow], so that the prompt fits in the context window of
+/// its model with room for the output: `max_completion_tokens`, or `max_tokens`, or else the
+/// maximum output of the model. The `tools` of the request are counted too.
+///
+/// Fails for models without a known context window, see [ModelId::info].
+#[allow(deprecated)]
+pub async fn fit_to_context<C: Config>(
+    client: &Client<C>,
+    request: &mut CreateChatCompletionRequest,
+    strategy: TruncationStrategy,
+) -> Result<(), OpenAIError> {
+    let model = ModelId::from(request.model.as_str());
+    let info = model.info().ok_or_else(|| {
+        OpenAIError::InvalidArgument(format!("unknown context window of {model}"))
+    })?;
+    let tokenizer = Tokenizer::for_model(model.as_str())?;
+
+    let max_output_tokens = request
+        .max_completion_tokens
+        .or(request.max_tokens)
+        .unwrap_or(info.max_output_tokens);
+    let tools = request
+        .tools
+        .as_ref()
+        .filter(|tools| !tools.is_empty())
+        .and_then(|tools| serde_json::to_string(tools).ok())
+        .map(|tools| tokenizer.count(&tools))
+        .unwrap_or_default();
+    let budget = (info.context_window as usize)
+        .saturating_sub(max_output_tokens as usize)
+        .saturating_sub(tools);
+
+    let mut history = ChatHistory::new(tokenizer, budget).with_strategy(strategy);
+    history.extend(std::mem::take(&mut request.messages));
+    history.summarize_overflow(client).await?;
+    request.messages = history.to_messages();
+
+    Ok(())
+}
+
+fn is_system(message: &ChatCompletionRequestMessage) -> bool {
+    matches!(
+        message,
+        ChatCompletionRequestMessage::System(_) | ChatCompletionRequestMessage::Developer(_)
+    )
+}
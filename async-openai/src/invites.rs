@@ -3,6 +3,7 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::Paginated,
     types::{Invite, InviteDeleteResponse, InviteListResponse, InviteRequest},
     Client,
 };
@@ -28,6 +29,14 @@ impl<'c, C: Config> Invites<'c, C> {
             .await
     }
 
+    /// Every page of [Invites::list], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate<Q>(&self, query: &Q) -> Result<Paginated<C, InviteListResponse>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(self.client, "/organization/invites".into(), query)
+    }
+
     /// Retrieves an invite.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, invite_id: &str) -> Result<Invite, OpenAIError> {
@@ -0,0 +1,257 @@
+//! Incremental deserialization of large response bodies, yielding items as soon as
+//! they are received instead of after the whole body has been read.
+use std::collections::VecDeque;
+
+use bytes::Bytes;
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    error::{deserialize, OpenAIError},
+    runtime::{BoxStream, MaybeSend},
+};
+
+/// Incremental parser splitting chunks of a body into the raw JSON of its items.
+pub(crate) trait ItemParser: Send + 'static {
+    /// Parse `chunk`, pushing each complete item to `items`.
+    fn feed(&mut self, chunk: &[u8], items: &mut VecDeque<Bytes>);
+
+    /// The body is complete, push the remaining item if any.
+    fn finish(&mut self, items: &mut VecDeque<Bytes>) -> Result<(), OpenAIError>;
+}
+
+/// Items of [JSON Lines](https://jsonlines.org), e.g. the output file of a batch.
+/// Blank lines are skipped.
+#[derive(Debug, Default)]
+pub(crate) struct JsonLinesParser {
+    /// Incomplete line carried over from the previous chunk.
+    line: Vec<u8>,
+}
+
+impl JsonLinesParser {
+    fn push_line(&mut self, items: &mut VecDeque<Bytes>) {
+        let mut line = std::mem::take(&mut self.line);
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        if !is_blank(&line) {
+            items.push_back(line.into());
+        }
+    }
+}
+
+impl ItemParser for JsonLinesParser {
+    fn feed(&mut self, chunk: &[u8], items: &mut VecDeque<Bytes>) {
+        for &byte in chunk {
+            match byte {
+                b'\n' => self.push_line(items),
+                _ => self.line.push(byte),
+            }
+        }
+    }
+
+    fn finish(&mut self, items: &mut VecDeque<Bytes>) -> Result<(), OpenAIError> {
+        self.push_line(items);
+        Ok(())
+    }
+}
+
+/// Elements of the `data` array of a list object, e.g. `{"object": "list", "data": [...]}`.
+/// Other fields of the list are skipped.
+#[derive(Debug, Default)]
+pub(crate) struct ListItemsParser {
+    /// Nesting of objects and arrays at the current byte.
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    /// String being read at the top level of the list, possibly a key.
+    string: Vec<u8>,
+    /// Last string completed at the top level of the list.
+    last_string: Option<Vec<u8>>,
+    /// Key of the value being read at the top level of the list.
+    key: Option<Vec<u8>>,
+    in_data: bool,
+    found_data: bool,
+    /// Element of `data` being read.
+    item: Vec<u8>,
+}
+
+impl ListItemsParser {
+    fn push_item(&mut self, items: &mut VecDeque<Bytes>) {
+        let mut item = std::mem::take(&mut self.item);
+        while item.last().is_some_and(u8::is_ascii_whitespace) {
+            item.pop();
+        }
+        if !item.is_empty() {
+            items.push_back(item.into());
+        }
+    }
+}
+
+impl ItemParser for ListItemsParser {
+    fn feed(&mut self, chunk: &[u8], items: &mut VecDeque<Bytes>) {
+        for &byte in chunk {
+            let top_level = self.depth == 1;
+
+            if self.in_string {
+                if std::mem::take(&mut self.escaped) {
+                    // escaped character
+                } else if byte == b'\\' {
+                    self.escaped = true;
+                } else if byte == b'"' {
+                    self.in_string = false;
+                    if top_level {
+                        self.last_string = Some(std::mem::take(&mut self.string));
+                    }
+                }
+
+                if top_level && self.in_string {
+                    self.string.push(byte);
+                }
+                if self.in_data {
+                    self.item.push(byte);
+                }
+                continue;
+            }
+
+            match byte {
+                b'"' => {
+                    self.in_string = true;
+                    self.string.clear();
+                }
+                b':' if top_level => self.key = self.last_string.take(),
+                b',' if top_level => self.key = None,
+                b'[' if top_level && self.key.as_deref() == Some(b"data") => {
+                    self.depth += 1;
+                    self.in_data = true;
+                    self.found_data = true;
+                    continue;
+                }
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' => {
+                    self.depth = self.depth.saturating_sub(1);
+                    if self.in_data && self.depth == 1 {
+                        self.push_item(items);
+                        self.in_data = false;
+                        continue;
+                    }
+                }
+                b',' if self.in_data && self.depth == 2 => {
+                    self.push_item(items);
+                    continue;
+                }
+                _ => {}
+            }
+
+            // whitespace before an element
+            if self.in_data && !(self.item.is_empty() && byte.is_ascii_whitespace()) {
+                self.item.push(byte);
+            }
+        }
+    }
+
+    fn finish(&mut self, _items: &mut VecDeque<Bytes>) -> Result<(), OpenAIError> {
+        if !self.found_data || self.in_data {
+            return Err(OpenAIError::StreamError(
+                "response body is not a complete list".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn is_blank(bytes: &[u8]) -> bool {
+    bytes.iter().all(u8::is_ascii_whitespace)
+}
+
+/// Deserialize the items found by `parser` in the chunks of `bytes`, as they are received.
+///
+/// Chunks are read only as the returned stream is polled. The stream ends after an error.
+pub(crate) fn items<O, P>(
+    bytes: BoxStream<'static, Result<Bytes, OpenAIError>>,
+    parser: P,
+) -> BoxStream<'static, Result<O, OpenAIError>>
+where
+    O: DeserializeOwned + MaybeSend + 'static,
+    P: ItemParser,
+{
+    let state = Some((bytes, parser, VecDeque::new(), false));
+
+    let stream = futures::stream::unfold(state, |state| async move {
+        let (mut bytes, mut parser, mut items, mut done) = state?;
+
+        loop {
+            if let Some(item) = items.pop_front() {
+                let item = deserialize::<O>(&item);
+                return Some((item, Some((bytes, parser, items, done))));
+            }
+
+            if done {
+                return None;
+            }
+
+            match bytes.next().await {
+                Some(Ok(chunk)) => parser.feed(&chunk, &mut items),
+                Some(Err(e)) => return Some((Err(e), None)),
+                None => {
+                    if let Err(e) = parser.finish(&mut items) {
+                        return Some((Err(e), None));
+                    }
+                    done = true;
+                }
+            }
+        }
+    });
+
+    Box::pin(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse<P: ItemParser + Default>(chunks: &[&str]) -> Result<Vec<Bytes>, OpenAIError> {
+        let mut parser = P::default();
+        let mut items = VecDeque::new();
+        for chunk in chunks {
+            parser.feed(chunk.as_bytes(), &mut items);
+        }
+        parser.finish(&mut items)?;
+        Ok(items.into())
+    }
+
+    #[test]
+    fn list_items_split_across_chunks() {
+        let items = parse::<ListItemsParser>(&[
+            r#"{"object": "list", "first_id": "data", "da"#,
+            r#"ta": [ {"id": "a", "tags": ["]", "\"}"]}, "#,
+            r#"{"id": "b", "data": [1, 2]}"#,
+            "\n], \"has_more\": false}",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            items,
+            [
+                r#"{"id": "a", "tags": ["]", "\"}"]}"#,
+                r#"{"id": "b", "data": [1, 2]}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn list_items_of_incomplete_body() {
+        assert!(parse::<ListItemsParser>(&[r#"{"data": [{"id": "a"}"#]).is_err());
+        assert!(parse::<ListItemsParser>(&[r#"{"error": {"data": []}}"#]).is_err());
+        assert!(parse::<ListItemsParser>(&[r#"{"data": []}"#])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn json_lines_split_across_chunks() {
+        let items = parse::<JsonLinesParser>(&["{\"a\": 1}\r\n\n{\"a\"", ": 2}"]).unwrap();
+
+        assert_eq!(items, ["{\"a\": 1}", "{\"a\": 2}"]);
+    }
+}
@@ -59,7 +59,7 @@
 //! ## Bring Your Own Types
 //!
 //! To use custom types for inputs and outputs, enable `byot` feature which provides additional generic methods with same name and `_byot` suffix.
-//! This feature is available on methods whose return type is not `Bytes`
+//! Methods returning `Bytes` (e.g. [Audio::speech]) get a `_byot` variant returning any `R: From<Bytes>`.
 //!
 //!```
 //!# #[cfg(feature = "byot")]
@@ -94,6 +94,8 @@
 //! # });
 //!```
 //!
+//! Multipart methods (files, audio, images) accept a hand-built [types::MultipartForm].
+//!
 //! ## Microsoft Azure
 //!
 //! ```
@@ -124,32 +126,69 @@ pub(crate) use async_openai_macros::byot;
 #[cfg(not(feature = "byot"))]
 pub(crate) use async_openai_macros::byot_passthrough as byot;
 
+pub mod agents;
 mod assistants;
 mod audio;
 mod audit_logs;
 mod batches;
+#[cfg(feature = "blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+pub mod blocking;
 mod chat;
 mod client;
 mod completion;
+pub mod concurrent;
 pub mod config;
+mod conversation_items;
+mod conversations;
 mod download;
 mod embedding;
 pub mod error;
 mod file;
 mod fine_tuning;
+#[cfg(feature = "tiktoken")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tiktoken")))]
+pub mod history;
 mod image;
 mod invites;
+mod json_stream;
 mod messages;
+pub mod middleware;
 mod model;
 mod moderation;
+pub mod pagination;
+pub mod pricing;
 mod project_api_keys;
 mod project_service_accounts;
 mod project_users;
 mod projects;
+#[cfg(feature = "realtime")]
+#[cfg_attr(docsrs, doc(cfg(feature = "realtime")))]
+pub mod realtime;
+mod responses;
+mod retry;
 mod runs;
+pub mod runtime;
+pub mod schema;
+mod single_flight;
+pub mod sink;
+#[cfg(feature = "tracing-spans")]
+mod spans;
+mod sse;
 mod steps;
+pub mod template;
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
 mod threads;
+#[cfg(feature = "tiktoken")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tiktoken")))]
+pub mod tokens;
+#[cfg(feature = "tools")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tools")))]
+pub mod tools;
 pub mod traits;
+pub mod transport;
 pub mod types;
 mod uploads;
 mod users;
@@ -165,6 +204,8 @@ pub use batches::Batches;
 pub use chat::Chat;
 pub use client::Client;
 pub use completion::Completions;
+pub use conversation_items::ConversationItems;
+pub use conversations::Conversations;
 pub use embedding::Embeddings;
 pub use file::Files;
 pub use fine_tuning::FineTuning;
@@ -177,6 +218,9 @@ pub use project_api_keys::ProjectAPIKeys;
 pub use project_service_accounts::ProjectServiceAccounts;
 pub use project_users::ProjectUsers;
 pub use projects::Projects;
+#[cfg(feature = "realtime")]
+pub use realtime::Realtime;
+pub use responses::Responses;
 pub use runs::Runs;
 pub use steps::Steps;
 pub use threads::Threads;
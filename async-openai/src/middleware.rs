@@ -0,0 +1,149 @@
+//! Hooks around the HTTP requests of a [Client](crate::Client), e.g. to refresh credentials,
+//! rewrite requests, log them for audits or record custom metrics.
+//!
+//! ```
+//! use async_openai::{
+//!     error::OpenAIError,
+//!     middleware::{Middleware, ResponseParts},
+//!     runtime::BoxFuture,
+//!     Client,
+//! };
+//!
+//! struct AuditLog;
+//!
+//! impl Middleware for AuditLog {
+//!     fn on_request<'a>(
+//!         &'a self,
+//!         request: &'a mut reqwest::Request,
+//!     ) -> BoxFuture<'a, Result<(), OpenAIError>> {
+//!         Box::pin(async move {
+//!             request
+//!                 .headers_mut()
+//!                 .insert("x-team", "search".parse().unwrap());
+//!             Ok(())
+//!         })
+//!     }
+//!
+//!     fn on_response<'a>(&'a self, response: &'a ResponseParts<'a>) -> BoxFuture<'a, ()> {
+//!         Box::pin(async move {
+//!             println!("{} {} -> {}", response.method, response.url.path(), response.status);
+//!         })
+//!     }
+//! }
+//!
+//! let client = Client::new().with_middleware(AuditLog);
+//! ```
+use std::sync::Arc;
+
+use bytes::Bytes;
+use reqwest::{header::HeaderMap, Method, StatusCode, Url};
+
+use crate::{
+    error::OpenAIError,
+    runtime::{BoxFuture, MaybeSend, MaybeSync},
+};
+
+/// A response received by a [Client](crate::Client), as seen by [Middleware::on_response].
+#[derive(Debug)]
+pub struct ResponseParts<'a> {
+    pub method: &'a Method,
+    pub url: &'a Url,
+    pub status: StatusCode,
+    pub headers: &'a HeaderMap,
+    /// The body of the response, `None` for SSE streams whose events are read as they arrive.
+    pub body: Option<&'a Bytes>,
+}
+
+impl ResponseParts<'_> {
+    /// The `x-request-id` header, to include when reporting issues to OpenAI support.
+    pub fn request_id(&self) -> Option<&str> {
+        self.headers.get("x-request-id")?.to_str().ok()
+    }
+}
+
+/// Hooks invoked around each HTTP request of a [Client](crate::Client), including each retry,
+/// registered with [Client::with_middleware](crate::Client::with_middleware).
+///
+/// Middlewares are invoked in the order they were registered. They are `Send` and `Sync`,
+/// except on wasm, see [MaybeSend].
+pub trait Middleware: MaybeSend + MaybeSync {
+    /// Invoked before `request` is sent, with its method, url, headers and body, which can be
+    /// modified. An error fails the request without sending it, and without retrying it.
+    fn on_request<'a>(
+        &'a self,
+        _request: &'a mut reqwest::Request,
+    ) -> BoxFuture<'a, Result<(), OpenAIError>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Invoked after a response is received, successful or not.
+    fn on_response<'a>(&'a self, _response: &'a ResponseParts<'a>) -> BoxFuture<'a, ()> {
+        Box::pin(async {})
+    }
+}
+
+impl std::fmt::Debug for dyn Middleware {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Middleware")
+    }
+}
+
+/// Invoke [Middleware::on_request] of each of `middlewares`, stopping at the first error.
+pub(crate) async fn on_request(
+    middlewares: &[Arc<dyn Middleware>],
+    request: &mut reqwest::Request,
+) -> Result<(), OpenAIError> {
+    for middleware in middlewares {
+        middleware.on_request(request).await?;
+    }
+    Ok(())
+}
+
+/// Invoke [Middleware::on_response] of each of `middlewares`.
+pub(crate) async fn on_response(middlewares: &[Arc<dyn Middleware>], response: &ResponseParts<'_>) {
+    for middleware in middlewares {
+        middleware.on_response(response).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Adds a header with its name, or fails.
+    struct Tag(&'static str);
+
+    impl Middleware for Tag {
+        fn on_request<'a>(
+            &'a self,
+            request: &'a mut reqwest::Request,
+        ) -> BoxFuture<'a, Result<(), OpenAIError>> {
+            Box::pin(async move {
+                if self.0 == "fail" {
+                    return Err(OpenAIError::InvalidArgument("rejected".into()));
+                }
+                request
+                    .headers_mut()
+                    .append("x-tag", self.0.parse().unwrap());
+                Ok(())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn on_request_in_order() {
+        let url = Url::parse("https://api.openai.com/v1/models").unwrap();
+        let mut request = reqwest::Request::new(Method::GET, url);
+
+        let middlewares: Vec<Arc<dyn Middleware>> =
+            vec![Arc::new(Tag("first")), Arc::new(Tag("second"))];
+        on_request(&middlewares, &mut request).await.unwrap();
+        let tags: Vec<_> = request.headers().get_all("x-tag").iter().collect();
+        assert_eq!(tags, ["first", "second"]);
+
+        let middlewares: Vec<Arc<dyn Middleware>> =
+            vec![Arc::new(Tag("fail")), Arc::new(Tag("third"))];
+        assert!(on_request(&middlewares, &mut request).await.is_err());
+        assert_eq!(request.headers().get_all("x-tag").iter().count(), 2);
+    }
+}
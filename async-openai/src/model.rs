@@ -1,6 +1,7 @@
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::Paginated,
     types::{DeleteModelResponse, ListModelResponse, Model},
     Client,
 };
@@ -24,6 +25,12 @@ impl<'c, C: Config> Models<'c, C> {
         self.client.get("/models").await
     }
 
+    /// [Models::list] as a [Paginated], for the same interface as other list endpoints.
+    /// Models are listed in a single page.
+    pub fn paginate(&self) -> Result<Paginated<C, ListModelResponse>, OpenAIError> {
+        Paginated::new(self.client, "/models".into(), &())
+    }
+
     /// Retrieves a model instance, providing basic information about the model
     /// such as the owner and permissioning.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
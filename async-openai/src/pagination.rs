@@ -0,0 +1,284 @@
+//! Pagination of cursor-based list endpoints.
+//!
+//! ```no_run
+//! use async_openai::Client;
+//! use futures::TryStreamExt;
+//!
+//! # tokio_test::block_on(async {
+//! let client = Client::new();
+//!
+//! let batches: Vec<_> = client
+//!     .batches()
+//!     .paginate(&())?
+//!     .with_limit(100)
+//!     .items()
+//!     .try_collect()
+//!     .await?;
+//! # Ok::<(), async_openai::error::OpenAIError>(())
+//! # });
+//! ```
+use std::marker::PhantomData;
+
+use futures::{stream, StreamExt, TryStreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    runtime::{BoxStream, MaybeSend, MaybeSync},
+    types::{
+        AssistantObject, AuditLog, Batch, ChatCompletionList, ChatCompletionMessageList,
+        ChatCompletionStoredMessage, ConversationItemList, CreateChatCompletionResponse,
+        FineTuningJob, FineTuningJobCheckpoint, FineTuningJobEvent, Invite, InviteListResponse,
+        Item, ListAssistantsResponse, ListAuditLogsResponse, ListBatchesResponse,
+        ListFilesResponse, ListFineTuningJobCheckpointsResponse, ListFineTuningJobEventsResponse,
+        ListMessagesResponse, ListModelResponse, ListPaginatedFineTuningJobsResponse,
+        ListRunStepsResponse, ListRunsResponse, ListVectorStoreFilesResponse,
+        ListVectorStoresResponse, MessageObject, Model, OpenAIFile, Project, ProjectApiKey,
+        ProjectApiKeyListResponse, ProjectListResponse, ProjectServiceAccount,
+        ProjectServiceAccountListResponse, ProjectUser, ProjectUserListResponse, ResponseItemList,
+        RunObject, RunStepObject, User, UserListResponse, VectorStoreFileObject, VectorStoreObject,
+    },
+    Client,
+};
+
+/// A page of a list endpoint paginated with the `after` query parameter.
+pub trait CursorPage: DeserializeOwned + MaybeSend + 'static {
+    type Item: MaybeSend + 'static;
+
+    /// Whether there are items after this page.
+    fn has_more(&self) -> bool;
+
+    /// Id of the last item of the page, the `after` cursor of the next page.
+    fn last_id(&self) -> Option<&str>;
+
+    fn into_items(self) -> Vec<Self::Item>;
+}
+
+/// `has_more` is optional in some list responses.
+trait HasMore {
+    fn has_more(&self) -> bool;
+}
+
+impl HasMore for bool {
+    fn has_more(&self) -> bool {
+        *self
+    }
+}
+
+impl HasMore for Option<bool> {
+    fn has_more(&self) -> bool {
+        self.unwrap_or_default()
+    }
+}
+
+/// `last_id` is optional in some list responses.
+trait LastId {
+    fn last_id(&self) -> Option<&str>;
+}
+
+impl LastId for String {
+    fn last_id(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl LastId for Option<String> {
+    fn last_id(&self) -> Option<&str> {
+        self.as_deref()
+    }
+}
+
+macro_rules! cursor_page {
+    ($page:ty, $item:ty) => {
+        impl CursorPage for $page {
+            type Item = $item;
+
+            fn has_more(&self) -> bool {
+                HasMore::has_more(&self.has_more)
+            }
+
+            fn last_id(&self) -> Option<&str> {
+                LastId::last_id(&self.last_id)
+            }
+
+            fn into_items(self) -> Vec<$item> {
+                self.data
+            }
+        }
+    };
+    // list responses without `last_id`
+    ($page:ty, $item:ty, id) => {
+        impl CursorPage for $page {
+            type Item = $item;
+
+            fn has_more(&self) -> bool {
+                HasMore::has_more(&self.has_more)
+            }
+
+            fn last_id(&self) -> Option<&str> {
+                self.data.last().map(|item| item.id.as_str())
+            }
+
+            fn into_items(self) -> Vec<$item> {
+                self.data
+            }
+        }
+    };
+}
+
+cursor_page!(ListAssistantsResponse, AssistantObject);
+cursor_page!(ListAuditLogsResponse, AuditLog);
+cursor_page!(ListBatchesResponse, Batch);
+cursor_page!(ChatCompletionList, CreateChatCompletionResponse);
+cursor_page!(ChatCompletionMessageList, ChatCompletionStoredMessage);
+cursor_page!(ListFilesResponse, OpenAIFile);
+cursor_page!(ListPaginatedFineTuningJobsResponse, FineTuningJob, id);
+cursor_page!(ListFineTuningJobEventsResponse, FineTuningJobEvent, id);
+cursor_page!(
+    ListFineTuningJobCheckpointsResponse,
+    FineTuningJobCheckpoint
+);
+cursor_page!(ConversationItemList, Item);
+cursor_page!(InviteListResponse, Invite);
+cursor_page!(ListMessagesResponse, MessageObject);
+cursor_page!(ProjectApiKeyListResponse, ProjectApiKey);
+cursor_page!(ProjectServiceAccountListResponse, ProjectServiceAccount);
+cursor_page!(ProjectUserListResponse, ProjectUser);
+cursor_page!(ProjectListResponse, Project);
+cursor_page!(ResponseItemList, Item);
+cursor_page!(ListRunsResponse, RunObject);
+cursor_page!(ListRunStepsResponse, RunStepObject);
+cursor_page!(UserListResponse, User);
+cursor_page!(ListVectorStoreFilesResponse, VectorStoreFileObject);
+cursor_page!(ListVectorStoresResponse, VectorStoreObject);
+
+/// Models are listed in a single page.
+impl CursorPage for ListModelResponse {
+    type Item = Model;
+
+    fn has_more(&self) -> bool {
+        false
+    }
+
+    fn last_id(&self) -> Option<&str> {
+        self.data.last().map(|model| model.id.as_str())
+    }
+
+    fn into_items(self) -> Vec<Model> {
+        self.data
+    }
+}
+
+/// All the pages of a list endpoint, requested one after the other
+/// with the `after` cursor of the previous page, as the streams are polled.
+///
+/// Created by the `paginate` methods of the API groups, see [the module](self).
+pub struct Paginated<C: Config, P: CursorPage> {
+    client: Client<C>,
+    path: String,
+    query: Vec<(String, Value)>,
+    page: PhantomData<fn() -> P>,
+}
+
+impl<C: Config, P: CursorPage> Paginated<C, P> {
+    /// Pages of `path`, filtered by `query` such as `[("purpose", "batch")]` or a struct.
+    pub(crate) fn new<Q>(client: &Client<C>, path: String, query: &Q) -> Result<Self, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Ok(Self {
+            client: client.clone(),
+            path,
+            query: query_pairs(query)?,
+            page: PhantomData,
+        })
+    }
+
+    /// Number of items per page, between 1 and 100 for most endpoints.
+    pub fn with_limit(self, limit: u32) -> Self {
+        self.with_param("limit", limit.into())
+    }
+
+    fn with_param(mut self, key: &str, value: Value) -> Self {
+        self.query.retain(|(k, _)| k != key);
+        self.query.push((key.to_string(), value));
+        self
+    }
+}
+
+impl<C, P> Paginated<C, P>
+where
+    C: Config + MaybeSend + MaybeSync + 'static,
+    P: CursorPage,
+{
+    /// Every page, starting after the `after` cursor of the query if any.
+    /// The stream ends after an error.
+    pub fn pages(self) -> BoxStream<'static, Result<P, OpenAIError>> {
+        let stream = stream::try_unfold(Some(self), |state| async move {
+            let Some(paginated) = state else {
+                return Ok(None);
+            };
+
+            let page: P = paginated
+                .client
+                .get_with_query(&paginated.path, &paginated.query)
+                .await?;
+
+            let next = match page.last_id() {
+                Some(last_id) if page.has_more() => {
+                    Some(paginated.with_param("after", last_id.into()))
+                }
+                _ => None,
+            };
+
+            Ok(Some((page, next)))
+        });
+
+        Box::pin(stream)
+    }
+
+    /// Every item of every page.
+    pub fn items(self) -> BoxStream<'static, Result<P::Item, OpenAIError>> {
+        let stream = self
+            .pages()
+            .map_ok(|page| stream::iter(page.into_items()).map(Ok))
+            .try_flatten();
+
+        Box::pin(stream)
+    }
+}
+
+/// Parameters of `query`, e.g. a struct, a map or a list of key value pairs.
+fn query_pairs<Q: Serialize + ?Sized>(query: &Q) -> Result<Vec<(String, Value)>, OpenAIError> {
+    let invalid = || {
+        OpenAIError::InvalidArgument(
+            "query must be a struct, a map or a list of key value pairs".into(),
+        )
+    };
+
+    let pairs = match serde_json::to_value(query)
+        .map_err(|e| OpenAIError::InvalidArgument(e.to_string()))?
+    {
+        Value::Null => vec![],
+        Value::Object(map) => map.into_iter().collect(),
+        Value::Array(pairs) => pairs
+            .into_iter()
+            .map(|pair| match pair {
+                Value::Array(pair) => match <[Value; 2]>::try_from(pair) {
+                    Ok([Value::String(key), value]) => Ok((key, value)),
+                    _ => Err(invalid()),
+                },
+                _ => Err(invalid()),
+            })
+            .collect::<Result<_, _>>()?,
+        _ => return Err(invalid()),
+    };
+
+    // unset optional fields
+    Ok(pairs
+        .into_iter()
+        .filter(|(_, value)| !value.is_null())
+        .collect())
+}
@@ -0,0 +1,237 @@
+//! Estimated cost of API calls from their token usage.
+//!
+//! Prices of well-known models are built in, in USD per million tokens, for the standard
+//! processing tier. They change over time: override them, and set the prices of other models,
+//! with [set_price].
+//!
+//! ```no_run
+//! use async_openai::{
+//!     pricing::{self, CostTracker, ModelPrice},
+//!     Client,
+//! };
+//!
+//! pricing::set_price("my-fine-tuned-model", ModelPrice::new(3.0, 12.0));
+//!
+//! // total cost of the calls made by the client
+//! let tracker = CostTracker::new();
+//! let client = Client::new().with_usage_observer(tracker.clone());
+//!
+//! // ... make requests ...
+//!
+//! println!("${:.4}", tracker.total_cost());
+//! ```
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock, RwLock},
+};
+
+use crate::{
+    traits::UsageObserver,
+    types::{CompletionUsage, ModelId, ResponseUsage, TokenUsage},
+};
+
+/// Prices of a model, in USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPrice {
+    pub input: f64,
+    /// Price of input tokens served from the prompt cache, `None` when they are not discounted.
+    pub cached_input: Option<f64>,
+    /// Price of output tokens, including reasoning tokens.
+    pub output: f64,
+}
+
+impl ModelPrice {
+    pub const fn new(input: f64, output: f64) -> Self {
+        Self {
+            input,
+            cached_input: None,
+            output,
+        }
+    }
+
+    pub const fn with_cached_input(mut self, cached_input: f64) -> Self {
+        self.cached_input = Some(cached_input);
+        self
+    }
+
+    /// Cost of `usage` in USD.
+    pub fn cost(&self, usage: &TokenUsage) -> f64 {
+        let cached = usage.cached_tokens.unwrap_or(0).min(usage.input_tokens);
+        let uncached = usage.input_tokens - cached;
+
+        (uncached as f64 * self.input
+            + cached as f64 * self.cached_input.unwrap_or(self.input)
+            + usage.output_tokens as f64 * self.output)
+            / 1_000_000.0
+    }
+}
+
+/// Prices set with [set_price], by model name.
+fn overrides() -> &'static RwLock<HashMap<String, ModelPrice>> {
+    static OVERRIDES: OnceLock<RwLock<HashMap<String, ModelPrice>>> = OnceLock::new();
+    OVERRIDES.get_or_init(Default::default)
+}
+
+/// Set the price of `model`, overriding the built-in price of well-known models, for all
+/// the estimates of this process.
+pub fn set_price<S: Into<String>>(model: S, price: ModelPrice) {
+    overrides()
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(model.into(), price);
+}
+
+/// Price of `model`: set with [set_price], or else built in for well-known models. Dated
+/// snapshots, e.g. `gpt-4o-2024-08-06`, have the price of their model unless set.
+pub fn price(model: &str) -> Option<ModelPrice> {
+    let overridden = overrides()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(model)
+        .copied();
+
+    overridden.or_else(|| builtin_price(&ModelId::from(model)))
+}
+
+fn builtin_price(model: &ModelId) -> Option<ModelPrice> {
+    let price = match model {
+        ModelId::Gpt5 => ModelPrice::new(1.25, 10.0).with_cached_input(0.125),
+        ModelId::Gpt5Mini => ModelPrice::new(0.25, 2.0).with_cached_input(0.025),
+        ModelId::Gpt5Nano => ModelPrice::new(0.05, 0.4).with_cached_input(0.005),
+        ModelId::Gpt41 => ModelPrice::new(2.0, 8.0).with_cached_input(0.5),
+        ModelId::Gpt41Mini => ModelPrice::new(0.4, 1.6).with_cached_input(0.1),
+        ModelId::Gpt41Nano => ModelPrice::new(0.1, 0.4).with_cached_input(0.025),
+        ModelId::Gpt4o => ModelPrice::new(2.5, 10.0).with_cached_input(1.25),
+        ModelId::Gpt4oMini => ModelPrice::new(0.15, 0.6).with_cached_input(0.075),
+        ModelId::Gpt4oAudioPreview => ModelPrice::new(2.5, 10.0),
+        ModelId::Gpt4Turbo => ModelPrice::new(10.0, 30.0),
+        ModelId::Gpt4 => ModelPrice::new(30.0, 60.0),
+        ModelId::Gpt35Turbo => ModelPrice::new(0.5, 1.5),
+        ModelId::O1 => ModelPrice::new(15.0, 60.0).with_cached_input(7.5),
+        ModelId::O1Mini => ModelPrice::new(1.1, 4.4).with_cached_input(0.55),
+        ModelId::O3 => ModelPrice::new(2.0, 8.0).with_cached_input(0.5),
+        ModelId::O3Mini => ModelPrice::new(1.1, 4.4).with_cached_input(0.55),
+        ModelId::O4Mini => ModelPrice::new(1.1, 4.4).with_cached_input(0.275),
+        ModelId::TextEmbedding3Small => ModelPrice::new(0.02, 0.0),
+        ModelId::TextEmbedding3Large => ModelPrice::new(0.13, 0.0),
+        ModelId::TextEmbeddingAda002 => ModelPrice::new(0.1, 0.0),
+        ModelId::Other(_) => return builtin_price(&model.snapshot_of()?),
+    };
+
+    Some(price)
+}
+
+impl TokenUsage {
+    /// Estimated cost in USD of this usage by `model`, `None` when its price is unknown,
+    /// see [price].
+    pub fn estimated_cost(&self, model: &str) -> Option<f64> {
+        price(model).map(|price| price.cost(self))
+    }
+}
+
+impl CompletionUsage {
+    /// Estimated cost in USD of this usage by `model`, see [TokenUsage::estimated_cost].
+    pub fn estimated_cost(&self, model: &str) -> Option<f64> {
+        TokenUsage::from(self).estimated_cost(model)
+    }
+}
+
+impl ResponseUsage {
+    /// Estimated cost in USD of this usage by `model`, see [TokenUsage::estimated_cost].
+    pub fn estimated_cost(&self, model: &str) -> Option<f64> {
+        TokenUsage::from(self).estimated_cost(model)
+    }
+}
+
+/// Usage and estimated cost of API calls, by model, e.g. of the responses of a stream or of
+/// all the calls of a [Client](crate::Client) when registered with
+/// [Client::with_usage_observer](crate::Client::with_usage_observer).
+///
+/// Clones share the same totals.
+#[derive(Debug, Clone, Default)]
+pub struct CostTracker {
+    usage: Arc<Mutex<HashMap<String, TokenUsage>>>,
+}
+
+impl CostTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `usage` by `model` to the totals.
+    pub fn record(&self, model: &str, usage: &TokenUsage) {
+        let mut totals = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        let total = totals.entry(model.to_string()).or_default();
+
+        total.input_tokens += usage.input_tokens;
+        total.output_tokens += usage.output_tokens;
+        total.total_tokens += usage.total_tokens;
+        for (total, tokens) in [
+            (&mut total.cached_tokens, usage.cached_tokens),
+            (&mut total.reasoning_tokens, usage.reasoning_tokens),
+        ] {
+            if let Some(tokens) = tokens {
+                *total.get_or_insert(0) += tokens;
+            }
+        }
+    }
+
+    /// Total usage by model.
+    pub fn usage(&self) -> HashMap<String, TokenUsage> {
+        self.usage.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Estimated cost in USD by model, `None` for models whose price is unknown.
+    pub fn cost_by_model(&self) -> HashMap<String, Option<f64>> {
+        self.usage()
+            .into_iter()
+            .map(|(model, usage)| {
+                let cost = usage.estimated_cost(&model);
+                (model, cost)
+            })
+            .collect()
+    }
+
+    /// Estimated cost in USD of all the usage, excluding models whose price is unknown.
+    pub fn total_cost(&self) -> f64 {
+        self.cost_by_model().into_values().flatten().sum()
+    }
+}
+
+impl UsageObserver for CostTracker {
+    fn observe(&self, model: &str, usage: &TokenUsage) {
+        self.record(model, usage);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimated_cost() {
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+            total_tokens: 1_100_000,
+            cached_tokens: Some(500_000),
+            reasoning_tokens: None,
+        };
+
+        // 0.5M uncached at 2.5, 0.5M cached at 1.25, 0.1M output at 10
+        let cost = usage.estimated_cost("gpt-4o-2024-08-06").unwrap();
+        assert!((cost - (1.25 + 0.625 + 1.0)).abs() < 1e-9);
+        assert_eq!(usage.estimated_cost("my-model"), None);
+
+        set_price("my-model", ModelPrice::new(1.0, 2.0));
+        let cost = usage.estimated_cost("my-model").unwrap();
+        assert!((cost - 1.2).abs() < 1e-9);
+
+        let tracker = CostTracker::new();
+        tracker.record("my-model", &usage);
+        tracker.observe("my-model", &usage);
+        tracker.record("unknown-model", &usage);
+        assert_eq!(tracker.usage()["my-model"].cached_tokens, Some(1_000_000));
+        assert!((tracker.total_cost() - 2.4).abs() < 1e-9);
+    }
+}
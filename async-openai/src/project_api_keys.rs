@@ -3,6 +3,7 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::Paginated,
     types::{ProjectApiKey, ProjectApiKeyDeleteResponse, ProjectApiKeyListResponse},
     Client,
 };
@@ -36,6 +37,21 @@ impl<'c, C: Config> ProjectAPIKeys<'c, C> {
             .await
     }
 
+    /// Every page of [ProjectAPIKeys::list], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate<Q>(
+        &self,
+        query: &Q,
+    ) -> Result<Paginated<C, ProjectApiKeyListResponse>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(
+            self.client,
+            format!("/organization/projects/{}/api_keys", self.project_id),
+            query,
+        )
+    }
+
     /// Retrieves an API key in the project.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, api_key: &str) -> Result<ProjectApiKey, OpenAIError> {
@@ -3,6 +3,7 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::Paginated,
     types::{
         ProjectServiceAccount, ProjectServiceAccountCreateRequest,
         ProjectServiceAccountCreateResponse, ProjectServiceAccountDeleteResponse,
@@ -46,6 +47,24 @@ impl<'c, C: Config> ProjectServiceAccounts<'c, C> {
             .await
     }
 
+    /// Every page of [ProjectServiceAccounts::list], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate<Q>(
+        &self,
+        query: &Q,
+    ) -> Result<Paginated<C, ProjectServiceAccountListResponse>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(
+            self.client,
+            format!(
+                "/organization/projects/{}/service_accounts",
+                self.project_id
+            ),
+            query,
+        )
+    }
+
     /// Creates a new service account in the project. This also returns an unredacted API key for the service account.
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn create(
@@ -3,6 +3,7 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::Paginated,
     types::{
         ProjectUser, ProjectUserCreateRequest, ProjectUserDeleteResponse, ProjectUserListResponse,
         ProjectUserUpdateRequest,
@@ -39,6 +40,21 @@ impl<'c, C: Config> ProjectUsers<'c, C> {
             .await
     }
 
+    /// Every page of [ProjectUsers::list], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate<Q>(
+        &self,
+        query: &Q,
+    ) -> Result<Paginated<C, ProjectUserListResponse>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(
+            self.client,
+            format!("/organization/projects/{}/users", self.project_id),
+            query,
+        )
+    }
+
     /// Adds a user to the project. Users must already be members of the organization to be added to a project.
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn create(
@@ -3,6 +3,7 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::Paginated,
     project_api_keys::ProjectAPIKeys,
     types::{Project, ProjectCreateRequest, ProjectListResponse, ProjectUpdateRequest},
     Client, ProjectServiceAccounts, ProjectUsers,
@@ -45,6 +46,14 @@ impl<'c, C: Config> Projects<'c, C> {
             .await
     }
 
+    /// Every page of [Projects::list], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate<Q>(&self, query: &Q) -> Result<Paginated<C, ProjectListResponse>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(self.client, "/organization/projects".into(), query)
+    }
+
     /// Create a new project in the organization. Projects can be created and archived, but cannot be deleted.
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn create(&self, request: ProjectCreateRequest) -> Result<Project, OpenAIError> {
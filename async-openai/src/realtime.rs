@@ -0,0 +1,241 @@
+//! Low-latency, multimodal conversations over a WebSocket with the
+//! [Realtime API](https://platform.openai.com/docs/guides/realtime).
+//!
+//! ```no_run
+//! use async_openai::{
+//!     types::realtime::{ConversationItemCreateEvent, Item, ResponseCreateEvent, ServerEvent},
+//!     Client,
+//! };
+//!
+//! # tokio_test::block_on(async {
+//! let client = Client::new();
+//! let mut session = client
+//!     .realtime()
+//!     .connect("gpt-4o-realtime-preview")
+//!     .await?;
+//!
+//! let item = Item::try_from(serde_json::json!({
+//!     "type": "message",
+//!     "role": "user",
+//!     "content": [{"type": "input_text", "text": "Hello!"}]
+//! }))?;
+//! session.send(ConversationItemCreateEvent::from(item)).await?;
+//! session.send(ResponseCreateEvent::default()).await?;
+//!
+//! while let Some(event) = session.recv().await {
+//!     match event? {
+//!         ServerEvent::ResponseTextDelta(event) => print!("{}", event.delta),
+//!         ServerEvent::ResponseDone(_) => break,
+//!         _ => {}
+//!     }
+//! }
+//! session.close().await?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! # });
+//! ```
+use std::sync::Arc;
+
+use futures::{SinkExt, StreamExt};
+use reqwest::header::HeaderMap;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{client::IntoClientRequest, Message},
+    MaybeTlsStream, WebSocketStream,
+};
+
+use crate::{
+    config::{Config, Credentials, OPENAI_BETA_HEADER},
+    error::OpenAIError,
+    types::realtime::{
+        ClientEvent, CreateRealtimeSessionRequest, CreateTranscriptionSessionRequest,
+        RealtimeSessionCreateResponse, ServerEvent, SessionUpdateEvent,
+        TranscriptionSessionCreateResponse,
+    },
+    Client,
+};
+
+/// Reconnections of a [RealtimeSession] in a row unless set with
+/// [RealtimeSession::with_max_reconnects].
+const DEFAULT_MAX_RECONNECTS: usize = 3;
+
+/// Communicate with a model in real time over a WebSocket, with text and audio.
+///
+/// Related guide: [Realtime API](https://platform.openai.com/docs/guides/realtime)
+pub struct Realtime<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Realtime<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// Create an ephemeral API token for a Realtime session, to authenticate browser and
+    /// mobile clients, e.g. over WebRTC, without exposing your API key.
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn create_session(
+        &self,
+        request: CreateRealtimeSessionRequest,
+    ) -> Result<RealtimeSessionCreateResponse, OpenAIError> {
+        self.client.post("/realtime/sessions", request).await
+    }
+
+    /// Create an ephemeral API token for a Realtime transcription session, to authenticate
+    /// browser and mobile clients without exposing your API key.
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn create_transcription_session(
+        &self,
+        request: CreateTranscriptionSessionRequest,
+    ) -> Result<TranscriptionSessionCreateResponse, OpenAIError> {
+        self.client
+            .post("/realtime/transcription_sessions", request)
+            .await
+    }
+
+    /// Open a WebSocket to `/realtime` for `model`, authenticated with the headers of the
+    /// client's config, and its [Credentials] refreshed on each reconnection.
+    pub async fn connect(&self, model: &str) -> Result<RealtimeSession, OpenAIError> {
+        let config = self.client.config();
+        let credentials = config.credentials();
+        if credentials.is_none() {
+            config.refresh().await?;
+        }
+
+        let url = config.url("/realtime");
+        let url = if let Some(rest) = url.strip_prefix("https://") {
+            format!("wss://{rest}")
+        } else if let Some(rest) = url.strip_prefix("http://") {
+            format!("ws://{rest}")
+        } else {
+            url
+        };
+
+        let mut url = reqwest::Url::parse(&url)
+            .map_err(|e| OpenAIError::InvalidArgument(format!("invalid realtime url: {e}")))?;
+        url.query_pairs_mut()
+            .append_pair("model", model)
+            .extend_pairs(config.query());
+        let url = url.to_string();
+
+        let mut headers = config.headers();
+        headers.insert(OPENAI_BETA_HEADER, "realtime=v1".parse().unwrap());
+
+        let socket = open(&url, &headers, credentials.as_deref()).await?;
+
+        Ok(RealtimeSession {
+            url,
+            headers,
+            credentials,
+            socket,
+            session: None,
+            max_reconnects: DEFAULT_MAX_RECONNECTS,
+        })
+    }
+}
+
+/// A Realtime session over a WebSocket.
+///
+/// Pings of the server are answered while receiving events. When the connection drops,
+/// the session reconnects and sends its last `session.update` event again. The
+/// conversation is not restored: a new connection starts a new conversation.
+pub struct RealtimeSession {
+    url: String,
+    headers: HeaderMap,
+    credentials: Option<Arc<dyn Credentials>>,
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    session: Option<SessionUpdateEvent>,
+    max_reconnects: usize,
+}
+
+impl RealtimeSession {
+    /// Maximum number of reconnections in a row when the connection drops, 3 by default.
+    /// Set to 0 to never reconnect.
+    pub fn with_max_reconnects(mut self, max_reconnects: usize) -> Self {
+        self.max_reconnects = max_reconnects;
+        self
+    }
+
+    /// Send a client event, e.g. `session.update` or `response.create`.
+    pub async fn send<E: Into<ClientEvent>>(&mut self, event: E) -> Result<(), OpenAIError> {
+        let event = event.into();
+        if let ClientEvent::SessionUpdate(session) = &event {
+            self.session = Some(session.clone());
+        }
+        self.socket
+            .send(event.into())
+            .await
+            .map_err(websocket_error)
+    }
+
+    /// The next event of the server, or `None` when the session is closed.
+    ///
+    /// Fails when an event can't be deserialized, and when the connection drops and can't
+    /// be reopened.
+    pub async fn recv(&mut self) -> Option<Result<ServerEvent, OpenAIError>> {
+        let mut reconnects = 0;
+        loop {
+            let error = match self.socket.next().await {
+                Some(Ok(Message::Text(text))) => {
+                    return Some(crate::error::deserialize(text.as_bytes()))
+                }
+                Some(Ok(Message::Ping(payload))) => {
+                    match self.socket.send(Message::Pong(payload)).await {
+                        Ok(()) => continue,
+                        Err(e) => websocket_error(e),
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => return None,
+                Some(Ok(_)) => continue,
+                Some(Err(e)) => websocket_error(e),
+            };
+
+            if reconnects == self.max_reconnects {
+                return Some(Err(error));
+            }
+            reconnects += 1;
+            if let Err(e) = self.reconnect().await {
+                return Some(Err(e));
+            }
+        }
+    }
+
+    /// Open a new connection, with refreshed credentials, sending the last `session.update`
+    /// event again.
+    pub async fn reconnect(&mut self) -> Result<(), OpenAIError> {
+        self.socket = open(&self.url, &self.headers, self.credentials.as_deref()).await?;
+        if let Some(session) = self.session.clone() {
+            self.socket
+                .send(ClientEvent::from(session).into())
+                .await
+                .map_err(websocket_error)?;
+        }
+        Ok(())
+    }
+
+    /// Close the connection.
+    pub async fn close(&mut self) -> Result<(), OpenAIError> {
+        self.socket.close(None).await.map_err(websocket_error)
+    }
+}
+
+/// Open a WebSocket to `url` with `headers`, and the headers of `credentials` refreshed first.
+async fn open(
+    url: &str,
+    headers: &HeaderMap,
+    credentials: Option<&dyn Credentials>,
+) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>, OpenAIError> {
+    let mut request = url.into_client_request().map_err(websocket_error)?;
+    request.headers_mut().extend(headers.clone());
+    if let Some(credentials) = credentials {
+        credentials.refresh().await?;
+        request.headers_mut().extend(credentials.headers());
+    }
+
+    let (socket, _) = connect_async(request).await.map_err(websocket_error)?;
+    Ok(socket)
+}
+
+fn websocket_error(error: tokio_tungstenite::tungstenite::Error) -> OpenAIError {
+    OpenAIError::StreamError(format!("websocket: {error}"))
+}
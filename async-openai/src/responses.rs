@@ -0,0 +1,255 @@
+use backoff::backoff::Backoff;
+use serde::Serialize;
+
+#[cfg(feature = "schemars")]
+use crate::types::{TextConfig, TextFormat, TextJsonSchema};
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    pagination::Paginated,
+    runtime,
+    types::{CreateResponse, DeleteResponse, Response, ResponseItemList, ResponseStream},
+    Client,
+};
+#[cfg(feature = "tools")]
+use crate::{
+    tools::ToolSet,
+    types::{FunctionToolCall, Item},
+};
+
+/// Given text or image inputs, the model generates text or JSON outputs, and can call your
+/// own functions or built-in tools like web search and file search.
+///
+/// Related guide: [Responses](https://platform.openai.com/docs/api-reference/responses)
+pub struct Responses<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Responses<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// Creates a model response. Provide [text](https://platform.openai.com/docs/guides/text)
+    /// or [image](https://platform.openai.com/docs/guides/images) inputs to generate
+    /// text or JSON outputs.
+    ///
+    /// byot: You must ensure "stream: false" in serialized `request`
+    #[crate::byot(
+        T0 = serde::Serialize,
+        R = serde::de::DeserializeOwned
+    )]
+    pub async fn create(&self, request: CreateResponse) -> Result<Response, OpenAIError> {
+        #[cfg(not(feature = "byot"))]
+        {
+            if request.stream.is_some() && request.stream.unwrap() {
+                return Err(OpenAIError::InvalidArgument(
+                    "When stream is true, use Responses::create_stream".into(),
+                ));
+            }
+        }
+        self.client.post("/responses", request).await
+    }
+
+    /// Creates a model response with [Structured Outputs](https://platform.openai.com/docs/guides/structured-outputs),
+    /// its text output deserialized into `T`.
+    ///
+    /// The `text.format` of `request` is set to the JSON Schema of `T`, in strict mode
+    /// (see [crate::schema::strict]), named after `T`.
+    ///
+    /// Fails with [OpenAIError::Refusal] when the model refuses to answer, and with
+    /// [OpenAIError::Deserialization] when the output is not a `T`, e.g. when the response is
+    /// incomplete because it reached `max_output_tokens`.
+    #[cfg(feature = "schemars")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+    pub async fn create_structured<T>(&self, mut request: CreateResponse) -> Result<T, OpenAIError>
+    where
+        T: schemars::JsonSchema + serde::de::DeserializeOwned,
+    {
+        let name = crate::schema::name_of::<T>();
+        let schema = crate::schema::strict(schemars::schema_for!(T).into())?;
+
+        request.text = Some(TextConfig {
+            format: TextFormat::JsonSchema(TextJsonSchema {
+                name,
+                schema,
+                description: None,
+                strict: Some(true),
+            }),
+        });
+
+        let response = self.create(request).await?;
+        if let Some(refusal) = response.refusal() {
+            return Err(OpenAIError::Refusal(refusal.to_string()));
+        }
+
+        crate::error::deserialize(response.output_text().as_bytes())
+    }
+
+    /// Creates a model response, streamed as [server-sent events](https://platform.openai.com/docs/api-reference/responses-streaming)
+    /// while the model generates it.
+    ///
+    /// [ResponseStream] is a parsed SSE stream, ending when the server closes the connection
+    /// after `response.completed`, `response.incomplete` or `response.failed`.
+    ///
+    /// byot: You must ensure "stream: true" in serialized `request`
+    #[crate::byot(
+        T0 = serde::Serialize,
+        R = serde::de::DeserializeOwned,
+        stream = "true",
+        where_clause = "R: crate::runtime::MaybeSend + 'static"
+    )]
+    #[allow(unused_mut)]
+    pub async fn create_stream(
+        &self,
+        mut request: CreateResponse,
+    ) -> Result<ResponseStream, OpenAIError> {
+        #[cfg(not(feature = "byot"))]
+        {
+            if request.stream.is_some() && !request.stream.unwrap() {
+                return Err(OpenAIError::InvalidArgument(
+                    "When stream is false, use Responses::create".into(),
+                ));
+            }
+
+            request.stream = Some(true);
+        }
+        Ok(self.client.post_stream("/responses", request).await)
+    }
+
+    /// Retrieves a model response with the given ID.
+    ///
+    /// Additional output data is included with the `include[]` query parameter,
+    /// e.g. `&[("include[]", Include::FileSearchCallResults)]`.
+    #[crate::byot(T0 = std::fmt::Display, T1 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn retrieve<Q>(&self, response_id: &str, query: &Q) -> Result<Response, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_with_query(&format!("/responses/{response_id}"), &query)
+            .await
+    }
+
+    /// Polls a response created with `background` set to true, until it is no longer
+    /// `queued` or `in_progress`, waiting between retrievals as set by `backoff`.
+    ///
+    /// With `backoff::ExponentialBackoff::default()`, polls every 500ms at first, then up
+    /// to every minute, for at most 15 minutes. Set `max_elapsed_time` to `None` to poll
+    /// until the response completes, and `multiplier` to 1 for a constant interval.
+    ///
+    /// Returns the response as last retrieved when `backoff` gives up, still pending.
+    pub async fn wait_for_completion(
+        &self,
+        response_id: &str,
+        mut backoff: backoff::ExponentialBackoff,
+    ) -> Result<Response, OpenAIError> {
+        backoff.reset();
+
+        loop {
+            let response: Response = self
+                .client
+                .get(&format!("/responses/{response_id}"))
+                .await?;
+            if !response.status.is_pending() {
+                return Ok(response);
+            }
+
+            match backoff.next_backoff() {
+                Some(interval) => runtime::sleep(interval).await,
+                None => return Ok(response),
+            }
+        }
+    }
+
+    /// Creates a model response for `request`, then runs its function calls with `tools` and
+    /// sends their outputs back, until a response has no function call.
+    ///
+    /// The `tools` of `request` should include [ToolSet::response_tools]. Turns are
+    /// chained with `previous_response_id`, or by sending the whole conversation back when
+    /// `store` is false. Failing calls are reported to the model, see [ToolSet::dispatch].
+    ///
+    /// Returns the last response, with its function calls not run, after `max_iterations`
+    /// rounds of function calls.
+    #[cfg(feature = "tools")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tools")))]
+    pub async fn create_with_tools(
+        &self,
+        mut request: CreateResponse,
+        tools: &ToolSet,
+        max_iterations: usize,
+    ) -> Result<Response, OpenAIError> {
+        request.stream = None;
+        let mut response = self.create(request.clone()).await?;
+
+        for _ in 0..max_iterations {
+            let function_calls: Vec<FunctionToolCall> = response
+                .output
+                .iter()
+                .filter_map(|item| match item {
+                    Item::FunctionCall(function_call) => Some(function_call.clone()),
+                    _ => None,
+                })
+                .collect();
+
+            if function_calls.is_empty() {
+                return Ok(response);
+            }
+
+            let outputs = tools.dispatch_function_calls(&function_calls).await;
+            crate::agents::follow_up(&mut request, response, outputs);
+            response = self.create(request.clone()).await?;
+        }
+
+        Ok(response)
+    }
+
+    /// Deletes a model response with the given ID.
+    #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
+    pub async fn delete(&self, response_id: &str) -> Result<DeleteResponse, OpenAIError> {
+        self.client
+            .delete(&format!("/responses/{response_id}"))
+            .await
+    }
+
+    /// Cancels a model response with the given ID. Only responses created with
+    /// `background` set to true can be cancelled.
+    #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
+    pub async fn cancel(&self, response_id: &str) -> Result<Response, OpenAIError> {
+        self.client
+            .post(&format!("/responses/{response_id}/cancel"), ())
+            .await
+    }
+
+    /// Returns a list of input items for the given response, paginated with the `after`,
+    /// `before`, `limit` and `order` query parameters.
+    #[crate::byot(T0 = std::fmt::Display, T1 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn list_input_items<Q>(
+        &self,
+        response_id: &str,
+        query: &Q,
+    ) -> Result<ResponseItemList, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_with_query(&format!("/responses/{response_id}/input_items"), &query)
+            .await
+    }
+
+    /// Every page of [Responses::list_input_items], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate_input_items<Q>(
+        &self,
+        response_id: &str,
+        query: &Q,
+    ) -> Result<Paginated<C, ResponseItemList>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(
+            self.client,
+            format!("/responses/{response_id}/input_items"),
+            query,
+        )
+    }
+}
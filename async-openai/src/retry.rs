@@ -0,0 +1,186 @@
+use std::{error::Error as _, future::Future, io, time::Duration};
+
+use backoff::{backoff::Backoff, ExponentialBackoff};
+
+use crate::runtime;
+
+/// Exponential backoff of the client, giving up after `max_retries` retries if set.
+#[derive(Debug, Clone)]
+pub(crate) struct RetryBackoff {
+    backoff: ExponentialBackoff,
+    max_retries: Option<u32>,
+    retries: u32,
+}
+
+impl RetryBackoff {
+    pub(crate) fn new(backoff: ExponentialBackoff, max_retries: Option<u32>) -> Self {
+        Self {
+            backoff,
+            max_retries,
+            retries: 0,
+        }
+    }
+}
+
+impl Backoff for RetryBackoff {
+    fn reset(&mut self) {
+        self.backoff.reset();
+        self.retries = 0;
+    }
+
+    fn next_backoff(&mut self) -> Option<Duration> {
+        if self.max_retries.is_some_and(|max| self.retries >= max) {
+            return None;
+        }
+        self.retries += 1;
+        self.backoff.next_backoff()
+    }
+}
+
+/// Next delay of `backoff`, lengthened to the `Retry-After` of the response if longer,
+/// or `None` when it gives up, so that its retry limits apply to rate limited requests too.
+pub(crate) fn next_delay(
+    backoff: &mut impl Backoff,
+    retry_after: Option<Duration>,
+) -> Option<Duration> {
+    let delay = backoff.next_backoff()?;
+    Some(retry_after.map_or(delay, |retry_after| retry_after.max(delay)))
+}
+
+/// Call `operation` until it succeeds, fails permanently, or `backoff` gives up, waiting the
+/// [next_delay] between attempts.
+///
+/// Like [backoff::future::retry], but with the timer of [runtime], so that it also runs on wasm,
+/// and without retrying forever when every response has a `Retry-After`.
+pub(crate) async fn retry<B, F, Fut, T, E>(mut backoff: B, mut operation: F) -> Result<T, E>
+where
+    B: Backoff,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, backoff::Error<E>>>,
+{
+    backoff.reset();
+    loop {
+        let (err, retry_after) = match operation().await {
+            Ok(output) => return Ok(output),
+            Err(backoff::Error::Permanent(err)) => return Err(err),
+            Err(backoff::Error::Transient { err, retry_after }) => (err, retry_after),
+        };
+        match next_delay(&mut backoff, retry_after) {
+            Some(delay) => runtime::sleep(delay).await,
+            None => return Err(err),
+        }
+    }
+}
+
+/// Whether a request failed because of the connection, e.g. it was refused, reset or timed out,
+/// rather than because of the request itself, so that it can be sent again.
+pub(crate) fn is_transient(error: &reqwest::Error) -> bool {
+    if error.is_timeout() {
+        return true;
+    }
+    // the fetch API of the browser does not tell why a request failed
+    #[cfg(not(target_family = "wasm"))]
+    if error.is_connect() {
+        return true;
+    }
+
+    let mut source = error.source();
+    while let Some(error) = source {
+        if let Some(error) = error.downcast_ref::<io::Error>() {
+            return matches!(
+                error.kind(),
+                io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::BrokenPipe
+                    | io::ErrorKind::UnexpectedEof
+            );
+        }
+        source = error.source();
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_delay_after_retry_after() {
+        let mut backoff = RetryBackoff::new(
+            ExponentialBackoff {
+                initial_interval: Duration::from_secs(1),
+                randomization_factor: 0.0,
+                ..Default::default()
+            },
+            Some(2),
+        );
+        backoff.reset();
+        let retry_after = Some(Duration::from_secs(30));
+
+        assert_eq!(
+            next_delay(&mut backoff, retry_after),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            next_delay(&mut backoff, None),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(next_delay(&mut backoff, retry_after), None);
+    }
+
+    #[test]
+    fn max_retries() {
+        let mut backoff = RetryBackoff::new(ExponentialBackoff::default(), Some(2));
+        assert!(backoff.next_backoff().is_some());
+        assert!(backoff.next_backoff().is_some());
+        assert!(backoff.next_backoff().is_none());
+
+        backoff.reset();
+        assert!(backoff.next_backoff().is_some());
+
+        let mut backoff = RetryBackoff::new(ExponentialBackoff::default(), Some(0));
+        assert!(backoff.next_backoff().is_none());
+    }
+
+    #[tokio::test]
+    async fn retry_until_max_retries() {
+        let backoff = ExponentialBackoff {
+            initial_interval: Duration::from_millis(1),
+            ..Default::default()
+        };
+        let attempts = std::cell::Cell::new(0);
+
+        let result: Result<(), &str> =
+            retry(RetryBackoff::new(backoff.clone(), Some(2)), || async {
+                attempts.set(attempts.get() + 1);
+                Err(backoff::Error::transient("failed"))
+            })
+            .await;
+        assert_eq!(result, Err("failed"));
+        assert_eq!(attempts.get(), 3);
+
+        attempts.set(0);
+        let result: Result<(), &str> =
+            retry(RetryBackoff::new(backoff.clone(), Some(2)), || async {
+                attempts.set(attempts.get() + 1);
+                Err(backoff::Error::retry_after(
+                    "rate limited",
+                    Duration::from_millis(1),
+                ))
+            })
+            .await;
+        assert_eq!(result, Err("rate limited"));
+        assert_eq!(attempts.get(), 3);
+
+        attempts.set(0);
+        let result: Result<(), &str> =
+            retry(RetryBackoff::new(Default::default(), None), || async {
+                attempts.set(attempts.get() + 1);
+                Err(backoff::Error::permanent("failed"))
+            })
+            .await;
+        assert_eq!(result, Err("failed"));
+        assert_eq!(attempts.get(), 1);
+    }
+}
@@ -0,0 +1,79 @@
+//! Timers which do not depend on the async runtime, and `Send` bounds which are relaxed on wasm.
+//!
+//! Futures and streams of the browser, e.g. those of [reqwest] on `wasm32-unknown-unknown`, are
+//! not `Send`, so traits of this crate which are implemented by users, like
+//! [HttpTransport](crate::transport::HttpTransport) or [Middleware](crate::middleware::Middleware),
+//! require [MaybeSend] and [MaybeSync] and return [BoxFuture]s and [BoxStream]s:
+//! they are `Send` and `Sync` on other targets, and nothing on wasm.
+
+use std::{future::Future, time::Duration};
+
+use futures::future::{select, Either};
+
+/// Those of [std::time] on all targets but wasm, where they panic, and of the browser on wasm.
+pub use web_time::{Instant, SystemTime};
+
+/// [Send] on all targets but wasm.
+#[cfg(not(target_family = "wasm"))]
+pub trait MaybeSend: Send {}
+
+#[cfg(not(target_family = "wasm"))]
+impl<T: Send + ?Sized> MaybeSend for T {}
+
+/// [Send] on all targets but wasm.
+#[cfg(target_family = "wasm")]
+pub trait MaybeSend {}
+
+#[cfg(target_family = "wasm")]
+impl<T: ?Sized> MaybeSend for T {}
+
+/// [Sync] on all targets but wasm.
+#[cfg(not(target_family = "wasm"))]
+pub trait MaybeSync: Sync {}
+
+#[cfg(not(target_family = "wasm"))]
+impl<T: Sync + ?Sized> MaybeSync for T {}
+
+/// [Sync] on all targets but wasm.
+#[cfg(target_family = "wasm")]
+pub trait MaybeSync {}
+
+#[cfg(target_family = "wasm")]
+impl<T: ?Sized> MaybeSync for T {}
+
+/// [futures::future::BoxFuture] on all targets but wasm, [futures::future::LocalBoxFuture] on wasm.
+#[cfg(not(target_family = "wasm"))]
+pub type BoxFuture<'a, T> = futures::future::BoxFuture<'a, T>;
+
+/// [futures::future::BoxFuture] on all targets but wasm, [futures::future::LocalBoxFuture] on wasm.
+#[cfg(target_family = "wasm")]
+pub type BoxFuture<'a, T> = futures::future::LocalBoxFuture<'a, T>;
+
+/// [futures::stream::BoxStream] on all targets but wasm, [futures::stream::LocalBoxStream] on wasm.
+#[cfg(not(target_family = "wasm"))]
+pub type BoxStream<'a, T> = futures::stream::BoxStream<'a, T>;
+
+/// [futures::stream::BoxStream] on all targets but wasm, [futures::stream::LocalBoxStream] on wasm.
+#[cfg(target_family = "wasm")]
+pub type BoxStream<'a, T> = futures::stream::LocalBoxStream<'a, T>;
+
+/// Wait for `duration`, with the timer of tokio, or of the browser on wasm.
+pub(crate) async fn sleep(duration: Duration) {
+    #[cfg(not(target_family = "wasm"))]
+    tokio::time::sleep(duration).await;
+    #[cfg(target_family = "wasm")]
+    gloo_timers::future::sleep(duration).await;
+}
+
+/// Wait until `deadline`, or return at once if it has passed.
+pub(crate) async fn sleep_until(deadline: Instant) {
+    sleep(deadline.saturating_duration_since(Instant::now())).await;
+}
+
+/// Output of `future`, or `None` if it did not complete within `duration`.
+pub(crate) async fn timeout<F: Future>(duration: Duration, future: F) -> Option<F::Output> {
+    match select(std::pin::pin!(future), std::pin::pin!(sleep(duration))).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(_) => None,
+    }
+}
@@ -0,0 +1,281 @@
+//! JSON Schema utilities for [Structured Outputs](https://platform.openai.com/docs/guides/structured-outputs).
+use serde_json::{json, Map, Value};
+
+use crate::error::OpenAIError;
+
+/// Annotations without effect on validation, removed from the schema.
+const IGNORED: &[&str] = &[
+    "$schema",
+    "$id",
+    "$comment",
+    "default",
+    "examples",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+];
+
+/// Keywords which strict mode rejects and cannot be rewritten.
+const UNSUPPORTED: &[&str] = &[
+    "not",
+    "if",
+    "then",
+    "else",
+    "dependentRequired",
+    "dependentSchemas",
+    "patternProperties",
+    "propertyNames",
+    "unevaluatedProperties",
+    "unevaluatedItems",
+    "minProperties",
+    "maxProperties",
+    "prefixItems",
+    "contains",
+    "minContains",
+    "maxContains",
+    "uniqueItems",
+    "minLength",
+    "maxLength",
+];
+
+/// String formats supported in strict mode, other formats (e.g. `int64` emitted by schemars) are removed.
+const FORMATS: &[&str] = &[
+    "date-time",
+    "time",
+    "date",
+    "duration",
+    "email",
+    "hostname",
+    "ipv4",
+    "ipv6",
+    "uuid",
+];
+
+/// Rewrite `schema`, e.g. generated by [schemars](https://docs.rs/schemars), into the subset of
+/// JSON Schema accepted in strict mode by `response_format` and function parameters:
+///
+/// - every object has `additionalProperties: false` and all its properties `required`;
+///   optional properties are made nullable instead, so the model sends `null` for them
+/// - `oneOf` is rewritten as `anyOf`, and an `allOf` of a single schema is inlined
+/// - `$ref` loses its sibling keywords, e.g. `description`
+/// - annotations like `default` or `examples`, and unsupported string formats, are removed
+///
+/// Fails with [OpenAIError::InvalidArgument], naming the location in the schema,
+/// when it cannot be represented: maps with arbitrary keys, values of any type,
+/// tuples, `allOf` of several schemas, length constraints on strings, etc.
+pub fn strict(mut schema: Value) -> Result<Value, OpenAIError> {
+    let is_object = schema
+        .as_object()
+        .is_some_and(|root| is_type(root, "object") && !root.contains_key("anyOf"));
+
+    if !is_object {
+        return Err(unrepresentable(
+            "#",
+            "the root schema must be of type object",
+        ));
+    }
+
+    rewrite(&mut schema, "#")?;
+    Ok(schema)
+}
+
+/// Name of the schema of `T` accepted by `response_format`: at most 64 characters among
+/// `a-z`, `A-Z`, `0-9`, `_` and `-`.
+#[cfg(feature = "schemars")]
+pub(crate) fn name_of<T: schemars::JsonSchema>() -> String {
+    T::schema_name()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(64)
+        .collect()
+}
+
+fn unrepresentable(path: &str, reason: &str) -> OpenAIError {
+    OpenAIError::InvalidArgument(format!(
+        "schema cannot be used in strict mode at `{path}`: {reason}"
+    ))
+}
+
+fn is_type(schema: &Map<String, Value>, name: &str) -> bool {
+    match schema.get("type") {
+        Some(Value::String(ty)) => ty == name,
+        Some(Value::Array(types)) => types.iter().any(|ty| ty == name),
+        _ => false,
+    }
+}
+
+fn rewrite(value: &mut Value, path: &str) -> Result<(), OpenAIError> {
+    let Value::Object(schema) = value else {
+        return Err(unrepresentable(
+            path,
+            "values of any type are not supported",
+        ));
+    };
+
+    for key in IGNORED {
+        schema.remove(*key);
+    }
+
+    if let Some(key) = UNSUPPORTED.iter().find(|key| schema.contains_key(**key)) {
+        return Err(unrepresentable(path, &format!("`{key}` is not supported")));
+    }
+
+    if let Some(Value::String(format)) = schema.get("format") {
+        if !FORMATS.contains(&format.as_str()) {
+            schema.remove("format");
+        }
+    }
+
+    if schema.contains_key("$ref") {
+        schema.retain(|key, _| key == "$ref");
+        return Ok(());
+    }
+
+    if let Some(all_of) = schema.remove("allOf") {
+        let inner = match all_of {
+            Value::Array(mut schemas) if schemas.len() == 1 => schemas.pop(),
+            _ => None,
+        };
+        let Some(Value::Object(inner)) = inner else {
+            return Err(unrepresentable(
+                path,
+                "`allOf` is only supported with a single schema",
+            ));
+        };
+
+        for (key, inner) in inner {
+            schema.entry(key).or_insert(inner);
+        }
+        // the inlined schema may bring a `$ref` or another `allOf`
+        return rewrite(value, path);
+    }
+
+    if let Some(one_of) = schema.remove("oneOf") {
+        if schema.contains_key("anyOf") {
+            return Err(unrepresentable(path, "both `oneOf` and `anyOf` are set"));
+        }
+        schema.insert("anyOf".into(), one_of);
+    }
+
+    if !["type", "enum", "const", "anyOf"]
+        .iter()
+        .any(|key| schema.contains_key(*key))
+    {
+        return Err(unrepresentable(
+            path,
+            "values of any type are not supported",
+        ));
+    }
+
+    for defs in ["$defs", "definitions"] {
+        if let Some(Value::Object(definitions)) = schema.get_mut(defs) {
+            for (name, definition) in definitions {
+                rewrite(definition, &format!("{path}/{defs}/{name}"))?;
+            }
+        }
+    }
+
+    if let Some(any_of) = schema.get_mut("anyOf") {
+        let Value::Array(schemas) = any_of else {
+            return Err(unrepresentable(path, "`anyOf` must be an array"));
+        };
+        for (i, schema) in schemas.iter_mut().enumerate() {
+            rewrite(schema, &format!("{path}/anyOf/{i}"))?;
+        }
+    }
+
+    if let Some(items) = schema.get_mut("items") {
+        if items.is_array() {
+            return Err(unrepresentable(path, "tuples are not supported"));
+        }
+        rewrite(items, &format!("{path}/items"))?;
+    }
+
+    if is_type(schema, "object") || schema.contains_key("properties") {
+        match schema.get("additionalProperties") {
+            None | Some(Value::Bool(false)) => {}
+            Some(_) => {
+                return Err(unrepresentable(
+                    path,
+                    "objects with arbitrary keys (maps) are not supported",
+                ))
+            }
+        }
+        schema.insert("additionalProperties".into(), Value::Bool(false));
+
+        let required: Vec<Value> = match schema.get("required") {
+            Some(Value::Array(required)) => required.clone(),
+            _ => vec![],
+        };
+
+        let mut names = vec![];
+        if let Some(Value::Object(properties)) = schema.get_mut("properties") {
+            for (name, property) in properties {
+                rewrite(property, &format!("{path}/properties/{name}"))?;
+
+                let name = Value::String(name.clone());
+                if !required.contains(&name) {
+                    nullable(property);
+                }
+                names.push(name);
+            }
+        }
+        schema.insert("required".into(), Value::Array(names));
+    }
+
+    Ok(())
+}
+
+/// Allow `null` in addition to the values accepted by `schema`.
+fn nullable(schema: &mut Value) {
+    let Value::Object(object) = schema else {
+        return;
+    };
+
+    if let Some(Value::Array(values)) = object.get_mut("enum") {
+        if !values.contains(&Value::Null) {
+            values.push(Value::Null);
+        }
+    }
+
+    if let Some(ty) = object.get_mut("type") {
+        match ty {
+            Value::String(name) if name != "null" => {
+                let name = std::mem::take(name);
+                *ty = json!([name, "null"]);
+            }
+            Value::Array(types) if !types.iter().any(|ty| ty == "null") => {
+                types.push("null".into());
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    if object.contains_key("enum") {
+        return;
+    }
+
+    if let Some(Value::Array(schemas)) = object.get_mut("anyOf") {
+        if !schemas.iter().any(|schema| is_null(schema)) {
+            schemas.push(json!({ "type": "null" }));
+        }
+        return;
+    }
+
+    // $ref or const
+    let inner = std::mem::take(schema);
+    *schema = json!({ "anyOf": [inner, { "type": "null" }] });
+}
+
+fn is_null(schema: &Value) -> bool {
+    schema
+        .as_object()
+        .is_some_and(|schema| is_type(schema, "null"))
+}
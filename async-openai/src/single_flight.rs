@@ -0,0 +1,180 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use bytes::Bytes;
+use futures::{
+    channel::oneshot,
+    future::{FutureExt, Shared},
+};
+
+/// Identity of a request: two requests with the same key are interchangeable.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct RequestKey {
+    method: reqwest::Method,
+    url: String,
+    headers: Vec<(String, Vec<u8>)>,
+    body: Vec<u8>,
+}
+
+impl RequestKey {
+    /// Returns `None` for requests whose body is a stream (i.e. multipart forms),
+    /// those are never coalesced.
+    pub(crate) fn new(request: &reqwest::Request) -> Option<Self> {
+        let body = match request.body() {
+            Some(body) => body.as_bytes()?.to_vec(),
+            None => vec![],
+        };
+
+        let mut headers: Vec<(String, Vec<u8>)> = request
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.as_bytes().to_vec()))
+            .collect();
+        headers.sort();
+
+        Some(Self {
+            method: request.method().clone(),
+            url: request.url().to_string(),
+            headers,
+            body,
+        })
+    }
+}
+
+type SharedResponse = Shared<oneshot::Receiver<Bytes>>;
+
+/// Registry of in-flight requests, used by [crate::Client::with_single_flight].
+#[derive(Default)]
+pub(crate) struct SingleFlight {
+    in_flight: Arc<Mutex<HashMap<RequestKey, SharedResponse>>>,
+}
+
+impl std::fmt::Debug for SingleFlight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let in_flight = self.in_flight.lock().map(|m| m.len()).unwrap_or_default();
+        f.debug_struct("SingleFlight")
+            .field("in_flight", &in_flight)
+            .finish()
+    }
+}
+
+pub(crate) enum Flight {
+    /// No identical request is in flight: the caller must make the request
+    /// and hand the response over with [Leader::complete].
+    Leader(Leader),
+    /// An identical request is in flight, await its response.
+    /// Resolves to `Err` if the leading request failed or was dropped.
+    Follower(SharedResponse),
+}
+
+impl SingleFlight {
+    pub(crate) fn join(&self, key: RequestKey) -> Flight {
+        let mut in_flight = self.in_flight.lock().unwrap();
+
+        if let Some(response) = in_flight.get(&key) {
+            return Flight::Follower(response.clone());
+        }
+
+        let (tx, rx) = oneshot::channel();
+        in_flight.insert(key.clone(), rx.shared());
+
+        Flight::Leader(Leader {
+            in_flight: self.in_flight.clone(),
+            key,
+            tx: Some(tx),
+        })
+    }
+}
+
+pub(crate) struct Leader {
+    in_flight: Arc<Mutex<HashMap<RequestKey, SharedResponse>>>,
+    key: RequestKey,
+    tx: Option<oneshot::Sender<Bytes>>,
+}
+
+impl Leader {
+    /// Fan out the response body to all followers.
+    pub(crate) fn complete(mut self, bytes: Bytes) {
+        if let Some(tx) = self.tx.take() {
+            // followers may all be gone already
+            let _ = tx.send(bytes);
+        }
+    }
+}
+
+impl Drop for Leader {
+    fn drop(&mut self) {
+        // On failure or cancellation `tx` is dropped here without sending,
+        // which wakes up followers so they can make their own request.
+        if let Ok(mut in_flight) = self.in_flight.lock() {
+            in_flight.remove(&self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use futures::{future::BoxFuture, StreamExt};
+    use reqwest::{header::HeaderMap, StatusCode};
+
+    use super::*;
+    use crate::{
+        error::OpenAIError,
+        transport::{HttpResponse, HttpTransport},
+        Client,
+    };
+
+    /// Answers every request with an empty list, after a while.
+    struct Slow {
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl HttpTransport for Slow {
+        fn execute(
+            &self,
+            _request: reqwest::Request,
+        ) -> BoxFuture<'_, Result<HttpResponse, OpenAIError>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                let mut headers = HeaderMap::new();
+                headers.insert(
+                    reqwest::header::CONTENT_TYPE,
+                    "application/json".parse().unwrap(),
+                );
+                let body = Bytes::from_static(br#"{"object": "list", "data": []}"#);
+                Ok(HttpResponse {
+                    status: StatusCode::OK,
+                    headers,
+                    body: futures::stream::once(async move { Ok(body) }).boxed(),
+                })
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn identical_concurrent_requests_share_a_call() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let client = Client::new()
+            .with_transport(Slow {
+                calls: calls.clone(),
+            })
+            .with_single_flight(true);
+
+        let (first, second) = tokio::join!(client.models().list(), client.models().list());
+        assert!(first.unwrap().data.is_empty());
+        assert!(second.unwrap().data.is_empty());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // the response is shared only while the request is in flight
+        client.models().list().await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
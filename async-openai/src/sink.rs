@@ -0,0 +1,112 @@
+//! Write the text of streamed responses to an [AsyncWrite], e.g. stdout, a file or a socket.
+//!
+//! ```no_run
+//! use async_openai::{sink::write_response_text, types::CreateResponseArgs, Client};
+//!
+//! # tokio_test::block_on(async {
+//! let client = Client::new();
+//!
+//! let request = CreateResponseArgs::default()
+//!     .model("gpt-4o")
+//!     .input("Write a haiku about Rust")
+//!     .build()?;
+//!
+//! let stream = client.responses().create_stream(request).await?;
+//! let response = write_response_text(stream, &mut tokio::io::stdout()).await?;
+//! println!("\n{:?}", response.usage);
+//! # Ok::<(), async_openai::error::OpenAIError>(())
+//! # });
+//! ```
+use std::fmt::Display;
+
+use futures::StreamExt;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::{
+    error::OpenAIError,
+    types::{
+        ChatCompletionAccumulator, ChatCompletionResponseStream, ChatCompletionStreamEvent,
+        CreateChatCompletionResponse, Response, ResponseStream, ResponseStreamEvent,
+    },
+};
+
+/// Write the text deltas of a Responses `stream` to `writer` as they arrive, flushing after
+/// each delta, and return the final response of the stream: completed, incomplete or failed.
+///
+/// Fails on the `error` event, and when the stream ends without a final response.
+pub async fn write_response_text<W>(
+    mut stream: ResponseStream,
+    writer: &mut W,
+) -> Result<Response, OpenAIError>
+where
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    while let Some(event) = stream.next().await {
+        let event = event?;
+        write_delta(writer, &event).await?;
+
+        match event {
+            ResponseStreamEvent::ResponseCompleted(event)
+            | ResponseStreamEvent::ResponseIncomplete(event)
+            | ResponseStreamEvent::ResponseFailed(event) => return Ok(event.response),
+            ResponseStreamEvent::Error(event) => {
+                return Err(OpenAIError::StreamError(event.message))
+            }
+            _ => {}
+        }
+    }
+
+    Err(OpenAIError::StreamError(
+        "stream ended before the response was completed".into(),
+    ))
+}
+
+/// Write the text and refusal deltas of the first choice of a chat completion `stream` to
+/// `writer` as they arrive, flushing after each delta, and return the response assembled from
+/// the chunks, see [ChatCompletionAccumulator::response]: the messages, tool calls and finish
+/// reasons of all choices, and the usage when `stream_options.include_usage` is set.
+///
+/// Fails when the stream ends before the first chunk.
+pub async fn write_chat_text<W>(
+    mut stream: ChatCompletionResponseStream,
+    writer: &mut W,
+) -> Result<CreateChatCompletionResponse, OpenAIError>
+where
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    let mut accumulator = ChatCompletionAccumulator::new();
+    while let Some(chunk) = stream.next().await {
+        for event in accumulator.apply(&chunk?) {
+            match event {
+                ChatCompletionStreamEvent::TextDelta { index: 0, delta }
+                | ChatCompletionStreamEvent::RefusalDelta { index: 0, delta } => {
+                    write_delta(writer, &delta).await?;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    accumulator
+        .response()
+        .ok_or_else(|| OpenAIError::StreamError("stream ended before the first chunk".into()))
+}
+
+/// Write the text of `delta`, if any, and return it.
+async fn write_delta<W>(writer: &mut W, delta: &impl Display) -> Result<String, OpenAIError>
+where
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    let text = delta.to_string();
+    if !text.is_empty() {
+        writer
+            .write_all(text.as_bytes())
+            .await
+            .map_err(|e| OpenAIError::StreamError(format!("failed to write text: {e}")))?;
+        writer
+            .flush()
+            .await
+            .map_err(|e| OpenAIError::StreamError(format!("failed to flush text: {e}")))?;
+    }
+    Ok(text)
+}
@@ -0,0 +1,63 @@
+//! [tracing] spans of API calls, with feature `tracing-spans`.
+//!
+//! Each call made by a [Client](crate::Client), including its retries, is instrumented with
+//! an `openai.request` span, recording the method, endpoint and model of the request, the status,
+//! request id and duration of the response, and its token usage. For streams, the span lasts
+//! until the stream is dropped, and a `debug` event is emitted for each chunk.
+use tracing::{field::Empty, Span};
+
+use crate::{
+    concurrent::request_model,
+    runtime::Instant,
+    types::{ResponseMetadata, TokenUsage},
+};
+
+/// Span of an API call, its fields recorded as the call progresses.
+pub(crate) fn request_span() -> Span {
+    tracing::info_span!(
+        "openai.request",
+        method = Empty,
+        endpoint = Empty,
+        model = Empty,
+        status = Empty,
+        request_id = Empty,
+        duration_ms = Empty,
+        input_tokens = Empty,
+        output_tokens = Empty,
+        total_tokens = Empty,
+    )
+}
+
+/// Record the method, endpoint and model of `request` in the current span.
+pub(crate) fn record_request(request: &reqwest::Request) {
+    let span = Span::current();
+    span.record("method", request.method().as_str());
+    span.record("endpoint", request.url().path());
+    let model = request_model(request);
+    if !model.is_empty() {
+        span.record("model", model.as_str());
+    }
+}
+
+/// Record the status and request id of a response in the current span, and the time
+/// elapsed since `started`.
+pub(crate) fn record_response(metadata: &ResponseMetadata, started: Instant) {
+    let span = Span::current();
+    span.record("status", metadata.status);
+    if let Some(request_id) = &metadata.request_id {
+        span.record("request_id", request_id.as_str());
+    }
+    span.record("duration_ms", started.elapsed().as_millis() as u64);
+}
+
+/// Record the token usage of a response or a stream in `span`.
+pub(crate) fn record_usage(span: &Span, usage: &TokenUsage) {
+    span.record("input_tokens", usage.input_tokens);
+    span.record("output_tokens", usage.output_tokens);
+    span.record("total_tokens", usage.total_tokens);
+}
+
+/// Emit an event for a chunk of a stream in `span`.
+pub(crate) fn record_chunk(span: &Span, data: &str) {
+    span.in_scope(|| tracing::debug!(bytes = data.len(), "openai.chunk"));
+}
@@ -0,0 +1,482 @@
+//! Server-sent events, parsed as specified by the
+//! [HTML standard](https://html.spec.whatwg.org/multipage/server-sent-events.html#event-stream-interpretation).
+use std::{collections::VecDeque, sync::Arc, time::Duration};
+
+use backoff::backoff::Backoff;
+use bytes::Bytes;
+use futures::StreamExt;
+
+use crate::{
+    concurrent::{request_model, RateLimiter},
+    config::Credentials,
+    error::{map_error_response, ApiErrorType, OpenAIError},
+    middleware::{self, Middleware, ResponseParts},
+    retry::{is_transient, next_delay, RetryBackoff},
+    runtime::{self, BoxStream},
+    transport::HttpTransport,
+    types::ResponseMetadata,
+};
+
+pub(crate) use eventsource_stream::Event;
+
+/// Delay before reconnecting when the server did not send a `retry` field.
+const DEFAULT_RETRY: Duration = Duration::from_secs(1);
+
+/// Reconnection attempts in a row, without receiving an event in between, before giving up.
+const MAX_RECONNECTS: u32 = 3;
+
+/// Incremental parser turning chunks of an event stream into [Event]s.
+///
+/// Handles `\n`, `\r\n` and `\r` line endings, comments, multi-line `data`
+/// and `id`/`retry` fields, regardless of how lines are split across chunks.
+#[derive(Debug, Default)]
+pub(crate) struct SseParser {
+    /// Incomplete line carried over from the previous chunk.
+    line: Vec<u8>,
+    /// The previous chunk ended with `\r`, so a leading `\n` is part of the same line ending.
+    pending_cr: bool,
+    /// Whether the leading byte order mark, if any, has been skipped.
+    started: bool,
+    event: String,
+    data: String,
+    last_event_id: String,
+    retry: Option<Duration>,
+}
+
+impl SseParser {
+    /// Parse `chunk`, pushing each complete event to `events`.
+    pub(crate) fn feed(&mut self, mut chunk: &[u8], events: &mut VecDeque<Event>) {
+        if !self.started && !chunk.is_empty() {
+            self.started = true;
+            chunk = chunk.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(chunk);
+        }
+
+        for &byte in chunk {
+            let pending_cr = std::mem::take(&mut self.pending_cr);
+            match byte {
+                b'\n' if pending_cr => {}
+                b'\n' | b'\r' => {
+                    self.pending_cr = byte == b'\r';
+                    let line = std::mem::take(&mut self.line);
+                    self.process_line(&String::from_utf8_lossy(&line), events);
+                }
+                _ => self.line.push(byte),
+            }
+        }
+    }
+
+    /// Discard the partially received event, e.g. after the connection was lost.
+    /// The last event id and reconnection time are kept.
+    pub(crate) fn reset(&mut self) {
+        self.line.clear();
+        self.pending_cr = false;
+        self.started = false;
+        self.event.clear();
+        self.data.clear();
+    }
+
+    /// Id of the last event received, empty if the server never sent one.
+    pub(crate) fn last_event_id(&self) -> &str {
+        &self.last_event_id
+    }
+
+    /// Reconnection time requested by the server.
+    pub(crate) fn retry(&self) -> Option<Duration> {
+        self.retry
+    }
+
+    fn process_line(&mut self, line: &str, events: &mut VecDeque<Event>) {
+        if line.is_empty() {
+            self.dispatch(events);
+            return;
+        }
+
+        // comment
+        if line.starts_with(':') {
+            return;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+
+        match field {
+            "event" => self.event = value.to_string(),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => self.last_event_id = value.to_string(),
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                self.retry = value.parse().ok().map(Duration::from_millis);
+            }
+            _ => {}
+        }
+    }
+
+    fn dispatch(&mut self, events: &mut VecDeque<Event>) {
+        let event = std::mem::take(&mut self.event);
+        let mut data = std::mem::take(&mut self.data);
+
+        if data.is_empty() {
+            return;
+        }
+        data.pop();
+
+        events.push_back(Event {
+            event: if event.is_empty() {
+                "message".to_string()
+            } else {
+                event
+            },
+            data,
+            id: self.last_event_id.clone(),
+            retry: self.retry,
+        });
+    }
+}
+
+/// Stream of [Event]s received in response to `request`.
+///
+/// When the connection fails after the server sent an event id, the request is sent again
+/// with a `Last-Event-ID` header so the server can resume where it left off. Without an event
+/// id the stream ends instead, as resending would start over e.g. a new generation. A stream
+/// closed by the server ends as well: servers ignoring `Last-Event-ID` would start over.
+///
+/// Until the first event is received, failures are retried with the backoff policy
+/// set by [EventSource::with_backoff], the same way as non-streaming requests.
+pub(crate) struct EventSource {
+    /// Taken on first connection if it has a streaming body, e.g. multipart, which can't be cloned.
+    request: Option<reqwest::RequestBuilder>,
+    body: Option<BoxStream<'static, Result<Bytes, OpenAIError>>>,
+    parser: SseParser,
+    events: VecDeque<Event>,
+    /// The client of the request builder when not set.
+    transport: Option<Arc<dyn HttpTransport>>,
+    backoff: Option<RetryBackoff>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    credentials: Option<Arc<dyn Credentials>>,
+    received: bool,
+    reconnects: u32,
+    closed: bool,
+}
+
+impl EventSource {
+    pub(crate) fn new(request: reqwest::RequestBuilder) -> Self {
+        Self {
+            request: Some(request),
+            body: None,
+            parser: SseParser::default(),
+            events: VecDeque::new(),
+            transport: None,
+            backoff: None,
+            rate_limiter: None,
+            middlewares: vec![],
+            credentials: None,
+            received: false,
+            reconnects: 0,
+            closed: false,
+        }
+    }
+
+    pub(crate) fn with_transport(mut self, transport: Arc<dyn HttpTransport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    pub(crate) fn with_backoff(mut self, mut backoff: RetryBackoff) -> Self {
+        backoff.reset();
+        self.backoff = Some(backoff);
+        self
+    }
+
+    /// Wait for the pause of the model of the request before each connection,
+    /// and pause from the rate limit headers of the response.
+    pub(crate) fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Invoke the hooks of `middlewares` around each connection.
+    pub(crate) fn with_middlewares(mut self, middlewares: Vec<Arc<dyn Middleware>>) -> Self {
+        self.middlewares = middlewares;
+        self
+    }
+
+    /// Refresh `credentials` before each connection, and send their headers.
+    pub(crate) fn with_credentials(mut self, credentials: Arc<dyn Credentials>) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    /// Wait for the next event, connecting first if needed.
+    /// Returns `None` once the stream has ended, and after an error.
+    pub(crate) async fn next(&mut self) -> Option<Result<Event, OpenAIError>> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                self.received = true;
+                self.reconnects = 0;
+                return Some(Ok(event));
+            }
+
+            if self.closed {
+                return None;
+            }
+
+            let result = if let Some(body) = &mut self.body {
+                match body.next().await {
+                    Some(Ok(chunk)) => {
+                        self.parser.feed(&chunk, &mut self.events);
+                        continue;
+                    }
+                    Some(Err(e)) => Err(e),
+                    // connection closed by server
+                    None => Ok(()),
+                }
+            } else {
+                let connected = match self.request().await {
+                    Ok(request) => {
+                        connect(
+                            request,
+                            self.transport.as_deref(),
+                            self.rate_limiter.as_deref(),
+                            &self.middlewares,
+                        )
+                        .await
+                    }
+                    Err(e) => Err(e),
+                };
+
+                match connected {
+                    Ok(body) => {
+                        self.body = Some(body);
+                        continue;
+                    }
+                    Err(e) => Err(e),
+                }
+            };
+
+            self.body = None;
+            self.parser.reset();
+
+            let delay = if self.received {
+                self.reconnect_delay(&result)
+            } else {
+                self.retry_delay(&result)
+            };
+
+            if let Some(delay) = delay {
+                runtime::sleep(delay).await;
+                continue;
+            }
+
+            self.closed = true;
+            if let Err(e) = result {
+                return Some(Err(e));
+            }
+        }
+    }
+
+    /// Delay before resuming a stream whose connection failed after some events were received,
+    /// `None` when it cannot be resumed, or was closed by the server.
+    fn reconnect_delay(&mut self, result: &Result<(), OpenAIError>) -> Option<Duration> {
+        let resumable = matches!(result, Err(OpenAIError::Reqwest(_)))
+            && !self.parser.last_event_id().is_empty()
+            && self.reconnects < MAX_RECONNECTS;
+
+        if !resumable {
+            return None;
+        }
+
+        self.reconnects += 1;
+        let delay = self.parser.retry().unwrap_or(DEFAULT_RETRY);
+        tracing::warn!(
+            "event stream interrupted, reconnecting in {delay:?} from event id {}",
+            self.parser.last_event_id()
+        );
+
+        Some(delay)
+    }
+
+    /// Delay before retrying a request which failed before any event was received,
+    /// `None` when the failure is permanent or retries are exhausted.
+    fn retry_delay(&mut self, result: &Result<(), OpenAIError>) -> Option<Duration> {
+        let backoff = self.backoff.as_mut()?;
+
+        let retry_after = match result {
+            // connection failed or was closed before the first event
+            Ok(()) => None,
+            Err(OpenAIError::Reqwest(e)) if is_transient(e) => None,
+            Err(OpenAIError::ApiError(e)) => {
+                let metadata = e.metadata.as_ref()?;
+                let rate_limited = metadata.status == 429
+                    // quota exceeded is reported with 429 as well
+                    && e.r#type != Some(ApiErrorType::InsufficientQuota);
+                if metadata.status < 500 && !rate_limited {
+                    return None;
+                }
+                metadata.retry_after
+            }
+            Err(OpenAIError::Http { status, .. }) if *status == 429 || *status >= 500 => None,
+            Err(_) => return None,
+        };
+
+        let delay = next_delay(backoff, retry_after)?;
+        tracing::warn!("event stream failed before the first event, retrying in {delay:?}");
+
+        Some(delay)
+    }
+
+    /// The request to (re)connect with.
+    async fn request(&mut self) -> Result<reqwest::RequestBuilder, OpenAIError> {
+        let mut request = match self.request.as_ref().and_then(|r| r.try_clone()) {
+            Some(request) => request,
+            None => self.request.take().ok_or_else(|| {
+                OpenAIError::StreamError("request with a streaming body cannot be resent".into())
+            })?,
+        };
+
+        if let Some(credentials) = &self.credentials {
+            credentials.refresh().await?;
+            request = request.headers(credentials.headers());
+        }
+
+        if self.parser.last_event_id().is_empty() {
+            Ok(request)
+        } else {
+            Ok(request.header("Last-Event-ID", self.parser.last_event_id()))
+        }
+    }
+}
+
+/// Send `request` with `transport`, or else the client of the request builder,
+/// and return the body of its response.
+async fn connect(
+    request: reqwest::RequestBuilder,
+    transport: Option<&dyn HttpTransport>,
+    rate_limiter: Option<&RateLimiter>,
+    middlewares: &[Arc<dyn Middleware>],
+) -> Result<BoxStream<'static, Result<Bytes, OpenAIError>>, OpenAIError> {
+    let (client, request) = request
+        .header(reqwest::header::ACCEPT, "text/event-stream")
+        .build_split();
+    let mut request = request?;
+    middleware::on_request(middlewares, &mut request).await?;
+    let (method, url) = (request.method().clone(), request.url().clone());
+    #[cfg(feature = "tracing-spans")]
+    let started = {
+        crate::spans::record_request(&request);
+        runtime::Instant::now()
+    };
+
+    let rate_limiter = rate_limiter.map(|rate_limiter| (rate_limiter, request_model(&request)));
+    if let Some((rate_limiter, model)) = &rate_limiter {
+        rate_limiter.wait(model).await;
+    }
+
+    let response = match transport {
+        Some(transport) => transport.execute(request).await?,
+        None => HttpTransport::execute(&client, request).await?,
+    };
+
+    let status = response.status;
+    let metadata = ResponseMetadata::from_response(status, &response.headers);
+    #[cfg(feature = "tracing-spans")]
+    crate::spans::record_response(&metadata, started);
+    if let Some((rate_limiter, model)) = &rate_limiter {
+        rate_limiter.observe(model, &metadata);
+    }
+
+    if !status.is_success() {
+        let headers = response.headers.clone();
+        let bytes = response.bytes().await?;
+        let parts = ResponseParts {
+            method: &method,
+            url: &url,
+            status,
+            headers: &headers,
+            body: Some(&bytes),
+        };
+        middleware::on_response(middlewares, &parts).await;
+        return Err(map_error_response(metadata, &bytes));
+    }
+
+    let parts = ResponseParts {
+        method: &method,
+        url: &url,
+        status,
+        headers: &response.headers,
+        body: None,
+    };
+    middleware::on_response(middlewares, &parts).await;
+
+    let content_type = response
+        .headers
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .unwrap_or_default();
+
+    if !content_type.starts_with("text/event-stream") {
+        return Err(OpenAIError::StreamError(format!(
+            "expected content type text/event-stream, got `{content_type}`"
+        )));
+    }
+
+    Ok(response.body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(chunks: &[&str]) -> Vec<Event> {
+        let mut parser = SseParser::default();
+        let mut events = VecDeque::new();
+        for chunk in chunks {
+            parser.feed(chunk.as_bytes(), &mut events);
+        }
+        events.into()
+    }
+
+    #[test]
+    fn multi_line_data_and_comments() {
+        let events =
+            parse(&[": keep-alive\n\nevent: thread.created\ndata: {\"a\":\ndata: 1}\nid: 7\n\n"]);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, "thread.created");
+        assert_eq!(events[0].data, "{\"a\":\n1}");
+        assert_eq!(events[0].id, "7");
+    }
+
+    #[test]
+    fn no_reconnect_when_closed_by_server() {
+        let request = reqwest::Client::new().post("http://localhost/v1/responses");
+        let mut source = EventSource::new(request);
+        source
+            .parser
+            .feed(b"id: 1\ndata: {}\n\n", &mut source.events);
+
+        assert_eq!(source.parser.last_event_id(), "1");
+        assert_eq!(source.reconnect_delay(&Ok(())), None);
+        assert_eq!(source.reconnects, 0);
+    }
+
+    #[test]
+    fn line_endings_split_across_chunks() {
+        let events = parse(&[
+            "\u{feff}data: one\r",
+            "\n\r",
+            "\ndata:two\r\rretry: 250\n",
+            "data: [DONE]\n",
+            "\n",
+        ]);
+
+        let data: Vec<_> = events.iter().map(|e| e.data.as_str()).collect();
+        assert_eq!(data, ["one", "two", "[DONE]"]);
+        assert_eq!(events[0].event, "message");
+        assert_eq!(events[2].retry, Some(Duration::from_millis(250)));
+    }
+}
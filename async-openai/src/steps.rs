@@ -3,6 +3,7 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::Paginated,
     types::{ListRunStepsResponse, RunStepObject},
     Client,
 };
@@ -47,4 +48,16 @@ impl<'c, C: Config> Steps<'c, C> {
             )
             .await
     }
+
+    /// Every page of [Steps::list], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate<Q>(&self, query: &Q) -> Result<Paginated<C, ListRunStepsResponse>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(
+            self.client,
+            format!("/threads/{}/runs/{}/steps", self.thread_id, self.run_id),
+            query,
+        )
+    }
 }
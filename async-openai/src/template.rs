@@ -0,0 +1,259 @@
+//! Prompt templates, so that prompts can live in configuration files and be filled at runtime.
+//!
+//! ```
+//! use async_openai::template::PromptTemplate;
+//! use serde_json::json;
+//!
+//! # fn main() -> Result<(), async_openai::error::OpenAIError> {
+//! let tone = PromptTemplate::new("Answer in a {{tone}} tone.")?;
+//! let template = PromptTemplate::new("You are a support agent for {{company.name}}. {{> tone}}")?
+//!     .with_partial("tone", tone);
+//!
+//! let prompt = template.render(&json!({
+//!     "company": { "name": "ACME" },
+//!     "tone": "friendly",
+//! }))?;
+//!
+//! assert_eq!(prompt, "You are a support agent for ACME. Answer in a friendly tone.");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Syntax:
+//! - `{{name}}` is replaced by the variable `name`, `{{a.b}}` by the field `b` of the object `a`
+//! - `{{> name}}` is replaced by the partial `name`, rendered with the same variables
+//! - `\{{` is a literal `{{`
+//!
+//! Variable values are inserted as is and never interpreted as template syntax,
+//! so user input cannot expand other variables or partials.
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    error::OpenAIError,
+    types::{
+        ChatCompletionRequestDeveloperMessage, ChatCompletionRequestSystemMessage,
+        ChatCompletionRequestUserMessage,
+    },
+};
+
+/// Partials including each other deeper than this are considered recursive.
+const MAX_PARTIAL_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Text(String),
+    Variable(Vec<String>),
+    Partial(String),
+}
+
+/// A prompt with `{{variables}}` and `{{> partials}}`, see [the module](self).
+///
+/// (De)serializes as its source string.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PromptTemplate {
+    source: String,
+    parts: Vec<Part>,
+    partials: HashMap<String, Arc<PromptTemplate>>,
+}
+
+impl PromptTemplate {
+    /// Parse `source`, failing on unclosed or empty tags.
+    pub fn new<S: Into<String>>(source: S) -> Result<Self, OpenAIError> {
+        let source = source.into();
+        let parts = parse(&source)?;
+
+        Ok(Self {
+            source,
+            parts,
+            partials: HashMap::new(),
+        })
+    }
+
+    /// Make `template` available as `{{> name}}`.
+    pub fn with_partial<S: Into<String>>(mut self, name: S, template: PromptTemplate) -> Self {
+        self.partials.insert(name.into(), Arc::new(template));
+        self
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Fill the template with the fields of `variables`, e.g. a `serde_json::json!` object,
+    /// a map, or a struct deriving `Serialize`.
+    ///
+    /// Fails when a variable or a partial is missing.
+    pub fn render<V: Serialize + ?Sized>(&self, variables: &V) -> Result<String, OpenAIError> {
+        let variables = serde_json::to_value(variables)
+            .map_err(|e| OpenAIError::InvalidArgument(format!("invalid variables: {e}")))?;
+
+        let mut output = String::new();
+        self.render_into(&variables, &self.partials, 0, &mut output)?;
+        Ok(output)
+    }
+
+    /// Render as a system message, see [PromptTemplate::render].
+    pub fn render_system<V: Serialize + ?Sized>(
+        &self,
+        variables: &V,
+    ) -> Result<ChatCompletionRequestSystemMessage, OpenAIError> {
+        Ok(self.render(variables)?.into())
+    }
+
+    /// Render as a developer message, see [PromptTemplate::render].
+    pub fn render_developer<V: Serialize + ?Sized>(
+        &self,
+        variables: &V,
+    ) -> Result<ChatCompletionRequestDeveloperMessage, OpenAIError> {
+        Ok(self.render(variables)?.into())
+    }
+
+    /// Render as a user message, see [PromptTemplate::render].
+    pub fn render_user<V: Serialize + ?Sized>(
+        &self,
+        variables: &V,
+    ) -> Result<ChatCompletionRequestUserMessage, OpenAIError> {
+        Ok(self.render(variables)?.into())
+    }
+
+    fn render_into(
+        &self,
+        variables: &Value,
+        partials: &HashMap<String, Arc<PromptTemplate>>,
+        depth: usize,
+        output: &mut String,
+    ) -> Result<(), OpenAIError> {
+        if depth > MAX_PARTIAL_DEPTH {
+            return Err(OpenAIError::InvalidArgument(
+                "template partials are recursive".into(),
+            ));
+        }
+
+        for part in &self.parts {
+            match part {
+                Part::Text(text) => output.push_str(text),
+                Part::Variable(path) => {
+                    let value = path
+                        .iter()
+                        .try_fold(variables, |value, key| value.get(key))
+                        .filter(|value| !value.is_null())
+                        .ok_or_else(|| {
+                            OpenAIError::InvalidArgument(format!(
+                                "missing template variable `{}`",
+                                path.join(".")
+                            ))
+                        })?;
+
+                    match value {
+                        Value::String(text) => output.push_str(text),
+                        value => output.push_str(&value.to_string()),
+                    }
+                }
+                Part::Partial(name) => {
+                    // partials of the partial take precedence
+                    let partial = self
+                        .partials
+                        .get(name)
+                        .or_else(|| partials.get(name))
+                        .ok_or_else(|| {
+                            OpenAIError::InvalidArgument(format!(
+                                "missing template partial `{name}`"
+                            ))
+                        })?;
+
+                    let mut scope = partials.clone();
+                    scope.extend(
+                        partial
+                            .partials
+                            .iter()
+                            .map(|(name, partial)| (name.clone(), partial.clone())),
+                    );
+                    partial.render_into(variables, &scope, depth + 1, output)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse(source: &str) -> Result<Vec<Part>, OpenAIError> {
+    let mut parts = vec![];
+    let mut text = String::new();
+    let mut rest = source;
+
+    while let Some(start) = rest.find("{{") {
+        // escaped `\{{`
+        if rest[..start].ends_with('\\') {
+            text.push_str(&rest[..start - 1]);
+            text.push_str("{{");
+            rest = &rest[start + 2..];
+            continue;
+        }
+
+        text.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after.find("}}").ok_or_else(|| {
+            OpenAIError::InvalidArgument(format!(
+                "unclosed `{{{{` at byte {} of template",
+                source.len() - rest.len() + start
+            ))
+        })?;
+
+        let tag = after[..end].trim();
+        let part = match tag.strip_prefix('>') {
+            Some(name) => Part::Partial(name.trim().to_string()),
+            None => Part::Variable(tag.split('.').map(|key| key.trim().to_string()).collect()),
+        };
+
+        let empty = match &part {
+            Part::Partial(name) => name.is_empty(),
+            Part::Variable(path) => path.iter().any(String::is_empty),
+            Part::Text(_) => false,
+        };
+        if empty {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "invalid template tag `{{{{{tag}}}}}`"
+            )));
+        }
+
+        if !text.is_empty() {
+            parts.push(Part::Text(std::mem::take(&mut text)));
+        }
+        parts.push(part);
+        rest = &after[end + 2..];
+    }
+
+    text.push_str(rest);
+    if !text.is_empty() {
+        parts.push(Part::Text(text));
+    }
+
+    Ok(parts)
+}
+
+impl FromStr for PromptTemplate {
+    type Err = OpenAIError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for PromptTemplate {
+    type Error = OpenAIError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<PromptTemplate> for String {
+    fn from(template: PromptTemplate) -> Self {
+        template.source
+    }
+}
@@ -0,0 +1,669 @@
+//! Mock [HttpTransport] to unit test code calling OpenAI, with feature `testing`.
+//!
+//! [MockTransport] answers requests with canned responses per endpoint, JSON bodies, SSE
+//! streams or errors, and records the requests it received for assertions:
+//!
+//! ```
+//! use async_openai::{
+//!     testing::{MockResponse, MockTransport},
+//!     types::{ChatCompletionRequestUserMessage, CreateChatCompletionRequestArgs},
+//! };
+//! use reqwest::Method;
+//!
+//! # tokio_test::block_on(async {
+//! let mock = MockTransport::new();
+//! mock.on(
+//!     Method::POST,
+//!     "/chat/completions",
+//!     MockResponse::json(serde_json::json!({
+//!         "id": "chatcmpl-123",
+//!         "object": "chat.completion",
+//!         "created": 1677652288,
+//!         "model": "gpt-4o-mini",
+//!         "choices": [{
+//!             "index": 0,
+//!             "message": {"role": "assistant", "content": "Hello!"},
+//!             "finish_reason": "stop"
+//!         }]
+//!     })),
+//! );
+//!
+//! let client = mock.client();
+//! let request = CreateChatCompletionRequestArgs::default()
+//!     .model("gpt-4o-mini")
+//!     .messages([ChatCompletionRequestUserMessage::from("Hi").into()])
+//!     .build()?;
+//! let response = client.chat().create(request).await?;
+//!
+//! assert_eq!(response.choices[0].message.content.as_deref(), Some("Hello!"));
+//! let requests = mock.requests();
+//! assert_eq!(requests[0].json::<serde_json::Value>()?["model"], "gpt-4o-mini");
+//! # Ok::<(), async_openai::error::OpenAIError>(())
+//! # });
+//! ```
+//!
+//! [Cassette] records the real requests and responses of a client to a JSON file, including
+//! streamed bodies, and replays them in later runs, so that integration tests e.g. of agents
+//! are reproducible and free:
+//!
+//! ```no_run
+//! use async_openai::{
+//!     testing::{Cassette, CassetteMode},
+//!     Client,
+//! };
+//!
+//! // records on the first run, replays once tests/cassettes/agent.json exists
+//! let cassette = Cassette::new("tests/cassettes/agent.json", CassetteMode::Once)?;
+//! let client = Client::new().with_transport(cassette);
+//! # Ok::<(), async_openai::error::OpenAIError>(())
+//! ```
+use std::{
+    collections::VecDeque,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use base64::{engine::general_purpose, Engine as _};
+use bytes::Bytes;
+use futures::TryStreamExt;
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE},
+    Method, StatusCode, Url,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    config::OpenAIConfig,
+    error::OpenAIError,
+    runtime::BoxFuture,
+    transport::{HttpResponse, HttpTransport},
+    Client,
+};
+
+/// A canned response of a [MockTransport].
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    /// Chunks of the body, sent one at a time, e.g. one per SSE event.
+    chunks: Vec<Bytes>,
+    /// Error failing the body after its chunks.
+    error: Option<String>,
+}
+
+impl MockResponse {
+    /// `200 OK` response with `body` serialized as JSON, e.g. a typed response or
+    /// a `serde_json::Value`.
+    ///
+    /// Panics if `body` fails to serialize.
+    pub fn json<T: Serialize>(body: T) -> Self {
+        let body = serde_json::to_vec(&body).expect("failed to serialize mock response");
+        Self::new(StatusCode::OK, "application/json", vec![body.into()])
+    }
+
+    /// `200 OK` server-sent events stream of `events` serialized as JSON, ended by `[DONE]`,
+    /// e.g. chat completion chunks. Each event is a separate chunk of the body.
+    ///
+    /// Panics if an event fails to serialize.
+    pub fn sse<T: Serialize>(events: impl IntoIterator<Item = T>) -> Self {
+        let chunks = events
+            .into_iter()
+            .map(|event| {
+                let event = serde_json::to_string(&event).expect("failed to serialize mock event");
+                Bytes::from(format!("data: {event}\n\n"))
+            })
+            .chain(std::iter::once(Bytes::from_static(b"data: [DONE]\n\n")))
+            .collect();
+        Self::new(StatusCode::OK, "text/event-stream", chunks)
+    }
+
+    /// Error response with an OpenAI error object, e.g. `429` with `rate_limit_exceeded`.
+    pub fn error(status: u16, r#type: &str, message: &str) -> Self {
+        let body = serde_json::json!({
+            "error": {
+                "message": message,
+                "type": r#type,
+                "param": null,
+                "code": null,
+            }
+        });
+        Self::json(body).with_status(status)
+    }
+
+    /// Response with `body` as is, e.g. an HTML error page of a gateway.
+    pub fn raw<B: Into<Bytes>>(status: u16, body: B) -> Self {
+        Self::new(StatusCode::OK, "text/plain", vec![body.into()]).with_status(status)
+    }
+
+    fn new(status: StatusCode, content_type: &'static str, chunks: Vec<Bytes>) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+        Self {
+            status,
+            headers,
+            chunks,
+            error: None,
+        }
+    }
+
+    /// Panics if `status` is not a valid status code.
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = StatusCode::from_u16(status).expect("invalid mock status");
+        self
+    }
+
+    /// Add a header, e.g. `x-request-id` or `retry-after`.
+    ///
+    /// Panics if `name` or `value` is not valid in a header.
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.insert(
+            HeaderName::try_from(name).expect("invalid mock header name"),
+            HeaderValue::try_from(value).expect("invalid mock header value"),
+        );
+        self
+    }
+
+    /// Fail the body after its chunks, e.g. to simulate a connection dropped mid-stream.
+    pub fn with_stream_error<S: Into<String>>(mut self, message: S) -> Self {
+        self.error = Some(message.into());
+        self
+    }
+}
+
+/// A request received by a [MockTransport].
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: HeaderMap,
+    /// The body of the request, `None` for multipart uploads whose body is a stream.
+    pub body: Option<Bytes>,
+}
+
+impl RecordedRequest {
+    /// Deserialize the JSON body of the request, e.g. into the request type.
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, OpenAIError> {
+        let body = self.body.as_deref().unwrap_or_default();
+        serde_json::from_slice(body).map_err(|e| {
+            OpenAIError::InvalidArgument(format!("request body is not valid JSON: {e}"))
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Route {
+    method: Method,
+    path: String,
+    /// Responses in the order they are returned, the last one repeated.
+    responses: VecDeque<MockResponse>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    routes: Vec<Route>,
+    requests: Vec<RecordedRequest>,
+}
+
+/// [HttpTransport] answering with the [MockResponse]s set with [MockTransport::on], see the
+/// [module](self) documentation.
+///
+/// Clones share their responses and recorded requests.
+#[derive(Debug, Clone, Default)]
+pub struct MockTransport {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Answer `method` requests whose url path ends with `path`, e.g. `/chat/completions`,
+    /// with `response`. Responses set for the same route are returned in order,
+    /// and the last one for any further request.
+    ///
+    /// Requests without a response fail with [OpenAIError::InvalidArgument].
+    pub fn on(&self, method: Method, path: &str, response: MockResponse) -> &Self {
+        let mut state = self.state.lock().unwrap();
+        match state
+            .routes
+            .iter_mut()
+            .find(|route| route.method == method && route.path == path)
+        {
+            Some(route) => route.responses.push_back(response),
+            None => state.routes.push(Route {
+                method,
+                path: path.to_string(),
+                responses: VecDeque::from([response]),
+            }),
+        }
+        self
+    }
+
+    /// The requests received so far, in order.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Client sending its requests to this transport, which doesn't retry failed requests.
+    pub fn client(&self) -> Client<OpenAIConfig> {
+        Client::with_config(OpenAIConfig::new().with_api_key("sk-test"))
+            .with_transport(self.clone())
+            .with_max_retries(0)
+    }
+
+    fn respond(&self, request: reqwest::Request) -> Result<MockResponse, OpenAIError> {
+        let mut state = self.state.lock().unwrap();
+        let recorded = RecordedRequest {
+            method: request.method().clone(),
+            url: request.url().clone(),
+            headers: request.headers().clone(),
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(Bytes::copy_from_slice),
+        };
+
+        let route = state.routes.iter_mut().find(|route| {
+            route.method == recorded.method && recorded.url.path().ends_with(&route.path)
+        });
+        let response = match route {
+            Some(route) if route.responses.len() > 1 => route.responses.pop_front(),
+            Some(route) => route.responses.front().cloned(),
+            None => None,
+        };
+
+        let response = response.ok_or_else(|| {
+            OpenAIError::InvalidArgument(format!(
+                "no mock response for {} {}",
+                recorded.method,
+                recorded.url.path()
+            ))
+        });
+        state.requests.push(recorded);
+        response
+    }
+}
+
+impl HttpTransport for MockTransport {
+    fn execute(
+        &self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'_, Result<HttpResponse, OpenAIError>> {
+        Box::pin(async move {
+            let response = self.respond(request)?;
+            let error = response
+                .error
+                .map(|message| Err(OpenAIError::StreamError(message)));
+            let body = futures::stream::iter(response.chunks.into_iter().map(Ok).chain(error));
+
+            Ok(HttpResponse {
+                status: response.status,
+                headers: response.headers,
+                body: Box::pin(body),
+            })
+        })
+    }
+}
+
+/// Headers whose values are not written to cassettes.
+const REDACTED_HEADERS: [&str; 4] = [
+    "authorization",
+    "api-key",
+    "openai-organization",
+    "openai-project",
+];
+
+/// Whether a [Cassette] sends requests or replays recorded ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Send requests and record them with their responses, replacing the cassette.
+    Record,
+    /// Replay recorded responses, failing requests which were not recorded.
+    Replay,
+    /// Replay if the cassette exists, record otherwise.
+    Once,
+}
+
+/// A body in a cassette, as text when it is valid UTF-8, in base64 otherwise,
+/// e.g. for audio.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum CassetteBody {
+    Text(String),
+    Base64(String),
+}
+
+impl From<&[u8]> for CassetteBody {
+    fn from(bytes: &[u8]) -> Self {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => CassetteBody::Text(text.to_string()),
+            Err(_) => CassetteBody::Base64(general_purpose::STANDARD.encode(bytes)),
+        }
+    }
+}
+
+impl CassetteBody {
+    fn to_bytes(&self) -> Result<Bytes, OpenAIError> {
+        match self {
+            CassetteBody::Text(text) => Ok(Bytes::from(text.clone())),
+            CassetteBody::Base64(b64) => general_purpose::STANDARD
+                .decode(b64)
+                .map(Bytes::from)
+                .map_err(|e| OpenAIError::FileReadError(format!("invalid cassette body: {e}"))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CassetteRequest {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<CassetteBody>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CassetteResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    /// Chunks of the body as received, e.g. SSE events.
+    chunks: Vec<CassetteBody>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    request: CassetteRequest,
+    response: CassetteResponse,
+}
+
+#[derive(Debug, Default)]
+struct CassetteState {
+    interactions: Vec<Interaction>,
+    /// Whether each interaction has been replayed already.
+    replayed: Vec<bool>,
+}
+
+/// [HttpTransport] recording requests and responses to a JSON file, or replaying them,
+/// see the [module](self) documentation.
+///
+/// `Authorization`, `api-key`, `OpenAI-Organization` and `OpenAI-Project` headers are redacted.
+/// Requests are replayed by method, url and body, each recorded interaction once, in order.
+/// Recorded bodies are read whole before they are returned, chunk by chunk as received.
+#[derive(Clone)]
+pub struct Cassette {
+    path: PathBuf,
+    /// Sends requests when recording.
+    transport: Option<Arc<dyn HttpTransport>>,
+    state: Arc<Mutex<CassetteState>>,
+}
+
+impl std::fmt::Debug for Cassette {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cassette")
+            .field("path", &self.path)
+            .field("recording", &self.transport.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Cassette {
+    /// Cassette at `path`, recording requests sent with a [reqwest::Client].
+    pub fn new<P: Into<PathBuf>>(path: P, mode: CassetteMode) -> Result<Self, OpenAIError> {
+        Self::with_transport(path, mode, reqwest::Client::new())
+    }
+
+    /// Cassette at `path`, recording requests sent with `transport`.
+    pub fn with_transport<P: Into<PathBuf>, T: HttpTransport + 'static>(
+        path: P,
+        mode: CassetteMode,
+        transport: T,
+    ) -> Result<Self, OpenAIError> {
+        let path = path.into();
+        let record = match mode {
+            CassetteMode::Record => true,
+            CassetteMode::Replay => false,
+            CassetteMode::Once => !path.exists(),
+        };
+
+        let state = if record {
+            CassetteState::default()
+        } else {
+            let json = std::fs::read(&path).map_err(|e| {
+                OpenAIError::FileReadError(format!("{e}, path: {}", path.display()))
+            })?;
+            let interactions: Vec<Interaction> = serde_json::from_slice(&json).map_err(|e| {
+                OpenAIError::FileReadError(format!("invalid cassette {}: {e}", path.display()))
+            })?;
+            CassetteState {
+                replayed: vec![false; interactions.len()],
+                interactions,
+            }
+        };
+
+        Ok(Self {
+            path,
+            transport: record.then(|| Arc::new(transport) as Arc<dyn HttpTransport>),
+            state: Arc::new(Mutex::new(state)),
+        })
+    }
+
+    fn cassette_request(request: &reqwest::Request) -> CassetteRequest {
+        CassetteRequest {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            headers: cassette_headers(request.headers()),
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(CassetteBody::from),
+        }
+    }
+
+    async fn record(
+        &self,
+        transport: &dyn HttpTransport,
+        request: reqwest::Request,
+    ) -> Result<HttpResponse, OpenAIError> {
+        let cassette_request = Self::cassette_request(&request);
+        let response = transport.execute(request).await?;
+        let chunks: Vec<Bytes> = response.body.try_collect().await?;
+
+        let interaction = Interaction {
+            request: cassette_request,
+            response: CassetteResponse {
+                status: response.status.as_u16(),
+                headers: cassette_headers(&response.headers),
+                chunks: chunks.iter().map(|chunk| chunk.as_ref().into()).collect(),
+            },
+        };
+        let json = {
+            let mut state = self.state.lock().unwrap();
+            state.interactions.push(interaction);
+            serde_json::to_vec_pretty(&state.interactions)
+                .map_err(|e| OpenAIError::FileSaveError(e.to_string()))?
+        };
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| OpenAIError::FileSaveError(e.to_string()))?;
+        }
+        std::fs::write(&self.path, json).map_err(|e| {
+            OpenAIError::FileSaveError(format!("{e}, path: {}", self.path.display()))
+        })?;
+
+        Ok(HttpResponse {
+            status: response.status,
+            headers: response.headers,
+            body: Box::pin(futures::stream::iter(chunks.into_iter().map(Ok))),
+        })
+    }
+
+    fn replay(&self, request: reqwest::Request) -> Result<HttpResponse, OpenAIError> {
+        let request = Self::cassette_request(&request);
+        let body = |body: &Option<CassetteBody>| {
+            body.as_ref()
+                .map(|body| body.to_bytes())
+                .transpose()
+                .ok()
+                .flatten()
+        };
+
+        let mut state = self.state.lock().unwrap();
+        let CassetteState {
+            interactions,
+            replayed,
+        } = &mut *state;
+        let (interaction, replayed) = interactions
+            .iter()
+            .zip(replayed.iter_mut())
+            .find(|(interaction, replayed)| {
+                !**replayed
+                    && interaction.request.method == request.method
+                    && interaction.request.url == request.url
+                    && body(&interaction.request.body) == body(&request.body)
+            })
+            .ok_or_else(|| {
+                OpenAIError::InvalidArgument(format!(
+                    "no recorded interaction for {} {} in cassette {}",
+                    request.method,
+                    request.url,
+                    self.path.display()
+                ))
+            })?;
+        *replayed = true;
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in &interaction.response.headers {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::try_from(name.as_str()),
+                HeaderValue::try_from(value.as_str()),
+            ) {
+                headers.append(name, value);
+            }
+        }
+        let chunks = interaction
+            .response
+            .chunks
+            .iter()
+            .map(CassetteBody::to_bytes)
+            .collect::<Vec<_>>();
+
+        Ok(HttpResponse {
+            status: StatusCode::from_u16(interaction.response.status)
+                .map_err(|e| OpenAIError::FileReadError(format!("invalid cassette status: {e}")))?,
+            headers,
+            body: Box::pin(futures::stream::iter(chunks)),
+        })
+    }
+}
+
+/// Headers of a request or response for a cassette, redacted.
+fn cassette_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if REDACTED_HEADERS.contains(&name.as_str()) {
+                "[REDACTED]".to_string()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+impl HttpTransport for Cassette {
+    fn execute(
+        &self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'_, Result<HttpResponse, OpenAIError>> {
+        Box::pin(async move {
+            match &self.transport {
+                Some(transport) => self.record(transport.as_ref(), request).await,
+                None => self.replay(request),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn responses_in_order() {
+        let mock = MockTransport::new();
+        mock.on(
+            Method::GET,
+            "/models",
+            MockResponse::error(500, "server_error", "boom"),
+        )
+        .on(
+            Method::GET,
+            "/models",
+            MockResponse::json(serde_json::json!({"data": []})),
+        );
+
+        let url = Url::parse("https://api.openai.com/v1/models").unwrap();
+        let statuses: Vec<_> = futures::future::join_all((0..3).map(|_| {
+            let request = reqwest::Request::new(Method::GET, url.clone());
+            async { mock.execute(request).await.unwrap().status.as_u16() }
+        }))
+        .await;
+        assert_eq!(statuses, [500, 200, 200]);
+        assert_eq!(mock.requests().len(), 3);
+
+        let request = reqwest::Request::new(Method::POST, url);
+        assert!(mock.execute(request).await.is_err());
+        assert_eq!(mock.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn cassette_record_replay() {
+        let path =
+            std::env::temp_dir().join(format!("async-openai-cassette-{}.json", std::process::id()));
+        let mock = MockTransport::new();
+        mock.on(
+            Method::POST,
+            "/chat/completions",
+            MockResponse::sse([serde_json::json!({"n": 1}), serde_json::json!({"n": 2})]),
+        );
+
+        let request = || {
+            let url = Url::parse("https://api.openai.com/v1/chat/completions").unwrap();
+            let mut request = reqwest::Request::new(Method::POST, url);
+            request
+                .headers_mut()
+                .insert("authorization", "Bearer sk-secret".parse().unwrap());
+            *request.body_mut() = Some(r#"{"model":"gpt-4o-mini"}"#.into());
+            request
+        };
+
+        let cassette = Cassette::with_transport(&path, CassetteMode::Record, mock).unwrap();
+        let recorded: Vec<Bytes> = cassette
+            .execute(request())
+            .await
+            .unwrap()
+            .body
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(recorded.len(), 3);
+        assert!(!std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("sk-secret"));
+
+        let cassette = Cassette::new(&path, CassetteMode::Once).unwrap();
+        let replayed: Vec<Bytes> = cassette
+            .execute(request())
+            .await
+            .unwrap()
+            .body
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(replayed, recorded);
+        assert!(cassette.execute(request()).await.is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
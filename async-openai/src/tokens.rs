@@ -0,0 +1,429 @@
+//! Estimate the number of prompt tokens of a request before sending it, e.g. to check that
+//! it fits in the context window of the model.
+//!
+//! ```no_run
+//! use async_openai::{
+//!     tokens::{TokenCount, Tokenizer},
+//!     types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs},
+//! };
+//!
+//! # fn main() -> Result<(), async_openai::error::OpenAIError> {
+//! let request = CreateChatCompletionRequestArgs::default()
+//!     .model("gpt-4o")
+//!     .messages([ChatCompletionRequestUserMessageArgs::default()
+//!         .content("What is the capital of France?")
+//!         .build()?
+//!         .into()])
+//!     .build()?;
+//!
+//! let tokenizer = Tokenizer::for_model(&request.model)?;
+//! let prompt_tokens = request.token_count(&tokenizer);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [count_tokens] does the same in one call, loading the tokenizer of the model each time.
+//! Requests and items of the [Responses](crate::Responses) API are counted like chat messages.
+//!
+//! Counts follow the accounting documented by OpenAI, the actual `prompt_tokens` reported
+//! in the usage of the response may differ slightly, in particular with tools. Files are
+//! not counted.
+use std::sync::Arc;
+
+use base64::Engine;
+use tiktoken_rs::CoreBPE;
+
+use crate::{
+    error::OpenAIError,
+    types::{
+        ChatCompletionRequestAssistantMessageContent,
+        ChatCompletionRequestAssistantMessageContentPart,
+        ChatCompletionRequestDeveloperMessageContent, ChatCompletionRequestMessage,
+        ChatCompletionRequestSystemMessageContent, ChatCompletionRequestSystemMessageContentPart,
+        ChatCompletionRequestToolMessageContent, ChatCompletionRequestToolMessageContentPart,
+        ChatCompletionRequestUserMessageContent, ChatCompletionRequestUserMessageContentPart,
+        ContentPart, CreateChatCompletionRequest, CreateEmbeddingRequest, CreateResponse,
+        EmbeddingInput, ImageDetail, ImageUrl, Input, InputContent, InputImage, Item,
+    },
+};
+
+/// Tokens added to every message, for the role and delimiters.
+const TOKENS_PER_MESSAGE: usize = 3;
+/// Tokens added when a message has a `name`.
+const TOKENS_PER_NAME: usize = 1;
+/// Tokens priming the reply of the assistant.
+const TOKENS_PER_REPLY: usize = 3;
+
+/// Tokens of an image with `detail: low`, and base tokens of other images.
+const IMAGE_BASE_TOKENS: usize = 85;
+/// Tokens of each 512px tile of an image with `detail: high`.
+const IMAGE_TILE_TOKENS: usize = 170;
+
+/// Encoding of the text of a model.
+///
+/// Creating a tokenizer loads its vocabulary, which is slow: create it once and reuse it,
+/// clones are cheap.
+#[derive(Clone)]
+pub struct Tokenizer {
+    bpe: Arc<CoreBPE>,
+}
+
+impl std::fmt::Debug for Tokenizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Tokenizer")
+    }
+}
+
+impl Tokenizer {
+    /// Tokenizer of `model`. Models unknown to tiktoken, e.g. of OpenAI compatible servers,
+    /// fall back to `o200k_base`, the encoding of recent OpenAI models.
+    pub fn for_model(model: &str) -> Result<Self, OpenAIError> {
+        let bpe = match tiktoken_rs::get_bpe_from_model(model) {
+            Ok(bpe) => bpe,
+            Err(_) => tiktoken_rs::o200k_base().map_err(|e| {
+                OpenAIError::InvalidArgument(format!("failed to load tokenizer: {e}"))
+            })?,
+        };
+
+        Ok(Self { bpe: Arc::new(bpe) })
+    }
+
+    /// Number of tokens of `text`.
+    pub fn count(&self, text: &str) -> usize {
+        self.bpe.encode_ordinary(text).len()
+    }
+}
+
+/// Estimate of the tokens of `input` for `model`, e.g. a request or its messages, see
+/// [TokenCount].
+///
+/// Loads the tokenizer of `model`, which is slow: to count often, reuse a [Tokenizer].
+pub fn count_tokens<T: TokenCount + ?Sized>(model: &str, input: &T) -> Result<usize, OpenAIError> {
+    Ok(input.token_count(&Tokenizer::for_model(model)?))
+}
+
+/// Estimate of the tokens of a prompt, or of a part of it.
+pub trait TokenCount {
+    fn token_count(&self, tokenizer: &Tokenizer) -> usize;
+}
+
+impl<T: TokenCount> TokenCount for [T] {
+    fn token_count(&self, tokenizer: &Tokenizer) -> usize {
+        self.iter().map(|item| item.token_count(tokenizer)).sum()
+    }
+}
+
+impl<T: TokenCount> TokenCount for Vec<T> {
+    fn token_count(&self, tokenizer: &Tokenizer) -> usize {
+        self.as_slice().token_count(tokenizer)
+    }
+}
+
+impl TokenCount for str {
+    fn token_count(&self, tokenizer: &Tokenizer) -> usize {
+        tokenizer.count(self)
+    }
+}
+
+impl TokenCount for String {
+    fn token_count(&self, tokenizer: &Tokenizer) -> usize {
+        tokenizer.count(self)
+    }
+}
+
+impl TokenCount for CreateChatCompletionRequest {
+    fn token_count(&self, tokenizer: &Tokenizer) -> usize {
+        let tools = self
+            .tools
+            .as_ref()
+            .filter(|tools| !tools.is_empty())
+            .and_then(|tools| serde_json::to_string(tools).ok())
+            .map(|tools| tokenizer.count(&tools))
+            .unwrap_or_default();
+
+        self.messages.token_count(tokenizer) + tools + TOKENS_PER_REPLY
+    }
+}
+
+impl TokenCount for ChatCompletionRequestMessage {
+    fn token_count(&self, tokenizer: &Tokenizer) -> usize {
+        let (content, name) = match self {
+            ChatCompletionRequestMessage::Developer(message) => {
+                let content = match &message.content {
+                    ChatCompletionRequestDeveloperMessageContent::Text(text) => {
+                        tokenizer.count(text)
+                    }
+                    ChatCompletionRequestDeveloperMessageContent::Array(parts) => {
+                        parts.iter().map(|part| tokenizer.count(&part.text)).sum()
+                    }
+                };
+                (content, message.name.as_deref())
+            }
+            ChatCompletionRequestMessage::System(message) => {
+                let content = match &message.content {
+                    ChatCompletionRequestSystemMessageContent::Text(text) => tokenizer.count(text),
+                    ChatCompletionRequestSystemMessageContent::Array(parts) => parts
+                        .iter()
+                        .map(|part| match part {
+                            ChatCompletionRequestSystemMessageContentPart::Text(part) => {
+                                tokenizer.count(&part.text)
+                            }
+                        })
+                        .sum(),
+                };
+                (content, message.name.as_deref())
+            }
+            ChatCompletionRequestMessage::User(message) => {
+                let content = match &message.content {
+                    ChatCompletionRequestUserMessageContent::Text(text) => tokenizer.count(text),
+                    ChatCompletionRequestUserMessageContent::Array(parts) => parts
+                        .iter()
+                        .map(|part| match part {
+                            ChatCompletionRequestUserMessageContentPart::Text(part) => {
+                                tokenizer.count(&part.text)
+                            }
+                            ChatCompletionRequestUserMessageContentPart::ImageUrl(part) => {
+                                part.image_url.token_count(tokenizer)
+                            }
+                            // audio is billed by duration, not tokenized
+                            ChatCompletionRequestUserMessageContentPart::InputAudio(_) => 0,
+                        })
+                        .sum(),
+                };
+                (content, message.name.as_deref())
+            }
+            ChatCompletionRequestMessage::Assistant(message) => {
+                let content = match &message.content {
+                    Some(ChatCompletionRequestAssistantMessageContent::Text(text)) => {
+                        tokenizer.count(text)
+                    }
+                    Some(ChatCompletionRequestAssistantMessageContent::Array(parts)) => parts
+                        .iter()
+                        .map(|part| match part {
+                            ChatCompletionRequestAssistantMessageContentPart::Text(part) => {
+                                tokenizer.count(&part.text)
+                            }
+                            ChatCompletionRequestAssistantMessageContentPart::Refusal(part) => {
+                                tokenizer.count(&part.refusal)
+                            }
+                        })
+                        .sum(),
+                    None => 0,
+                };
+
+                let refusal = message
+                    .refusal
+                    .as_deref()
+                    .map(|refusal| tokenizer.count(refusal))
+                    .unwrap_or_default();
+
+                let tool_calls: usize = message
+                    .tool_calls
+                    .iter()
+                    .flatten()
+                    .map(|tool_call| {
+                        TOKENS_PER_MESSAGE
+                            + tokenizer.count(&tool_call.function.name)
+                            + tokenizer.count(&tool_call.function.arguments)
+                    })
+                    .sum();
+
+                (content + refusal + tool_calls, message.name.as_deref())
+            }
+            ChatCompletionRequestMessage::Tool(message) => {
+                let content = match &message.content {
+                    ChatCompletionRequestToolMessageContent::Text(text) => tokenizer.count(text),
+                    ChatCompletionRequestToolMessageContent::Array(parts) => parts
+                        .iter()
+                        .map(|part| match part {
+                            ChatCompletionRequestToolMessageContentPart::Text(part) => {
+                                tokenizer.count(&part.text)
+                            }
+                        })
+                        .sum(),
+                };
+                (content, None)
+            }
+            ChatCompletionRequestMessage::Function(message) => {
+                let content = message
+                    .content
+                    .as_deref()
+                    .map(|content| tokenizer.count(content))
+                    .unwrap_or_default();
+                (content, Some(message.name.as_str()))
+            }
+        };
+
+        let name = name
+            .map(|name| TOKENS_PER_NAME + tokenizer.count(name))
+            .unwrap_or_default();
+
+        TOKENS_PER_MESSAGE + content + name
+    }
+}
+
+impl TokenCount for ImageUrl {
+    /// Images are resized to fit in 2048x2048, then so that their shortest side is at most
+    /// 768px, and billed per 512px tile. Only the dimensions of PNG, JPEG and GIF images
+    /// sent as data URLs are known: other images are counted with the largest possible size.
+    fn token_count(&self, _tokenizer: &Tokenizer) -> usize {
+        image_url_tokens(Some(&self.url), self.detail.as_ref())
+    }
+}
+
+impl TokenCount for CreateResponse {
+    fn token_count(&self, tokenizer: &Tokenizer) -> usize {
+        let instructions = self
+            .instructions
+            .as_deref()
+            .map(|instructions| TOKENS_PER_MESSAGE + tokenizer.count(instructions))
+            .unwrap_or_default();
+
+        let tools = self
+            .tools
+            .as_ref()
+            .filter(|tools| !tools.is_empty())
+            .and_then(|tools| serde_json::to_string(tools).ok())
+            .map(|tools| tokenizer.count(&tools))
+            .unwrap_or_default();
+
+        instructions + self.input.token_count(tokenizer) + tools + TOKENS_PER_REPLY
+    }
+}
+
+impl TokenCount for Input {
+    fn token_count(&self, tokenizer: &Tokenizer) -> usize {
+        match self {
+            Input::Text(text) => TOKENS_PER_MESSAGE + tokenizer.count(text),
+            Input::Items(items) => items.token_count(tokenizer),
+        }
+    }
+}
+
+impl TokenCount for Item {
+    /// Messages are counted like chat messages, function calls and their outputs like tool
+    /// calls and tool messages. Other items, e.g. of built-in tools, are counted by their JSON.
+    /// References to items of previous responses are not counted.
+    fn token_count(&self, tokenizer: &Tokenizer) -> usize {
+        let content = match self {
+            Item::Message(message) => match &message.content {
+                InputContent::Text(text) => tokenizer.count(text),
+                InputContent::Parts(parts) => parts
+                    .iter()
+                    .map(|part| match part {
+                        ContentPart::InputText(part) => tokenizer.count(&part.text),
+                        ContentPart::OutputText(part) => tokenizer.count(&part.text),
+                        ContentPart::Refusal(part) => tokenizer.count(&part.refusal),
+                        ContentPart::InputImage(image) => image.token_count(tokenizer),
+                        ContentPart::InputFile(_) => 0,
+                    })
+                    .sum(),
+            },
+            Item::FunctionCall(call) => {
+                tokenizer.count(&call.name) + tokenizer.count(&call.arguments)
+            }
+            Item::FunctionCallOutput(output) => tokenizer.count(&output.output),
+            Item::ItemReference(_) => return 0,
+            item => serde_json::to_string(item)
+                .map(|item| tokenizer.count(&item))
+                .unwrap_or_default(),
+        };
+
+        TOKENS_PER_MESSAGE + content
+    }
+}
+
+impl TokenCount for InputImage {
+    /// See [ImageUrl::token_count], images sent by file ID are counted with the largest
+    /// possible size.
+    fn token_count(&self, _tokenizer: &Tokenizer) -> usize {
+        image_url_tokens(self.image_url.as_deref(), Some(&self.detail))
+    }
+}
+
+impl TokenCount for EmbeddingInput {
+    fn token_count(&self, tokenizer: &Tokenizer) -> usize {
+        match self {
+            EmbeddingInput::String(text) => tokenizer.count(text),
+            EmbeddingInput::StringArray(texts) => texts.token_count(tokenizer),
+            EmbeddingInput::IntegerArray(tokens) => tokens.len(),
+            EmbeddingInput::ArrayOfIntegerArray(tokens) => tokens.iter().map(Vec::len).sum(),
+        }
+    }
+}
+
+impl TokenCount for CreateEmbeddingRequest {
+    fn token_count(&self, tokenizer: &Tokenizer) -> usize {
+        self.input.token_count(tokenizer)
+    }
+}
+
+/// Tokens of an image of `width` x `height` pixels with `detail: high`.
+pub fn image_tokens(width: u32, height: u32) -> usize {
+    let (mut width, mut height) = (width.max(1) as f64, height.max(1) as f64);
+
+    let fit = (2048.0 / width.max(height)).min(1.0);
+    width *= fit;
+    height *= fit;
+
+    let shortest = (768.0 / width.min(height)).min(1.0);
+    width *= shortest;
+    height *= shortest;
+
+    let tiles = (width / 512.0).ceil() as usize * (height / 512.0).ceil() as usize;
+    IMAGE_BASE_TOKENS + IMAGE_TILE_TOKENS * tiles
+}
+
+/// Tokens of the image at `url`, see [ImageUrl::token_count].
+fn image_url_tokens(url: Option<&str>, detail: Option<&ImageDetail>) -> usize {
+    if detail == Some(&ImageDetail::Low) {
+        return IMAGE_BASE_TOKENS;
+    }
+
+    let (width, height) = url.and_then(data_url_dimensions).unwrap_or((768, 2048));
+    image_tokens(width, height)
+}
+
+/// Dimensions of the image of a base64 `data:` URL.
+fn data_url_dimensions(url: &str) -> Option<(u32, u32)> {
+    let (header, data) = url.strip_prefix("data:")?.split_once(',')?;
+    if !header.ends_with(";base64") {
+        return None;
+    }
+
+    // the dimensions are within the first bytes, except for JPEG where they follow metadata
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .ok()?;
+
+    image_dimensions(&bytes)
+}
+
+fn image_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |i: usize| Some(u16::from_be_bytes(bytes.get(i..i + 2)?.try_into().ok()?) as u32);
+    let be32 = |i: usize| Some(u32::from_be_bytes(bytes.get(i..i + 4)?.try_into().ok()?));
+    let le16 = |i: usize| Some(u16::from_le_bytes(bytes.get(i..i + 2)?.try_into().ok()?) as u32);
+
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Some((be32(16)?, be32(20)?));
+    }
+
+    if bytes.starts_with(b"GIF8") {
+        return Some((le16(6)?, le16(8)?));
+    }
+
+    if bytes.starts_with(&[0xFF, 0xD8]) {
+        // walk the segments up to the start of frame
+        let mut i = 2;
+        while *bytes.get(i)? == 0xFF {
+            let marker = *bytes.get(i + 1)?;
+            let is_sof = matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC);
+            if is_sof {
+                return Some((be16(i + 7)?, be16(i + 5)?));
+            }
+            i += 2 + be16(i + 2)? as usize;
+        }
+    }
+
+    None
+}
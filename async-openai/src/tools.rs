@@ -0,0 +1,479 @@
+//! Function calling with Rust functions.
+//!
+//! The [tool] attribute turns an async function into a [Tool]: its doc comment becomes the
+//! description, and its arguments the JSON Schema of the parameters. Tools are collected in a
+//! [ToolSet], which provides the `tools` of a request and runs the tool calls of the model.
+//!
+//! ```no_run
+//! use async_openai::tools::{tool, ToolSet};
+//!
+//! /// Get the current weather in a given location.
+//! ///
+//! /// # Arguments
+//! ///
+//! /// * `location` - The city and state, e.g. San Francisco, CA
+//! /// * `unit` - `celsius` or `fahrenheit`
+//! #[tool]
+//! async fn get_current_weather(
+//!     location: String,
+//!     unit: Option<String>,
+//! ) -> Result<serde_json::Value, std::io::Error> {
+//!     Ok(serde_json::json!({ "location": location, "temperature": 22, "unit": unit }))
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let tool_set = ToolSet::new().with_tool(GetCurrentWeatherTool);
+//!
+//! // `tools` of a chat completion request
+//! let tools = tool_set.chat_tools();
+//!
+//! // run the tool calls of the response, and send the resulting messages back
+//! # let tool_calls: Vec<async_openai::types::ChatCompletionMessageToolCall> = vec![];
+//! let messages = tool_set.dispatch_all(&tool_calls).await;
+//! # });
+//! ```
+//!
+//! The [tool] attribute also applies to a struct of parameters, implementing [ToolParameters],
+//! to register a closure handling them with [ToolSet::register_handler]:
+//!
+//! ```no_run
+//! use async_openai::tools::{tool, ToolSet};
+//!
+//! /// Get the current weather in a given location.
+//! #[tool(name = "get_current_weather")]
+//! struct Weather {
+//!     /// The city and state, e.g. San Francisco, CA
+//!     location: String,
+//! }
+//!
+//! let tool_set = ToolSet::new().with_handler(|weather: Weather| async move {
+//!     Ok::<_, std::io::Error>(serde_json::json!({ "location": weather.location, "temperature": 22 }))
+//! });
+//! ```
+//!
+//! With the Responses API, [crate::Responses::create_with_tools] runs the function calls of
+//! the model until it answers, with the `tools` of the request from [ToolSet::response_tools].
+//!
+//! Tool functions must return a `Result` whose value is `Serialize` and error `Display`.
+//! Argument types must implement `Deserialize` and [schemars::JsonSchema].
+use std::{collections::BTreeMap, fmt::Display, future::Future, marker::PhantomData, sync::Arc};
+
+use futures::future::join_all;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    error::OpenAIError,
+    runtime::{BoxFuture, MaybeSend, MaybeSync},
+    types::{
+        AssistantTools, AssistantToolsFunction, ChatCompletionMessageToolCall,
+        ChatCompletionRequestMessage, ChatCompletionRequestToolMessage, ChatCompletionTool,
+        ChatCompletionToolType, FunctionCallOutput, FunctionObject, FunctionTool, FunctionToolCall,
+        Item, RunObject, RunToolCallObject, SubmitToolOutputsRunRequest, ToolsOutputs,
+    },
+};
+
+/// Turn an async function into a [Tool], or a struct into [ToolParameters].
+///
+/// On a function, generates a unit struct named after the function, e.g. `GetWeatherTool` for
+/// `get_weather`, which implements [Tool]. The function name is the name of the tool unless
+/// overridden with `#[tool(name = "...")]`.
+///
+/// The doc comment describes the function to the model. Arguments are described by an
+/// `# Arguments` section, following rustdoc conventions.
+///
+/// On a struct with named fields, derives `Deserialize` and [schemars::JsonSchema] and implements
+/// [ToolParameters]. The name of the tool is the name of the struct in snake case, e.g.
+/// `get_weather` for `GetWeather`, unless overridden, and fields are described by their doc
+/// comments.
+pub use async_openai_macros::tool;
+
+/// A function the model can call.
+///
+/// Usually implemented with the [tool] attribute.
+pub trait Tool: MaybeSend + MaybeSync {
+    /// Name, description and parameters of the function.
+    fn definition(&self) -> FunctionObject;
+
+    /// Call the function with `arguments` as generated by the model, in JSON format.
+    /// Returns the output to send back to the model.
+    fn call<'a>(&'a self, arguments: &'a str) -> BoxFuture<'a, Result<String, OpenAIError>>;
+}
+
+/// Parameters of a function the model can call, deserialized from the arguments it generates.
+///
+/// Usually implemented with the [tool] attribute on a struct.
+pub trait ToolParameters: DeserializeOwned + schemars::JsonSchema + MaybeSend + 'static {
+    /// Name, description and parameters of the function.
+    fn definition() -> FunctionObject;
+}
+
+/// A [Tool] handling [ToolParameters] with a closure, see [ToolSet::register_handler].
+struct HandlerTool<P, F> {
+    f: F,
+    parameters: PhantomData<fn() -> P>,
+}
+
+impl<P, F, Fut, T, E> Tool for HandlerTool<P, F>
+where
+    P: ToolParameters,
+    F: Fn(P) -> Fut + MaybeSend + MaybeSync,
+    Fut: Future<Output = Result<T, E>> + MaybeSend + 'static,
+    T: Serialize,
+    E: Display,
+{
+    fn definition(&self) -> FunctionObject {
+        P::definition()
+    }
+
+    fn call<'a>(&'a self, arguments: &'a str) -> BoxFuture<'a, Result<String, OpenAIError>> {
+        Box::pin(async move {
+            let name = P::definition().name;
+            let parameters = __private::arguments(&name, arguments)?;
+            __private::output(&name, (self.f)(parameters).await)
+        })
+    }
+}
+
+/// A [Tool] defined by a closure, see [ToolSet::register_fn].
+struct FnTool<F> {
+    definition: FunctionObject,
+    f: F,
+}
+
+impl<F, Fut> Tool for FnTool<F>
+where
+    F: Fn(String) -> Fut + MaybeSend + MaybeSync,
+    Fut: Future<Output = Result<String, OpenAIError>> + MaybeSend + 'static,
+{
+    fn definition(&self) -> FunctionObject {
+        self.definition.clone()
+    }
+
+    fn call<'a>(&'a self, arguments: &'a str) -> BoxFuture<'a, Result<String, OpenAIError>> {
+        Box::pin((self.f)(arguments.to_string()))
+    }
+}
+
+/// A call of a [Tool] by the model, in the shape of one of the APIs, see [ToolSet::dispatch].
+pub trait ToolCall {
+    /// The message or item answering the call.
+    type Output;
+
+    /// Name of the function called.
+    fn name(&self) -> &str;
+
+    /// Arguments generated by the model, in JSON format.
+    fn arguments(&self) -> &str;
+
+    /// Answer the call with the `output` of the function.
+    fn output(&self, output: String) -> Self::Output;
+}
+
+impl ToolCall for ChatCompletionMessageToolCall {
+    type Output = ChatCompletionRequestMessage;
+
+    fn name(&self) -> &str {
+        &self.function.name
+    }
+
+    fn arguments(&self) -> &str {
+        &self.function.arguments
+    }
+
+    fn output(&self, output: String) -> Self::Output {
+        ChatCompletionRequestToolMessage {
+            content: output.into(),
+            tool_call_id: self.id.clone(),
+        }
+        .into()
+    }
+}
+
+impl ToolCall for FunctionToolCall {
+    type Output = Item;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn arguments(&self) -> &str {
+        &self.arguments
+    }
+
+    fn output(&self, output: String) -> Self::Output {
+        Item::FunctionCallOutput(FunctionCallOutput {
+            id: None,
+            call_id: self.call_id.clone(),
+            output,
+            status: None,
+        })
+    }
+}
+
+impl ToolCall for RunToolCallObject {
+    type Output = ToolsOutputs;
+
+    fn name(&self) -> &str {
+        &self.function.name
+    }
+
+    fn arguments(&self) -> &str {
+        &self.function.arguments
+    }
+
+    fn output(&self, output: String) -> Self::Output {
+        ToolsOutputs {
+            tool_call_id: Some(self.id.clone()),
+            output: Some(output),
+        }
+    }
+}
+
+/// Set of [Tool]s, by name, shared by chat completions, [Responses](crate::Responses) and
+/// assistants: it provides the `tools` of their requests and answers the tool calls of their
+/// models.
+#[derive(Clone, Default)]
+pub struct ToolSet {
+    tools: BTreeMap<String, (FunctionObject, Arc<dyn Tool>)>,
+}
+
+/// Former name of [ToolSet].
+#[deprecated(note = "renamed to ToolSet")]
+pub type ToolRegistry = ToolSet;
+
+impl std::fmt::Debug for ToolSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.tools.keys()).finish()
+    }
+}
+
+impl ToolSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `tool`, replacing any tool of the same name.
+    pub fn register<T: Tool + 'static>(&mut self, tool: T) -> &mut Self {
+        let definition = tool.definition();
+        self.tools
+            .insert(definition.name.clone(), (definition, Arc::new(tool)));
+        self
+    }
+
+    /// Add `tool`, replacing any tool of the same name.
+    pub fn with_tool<T: Tool + 'static>(mut self, tool: T) -> Self {
+        self.register(tool);
+        self
+    }
+
+    /// Add a tool defined by `definition`, called with the arguments generated by the model
+    /// in JSON format, replacing any tool of the same name.
+    pub fn register_fn<F, Fut>(&mut self, definition: FunctionObject, f: F) -> &mut Self
+    where
+        F: Fn(String) -> Fut + MaybeSend + MaybeSync + 'static,
+        Fut: Future<Output = Result<String, OpenAIError>> + MaybeSend + 'static,
+    {
+        self.register(FnTool { definition, f })
+    }
+
+    /// Add a tool defined by `definition`, see [ToolSet::register_fn].
+    pub fn with_fn<F, Fut>(mut self, definition: FunctionObject, f: F) -> Self
+    where
+        F: Fn(String) -> Fut + MaybeSend + MaybeSync + 'static,
+        Fut: Future<Output = Result<String, OpenAIError>> + MaybeSend + 'static,
+    {
+        self.register_fn(definition, f);
+        self
+    }
+
+    /// Add a tool handling the [ToolParameters] `P` with `f`, replacing any tool of the same name.
+    ///
+    /// Like functions with the [tool] attribute, `f` returns a `Result` whose value is
+    /// `Serialize` and error `Display`.
+    pub fn register_handler<P, F, Fut, T, E>(&mut self, f: F) -> &mut Self
+    where
+        P: ToolParameters,
+        F: Fn(P) -> Fut + MaybeSend + MaybeSync + 'static,
+        Fut: Future<Output = Result<T, E>> + MaybeSend + 'static,
+        T: Serialize + 'static,
+        E: Display + 'static,
+    {
+        self.register(HandlerTool {
+            f,
+            parameters: PhantomData,
+        })
+    }
+
+    /// Add a tool handling the [ToolParameters] `P`, see [ToolSet::register_handler].
+    pub fn with_handler<P, F, Fut, T, E>(mut self, f: F) -> Self
+    where
+        P: ToolParameters,
+        F: Fn(P) -> Fut + MaybeSend + MaybeSync + 'static,
+        Fut: Future<Output = Result<T, E>> + MaybeSend + 'static,
+        T: Serialize + 'static,
+        E: Display + 'static,
+    {
+        self.register_handler(f);
+        self
+    }
+
+    /// Definitions of the registered tools.
+    pub fn definitions(&self) -> impl Iterator<Item = &FunctionObject> {
+        self.tools.values().map(|(definition, _)| definition)
+    }
+
+    /// The registered tools, for the `tools` of a chat completion request.
+    pub fn chat_tools(&self) -> Vec<ChatCompletionTool> {
+        self.definitions()
+            .map(|definition| ChatCompletionTool {
+                r#type: ChatCompletionToolType::Function,
+                function: definition.clone(),
+            })
+            .collect()
+    }
+
+    /// The registered tools, for the `tools` of a [Responses](crate::Responses) request.
+    pub fn response_tools(&self) -> Vec<crate::types::Tool> {
+        self.definitions()
+            .map(|definition| {
+                crate::types::Tool::Function(FunctionTool {
+                    name: definition.name.clone(),
+                    description: definition.description.clone(),
+                    parameters: definition
+                        .parameters
+                        .clone()
+                        .unwrap_or_else(|| serde_json::json!({"type": "object", "properties": {}})),
+                    strict: definition.strict,
+                })
+            })
+            .collect()
+    }
+
+    /// The registered tools, for the `tools` of an assistant, or of a run overriding them.
+    pub fn assistant_tools(&self) -> Vec<AssistantTools> {
+        self.definitions()
+            .map(|definition| {
+                AssistantTools::Function(AssistantToolsFunction {
+                    function: definition.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Call the tool `name` with `arguments` in JSON format.
+    pub async fn call(&self, name: &str, arguments: &str) -> Result<String, OpenAIError> {
+        let (_, tool) = self.tools.get(name).ok_or_else(|| OpenAIError::Tool {
+            name: name.to_string(),
+            message: "no such tool".into(),
+        })?;
+
+        tool.call(arguments).await
+    }
+
+    /// Run a tool call of the model and return the message or item answering it: a tool
+    /// message for chat completions, a `function_call_output` item for
+    /// [Responses](crate::Responses), or a tool output for assistant runs.
+    ///
+    /// Failures are reported to the model in the answer rather than returned,
+    /// so that it can correct its arguments or proceed without the tool.
+    pub async fn dispatch<C: ToolCall>(&self, tool_call: &C) -> C::Output {
+        let output = self
+            .call(tool_call.name(), tool_call.arguments())
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("{e}");
+                format!("error: {e}")
+            });
+
+        tool_call.output(output)
+    }
+
+    /// Run tool calls concurrently, see [ToolSet::dispatch].
+    /// Answers are in the same order as `tool_calls`.
+    pub async fn dispatch_all<C: ToolCall>(&self, tool_calls: &[C]) -> Vec<C::Output> {
+        join_all(tool_calls.iter().map(|tool_call| self.dispatch(tool_call))).await
+    }
+
+    /// Run a function call of a [Responses](crate::Responses) model and return the
+    /// `function_call_output` item answering it, see [ToolSet::dispatch].
+    pub async fn dispatch_function_call(&self, function_call: &FunctionToolCall) -> Item {
+        self.dispatch(function_call).await
+    }
+
+    /// Run function calls concurrently, see [ToolSet::dispatch_function_call].
+    /// Items are in the same order as `function_calls`.
+    pub async fn dispatch_function_calls(&self, function_calls: &[FunctionToolCall]) -> Vec<Item> {
+        self.dispatch_all(function_calls).await
+    }
+
+    /// Run the tool calls required by an assistant `run`, to continue it with
+    /// [Runs::submit_tool_outputs](crate::Runs::submit_tool_outputs).
+    ///
+    /// `None` when the run does not require tool outputs.
+    pub async fn submit_tool_outputs(
+        &self,
+        run: &RunObject,
+    ) -> Option<SubmitToolOutputsRunRequest> {
+        let required_action = run.required_action.as_ref()?;
+        let tool_outputs = self
+            .dispatch_all(&required_action.submit_tool_outputs.tool_calls)
+            .await;
+
+        Some(SubmitToolOutputsRunRequest {
+            tool_outputs,
+            stream: None,
+        })
+    }
+}
+
+/// Used by the code generated by [tool].
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::runtime::BoxFuture;
+    pub use schemars;
+    pub use serde;
+
+    use super::{DeserializeOwned, Display, OpenAIError, Serialize};
+
+    /// JSON Schema of the parameters `T`.
+    pub fn parameters<T: schemars::JsonSchema>() -> serde_json::Value {
+        let mut schema = serde_json::Value::from(schemars::schema_for!(T));
+        if let Some(schema) = schema.as_object_mut() {
+            schema.remove("$schema");
+            schema.remove("title");
+            // the description of a struct of parameters is the description of the function
+            schema.remove("description");
+        }
+        schema
+    }
+
+    pub fn arguments<T: DeserializeOwned>(name: &str, arguments: &str) -> Result<T, OpenAIError> {
+        // models send an empty string for functions without parameters
+        let arguments = if arguments.trim().is_empty() {
+            "{}"
+        } else {
+            arguments
+        };
+
+        serde_json::from_str(arguments).map_err(|e| OpenAIError::Tool {
+            name: name.to_string(),
+            message: format!("invalid arguments: {e}"),
+        })
+    }
+
+    pub fn output<T: Serialize, E: Display>(
+        name: &str,
+        output: Result<T, E>,
+    ) -> Result<String, OpenAIError> {
+        let error = |message: String| OpenAIError::Tool {
+            name: name.to_string(),
+            message,
+        };
+
+        match serde_json::to_value(output.map_err(|e| error(e.to_string()))?) {
+            Ok(serde_json::Value::String(text)) => Ok(text),
+            Ok(value) => Ok(value.to_string()),
+            Err(e) => Err(error(format!("cannot serialize output: {e}"))),
+        }
+    }
+}
@@ -1,7 +1,191 @@
+use std::{collections::VecDeque, future::Future};
+
+use futures::{Stream, StreamExt};
+
+use crate::{
+    error::OpenAIError,
+    runtime::{MaybeSend, MaybeSync},
+    types::{
+        ChatCompletionAccumulator, ChatCompletionStreamEvent, CreateChatCompletionResponse,
+        CreateChatCompletionStreamResponse, Response, ResponseAccumulator, ResponseStreamEvent,
+        TokenUsage,
+    },
+};
+
 pub trait AsyncTryFrom<T>: Sized {
     /// The type returned in the event of a conversion error.
     type Error;
 
     /// Performs the conversion.
-    fn try_from(value: T) -> impl std::future::Future<Output = Result<Self, Self::Error>> + Send;
+    fn try_from(
+        value: T,
+    ) -> impl std::future::Future<Output = Result<Self, Self::Error>> + MaybeSend;
+}
+
+/// Observer of token usage, invoked with the model and [TokenUsage] of every completed
+/// generation call made by a [crate::Client]: JSON responses of POST requests, and the final
+/// usage of SSE streams (e.g. chat streams with `stream_options.include_usage`).
+///
+/// Register it with [crate::Client::with_usage_observer] to meter consumption centrally.
+/// Any `Fn(&str, &TokenUsage) + Send + Sync` closure is an observer.
+pub trait UsageObserver: MaybeSend + MaybeSync {
+    fn observe(&self, model: &str, usage: &TokenUsage);
+}
+
+impl<F> UsageObserver for F
+where
+    F: Fn(&str, &TokenUsage) + MaybeSend + MaybeSync,
+{
+    fn observe(&self, model: &str, usage: &TokenUsage) {
+        self(model, usage)
+    }
+}
+
+impl std::fmt::Debug for dyn UsageObserver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("UsageObserver")
+    }
+}
+
+/// Extension of streams of [ResponseStreamEvent], like [crate::types::ResponseStream].
+pub trait ResponseStreamExt:
+    Stream<Item = Result<ResponseStreamEvent, OpenAIError>> + MaybeSend + Sized
+{
+    /// Consume the stream and return the response assembled from its events, see
+    /// [ResponseAccumulator]: completed, incomplete or failed.
+    ///
+    /// Fails on the `error` event, and when the stream ends before the final response.
+    fn collect_final(self) -> impl Future<Output = Result<Response, OpenAIError>> + MaybeSend {
+        async move {
+            let mut stream = std::pin::pin!(self);
+            let mut accumulator = ResponseAccumulator::new();
+
+            while let Some(event) = stream.next().await {
+                match event? {
+                    ResponseStreamEvent::Error(event) => {
+                        return Err(OpenAIError::StreamError(event.message))
+                    }
+                    event => accumulator.apply(&event),
+                }
+            }
+
+            match accumulator.into_response() {
+                Some(response) if !response.status.is_pending() => Ok(response),
+                _ => Err(OpenAIError::StreamError(
+                    "stream ended before the response was completed".into(),
+                )),
+            }
+        }
+    }
+}
+
+impl<S> ResponseStreamExt for S where
+    S: Stream<Item = Result<ResponseStreamEvent, OpenAIError>> + MaybeSend + Sized
+{
+}
+
+/// Extension of streams of [CreateChatCompletionStreamResponse], like
+/// [crate::types::ChatCompletionResponseStream].
+pub trait ChatCompletionStreamExt:
+    Stream<Item = Result<CreateChatCompletionStreamResponse, OpenAIError>> + MaybeSend + Sized
+{
+    /// Stream of the high level events of the chunks, see [ChatCompletionAccumulator]: text
+    /// deltas, complete tool calls and finish reasons, then the assembled message of each
+    /// choice when the stream ends.
+    ///
+    /// ```no_run
+    /// # use async_openai::types::ChatCompletionResponseStream;
+    /// use async_openai::{traits::ChatCompletionStreamExt, types::ChatCompletionStreamEvent};
+    /// use futures::StreamExt;
+    ///
+    /// # async fn run(stream: ChatCompletionResponseStream) -> Result<(), async_openai::error::OpenAIError> {
+    /// let mut events = std::pin::pin!(stream.accumulate());
+    /// while let Some(event) = events.next().await {
+    ///     match event? {
+    ///         ChatCompletionStreamEvent::TextDelta { delta, .. } => print!("{delta}"),
+    ///         ChatCompletionStreamEvent::ToolCallCompleted { tool_call, .. } => {
+    ///             println!("{}({})", tool_call.function.name, tool_call.function.arguments)
+    ///         }
+    ///         _ => {}
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Ends after the first error.
+    fn accumulate(
+        self,
+    ) -> impl Stream<Item = Result<ChatCompletionStreamEvent, OpenAIError>> + MaybeSend {
+        let state = (
+            Box::pin(self),
+            ChatCompletionAccumulator::new(),
+            VecDeque::new(),
+            false,
+        );
+
+        futures::stream::unfold(
+            state,
+            |(mut stream, mut accumulator, mut events, mut ended)| async move {
+                loop {
+                    if let Some(event) = events.pop_front() {
+                        return Some((Ok(event), (stream, accumulator, events, ended)));
+                    }
+                    if ended {
+                        return None;
+                    }
+
+                    match stream.next().await {
+                        Some(Ok(chunk)) => events.extend(accumulator.apply(&chunk)),
+                        Some(Err(e)) => {
+                            return Some((Err(e), (stream, accumulator, events, true)));
+                        }
+                        None => {
+                            ended = true;
+                            events.extend(accumulator.finish());
+                        }
+                    }
+                }
+            },
+        )
+    }
+
+    /// Consume the stream and return the response assembled from its chunks: the message of
+    /// each choice and, when `stream_options.include_usage` is set, the usage of the last
+    /// chunk, with its cached and reasoning tokens.
+    ///
+    /// ```no_run
+    /// # use async_openai::types::ChatCompletionResponseStream;
+    /// use async_openai::traits::ChatCompletionStreamExt;
+    ///
+    /// # async fn run(stream: ChatCompletionResponseStream) -> Result<(), async_openai::error::OpenAIError> {
+    /// let response = stream.collect_final().await?;
+    /// println!("{}", response.first_text().unwrap_or_default());
+    /// if let Some(usage) = response.usage {
+    ///     println!("{} tokens", usage.total_tokens);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn collect_final(
+        self,
+    ) -> impl Future<Output = Result<CreateChatCompletionResponse, OpenAIError>> + MaybeSend {
+        async move {
+            let mut stream = std::pin::pin!(self);
+            let mut accumulator = ChatCompletionAccumulator::new();
+
+            while let Some(chunk) = stream.next().await {
+                accumulator.apply(&chunk?);
+            }
+
+            accumulator.response().ok_or_else(|| {
+                OpenAIError::StreamError("stream ended before the first chunk".into())
+            })
+        }
+    }
+}
+
+impl<S> ChatCompletionStreamExt for S where
+    S: Stream<Item = Result<CreateChatCompletionStreamResponse, OpenAIError>> + MaybeSend + Sized
+{
 }
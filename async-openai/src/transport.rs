@@ -0,0 +1,109 @@
+//! The HTTP transport of a [Client](crate::Client), [reqwest] by default.
+//!
+//! Requests are built with [reqwest::Request], whatever the transport: implement
+//! [HttpTransport] to send them with another HTTP stack, e.g. hyper, isahc or a custom
+//! corporate one. The body of a request is either bytes, see [reqwest::Body::as_bytes], or
+//! for multipart uploads a stream; [reqwest::Body] implements `http_body::Body`, and a
+//! [reqwest::Request] converts to an `http::Request<reqwest::Body>`, e.g. for hyper.
+//!
+//! ```
+//! use async_openai::{
+//!     error::OpenAIError,
+//!     runtime::BoxFuture,
+//!     transport::{HttpResponse, HttpTransport},
+//!     Client,
+//! };
+//!
+//! /// Sends requests with reqwest, logging them.
+//! struct Logged(reqwest::Client);
+//!
+//! impl HttpTransport for Logged {
+//!     fn execute(
+//!         &self,
+//!         request: reqwest::Request,
+//!     ) -> BoxFuture<'_, Result<HttpResponse, OpenAIError>> {
+//!         println!("{} {}", request.method(), request.url());
+//!         HttpTransport::execute(&self.0, request)
+//!     }
+//! }
+//!
+//! let client = Client::new().with_transport(Logged(reqwest::Client::new()));
+//! ```
+use bytes::Bytes;
+use futures::TryStreamExt;
+use reqwest::{header::HeaderMap, StatusCode};
+
+use crate::{
+    error::OpenAIError,
+    runtime::{BoxFuture, BoxStream, MaybeSend, MaybeSync},
+};
+
+/// A response received by an [HttpTransport], its body read as it is polled, e.g. for SSE streams.
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: BoxStream<'static, Result<Bytes, OpenAIError>>,
+}
+
+impl HttpResponse {
+    /// Read the whole body.
+    pub async fn bytes(self) -> Result<Bytes, OpenAIError> {
+        let chunks: Vec<Bytes> = self.body.try_collect().await?;
+        Ok(match <[Bytes; 1]>::try_from(chunks) {
+            Ok([chunk]) => chunk,
+            Err(chunks) => chunks.concat().into(),
+        })
+    }
+}
+
+impl From<reqwest::Response> for HttpResponse {
+    fn from(response: reqwest::Response) -> Self {
+        Self {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: Box::pin(response.bytes_stream().map_err(OpenAIError::Reqwest)),
+        }
+    }
+}
+
+impl std::fmt::Debug for HttpResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpResponse")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Sends the HTTP requests of a [Client](crate::Client), including SSE streams and multipart
+/// uploads, set with [Client::with_transport](crate::Client::with_transport).
+///
+/// Responses with a rate limit or server error status are retried by the client, as are
+/// [OpenAIError::Reqwest] errors of the connection, e.g. reset; other errors fail the request.
+///
+/// Transports are `Send` and `Sync`, except on wasm, see [MaybeSend].
+pub trait HttpTransport: MaybeSend + MaybeSync {
+    /// Send `request` and return the response, its body not read yet.
+    fn execute(
+        &self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'_, Result<HttpResponse, OpenAIError>>;
+}
+
+impl HttpTransport for reqwest::Client {
+    fn execute(
+        &self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'_, Result<HttpResponse, OpenAIError>> {
+        Box::pin(async move {
+            let response = reqwest::Client::execute(self, request).await?;
+            Ok(response.into())
+        })
+    }
+}
+
+impl std::fmt::Debug for dyn HttpTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("HttpTransport")
+    }
+}
@@ -5,21 +5,24 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::OpenAIError;
 
-use super::{FunctionName, FunctionObject, ResponseFormat};
+use super::{validate, FunctionName, FunctionObject, ResponseFormat};
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AssistantToolCodeInterpreterResources {
     ///A list of [file](https://platform.openai.com/docs/api-reference/files) IDs made available to the `code_interpreter`` tool. There can be a maximum of 20 files associated with the tool.
     pub file_ids: Vec<String>, // maxItems: 20
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AssistantToolFileSearchResources {
     /// The ID of the [vector store](https://platform.openai.com/docs/api-reference/vector-stores/object) attached to this assistant. There can be a maximum of 1 vector store attached to the assistant.
     pub vector_store_ids: Vec<String>,
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AssistantToolResources {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub code_interpreter: Option<AssistantToolCodeInterpreterResources>,
@@ -28,6 +31,7 @@ pub struct AssistantToolResources {
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateAssistantToolResources {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub code_interpreter: Option<AssistantToolCodeInterpreterResources>,
@@ -36,6 +40,7 @@ pub struct CreateAssistantToolResources {
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateAssistantToolFileSearchResources {
     ///  The [vector store](https://platform.openai.com/docs/api-reference/vector-stores/object) attached to this assistant. There can be a maximum of 1 vector store attached to the assistant.
     pub vector_store_ids: Option<Vec<String>>,
@@ -44,6 +49,7 @@ pub struct CreateAssistantToolFileSearchResources {
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AssistantVectorStore {
     /// A list of [file](https://platform.openai.com/docs/api-reference/files) IDs to add to the vector store. There can be a maximum of 10000 files in a vector store.
     pub file_ids: Vec<String>,
@@ -56,6 +62,7 @@ pub struct AssistantVectorStore {
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum AssistantVectorStoreChunkingStrategy {
     /// The default strategy. This strategy currently uses a `max_chunk_size_tokens` of `800` and `chunk_overlap_tokens` of `400`.
@@ -68,6 +75,7 @@ pub enum AssistantVectorStoreChunkingStrategy {
 
 /// Static Chunking Strategy
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct StaticChunkingStrategy {
     /// The maximum number of tokens in each chunk. The default value is `800`. The minimum value is `100` and the maximum value is `4096`.
     pub max_chunk_size_tokens: u16,
@@ -118,6 +126,7 @@ pub struct AssistantObject {
 ///
 /// **Important:** when using JSON mode, you **must** also instruct the model to produce JSON yourself via a system or user message. Without this, the model may generate an unending stream of whitespace until the generation reaches the token limit, resulting in a long-running and seemingly "stuck" request. Also note that the message content may be partially cut off if `finish_reason="length"`, which indicates the generation exceeded `max_tokens` or the conversation exceeded the max context length.
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum AssistantsApiResponseFormatOption {
     #[default]
     #[serde(rename = "auto")]
@@ -128,6 +137,7 @@ pub enum AssistantsApiResponseFormatOption {
 
 /// Retrieval tool
 #[derive(Clone, Serialize, Debug, Default, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AssistantToolsFileSearch {
     /// Overrides for the file search tool.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -135,6 +145,7 @@ pub struct AssistantToolsFileSearch {
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AssistantToolsFileSearchOverrides {
     ///  The maximum number of results the file search tool should output. The default is 20 for gpt-4* models and 5 for gpt-3.5-turbo. This number should be between 1 and 50 inclusive.
     ///
@@ -144,6 +155,7 @@ pub struct AssistantToolsFileSearchOverrides {
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum FileSearchRanker {
     #[serde(rename = "auto")]
     Auto,
@@ -155,6 +167,7 @@ pub enum FileSearchRanker {
 ///
 /// See the [file search tool documentation](https://platform.openai.com/docs/assistants/tools/file-search#customizing-file-search-settings) for more information.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FileSearchRankingOptions {
     /// The ranker to use for the file search. If not specified will use the `auto` ranker.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -166,11 +179,13 @@ pub struct FileSearchRankingOptions {
 
 /// Function tool
 #[derive(Clone, Serialize, Debug, Default, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AssistantToolsFunction {
     pub function: FunctionObject,
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 #[serde(rename_all = "snake_case")]
 pub enum AssistantTools {
@@ -180,11 +195,12 @@ pub enum AssistantTools {
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "CreateAssistantRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateAssistantRequest {
     /// ID of the model to use. You can use the [List models](https://platform.openai.com/docs/api-reference/models/list) API to see all of your available models, or see our [Model overview](https://platform.openai.com/docs/models/overview) for descriptions of them.
     pub model: String,
@@ -227,12 +243,21 @@ pub struct CreateAssistantRequest {
     pub response_format: Option<AssistantsApiResponseFormatOption>,
 }
 
+impl CreateAssistantRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        validate::sampling(self.temperature.flatten(), self.top_p.flatten())?;
+
+        Ok(())
+    }
+}
+
 #[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "ModifyAssistantRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct ModifyAssistantRequest {
     /// ID of the model to use. You can use the [List models](https://platform.openai.com/docs/api-reference/models/list) API to see all of your available models, or see our [Model overview](https://platform.openai.com/docs/models/overview) for descriptions of them.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -275,6 +300,14 @@ pub struct ModifyAssistantRequest {
     pub response_format: Option<AssistantsApiResponseFormatOption>,
 }
 
+impl ModifyAssistantRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        validate::sampling(self.temperature.flatten(), self.top_p.flatten())?;
+
+        Ok(())
+    }
+}
+
 #[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
 pub struct DeleteAssistantResponse {
     pub id: String,
@@ -297,6 +330,7 @@ pub struct ListAssistantsResponse {
 /// `required` means the model must call one or more tools before responding to the user.
 /// Specifying a particular tool like `{"type": "file_search"}` or `{"type": "function", "function": {"name": "my_function"}}` forces the model to call that tool.
 #[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum AssistantsApiToolChoiceOption {
     #[default]
@@ -309,6 +343,7 @@ pub enum AssistantsApiToolChoiceOption {
 
 /// Specifies a tool the model should use. Use to force the model to call a specific tool.
 #[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AssistantsNamedToolChoice {
     /// The type of the tool. If type is `function`, the function name must be set
     pub r#type: AssistantToolType,
@@ -317,6 +352,7 @@ pub struct AssistantsNamedToolChoice {
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum AssistantToolType {
     #[default]
@@ -3,7 +3,7 @@ use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
 use super::InputSource;
-use crate::error::OpenAIError;
+use crate::{error::OpenAIError, runtime::BoxStream};
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct AudioInput {
@@ -22,6 +22,7 @@ pub enum AudioResponseFormat {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum SpeechResponseFormat {
     #[default]
@@ -34,6 +35,7 @@ pub enum SpeechResponseFormat {
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum Voice {
@@ -50,6 +52,7 @@ pub enum Voice {
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum SpeechModel {
     #[default]
     #[serde(rename = "tts-1")]
@@ -95,6 +98,10 @@ pub struct CreateTranscriptionRequest {
 
     /// The timestamp granularities to populate for this transcription. `response_format` must be set `verbose_json` to use timestamp granularities. Either or both of these options are supported: `word`, or `segment`. Note: There is no additional latency for segment timestamps, but generating word timestamps incurs additional latency.
     pub timestamp_granularities: Option<Vec<TimestampGranularity>>,
+
+    /// If set to true, the transcript is streamed as it is generated, see [crate::Audio::transcribe_stream].
+    /// Not supported by `whisper-1`.
+    pub stream: Option<bool>,
 }
 
 /// Represents a transcription response returned by model, based on the provided
@@ -175,7 +182,56 @@ pub struct TranscriptionSegment {
     pub no_speech_prob: f32,
 }
 
+/// Log probability of a token of a streamed transcript.
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct TranscriptionLogprob {
+    /// The token in the transcription.
+    pub token: String,
+
+    /// The log probability of the token.
+    pub logprob: f32,
+
+    /// The bytes of the token.
+    pub bytes: Option<Vec<u8>>,
+}
+
+/// Emitted when there is an additional text delta.
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct TranscriptTextDeltaEvent {
+    /// The text delta that was additionally transcribed.
+    pub delta: String,
+
+    /// The log probabilities of the delta, when requested with `include[]=logprobs`.
+    pub logprobs: Option<Vec<TranscriptionLogprob>>,
+}
+
+/// Emitted when the transcription is complete.
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct TranscriptTextDoneEvent {
+    /// The text that was transcribed.
+    pub text: String,
+
+    /// The log probabilities of the individual tokens in the transcription,
+    /// when requested with `include[]=logprobs`.
+    pub logprobs: Option<Vec<TranscriptionLogprob>>,
+}
+
+/// Event of a streamed transcription, see [crate::Audio::transcribe_stream].
+#[derive(Debug, Deserialize, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum CreateTranscriptionResponseStreamEvent {
+    #[serde(rename = "transcript.text.delta")]
+    TranscriptTextDelta(TranscriptTextDeltaEvent),
+    #[serde(rename = "transcript.text.done")]
+    TranscriptTextDone(TranscriptTextDoneEvent),
+}
+
+/// Parsed server side events stream of a transcription.
+pub type TranscriptionResponseStream =
+    BoxStream<'static, Result<CreateTranscriptionResponseStreamEvent, OpenAIError>>;
+
 #[derive(Clone, Default, Debug, Builder, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "CreateSpeechRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use crate::error::OpenAIError;
 
 #[derive(Debug, Serialize, Default, Clone, Builder, PartialEq, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "BatchRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -30,6 +31,7 @@ pub struct BatchRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum BatchEndpoint {
     #[default]
     #[serde(rename = "/v1/chat/completions")]
@@ -41,6 +43,7 @@ pub enum BatchEndpoint {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Default, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum BatchCompletionWindow {
     #[default]
     #[serde(rename = "24h")]
@@ -48,6 +51,10 @@ pub enum BatchCompletionWindow {
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct Batch {
     pub id: String,
     /// The object type, which is always `batch`.
@@ -0,0 +1,103 @@
+//! Consuming use of the request builders, whose setters take and return `&mut Self`.
+use super::*;
+
+/// Owned (consuming) use of a request builder, so that a builder can be configured and
+/// returned in a single expression, e.g. from a closure of an iterator chain:
+///
+/// ```
+/// use async_openai::types::{CreateEmbeddingRequestArgs, OwnedBuilder};
+///
+/// let builders: Vec<CreateEmbeddingRequestArgs> = ["one", "two"]
+///     .into_iter()
+///     .map(|input| {
+///         CreateEmbeddingRequestArgs::default()
+///             .model("text-embedding-3-small")
+///             .input(input)
+///             .owned()
+///     })
+///     .collect();
+///
+/// let request = CreateEmbeddingRequestArgs::default()
+///     .with(|args| args.model("text-embedding-3-small").input("three"))
+///     .build()
+///     .unwrap();
+/// ```
+pub trait OwnedBuilder: Default + Sized {
+    /// Move the builder out of the `&mut Self` returned by its setters, leaving a default builder behind.
+    fn owned(&mut self) -> Self {
+        std::mem::take(self)
+    }
+
+    /// Configure the builder with its `&mut` setters and return it by value.
+    fn with<F>(mut self, configure: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        configure(&mut self);
+        self
+    }
+}
+
+macro_rules! owned_builder {
+    ($($builder:ty),* $(,)?) => {
+        $(impl OwnedBuilder for $builder {})*
+    };
+}
+
+owned_builder!(
+    CreateAssistantRequestArgs,
+    ModifyAssistantRequestArgs,
+    CreateTranscriptionRequestArgs,
+    CreateSpeechRequestArgs,
+    CreateTranslationRequestArgs,
+    BatchRequestArgs,
+    ChatCompletionRequestDeveloperMessageArgs,
+    ChatCompletionRequestSystemMessageArgs,
+    ChatCompletionRequestMessageContentPartTextArgs,
+    ChatCompletionRequestMessageContentPartRefusalBuilder,
+    ImageUrlArgs,
+    InputFileArgs,
+    InputImageArgs,
+    InputMessageArgs,
+    FunctionToolArgs,
+    McpToolArgs,
+    ImageGenerationToolArgs,
+    ResponsePromptArgs,
+    CreateResponseArgs,
+    CreateConversationRequestArgs,
+    ChatCompletionRequestMessageContentPartImageArgs,
+    ChatCompletionRequestMessageContentPartAudioArgs,
+    ChatCompletionRequestUserMessageArgs,
+    ChatCompletionRequestAssistantMessageArgs,
+    ChatCompletionRequestToolMessageArgs,
+    ChatCompletionRequestFunctionMessageArgs,
+    ChatCompletionFunctionsArgs,
+    FunctionObjectArgs,
+    ChatCompletionToolArgs,
+    CreateChatCompletionRequestArgs,
+    CreateCompletionRequestArgs,
+    CreateEmbeddingRequestArgs,
+    CreateFileRequestArgs,
+    CreateFineTuningJobRequestArgs,
+    CreateImageRequestArgs,
+    CreateImageEditRequestArgs,
+    CreateImageVariationRequestArgs,
+    InviteRequestArgs,
+    CreateMessageRequestArgs,
+    CreateModerationRequestArgs,
+    ProjectUserCreateRequestArgs,
+    ProjectUserUpdateRequestArgs,
+    ProjectCreateRequestArgs,
+    ProjectUpdateRequestArgs,
+    CreateRunRequestArgs,
+    ToolsOutputsArgs,
+    CreateThreadRequestArgs,
+    CreateThreadAndRunRequestArgs,
+    CreateUploadRequestArgs,
+    UserRoleUpdateRequestArgs,
+    CreateVectorStoreRequestArgs,
+    UpdateVectorStoreRequestArgs,
+    CreateVectorStoreFileRequestArgs,
+    CreateVectorStoreFileBatchRequestArgs,
+    VectorStoreSearchRequestArgs
+);
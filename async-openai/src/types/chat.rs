@@ -1,12 +1,14 @@
-use std::{collections::HashMap, pin::Pin};
+use std::collections::HashMap;
 
 use derive_builder::Builder;
-use futures::Stream;
 use serde::{Deserialize, Serialize};
 
-use crate::error::OpenAIError;
+use crate::{error::OpenAIError, runtime::BoxStream};
+
+use super::{validate, Modality};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Prompt {
     String(String),
@@ -17,6 +19,7 @@ pub enum Prompt {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Stop {
     String(String),           // nullable: true
@@ -40,14 +43,33 @@ pub enum CompletionFinishReason {
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct Choice {
     pub text: String,
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub index: u32,
     pub logprobs: Option<Logprobs>,
+    #[cfg_attr(
+        feature = "lenient",
+        serde(
+            default,
+            deserialize_with = "crate::types::lenient::deserialize_or_none"
+        )
+    )]
     pub finish_reason: Option<CompletionFinishReason>,
+
+    /// Fields which are not part of OpenAI's schema, e.g. added by OpenAI compatible servers.
+    /// Empty unless the `extra-fields` feature is enabled.
+    #[cfg_attr(feature = "extra-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "extra-fields"), serde(skip))]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ChatCompletionFunctionCall {
     /// The model does not call a function, and responds to the end-user.
     #[serde(rename = "none")]
@@ -77,6 +99,7 @@ pub enum Role {
 
 /// The name and arguments of a function that should be called, as generated by the model.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionCall {
     /// The name of the function to call.
     pub name: String,
@@ -86,17 +109,30 @@ pub struct FunctionCall {
 
 /// Usage statistics for the completion request.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct CompletionUsage {
     /// Number of tokens in the prompt.
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub prompt_tokens: u32,
     /// Number of tokens in the generated completion.
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub completion_tokens: u32,
     /// Total number of tokens used in the request (prompt + completion).
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub total_tokens: u32,
     /// Breakdown of tokens used in the prompt.
     pub prompt_tokens_details: Option<PromptTokensDetails>,
     /// Breakdown of tokens used in a completion.
     pub completion_tokens_details: Option<CompletionTokensDetails>,
+
+    /// Fields which are not part of OpenAI's schema, e.g. added by OpenAI compatible servers.
+    /// Empty unless the `extra-fields` feature is enabled.
+    #[cfg_attr(feature = "extra-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "extra-fields"), serde(skip))]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Breakdown of tokens used in a completion.
@@ -111,6 +147,8 @@ pub struct PromptTokensDetails {
 /// Breakdown of tokens used in a completion.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct CompletionTokensDetails {
+    /// When using Predicted Outputs, the number of tokens in the
+    /// prediction that appeared in the completion.
     pub accepted_prediction_tokens: Option<u32>,
     /// Audio input tokens generated by the model.
     pub audio_tokens: Option<u32>,
@@ -125,6 +163,7 @@ pub struct CompletionTokensDetails {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "ChatCompletionRequestDeveloperMessageArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -140,6 +179,7 @@ pub struct ChatCompletionRequestDeveloperMessage {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum ChatCompletionRequestDeveloperMessageContent {
     Text(String),
@@ -147,6 +187,7 @@ pub enum ChatCompletionRequestDeveloperMessageContent {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "ChatCompletionRequestSystemMessageArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -161,6 +202,7 @@ pub struct ChatCompletionRequestSystemMessage {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "ChatCompletionRequestMessageContentPartTextArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -171,12 +213,14 @@ pub struct ChatCompletionRequestMessageContentPartText {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ChatCompletionRequestMessageContentPartRefusal {
     /// The refusal message generated by the model.
     pub refusal: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ImageDetail {
     #[default]
@@ -186,6 +230,7 @@ pub enum ImageDetail {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "ImageUrlArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -199,6 +244,7 @@ pub struct ImageUrl {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "ChatCompletionRequestMessageContentPartImageArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -209,6 +255,7 @@ pub struct ChatCompletionRequestMessageContentPartImage {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum InputAudioFormat {
     Wav,
@@ -217,6 +264,7 @@ pub enum InputAudioFormat {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct InputAudio {
     /// Base64 encoded audio data.
     pub data: String,
@@ -226,6 +274,7 @@ pub struct InputAudio {
 
 /// Learn about [audio inputs](https://platform.openai.com/docs/guides/audio).
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "ChatCompletionRequestMessageContentPartAudioArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -236,6 +285,7 @@ pub struct ChatCompletionRequestMessageContentPartAudio {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 #[serde(rename_all = "snake_case")]
 pub enum ChatCompletionRequestUserMessageContentPart {
@@ -245,6 +295,7 @@ pub enum ChatCompletionRequestUserMessageContentPart {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 #[serde(rename_all = "snake_case")]
 pub enum ChatCompletionRequestSystemMessageContentPart {
@@ -252,6 +303,7 @@ pub enum ChatCompletionRequestSystemMessageContentPart {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 #[serde(rename_all = "snake_case")]
 pub enum ChatCompletionRequestAssistantMessageContentPart {
@@ -260,6 +312,7 @@ pub enum ChatCompletionRequestAssistantMessageContentPart {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 #[serde(rename_all = "snake_case")]
 pub enum ChatCompletionRequestToolMessageContentPart {
@@ -267,6 +320,7 @@ pub enum ChatCompletionRequestToolMessageContentPart {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum ChatCompletionRequestSystemMessageContent {
     /// The text contents of the system message.
@@ -276,6 +330,7 @@ pub enum ChatCompletionRequestSystemMessageContent {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum ChatCompletionRequestUserMessageContent {
     /// The text contents of the message.
@@ -285,6 +340,7 @@ pub enum ChatCompletionRequestUserMessageContent {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum ChatCompletionRequestAssistantMessageContent {
     /// The text contents of the message.
@@ -294,6 +350,7 @@ pub enum ChatCompletionRequestAssistantMessageContent {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum ChatCompletionRequestToolMessageContent {
     /// The text contents of the tool message.
@@ -303,6 +360,7 @@ pub enum ChatCompletionRequestToolMessageContent {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "ChatCompletionRequestUserMessageArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -317,12 +375,14 @@ pub struct ChatCompletionRequestUserMessage {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ChatCompletionRequestAssistantMessageAudio {
     /// Unique identifier for a previous audio response from the model.
     pub id: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "ChatCompletionRequestAssistantMessageArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -352,6 +412,7 @@ pub struct ChatCompletionRequestAssistantMessage {
 
 /// Tool message
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "ChatCompletionRequestToolMessageArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -364,6 +425,7 @@ pub struct ChatCompletionRequestToolMessage {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "ChatCompletionRequestFunctionMessageArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -377,6 +439,7 @@ pub struct ChatCompletionRequestFunctionMessage {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "role")]
 #[serde(rename_all = "lowercase")]
 pub enum ChatCompletionRequestMessage {
@@ -389,10 +452,18 @@ pub enum ChatCompletionRequestMessage {
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ChatCompletionMessageToolCall {
     /// The ID of the tool call.
     pub id: String,
     /// The type of the tool. Currently, only `function` is supported.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(
+            default,
+            deserialize_with = "crate::types::lenient::deserialize_or_default"
+        )
+    )]
     pub r#type: ChatCompletionToolType,
     /// The function that the model called.
     pub function: FunctionCall,
@@ -412,6 +483,10 @@ pub struct ChatCompletionResponseMessageAudio {
 
 /// A chat completion message generated by the model.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct ChatCompletionResponseMessage {
     /// The contents of the message.
     pub content: Option<String>,
@@ -430,9 +505,16 @@ pub struct ChatCompletionResponseMessage {
 
     /// If the audio output modality is requested, this object contains data about the audio response from the model. [Learn more](https://platform.openai.com/docs/guides/audio).
     pub audio: Option<ChatCompletionResponseMessageAudio>,
+
+    /// Fields which are not part of OpenAI's schema, e.g. added by OpenAI compatible servers.
+    /// Empty unless the `extra-fields` feature is enabled.
+    #[cfg_attr(feature = "extra-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "extra-fields"), serde(skip))]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "ChatCompletionFunctionsArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -452,6 +534,7 @@ pub struct ChatCompletionFunctions {
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "FunctionObjectArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -475,6 +558,7 @@ pub struct FunctionObject {
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ResponseFormat {
     /// The type of response format being defined: `text`
@@ -488,6 +572,7 @@ pub enum ResponseFormat {
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ResponseFormatJsonSchema {
     /// A description of what the response format is for, used by the model to determine how to respond in the format.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -503,6 +588,7 @@ pub struct ResponseFormatJsonSchema {
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ChatCompletionToolType {
     #[default]
@@ -510,6 +596,7 @@ pub enum ChatCompletionToolType {
 }
 
 #[derive(Clone, Serialize, Default, Debug, Builder, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "ChatCompletionToolArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -522,6 +609,7 @@ pub struct ChatCompletionTool {
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionName {
     /// The name of the function to call.
     pub name: String,
@@ -529,6 +617,7 @@ pub struct FunctionName {
 
 /// Specifies a tool the model should use. Use to force the model to call a specific function.
 #[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ChatCompletionNamedToolChoice {
     /// The type of the tool. Currently, only `function` is supported.
     pub r#type: ChatCompletionToolType,
@@ -544,6 +633,7 @@ pub struct ChatCompletionNamedToolChoice {
 ///
 /// `none` is the default when no tools are present. `auto` is the default if tools are present.present.
 #[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ChatCompletionToolChoiceOption {
     #[default]
@@ -555,10 +645,14 @@ pub enum ChatCompletionToolChoiceOption {
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ServiceTier {
     Auto,
     Default,
+    /// Lower prices with slower responses and occasional unavailability, see [flex processing](https://platform.openai.com/docs/guides/flex-processing).
+    Flex,
+    Priority,
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
@@ -566,9 +660,12 @@ pub enum ServiceTier {
 pub enum ServiceTierResponse {
     Scale,
     Default,
+    Flex,
+    Priority,
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ReasoningEffort {
     Low,
@@ -583,6 +680,7 @@ pub enum ReasoningEffort {
 /// The `gpt-4o-audio-preview` model can also be used to [generate
 /// audio](https://platform.openai.com/docs/guides/audio). To request that this model generate both text and audio responses, you can use: `["text", "audio"]`
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ChatCompletionModalities {
     Text,
@@ -591,6 +689,7 @@ pub enum ChatCompletionModalities {
 
 /// The content that should be matched when generating a model response. If generated tokens would match this content, the entire model response can be returned much more quickly.
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum PredictionContentContent {
     /// The content used for a Predicted Output. This is often the text of a file you are regenerating with minor changes.
@@ -601,6 +700,7 @@ pub enum PredictionContentContent {
 
 /// Static predicted output content, such as the content of a text file that is being regenerated.
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "lowercase", content = "content")]
 pub enum PredictionContent {
     /// The type of the predicted content you want to provide. This type is
@@ -609,6 +709,7 @@ pub enum PredictionContent {
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ChatCompletionAudioVoice {
     Alloy,
@@ -622,6 +723,7 @@ pub enum ChatCompletionAudioVoice {
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ChatCompletionAudioFormat {
     Wav,
@@ -632,6 +734,7 @@ pub enum ChatCompletionAudioFormat {
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ChatCompletionAudio {
     /// The voice the model uses to respond. Supported voices are `ash`, `ballad`, `coral`, `sage`, and `verse` (also supported but not recommended are `alloy`, `echo`, and `shimmer`; these voices are less expressive).
     pub voice: ChatCompletionAudioVoice,
@@ -640,11 +743,12 @@ pub struct ChatCompletionAudio {
 }
 
 #[derive(Clone, Serialize, Default, Debug, Builder, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "CreateChatCompletionRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateChatCompletionRequest {
     /// A list of messages comprising the conversation so far. Depending on the [model](https://platform.openai.com/docs/models) you use, different message types (modalities) are supported, like [text](https://platform.openai.com/docs/guides/text-generation), [images](https://platform.openai.com/docs/guides/vision), and [audio](https://platform.openai.com/docs/guides/audio).
     pub messages: Vec<ChatCompletionRequestMessage>, // min: 1
@@ -818,8 +922,57 @@ pub struct CreateChatCompletionRequest {
     pub functions: Option<Vec<ChatCompletionFunctions>>,
 }
 
+impl CreateChatCompletionRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        validate::sampling(self.temperature.flatten(), self.top_p.flatten())?;
+        validate::range(
+            "presence_penalty",
+            self.presence_penalty.flatten(),
+            -2.0,
+            2.0,
+        )?;
+        validate::range(
+            "frequency_penalty",
+            self.frequency_penalty.flatten(),
+            -2.0,
+            2.0,
+        )?;
+        validate::range("n", self.n.flatten(), 1, 128)?;
+        validate::range("top_logprobs", self.top_logprobs.flatten(), 0, 20)?;
+        validate::stream_options(
+            self.stream.flatten(),
+            self.stream_options.flatten().is_some(),
+        )?;
+
+        let modalities: Vec<Modality> = self
+            .modalities
+            .iter()
+            .flatten()
+            .flatten()
+            .map(|modality| match modality {
+                ChatCompletionModalities::Text => Modality::Text,
+                ChatCompletionModalities::Audio => Modality::Audio,
+            })
+            .collect();
+        validate::model(
+            self.model.as_deref(),
+            self.max_completion_tokens.flatten(),
+            &modalities,
+        )?;
+
+        if self.top_logprobs.flatten().is_some() && self.logprobs.flatten() != Some(true) {
+            return Err(OpenAIError::InvalidArgument(
+                "top_logprobs requires logprobs to be true".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 /// Options for streaming response. Only set this when you set `stream: true`.
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ChatCompletionStreamOptions {
     /// If set, an additional chunk will be streamed before the `data: [DONE]` message. The `usage` field on this chunk shows the token usage statistics for the entire request, and the `choices` field will always be an empty array. All other chunks will also include a `usage` field, but with a null value.
     pub include_usage: bool,
@@ -865,31 +1018,62 @@ pub struct ChatChoiceLogprobs {
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct ChatChoice {
     /// The index of the choice in the list of choices.
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub index: u32,
     pub message: ChatCompletionResponseMessage,
     /// The reason the model stopped generating tokens. This will be `stop` if the model hit a natural stop point or a provided stop sequence,
     /// `length` if the maximum number of tokens specified in the request was reached,
     /// `content_filter` if content was omitted due to a flag from our content filters,
     /// `tool_calls` if the model called a tool, or `function_call` (deprecated) if the model called a function.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(
+            default,
+            deserialize_with = "crate::types::lenient::deserialize_or_none"
+        )
+    )]
     pub finish_reason: Option<FinishReason>,
     /// Log probability information for the choice.
     pub logprobs: Option<ChatChoiceLogprobs>,
+
+    /// Fields which are not part of OpenAI's schema, e.g. added by OpenAI compatible servers.
+    /// Empty unless the `extra-fields` feature is enabled.
+    #[cfg_attr(feature = "extra-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "extra-fields"), serde(skip))]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Represents a chat completion response returned by model, based on the provided input.
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct CreateChatCompletionResponse {
     /// A unique identifier for the chat completion.
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub id: String,
     /// A list of chat completion choices. Can be more than one if `n` is greater than 1.
     pub choices: Vec<ChatChoice>,
     /// The Unix timestamp (in seconds) of when the chat completion was created.
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub created: u32,
     /// The model used for the chat completion.
     pub model: String,
     /// The service tier used for processing the request. This field is only included if the `service_tier` parameter is specified in the request.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(
+            default,
+            deserialize_with = "crate::types::lenient::deserialize_or_none"
+        )
+    )]
     pub service_tier: Option<ServiceTierResponse>,
     /// This fingerprint represents the backend configuration that the model runs with.
     ///
@@ -897,13 +1081,20 @@ pub struct CreateChatCompletionResponse {
     pub system_fingerprint: Option<String>,
 
     /// The object type, which is always `chat.completion`.
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub object: String,
     pub usage: Option<CompletionUsage>,
+
+    /// Fields which are not part of OpenAI's schema, e.g. added by OpenAI compatible servers.
+    /// Empty unless the `extra-fields` feature is enabled.
+    #[cfg_attr(feature = "extra-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "extra-fields"), serde(skip))]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Parsed server side events stream until an \[DONE\] is received from server.
 pub type ChatCompletionResponseStream =
-    Pin<Box<dyn Stream<Item = Result<CreateChatCompletionStreamResponse, OpenAIError>> + Send>>;
+    BoxStream<'static, Result<CreateChatCompletionStreamResponse, OpenAIError>>;
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct FunctionCallStream {
@@ -918,16 +1109,28 @@ pub struct FunctionCallStream {
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct ChatCompletionMessageToolCallChunk {
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub index: u32,
     /// The ID of the tool call.
     pub id: Option<String>,
     /// The type of the tool. Currently, only `function` is supported.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(
+            default,
+            deserialize_with = "crate::types::lenient::deserialize_or_none"
+        )
+    )]
     pub r#type: Option<ChatCompletionToolType>,
     pub function: Option<FunctionCallStream>,
 }
 
 /// A chat completion delta generated by streamed model responses.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct ChatCompletionStreamResponseDelta {
     /// The contents of the chunk message.
     pub content: Option<String>,
@@ -937,14 +1140,50 @@ pub struct ChatCompletionStreamResponseDelta {
 
     pub tool_calls: Option<Vec<ChatCompletionMessageToolCallChunk>>,
     /// The role of the author of this message.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(
+            default,
+            deserialize_with = "crate::types::lenient::deserialize_or_none"
+        )
+    )]
     pub role: Option<Role>,
     /// The refusal message generated by the model.
     pub refusal: Option<String>,
+    /// A fragment of the audio response, when the audio output modality is requested.
+    pub audio: Option<ChatCompletionStreamResponseDeltaAudio>,
+
+    /// Fields which are not part of OpenAI's schema, e.g. added by OpenAI compatible servers.
+    /// Empty unless the `extra-fields` feature is enabled.
+    #[cfg_attr(feature = "extra-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "extra-fields"), serde(skip))]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A fragment of [ChatCompletionResponseMessageAudio]: the id and expiration in the first chunk,
+/// then fragments of the audio data and of its transcript.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct ChatCompletionStreamResponseDeltaAudio {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u32>,
+    /// Base64 encoded fragment of the audio bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+    /// Fragment of the transcript of the audio.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcript: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct ChatChoiceStream {
     /// The index of the choice in the list of choices.
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub index: u32,
     pub delta: ChatCompletionStreamResponseDelta,
     /// The reason the model stopped generating tokens. This will be
@@ -957,32 +1196,177 @@ pub struct ChatChoiceStream {
     /// content filters,
     /// `tool_calls` if the model called a tool, or `function_call`
     /// (deprecated) if the model called a function.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(
+            default,
+            deserialize_with = "crate::types::lenient::deserialize_or_none"
+        )
+    )]
     pub finish_reason: Option<FinishReason>,
     /// Log probability information for the choice.
     pub logprobs: Option<ChatChoiceLogprobs>,
+
+    /// Fields which are not part of OpenAI's schema, e.g. added by OpenAI compatible servers.
+    /// Empty unless the `extra-fields` feature is enabled.
+    #[cfg_attr(feature = "extra-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "extra-fields"), serde(skip))]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
 /// Represents a streamed chunk of a chat completion response returned by model, based on the provided input.
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct CreateChatCompletionStreamResponse {
     /// A unique identifier for the chat completion. Each chunk has the same ID.
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub id: String,
     /// A list of chat completion choices. Can contain more than one elements if `n` is greater than 1. Can also be empty for the last chunk if you set `stream_options: {"include_usage": true}`.
     pub choices: Vec<ChatChoiceStream>,
 
     /// The Unix timestamp (in seconds) of when the chat completion was created. Each chunk has the same timestamp.
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub created: u32,
     /// The model to generate the completion.
     pub model: String,
     /// The service tier used for processing the request. This field is only included if the `service_tier` parameter is specified in the request.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(
+            default,
+            deserialize_with = "crate::types::lenient::deserialize_or_none"
+        )
+    )]
     pub service_tier: Option<ServiceTierResponse>,
     /// This fingerprint represents the backend configuration that the model runs with.
     /// Can be used in conjunction with the `seed` request parameter to understand when backend changes have been made that might impact determinism.
     pub system_fingerprint: Option<String>,
     /// The object type, which is always `chat.completion.chunk`.
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub object: String,
 
     /// An optional field that will only be present when you set `stream_options: {"include_usage": true}` in your request.
     /// When present, it contains a null value except for the last chunk which contains the token usage statistics for the entire request.
     pub usage: Option<CompletionUsage>,
+
+    /// Fields which are not part of OpenAI's schema, e.g. added by OpenAI compatible servers.
+    /// Empty unless the `extra-fields` feature is enabled.
+    #[cfg_attr(feature = "extra-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "extra-fields"), serde(skip))]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// High level event of a [ChatCompletionResponseStream], see
+/// [crate::traits::ChatCompletionStreamExt::accumulate].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatCompletionStreamEvent {
+    /// Text generated for the choice at `index`.
+    TextDelta { index: u32, delta: String },
+    /// Refusal generated for the choice at `index`.
+    RefusalDelta { index: u32, delta: String },
+    /// Base64 encoded audio generated for the choice at `index`.
+    AudioDelta { index: u32, data: String },
+    /// Transcript of the audio generated for the choice at `index`.
+    AudioTranscriptDelta { index: u32, delta: String },
+    /// A tool call of the choice at `index`, its arguments complete.
+    ToolCallCompleted {
+        index: u32,
+        tool_call: ChatCompletionMessageToolCall,
+    },
+    /// The reason the model stopped generating the choice at `index`.
+    FinishReason { index: u32, reason: FinishReason },
+    /// The message of the choice at `index`, assembled from its deltas, when the stream ends.
+    Completed {
+        index: u32,
+        message: ChatCompletionResponseMessage,
+    },
+    /// Token usage of the request, in the last chunk when `stream_options.include_usage` is set.
+    Usage(CompletionUsage),
+}
+
+/// Assembles the messages of the choices of a [ChatCompletionResponseStream] from the deltas
+/// of its chunks: text, refusal, audio, and tool calls whose ids, names and arguments are streamed
+/// in fragments, and keeps the usage of the last chunk.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ChatCompletionAccumulator {
+    pub(crate) choices: Vec<AccumulatedChoice>,
+    /// The first chunk, without its choices.
+    pub(crate) first_chunk: Option<CreateChatCompletionStreamResponse>,
+    pub(crate) usage: Option<CompletionUsage>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct AccumulatedChoice {
+    pub(crate) role: Option<Role>,
+    pub(crate) content: Option<String>,
+    pub(crate) refusal: Option<String>,
+    pub(crate) tool_calls: Vec<ChatCompletionMessageToolCall>,
+    pub(crate) audio: Option<ChatCompletionResponseMessageAudio>,
+    /// Number of tool calls reported as completed.
+    pub(crate) completed_tool_calls: usize,
+    pub(crate) finish_reason: Option<FinishReason>,
+}
+
+/// A list of stored chat completions, created with `store: true`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ChatCompletionList {
+    pub object: String,
+    pub data: Vec<CreateChatCompletionResponse>,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+    pub has_more: bool,
+}
+
+/// A message of a stored chat completion: the messages of its request, then its response.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ChatCompletionStoredMessage {
+    /// The identifier of the message.
+    pub id: String,
+    /// The role of the author of the message.
+    pub role: Role,
+    /// The text contents of the message.
+    pub content: Option<String>,
+    /// The name of the author of the message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The refusal message generated by the model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refusal: Option<String>,
+    /// The tool calls generated by the model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ChatCompletionMessageToolCall>>,
+    /// The audio response of the model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio: Option<ChatCompletionResponseMessageAudio>,
+    /// The content parts of a user message with text, images or audio.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_parts: Option<Vec<ChatCompletionRequestUserMessageContentPart>>,
+}
+
+/// A list of the messages of a stored chat completion.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ChatCompletionMessageList {
+    pub object: String,
+    pub data: Vec<ChatCompletionStoredMessage>,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct UpdateChatCompletionRequest {
+    /// Set of 16 key-value pairs that can be attached to the stored chat completion,
+    /// replacing its current metadata.
+    pub metadata: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct DeleteChatCompletionResponse {
+    pub id: String,
+    pub object: String,
+    pub deleted: bool,
 }
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
@@ -10,9 +10,144 @@ pub enum InputSource {
     VecU8 { filename: String, vec: Vec<u8> },
 }
 
+/// A multipart form built by hand, to call multipart `_byot` methods (files, audio, images)
+/// with parameters this library does not model yet.
+///
+/// Unlike [reqwest::multipart::Form] it is `Clone`, so the request can be retried.
+///
+/// ```
+/// use async_openai::types::{InputSource, MultipartForm};
+///
+/// let form = MultipartForm::new()
+///     .file("file", InputSource::Path { path: "audio.mp3".into() })
+///     .text("model", "gpt-4o-transcribe")
+///     .text("include[]", "logprobs");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MultipartForm {
+    pub(crate) parts: Vec<(String, MultipartValue)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum MultipartValue {
+    Text(String),
+    File(InputSource),
+}
+
+impl MultipartForm {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a text field. Fields can be repeated, e.g. for array parameters like `include[]`.
+    pub fn text(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.parts
+            .push((name.into(), MultipartValue::Text(value.into())));
+        self
+    }
+
+    /// Add a file field.
+    pub fn file(mut self, name: impl Into<String>, source: InputSource) -> Self {
+        self.parts.push((name.into(), MultipartValue::File(source)));
+        self
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum OrganizationRole {
     Owner,
     Reader,
 }
+
+/// Token usage of an API call, normalized across the usage objects of the different API groups,
+/// e.g. `prompt_tokens`/`completion_tokens` in Chat and `input_tokens`/`output_tokens` in Realtime.
+///
+/// Passed to [crate::traits::UsageObserver].
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(from = "RawTokenUsage")]
+pub struct TokenUsage {
+    /// Number of tokens in the input (prompt).
+    pub input_tokens: u32,
+    /// Number of tokens generated, including reasoning tokens.
+    pub output_tokens: u32,
+    /// Total number of tokens used.
+    pub total_tokens: u32,
+    /// Input tokens served from the prompt cache.
+    pub cached_tokens: Option<u32>,
+    /// Output tokens used for reasoning.
+    pub reasoning_tokens: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct RawTokenUsage {
+    #[serde(default, alias = "prompt_tokens")]
+    input_tokens: u32,
+    #[serde(default, alias = "completion_tokens")]
+    output_tokens: u32,
+    #[serde(default)]
+    total_tokens: u32,
+    #[serde(default, alias = "prompt_tokens_details")]
+    input_tokens_details: Option<RawTokenDetails>,
+    #[serde(default, alias = "completion_tokens_details")]
+    output_tokens_details: Option<RawTokenDetails>,
+    // flat form, as serialized by [TokenUsage]
+    cached_tokens: Option<u32>,
+    reasoning_tokens: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct RawTokenDetails {
+    cached_tokens: Option<u32>,
+    reasoning_tokens: Option<u32>,
+}
+
+impl From<RawTokenUsage> for TokenUsage {
+    fn from(value: RawTokenUsage) -> Self {
+        Self {
+            input_tokens: value.input_tokens,
+            output_tokens: value.output_tokens,
+            total_tokens: value.total_tokens,
+            cached_tokens: value
+                .input_tokens_details
+                .and_then(|d| d.cached_tokens)
+                .or(value.cached_tokens),
+            reasoning_tokens: value
+                .output_tokens_details
+                .and_then(|d| d.reasoning_tokens)
+                .or(value.reasoning_tokens),
+        }
+    }
+}
+
+/// HTTP details of an API response: status code, request id and rate limit headers.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResponseMetadata {
+    /// HTTP status code.
+    pub status: u16,
+    /// Value of the `x-request-id` header, include it when reporting issues to OpenAI support.
+    pub request_id: Option<String>,
+    /// How long to wait before retrying, from the `retry-after-ms` or `retry-after` header.
+    pub retry_after: Option<Duration>,
+    /// Values of the `x-ratelimit-*` headers.
+    pub rate_limit: RateLimitHeaders,
+}
+
+/// [Rate limits](https://platform.openai.com/docs/guides/rate-limits#rate-limits-in-headers)
+/// from the `x-ratelimit-*` headers of a response.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateLimitHeaders {
+    /// The maximum number of requests that are permitted before exhausting the rate limit.
+    pub limit_requests: Option<u64>,
+    /// The maximum number of tokens that are permitted before exhausting the rate limit.
+    pub limit_tokens: Option<u64>,
+    /// The remaining number of requests that are permitted before exhausting the rate limit.
+    pub remaining_requests: Option<u64>,
+    /// The remaining number of tokens that are permitted before exhausting the rate limit.
+    pub remaining_tokens: Option<u64>,
+    /// The time until the rate limit (based on requests) resets to its initial state.
+    pub reset_requests: Option<Duration>,
+    /// The time until the rate limit (based on tokens) resets to its initial state.
+    pub reset_tokens: Option<Duration>,
+}
@@ -1,19 +1,19 @@
-use std::{collections::HashMap, pin::Pin};
+use std::collections::HashMap;
 
 use derive_builder::Builder;
-use futures::Stream;
 use serde::{Deserialize, Serialize};
 
-use crate::error::OpenAIError;
+use crate::{error::OpenAIError, runtime::BoxStream};
 
-use super::{ChatCompletionStreamOptions, Choice, CompletionUsage, Prompt, Stop};
+use super::{validate, ChatCompletionStreamOptions, Choice, CompletionUsage, Prompt, Stop};
 
 #[derive(Clone, Serialize, Deserialize, Default, Debug, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "CreateCompletionRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateCompletionRequest {
     /// ID of the model to use. You can use the [List models](https://platform.openai.com/docs/api-reference/models/list) API to see all of your available models, or see our [Model overview](https://platform.openai.com/docs/models/overview) for descriptions of them.
     pub model: String,
@@ -115,12 +115,58 @@ pub struct CreateCompletionRequest {
     pub seed: Option<i64>,
 }
 
+impl CreateCompletionRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        validate::sampling(self.temperature.flatten(), self.top_p.flatten())?;
+        validate::range(
+            "presence_penalty",
+            self.presence_penalty.flatten(),
+            -2.0,
+            2.0,
+        )?;
+        validate::range(
+            "frequency_penalty",
+            self.frequency_penalty.flatten(),
+            -2.0,
+            2.0,
+        )?;
+        validate::range("n", self.n.flatten(), 1, 128)?;
+        validate::range("logprobs", self.logprobs.flatten(), 0, 5)?;
+        validate::range("best_of", self.best_of.flatten(), 0, 20)?;
+        validate::stream_options(
+            self.stream.flatten(),
+            self.stream_options.flatten().is_some(),
+        )?;
+
+        if let Some(best_of) = self.best_of.flatten() {
+            if best_of < self.n.flatten().unwrap_or(1) {
+                return Err(OpenAIError::InvalidArgument(
+                    "best_of must be greater than or equal to n".into(),
+                ));
+            }
+            if best_of > 1 && self.stream.flatten().unwrap_or_default() {
+                return Err(OpenAIError::InvalidArgument(
+                    "best_of greater than 1 cannot be streamed".into(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct CreateCompletionResponse {
     /// A unique identifier for the completion.
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub id: String,
     pub choices: Vec<Choice>,
     /// The Unix timestamp (in seconds) of when the completion was created.
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub created: u32,
 
     /// The model used for completion.
@@ -132,10 +178,17 @@ pub struct CreateCompletionResponse {
     pub system_fingerprint: Option<String>,
 
     /// The object type, which is always "text_completion"
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub object: String,
     pub usage: Option<CompletionUsage>,
+
+    /// Fields which are not part of OpenAI's schema, e.g. added by OpenAI compatible servers.
+    /// Empty unless the `extra-fields` feature is enabled.
+    #[cfg_attr(feature = "extra-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "extra-fields"), serde(skip))]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Parsed server side events stream until an \[DONE\] is received from server.
 pub type CompletionResponseStream =
-    Pin<Box<dyn Stream<Item = Result<CreateCompletionResponse, OpenAIError>> + Send>>;
+    BoxStream<'static, Result<CreateCompletionResponse, OpenAIError>>;
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::error::OpenAIError;
+
+use super::Item;
+
+/// A conversation, whose items are the state of a [Responses](crate::Responses) conversation.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
+pub struct Conversation {
+    /// The unique ID of the conversation.
+    pub id: String,
+    /// The object type, which is always `conversation`.
+    pub object: String,
+    /// The Unix timestamp (in seconds) for when the conversation was created.
+    pub created_at: u64,
+    /// Set of 16 key-value pairs attached to the conversation.
+    pub metadata: Option<HashMap<String, String>>,
+
+    /// Fields which are not part of OpenAI's schema, e.g. added by OpenAI compatible servers.
+    /// Empty unless the `extra-fields` feature is enabled.
+    #[cfg_attr(feature = "extra-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "extra-fields"), serde(skip))]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[builder(name = "CreateConversationRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct CreateConversationRequest {
+    /// Initial items to include in the context of the conversation, up to 20 items at a time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Vec<Item>>,
+
+    /// Set of 16 key-value pairs that can be attached to the conversation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct UpdateConversationRequest {
+    /// Set of 16 key-value pairs that can be attached to the conversation,
+    /// replacing its current metadata.
+    pub metadata: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct DeleteConversationResponse {
+    pub id: String,
+    pub object: String,
+    pub deleted: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CreateConversationItemsRequest {
+    /// The items to add to the conversation, up to 20 items at a time.
+    pub items: Vec<Item>,
+}
+
+/// A list of the items of a conversation.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConversationItemList {
+    pub object: String,
+    pub data: Vec<Item>,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+    pub has_more: bool,
+}
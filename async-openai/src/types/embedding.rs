@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use crate::error::OpenAIError;
 
 #[derive(Debug, Serialize, Clone, PartialEq, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum EmbeddingInput {
     String(String),
@@ -15,6 +16,7 @@ pub enum EmbeddingInput {
 }
 
 #[derive(Debug, Serialize, Default, Clone, PartialEq, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum EncodingFormat {
     #[default]
@@ -23,6 +25,7 @@ pub enum EncodingFormat {
 }
 
 #[derive(Debug, Serialize, Default, Clone, Builder, PartialEq, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "CreateEmbeddingRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -55,14 +58,25 @@ pub struct CreateEmbeddingRequest {
 
 /// Represents an embedding vector returned by embedding endpoint.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct Embedding {
     /// The index of the embedding in the list of embeddings.
     pub index: u32,
     /// The object type, which is always "embedding".
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub object: String,
     /// The embedding vector, which is a list of floats. The length of vector
     /// depends on the model as listed in the [embedding guide](https://platform.openai.com/docs/guides/embeddings).
     pub embedding: Vec<f32>,
+
+    /// Fields which are not part of OpenAI's schema, e.g. added by OpenAI compatible servers.
+    /// Empty unless the `extra-fields` feature is enabled.
+    #[cfg_attr(feature = "extra-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "extra-fields"), serde(skip))]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -92,15 +106,32 @@ pub struct Base64Embedding {
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct EmbeddingUsage {
     /// The number of tokens used by the prompt.
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub prompt_tokens: u32,
     /// The total number of tokens used by the request.
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub total_tokens: u32,
+
+    /// Fields which are not part of OpenAI's schema, e.g. added by OpenAI compatible servers.
+    /// Empty unless the `extra-fields` feature is enabled.
+    #[cfg_attr(feature = "extra-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "extra-fields"), serde(skip))]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct CreateEmbeddingResponse {
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub object: String,
     /// The name of the model used to generate the embedding.
     pub model: String,
@@ -108,6 +139,12 @@ pub struct CreateEmbeddingResponse {
     pub data: Vec<Embedding>,
     /// The usage information for the request.
     pub usage: EmbeddingUsage,
+
+    /// Fields which are not part of OpenAI's schema, e.g. added by OpenAI compatible servers.
+    /// Empty unless the `extra-fields` feature is enabled.
+    #[cfg_attr(feature = "extra-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "extra-fields"), serde(skip))]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
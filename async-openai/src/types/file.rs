@@ -1,7 +1,8 @@
+use bytes::Bytes;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
-use crate::error::OpenAIError;
+use crate::{error::OpenAIError, runtime::BoxStream};
 
 use super::InputSource;
 
@@ -36,11 +37,24 @@ pub struct CreateFileRequest {
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct ListFilesResponse {
     pub object: String,
     pub data: Vec<OpenAIFile>,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+    pub has_more: Option<bool>,
 }
 
+/// Files of a list, deserialized as they are received.
+pub type OpenAIFileStream = BoxStream<'static, Result<OpenAIFile, OpenAIError>>;
+
+/// Content of a file, in chunks as they are received.
+pub type FileContentStream = BoxStream<'static, Result<Bytes, OpenAIError>>;
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
 pub struct DeleteFileResponse {
     pub id: String,
@@ -68,6 +82,10 @@ pub enum OpenAIFilePurpose {
 
 /// The `File` object represents a document that has been uploaded to OpenAI.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct OpenAIFile {
     /// The file identifier, which can be referenced in the API endpoints.
     pub id: String,
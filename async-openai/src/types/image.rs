@@ -3,9 +3,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::OpenAIError;
 
-use super::InputSource;
+use super::{validate, InputSource};
 
 #[derive(Default, Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ImageSize {
     #[serde(rename = "256x256")]
     S256x256,
@@ -32,6 +33,7 @@ pub enum DallE2ImageSize {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ImageResponseFormat {
     #[default]
@@ -41,6 +43,7 @@ pub enum ImageResponseFormat {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ImageModel {
     #[default]
     #[serde(rename = "dall-e-2")]
@@ -52,6 +55,7 @@ pub enum ImageModel {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ImageQuality {
     #[default]
@@ -60,6 +64,7 @@ pub enum ImageQuality {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ImageStyle {
     #[default]
@@ -68,11 +73,12 @@ pub enum ImageStyle {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "CreateImageRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateImageRequest {
     /// A text description of the desired image(s). The maximum length is 1000 characters for `dall-e-2`
     /// and 4000 characters for `dall-e-3`.
@@ -112,6 +118,14 @@ pub struct CreateImageRequest {
     pub user: Option<String>,
 }
 
+impl CreateImageRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        validate::range("n", self.n.flatten(), 1, 10)?;
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum Image {
@@ -128,9 +142,19 @@ pub enum Image {
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct ImagesResponse {
     pub created: u32,
     pub data: Vec<std::sync::Arc<Image>>,
+
+    /// Fields which are not part of OpenAI's schema, e.g. added by OpenAI compatible servers.
+    /// Empty unless the `extra-fields` feature is enabled.
+    #[cfg_attr(feature = "extra-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "extra-fields"), serde(skip))]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -143,7 +167,7 @@ pub struct ImageInput {
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateImageEditRequest {
     /// The image to edit. Must be a valid PNG file, less than 4MB, and square. If mask is not provided, image must have transparency, which will be used as the mask.
     pub image: ImageInput,
@@ -170,12 +194,20 @@ pub struct CreateImageEditRequest {
     pub user: Option<String>,
 }
 
+impl CreateImageEditRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        validate::range("n", self.n.flatten(), 1, 10)?;
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Default, Clone, Builder, PartialEq)]
 #[builder(name = "CreateImageVariationRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateImageVariationRequest {
     /// The image to use as the basis for the variation(s). Must be a valid PNG file, less than 4MB, and square.
     pub image: ImageInput,
@@ -195,3 +227,11 @@ pub struct CreateImageVariationRequest {
     /// A unique identifier representing your end-user, which will help OpenAI to monitor and detect abuse. [Learn more](https://platform.openai.com/docs/usage-policies/end-user-ids).
     pub user: Option<String>,
 }
+
+impl CreateImageVariationRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        validate::range("n", self.n.flatten(), 1, 10)?;
+
+        Ok(())
+    }
+}
@@ -1,34 +1,55 @@
 use std::{
     fmt::Display,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 use crate::{
-    download::{download_url, save_b64},
+    download::decode_b64,
     error::OpenAIError,
     traits::AsyncTryFrom,
-    types::InputSource,
-    util::{create_all_dir, create_file_part},
+    types::{InputSource, MultipartForm, MultipartValue},
+    util::create_file_part,
 };
 
+#[cfg(not(target_family = "wasm"))]
+use crate::{
+    download::{download_url, save_b64},
+    util::create_all_dir,
+};
+
+use base64::{engine::general_purpose, Engine as _};
 use bytes::Bytes;
 
 use super::{
-    AddUploadPartRequest, AudioInput, AudioResponseFormat, ChatCompletionFunctionCall,
-    ChatCompletionFunctions, ChatCompletionNamedToolChoice, ChatCompletionRequestAssistantMessage,
-    ChatCompletionRequestAssistantMessageContent, ChatCompletionRequestDeveloperMessage,
+    AccumulatedChoice, AddUploadPartRequest, AudioInput, AudioResponseFormat, ChatChoice,
+    ChatCompletionAccumulator, ChatCompletionFunctionCall, ChatCompletionFunctions,
+    ChatCompletionMessageToolCall, ChatCompletionNamedToolChoice,
+    ChatCompletionRequestAssistantMessage, ChatCompletionRequestAssistantMessageContent,
+    ChatCompletionRequestAssistantMessageContentPart, ChatCompletionRequestDeveloperMessage,
     ChatCompletionRequestDeveloperMessageContent, ChatCompletionRequestFunctionMessage,
     ChatCompletionRequestMessage, ChatCompletionRequestMessageContentPartAudio,
     ChatCompletionRequestMessageContentPartImage, ChatCompletionRequestMessageContentPartText,
     ChatCompletionRequestSystemMessage, ChatCompletionRequestSystemMessageContent,
-    ChatCompletionRequestToolMessage, ChatCompletionRequestToolMessageContent,
+    ChatCompletionRequestSystemMessageContentPart, ChatCompletionRequestToolMessage,
+    ChatCompletionRequestToolMessageContent, ChatCompletionRequestToolMessageContentPart,
     ChatCompletionRequestUserMessage, ChatCompletionRequestUserMessageContent,
-    ChatCompletionRequestUserMessageContentPart, ChatCompletionToolChoiceOption, CreateFileRequest,
-    CreateImageEditRequest, CreateImageVariationRequest, CreateMessageRequestContent,
-    CreateSpeechResponse, CreateTranscriptionRequest, CreateTranslationRequest, DallE2ImageSize,
-    EmbeddingInput, FileInput, FilePurpose, FunctionName, Image, ImageInput, ImageModel,
-    ImageResponseFormat, ImageSize, ImageUrl, ImagesResponse, ModerationInput, Prompt, Role, Stop,
-    TimestampGranularity,
+    ChatCompletionRequestUserMessageContentPart, ChatCompletionResponseMessage,
+    ChatCompletionStreamEvent, ChatCompletionStreamResponseDelta, ChatCompletionToolChoiceOption,
+    ChatCompletionToolType, CompletionUsage, ComputerCallOutput, ContentPart,
+    CreateChatCompletionRequest, CreateChatCompletionResponse, CreateChatCompletionStreamResponse,
+    CreateFileRequest, CreateImageEditRequest, CreateImageVariationRequest,
+    CreateMessageRequestContent, CreateSpeechResponse, CreateTranscriptionRequest,
+    CreateTranslationRequest, DallE2ImageSize, EmbeddingInput, FileInput, FilePurpose,
+    FunctionCall, FunctionCallOutput, FunctionName, Image, ImageGenerationToolCall, ImageInput,
+    ImageModel, ImageResponseFormat, ImageSize, ImageUrl, ImagesResponse, Input, InputAudio,
+    InputAudioFormat, InputContent, InputFile, InputImage, InputMessage, InputRole, InputText,
+    Item, ItemReference, ItemStatus, LocalShellCallOutput, McpApprovalResponse, MessageContent,
+    MessageDeltaContent, MessageDeltaObject, MessageObject, ModelId, ModerationInput, OutputText,
+    PredictionContent, PredictionContentContent, Prompt, PromptVariable, RateLimitHeaders,
+    Reasoning, Response, ResponseAccumulator, ResponseImageGenerationCallPartialImageEvent,
+    ResponseMetadata, ResponseStreamEvent, ResponseUsage, Role, Stop, SummaryPart, TextConfig,
+    TextFormat, TextJsonSchema, TimestampGranularity, TokenUsage,
 };
 
 /// for `impl_from!(T, Enum)`, implements
@@ -280,6 +301,7 @@ impl Display for FilePurpose {
 impl ImagesResponse {
     /// Save each image in a dedicated Tokio task and return paths to saved files.
     /// For [ResponseFormat::Url] each file is downloaded in dedicated Tokio task.
+    #[cfg(not(target_family = "wasm"))]
     pub async fn save<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<PathBuf>, OpenAIError> {
         create_all_dir(dir.as_ref())?;
 
@@ -317,7 +339,14 @@ impl ImagesResponse {
     }
 }
 
+impl From<Bytes> for CreateSpeechResponse {
+    fn from(bytes: Bytes) -> Self {
+        Self { bytes }
+    }
+}
+
 impl CreateSpeechResponse {
+    #[cfg(not(target_family = "wasm"))]
     pub async fn save<P: AsRef<Path>>(&self, file_path: P) -> Result<(), OpenAIError> {
         let dir = file_path.as_ref().parent();
 
@@ -334,12 +363,53 @@ impl CreateSpeechResponse {
 }
 
 impl Image {
-    async fn save<P: AsRef<Path>>(&self, dir: P) -> Result<PathBuf, OpenAIError> {
+    /// Save the image in `dir`: a base64 image is decoded chunk by chunk into the file,
+    /// an image URL is downloaded.
+    #[cfg(not(target_family = "wasm"))]
+    pub async fn save<P: AsRef<Path>>(&self, dir: P) -> Result<PathBuf, OpenAIError> {
         match self {
             Image::Url { url, .. } => download_url(url, dir).await,
             Image::B64Json { b64_json, .. } => save_b64(b64_json, dir).await,
         }
     }
+
+    /// Decoded bytes of a base64 image, i.e. for [ImageResponseFormat::B64Json].
+    /// Fails for an image URL.
+    pub fn as_bytes(&self) -> Result<Bytes, OpenAIError> {
+        match self {
+            Image::Url { .. } => Err(OpenAIError::InvalidArgument(
+                "image is a URL, use `save` to download it".into(),
+            )),
+            Image::B64Json { b64_json, .. } => decode_b64(b64_json),
+        }
+    }
+}
+
+impl ImageGenerationToolCall {
+    /// Save the generated image in `dir`, decoded chunk by chunk into the file.
+    /// Fails when the call did not generate an image.
+    #[cfg(not(target_family = "wasm"))]
+    pub async fn save<P: AsRef<Path>>(&self, dir: P) -> Result<PathBuf, OpenAIError> {
+        save_b64(self.b64_result()?, dir).await
+    }
+
+    /// Decoded bytes of the generated image. Fails when the call did not generate an image.
+    pub fn as_bytes(&self) -> Result<Bytes, OpenAIError> {
+        decode_b64(self.b64_result()?)
+    }
+
+    fn b64_result(&self) -> Result<&str, OpenAIError> {
+        self.result.as_deref().ok_or_else(|| {
+            OpenAIError::InvalidArgument(format!("image generation call {} has no image", self.id))
+        })
+    }
+}
+
+impl ResponseImageGenerationCallPartialImageEvent {
+    /// Decoded bytes of the partial image.
+    pub fn as_bytes(&self) -> Result<Bytes, OpenAIError> {
+        decode_b64(&self.partial_image_b64)
+    }
 }
 
 macro_rules! impl_from_for_integer_array {
@@ -674,6 +744,43 @@ impl From<String> for ChatCompletionRequestToolMessageContent {
     }
 }
 
+impl From<&str> for PredictionContentContent {
+    fn from(value: &str) -> Self {
+        PredictionContentContent::Text(value.into())
+    }
+}
+
+impl From<String> for PredictionContentContent {
+    fn from(value: String) -> Self {
+        PredictionContentContent::Text(value)
+    }
+}
+
+impl From<Vec<ChatCompletionRequestMessageContentPartText>> for PredictionContentContent {
+    fn from(value: Vec<ChatCompletionRequestMessageContentPartText>) -> Self {
+        PredictionContentContent::Array(value)
+    }
+}
+
+impl From<PredictionContentContent> for PredictionContent {
+    fn from(value: PredictionContentContent) -> Self {
+        PredictionContent::Content(value)
+    }
+}
+
+/// Predicted output of the text, e.g. of the file being regenerated.
+impl From<&str> for PredictionContent {
+    fn from(value: &str) -> Self {
+        PredictionContent::Content(value.into())
+    }
+}
+
+impl From<String> for PredictionContent {
+    fn from(value: String) -> Self {
+        PredictionContent::Content(value.into())
+    }
+}
+
 impl From<&str> for ChatCompletionRequestUserMessage {
     fn from(value: &str) -> Self {
         ChatCompletionRequestUserMessageContent::Text(value.into()).into()
@@ -722,6 +829,222 @@ impl From<String> for ChatCompletionRequestAssistantMessage {
     }
 }
 
+impl Default for Input {
+    fn default() -> Self {
+        Input::Text(String::new())
+    }
+}
+
+impl From<&str> for Input {
+    fn from(value: &str) -> Self {
+        Input::Text(value.into())
+    }
+}
+
+impl From<String> for Input {
+    fn from(value: String) -> Self {
+        Input::Text(value)
+    }
+}
+
+impl From<Vec<Item>> for Input {
+    fn from(value: Vec<Item>) -> Self {
+        Input::Items(value)
+    }
+}
+
+impl Default for InputContent {
+    fn default() -> Self {
+        InputContent::Text(String::new())
+    }
+}
+
+impl From<&str> for InputContent {
+    fn from(value: &str) -> Self {
+        InputContent::Text(value.into())
+    }
+}
+
+impl From<String> for InputContent {
+    fn from(value: String) -> Self {
+        InputContent::Text(value)
+    }
+}
+
+impl InputMessage {
+    /// A message with `role` and text `content`.
+    pub fn new<S: Into<String>>(role: InputRole, content: S) -> Self {
+        Self {
+            id: None,
+            role,
+            content: InputContent::Text(content.into()),
+            status: None,
+        }
+    }
+}
+
+impl From<Vec<ContentPart>> for InputContent {
+    fn from(value: Vec<ContentPart>) -> Self {
+        InputContent::Parts(value)
+    }
+}
+
+impl From<InputText> for ContentPart {
+    fn from(value: InputText) -> Self {
+        ContentPart::InputText(value)
+    }
+}
+
+impl From<InputImage> for ContentPart {
+    fn from(value: InputImage) -> Self {
+        ContentPart::InputImage(value)
+    }
+}
+
+impl From<InputFile> for ContentPart {
+    fn from(value: InputFile) -> Self {
+        ContentPart::InputFile(value)
+    }
+}
+
+impl Item {
+    /// A text message from the user.
+    pub fn user_text<S: Into<String>>(text: S) -> Self {
+        InputMessage::new(InputRole::User, text).into()
+    }
+
+    /// A text message from the model, e.g. an earlier answer in a conversation.
+    pub fn assistant_text<S: Into<String>>(text: S) -> Self {
+        InputMessage::new(InputRole::Assistant, text).into()
+    }
+
+    /// A text message with instructions from the developer, taking precedence over messages
+    /// of the user.
+    pub fn developer_text<S: Into<String>>(text: S) -> Self {
+        InputMessage::new(InputRole::Developer, text).into()
+    }
+
+    /// An image at `url` from the user. `url` can also be a base64 encoded data URL.
+    pub fn image_url<S: Into<String>>(url: S) -> Self {
+        InputMessage {
+            id: None,
+            role: InputRole::User,
+            content: vec![InputImage::from_url(url).into()].into(),
+            status: None,
+        }
+        .into()
+    }
+
+    /// The `output` of the function call `call_id`. Strings are sent as is, other values as JSON.
+    pub fn function_output<S: Into<String>, V: Into<serde_json::Value>>(
+        call_id: S,
+        output: V,
+    ) -> Self {
+        let output = match output.into() {
+            serde_json::Value::String(text) => text,
+            value => value.to_string(),
+        };
+
+        FunctionCallOutput {
+            id: None,
+            call_id: call_id.into(),
+            output,
+            status: None,
+        }
+        .into()
+    }
+}
+
+impl From<&str> for PromptVariable {
+    fn from(value: &str) -> Self {
+        PromptVariable::Text(value.into())
+    }
+}
+
+impl From<String> for PromptVariable {
+    fn from(value: String) -> Self {
+        PromptVariable::Text(value)
+    }
+}
+
+impl From<InputText> for PromptVariable {
+    fn from(value: InputText) -> Self {
+        PromptVariable::Content(ContentPart::InputText(value))
+    }
+}
+
+impl From<InputImage> for PromptVariable {
+    fn from(value: InputImage) -> Self {
+        PromptVariable::Content(ContentPart::InputImage(value))
+    }
+}
+
+impl From<InputFile> for PromptVariable {
+    fn from(value: InputFile) -> Self {
+        PromptVariable::Content(ContentPart::InputFile(value))
+    }
+}
+
+impl From<TextJsonSchema> for TextFormat {
+    fn from(value: TextJsonSchema) -> Self {
+        TextFormat::JsonSchema(value)
+    }
+}
+
+impl From<TextFormat> for TextConfig {
+    fn from(format: TextFormat) -> Self {
+        TextConfig { format }
+    }
+}
+
+impl From<TextJsonSchema> for TextConfig {
+    fn from(value: TextJsonSchema) -> Self {
+        TextFormat::from(value).into()
+    }
+}
+
+impl From<InputMessage> for Item {
+    fn from(value: InputMessage) -> Self {
+        Item::Message(value)
+    }
+}
+
+impl From<FunctionCallOutput> for Item {
+    fn from(value: FunctionCallOutput) -> Self {
+        Item::FunctionCallOutput(value)
+    }
+}
+
+impl From<ComputerCallOutput> for Item {
+    fn from(value: ComputerCallOutput) -> Self {
+        Item::ComputerCallOutput(value)
+    }
+}
+
+impl From<Reasoning> for Item {
+    fn from(value: Reasoning) -> Self {
+        Item::Reasoning(value)
+    }
+}
+
+impl From<LocalShellCallOutput> for Item {
+    fn from(value: LocalShellCallOutput) -> Self {
+        Item::LocalShellCallOutput(value)
+    }
+}
+
+impl From<McpApprovalResponse> for Item {
+    fn from(value: McpApprovalResponse) -> Self {
+        Item::McpApprovalResponse(value)
+    }
+}
+
+impl From<ItemReference> for Item {
+    fn from(value: ItemReference) -> Self {
+        Item::ItemReference(value)
+    }
+}
+
 impl From<Vec<ChatCompletionRequestUserMessageContentPart>>
     for ChatCompletionRequestUserMessageContent
 {
@@ -730,6 +1053,67 @@ impl From<Vec<ChatCompletionRequestUserMessageContentPart>>
     }
 }
 
+impl FromIterator<ChatCompletionRequestUserMessageContentPart>
+    for ChatCompletionRequestUserMessageContent
+{
+    fn from_iter<I: IntoIterator<Item = ChatCompletionRequestUserMessageContentPart>>(
+        iter: I,
+    ) -> Self {
+        ChatCompletionRequestUserMessageContent::Array(iter.into_iter().collect())
+    }
+}
+
+impl ChatCompletionRequestUserMessageContent {
+    /// Split the content into its parts, text content being a single text part.
+    pub fn into_parts(self) -> Vec<ChatCompletionRequestUserMessageContentPart> {
+        match self {
+            ChatCompletionRequestUserMessageContent::Text(text) => {
+                vec![ChatCompletionRequestUserMessageContentPart::Text(
+                    text.into(),
+                )]
+            }
+            ChatCompletionRequestUserMessageContent::Array(parts) => parts,
+        }
+    }
+
+    /// Append the parts of `other`, e.g. context gathered from another source, after the parts of this content.
+    pub fn append<C: Into<ChatCompletionRequestUserMessageContent>>(&mut self, other: C) {
+        let mut parts = std::mem::take(self).into_parts();
+        parts.extend(other.into().into_parts());
+        *self = ChatCompletionRequestUserMessageContent::Array(parts);
+    }
+
+    /// Concatenate adjacent text parts with `separator`, keeping images and audio in place
+    /// between the texts, and drop empty text parts.
+    /// Content left with a single text part becomes text content.
+    pub fn merge_text(self, separator: &str) -> Self {
+        use ChatCompletionRequestUserMessageContentPart as Part;
+
+        let mut parts: Vec<Part> = vec![];
+        for part in self.into_parts() {
+            match part {
+                Part::Text(text) if text.text.is_empty() => {}
+                Part::Text(text) => match parts.last_mut() {
+                    Some(Part::Text(last)) => {
+                        last.text.push_str(separator);
+                        last.text.push_str(&text.text);
+                    }
+                    _ => parts.push(Part::Text(text)),
+                },
+                part => parts.push(part),
+            }
+        }
+
+        match parts.as_mut_slice() {
+            [] => ChatCompletionRequestUserMessageContent::Text(String::new()),
+            [Part::Text(text)] => {
+                ChatCompletionRequestUserMessageContent::Text(std::mem::take(&mut text.text))
+            }
+            _ => ChatCompletionRequestUserMessageContent::Array(parts),
+        }
+    }
+}
+
 impl From<ChatCompletionRequestMessageContentPartText>
     for ChatCompletionRequestUserMessageContentPart
 {
@@ -826,6 +1210,908 @@ impl Default for ChatCompletionRequestToolMessageContent {
     }
 }
 
+impl InputAudio {
+    /// Base64 encode `bytes` of a WAV file.
+    pub fn from_wav_bytes<B: AsRef<[u8]>>(bytes: B) -> Self {
+        Self::from_bytes(bytes.as_ref(), InputAudioFormat::Wav)
+    }
+
+    /// Base64 encode `bytes` of an MP3 file.
+    pub fn from_mp3_bytes<B: AsRef<[u8]>>(bytes: B) -> Self {
+        Self::from_bytes(bytes.as_ref(), InputAudioFormat::Mp3)
+    }
+
+    fn from_bytes(bytes: &[u8], format: InputAudioFormat) -> Self {
+        Self {
+            data: general_purpose::STANDARD.encode(bytes),
+            format,
+        }
+    }
+}
+
+impl From<InputAudio> for ChatCompletionRequestMessageContentPartAudio {
+    fn from(input_audio: InputAudio) -> Self {
+        Self { input_audio }
+    }
+}
+
+// start: Display of message contents and deltas, as their text
+
+impl Display for ChatCompletionRequestMessageContentPartText {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl Display for ChatCompletionRequestSystemMessageContent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text(text) => f.write_str(text),
+            Self::Array(parts) => parts.iter().try_for_each(|part| match part {
+                ChatCompletionRequestSystemMessageContentPart::Text(text) => text.fmt(f),
+            }),
+        }
+    }
+}
+
+impl Display for ChatCompletionRequestDeveloperMessageContent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text(text) => f.write_str(text),
+            Self::Array(parts) => parts.iter().try_for_each(|text| text.fmt(f)),
+        }
+    }
+}
+
+/// Images and audio are omitted.
+impl Display for ChatCompletionRequestUserMessageContent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text(text) => f.write_str(text),
+            Self::Array(parts) => parts.iter().try_for_each(|part| match part {
+                ChatCompletionRequestUserMessageContentPart::Text(text) => text.fmt(f),
+                ChatCompletionRequestUserMessageContentPart::ImageUrl(_)
+                | ChatCompletionRequestUserMessageContentPart::InputAudio(_) => Ok(()),
+            }),
+        }
+    }
+}
+
+impl Display for ChatCompletionRequestAssistantMessageContent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text(text) => f.write_str(text),
+            Self::Array(parts) => parts.iter().try_for_each(|part| match part {
+                ChatCompletionRequestAssistantMessageContentPart::Text(text) => text.fmt(f),
+                ChatCompletionRequestAssistantMessageContentPart::Refusal(refusal) => {
+                    f.write_str(&refusal.refusal)
+                }
+            }),
+        }
+    }
+}
+
+impl Display for ChatCompletionRequestToolMessageContent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text(text) => f.write_str(text),
+            Self::Array(parts) => parts.iter().try_for_each(|part| match part {
+                ChatCompletionRequestToolMessageContentPart::Text(text) => text.fmt(f),
+            }),
+        }
+    }
+}
+
+/// The content, or the refusal.
+impl Display for ChatCompletionResponseMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.content, &self.refusal) {
+            (Some(content), _) => f.write_str(content),
+            (None, Some(refusal)) => f.write_str(refusal),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// The text of the messages of all choices, in order.
+impl Display for CreateChatCompletionResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.choices
+            .iter()
+            .try_for_each(|choice| choice.message.fmt(f))
+    }
+}
+
+/// The content, or the refusal, of the delta.
+impl Display for ChatCompletionStreamResponseDelta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.content, &self.refusal) {
+            (Some(content), _) => f.write_str(content),
+            (None, Some(refusal)) => f.write_str(refusal),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// The text of the deltas of all choices, in order, so that the text of a stream
+/// with a single choice is the concatenation of its chunks:
+///
+/// ```no_run
+/// # use async_openai::types::ChatCompletionResponseStream;
+/// use futures::TryStreamExt;
+///
+/// # async fn run(stream: ChatCompletionResponseStream) -> Result<(), async_openai::error::OpenAIError> {
+/// let text: String = stream.map_ok(|chunk| chunk.to_string()).try_collect().await?;
+/// # Ok(())
+/// # }
+/// ```
+impl Display for CreateChatCompletionStreamResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.choices
+            .iter()
+            .try_for_each(|choice| choice.delta.fmt(f))
+    }
+}
+
+/// Texts and refusals, images are omitted.
+impl Display for MessageContent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MessageContent::Text(text) => f.write_str(&text.text.value),
+            MessageContent::Refusal(refusal) => f.write_str(&refusal.refusal),
+            MessageContent::ImageFile(_) | MessageContent::ImageUrl(_) => Ok(()),
+        }
+    }
+}
+
+/// Texts and refusals, images are omitted.
+impl Display for MessageDeltaContent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MessageDeltaContent::Text(text) => {
+                let value = text.text.as_ref().and_then(|text| text.value.as_deref());
+                f.write_str(value.unwrap_or_default())
+            }
+            MessageDeltaContent::Refusal(refusal) => {
+                f.write_str(refusal.refusal.as_deref().unwrap_or_default())
+            }
+            MessageDeltaContent::ImageFile(_) | MessageDeltaContent::ImageUrl(_) => Ok(()),
+        }
+    }
+}
+
+impl Display for MessageObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.content.iter().try_for_each(|content| content.fmt(f))
+    }
+}
+
+impl Display for MessageDeltaObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.delta
+            .content
+            .iter()
+            .flatten()
+            .try_for_each(|content| content.fmt(f))
+    }
+}
+
+/// The text delta of `response.output_text.delta` events, other events display nothing,
+/// so that the text of a streamed response is the concatenation of its events.
+impl Display for ResponseStreamEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResponseStreamEvent::ResponseOutputTextDelta(event) => f.write_str(&event.delta),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The `output_text` content parts of the output messages, in order. Refusals are omitted.
+impl Display for Response {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.output
+            .iter()
+            .filter_map(|item| match item {
+                Item::Message(message) => Some(&message.content),
+                _ => None,
+            })
+            .try_for_each(|content| match content {
+                InputContent::Text(text) => f.write_str(text),
+                InputContent::Parts(parts) => parts.iter().try_for_each(|part| match part {
+                    ContentPart::OutputText(output) => f.write_str(&output.text),
+                    _ => Ok(()),
+                }),
+            })
+    }
+}
+
+impl CreateChatCompletionResponse {
+    /// The choice at `index`, of the `n` requested.
+    pub fn choice(&self, index: u32) -> Option<&ChatChoice> {
+        self.choices.iter().find(|choice| choice.index == index)
+    }
+
+    /// The content of the message of the first choice.
+    pub fn first_text(&self) -> Option<&str> {
+        self.choice(0)?.message.content.as_deref()
+    }
+
+    /// The contents of the messages of the choices with content, by index, e.g. the `n`
+    /// samples of a request. Choices with only tool calls or a refusal are skipped.
+    pub fn texts(&self) -> Vec<&str> {
+        let mut choices: Vec<&ChatChoice> = self.choices.iter().collect();
+        choices.sort_by_key(|choice| choice.index);
+        choices
+            .into_iter()
+            .filter_map(|choice| choice.message.content.as_deref())
+            .collect()
+    }
+}
+
+impl Response {
+    /// The text of all `output_text` content parts of the output messages, concatenated,
+    /// like `output_text` in the official SDKs.
+    pub fn output_text(&self) -> String {
+        self.to_string()
+    }
+
+    /// The explanation of the model when it refused to answer, e.g. for safety reasons.
+    pub fn refusal(&self) -> Option<&str> {
+        self.output.iter().find_map(|item| match item {
+            Item::Message(InputMessage {
+                content: InputContent::Parts(parts),
+                ..
+            }) => parts.iter().find_map(|part| match part {
+                ContentPart::Refusal(refusal) => Some(refusal.refusal.as_str()),
+                _ => None,
+            }),
+            _ => None,
+        })
+    }
+
+    /// The output items, to append to the input of the next turn of a conversation
+    /// managed by the client, e.g. with `store` set to false: messages, tool calls and
+    /// reasoning items, the latter with their `encrypted_content` when the response was
+    /// created with [crate::types::Include::ReasoningEncryptedContent].
+    ///
+    /// Items which were not completed, e.g. of an incomplete response, are skipped.
+    pub fn into_input_items(self) -> Vec<Item> {
+        self.output
+            .into_iter()
+            .filter(|item| match item {
+                Item::Message(message) => message.status != Some(ItemStatus::InProgress),
+                Item::FunctionCall(call) => call.status != Some(ItemStatus::InProgress),
+                _ => true,
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "schemars")]
+#[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+impl super::ResponseFormat {
+    /// Strict [ResponseFormat::JsonSchema](Self::JsonSchema) of the JSON Schema of `T`, rewritten with
+    /// [strict](crate::schema::strict), for the model to answer with a `T`.
+    ///
+    /// Fails with [OpenAIError::InvalidArgument] when the schema of `T` cannot be used in strict mode.
+    pub fn json_schema_for<T: schemars::JsonSchema>() -> Result<Self, OpenAIError> {
+        Ok(Self::JsonSchema {
+            json_schema: super::ResponseFormatJsonSchema {
+                description: None,
+                name: crate::schema::name_of::<T>(),
+                schema: Some(crate::schema::strict(schemars::schema_for!(T).into())?),
+                strict: Some(true),
+            },
+        })
+    }
+}
+
+impl ChatCompletionAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply the deltas of `chunk` to the messages, and return the events they make up.
+    ///
+    /// A tool call is completed when the next one starts, or when its choice finishes.
+    pub fn apply(
+        &mut self,
+        chunk: &CreateChatCompletionStreamResponse,
+    ) -> Vec<ChatCompletionStreamEvent> {
+        let mut events = vec![];
+
+        if self.first_chunk.is_none() {
+            self.first_chunk = Some(CreateChatCompletionStreamResponse {
+                choices: vec![],
+                ..chunk.clone()
+            });
+        }
+
+        for choice in &chunk.choices {
+            let index = choice.index;
+            if self.choices.len() <= index as usize {
+                self.choices
+                    .resize_with(index as usize + 1, Default::default);
+            }
+            let accumulated = &mut self.choices[index as usize];
+            let delta = &choice.delta;
+
+            if let Some(role) = delta.role {
+                accumulated.role = Some(role);
+            }
+            if let Some(content) = delta.content.as_ref().filter(|c| !c.is_empty()) {
+                accumulated
+                    .content
+                    .get_or_insert_with(String::new)
+                    .push_str(content);
+                events.push(ChatCompletionStreamEvent::TextDelta {
+                    index,
+                    delta: content.clone(),
+                });
+            }
+            if let Some(refusal) = delta.refusal.as_ref().filter(|r| !r.is_empty()) {
+                accumulated
+                    .refusal
+                    .get_or_insert_with(String::new)
+                    .push_str(refusal);
+                events.push(ChatCompletionStreamEvent::RefusalDelta {
+                    index,
+                    delta: refusal.clone(),
+                });
+            }
+
+            if let Some(audio) = &delta.audio {
+                let accumulated = accumulated.audio.get_or_insert_with(Default::default);
+                if let Some(id) = &audio.id {
+                    accumulated.id.clone_from(id);
+                }
+                if let Some(expires_at) = audio.expires_at {
+                    accumulated.expires_at = expires_at;
+                }
+                if let Some(data) = audio.data.as_ref().filter(|d| !d.is_empty()) {
+                    accumulated.data.push_str(data);
+                    events.push(ChatCompletionStreamEvent::AudioDelta {
+                        index,
+                        data: data.clone(),
+                    });
+                }
+                if let Some(transcript) = audio.transcript.as_ref().filter(|t| !t.is_empty()) {
+                    accumulated.transcript.push_str(transcript);
+                    events.push(ChatCompletionStreamEvent::AudioTranscriptDelta {
+                        index,
+                        delta: transcript.clone(),
+                    });
+                }
+            }
+
+            for chunk in delta.tool_calls.iter().flatten() {
+                let position = chunk.index as usize;
+                if position >= accumulated.tool_calls.len() {
+                    accumulated.complete_tool_calls(index, position, &mut events);
+                    accumulated.tool_calls.resize_with(position + 1, || {
+                        ChatCompletionMessageToolCall {
+                            id: String::new(),
+                            r#type: ChatCompletionToolType::Function,
+                            function: FunctionCall {
+                                name: String::new(),
+                                arguments: String::new(),
+                            },
+                        }
+                    });
+                }
+
+                let tool_call = &mut accumulated.tool_calls[position];
+                if let Some(id) = &chunk.id {
+                    tool_call.id.push_str(id);
+                }
+                if let Some(function) = &chunk.function {
+                    if let Some(name) = &function.name {
+                        tool_call.function.name.push_str(name);
+                    }
+                    if let Some(arguments) = &function.arguments {
+                        tool_call.function.arguments.push_str(arguments);
+                    }
+                }
+            }
+
+            if let Some(reason) = choice.finish_reason {
+                let count = accumulated.tool_calls.len();
+                accumulated.complete_tool_calls(index, count, &mut events);
+                accumulated.finish_reason = Some(reason);
+                events.push(ChatCompletionStreamEvent::FinishReason { index, reason });
+            }
+        }
+
+        if let Some(usage) = &chunk.usage {
+            self.usage = Some(usage.clone());
+            events.push(ChatCompletionStreamEvent::Usage(usage.clone()));
+        }
+
+        events
+    }
+
+    /// The events at the end of the stream: tool calls not completed yet,
+    /// and the message of each choice.
+    pub fn finish(&mut self) -> Vec<ChatCompletionStreamEvent> {
+        let mut events = vec![];
+        for (index, choice) in self.choices.iter_mut().enumerate() {
+            let count = choice.tool_calls.len();
+            choice.complete_tool_calls(index as u32, count, &mut events);
+        }
+        events.extend(
+            self.messages()
+                .into_iter()
+                .enumerate()
+                .map(|(index, message)| ChatCompletionStreamEvent::Completed {
+                    index: index as u32,
+                    message,
+                }),
+        );
+        events
+    }
+
+    /// Token usage of the request, from the last chunk when `stream_options.include_usage` is set,
+    /// including cached and reasoning tokens.
+    pub fn usage(&self) -> Option<&CompletionUsage> {
+        self.usage.as_ref()
+    }
+
+    /// The response as assembled so far, with the messages and finish reasons of its choices
+    /// and its usage, `None` before the first chunk. Log probabilities are not kept.
+    pub fn response(&self) -> Option<CreateChatCompletionResponse> {
+        let first_chunk = self.first_chunk.as_ref()?;
+
+        let choices = self
+            .messages()
+            .into_iter()
+            .zip(&self.choices)
+            .enumerate()
+            .map(|(index, (message, choice))| ChatChoice {
+                index: index as u32,
+                message,
+                finish_reason: choice.finish_reason,
+                logprobs: None,
+                extra: Default::default(),
+            })
+            .collect();
+
+        Some(CreateChatCompletionResponse {
+            id: first_chunk.id.clone(),
+            choices,
+            created: first_chunk.created,
+            model: first_chunk.model.clone(),
+            service_tier: first_chunk.service_tier.clone(),
+            system_fingerprint: first_chunk.system_fingerprint.clone(),
+            object: "chat.completion".into(),
+            usage: self.usage.clone(),
+            extra: first_chunk.extra.clone(),
+        })
+    }
+
+    /// The messages of the choices as assembled so far, by index.
+    #[allow(deprecated)]
+    pub fn messages(&self) -> Vec<ChatCompletionResponseMessage> {
+        self.choices
+            .iter()
+            .map(|choice| ChatCompletionResponseMessage {
+                content: choice.content.clone(),
+                refusal: choice.refusal.clone(),
+                tool_calls: (!choice.tool_calls.is_empty()).then(|| choice.tool_calls.clone()),
+                role: choice.role.unwrap_or(Role::Assistant),
+                function_call: None,
+                audio: choice.audio.clone(),
+                extra: Default::default(),
+            })
+            .collect()
+    }
+}
+
+impl AccumulatedChoice {
+    /// Report the tool calls before `end` not reported yet as completed.
+    fn complete_tool_calls(
+        &mut self,
+        index: u32,
+        end: usize,
+        events: &mut Vec<ChatCompletionStreamEvent>,
+    ) {
+        let end = end.min(self.tool_calls.len());
+        for tool_call in self
+            .tool_calls
+            .get(self.completed_tool_calls..end)
+            .unwrap_or(&[])
+        {
+            events.push(ChatCompletionStreamEvent::ToolCallCompleted {
+                index,
+                tool_call: tool_call.clone(),
+            });
+        }
+        self.completed_tool_calls = self.completed_tool_calls.max(end);
+    }
+}
+
+impl ResponseAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The response as assembled so far, `None` before the `response.created` event.
+    pub fn response(&self) -> Option<&Response> {
+        self.response.as_ref()
+    }
+
+    pub fn into_response(self) -> Option<Response> {
+        self.response
+    }
+
+    /// Apply `event` to the response. Events of tool calls which only update their
+    /// status are ignored, the status is set by the `response.output_item.done` event.
+    pub fn apply(&mut self, event: &ResponseStreamEvent) {
+        match event {
+            ResponseStreamEvent::ResponseCreated(event)
+            | ResponseStreamEvent::ResponseInProgress(event) => {
+                let mut response = event.response.clone();
+                if let Some(previous) = self.response.take() {
+                    if response.output.is_empty() {
+                        response.output = previous.output;
+                    }
+                }
+                self.response = Some(response);
+            }
+            ResponseStreamEvent::ResponseCompleted(event)
+            | ResponseStreamEvent::ResponseIncomplete(event)
+            | ResponseStreamEvent::ResponseFailed(event) => {
+                self.response = Some(event.response.clone());
+            }
+            ResponseStreamEvent::ResponseOutputItemAdded(event)
+            | ResponseStreamEvent::ResponseOutputItemDone(event) => {
+                if let Some(response) = &mut self.response {
+                    set(&mut response.output, event.output_index, event.item.clone());
+                }
+            }
+            ResponseStreamEvent::ResponseContentPartAdded(event)
+            | ResponseStreamEvent::ResponseContentPartDone(event) => {
+                if let Some(Item::Message(message)) = self.item(event.output_index) {
+                    if let InputContent::Text(text) = &message.content {
+                        message.content = InputContent::Parts(if text.is_empty() {
+                            vec![]
+                        } else {
+                            vec![ContentPart::OutputText(OutputText {
+                                text: text.clone(),
+                                annotations: vec![],
+                            })]
+                        });
+                    }
+                    if let InputContent::Parts(parts) = &mut message.content {
+                        set(parts, event.content_index, event.part.clone());
+                    }
+                }
+            }
+            ResponseStreamEvent::ResponseOutputTextDelta(event) => {
+                if let Some(ContentPart::OutputText(text)) =
+                    self.part(event.output_index, event.content_index)
+                {
+                    text.text.push_str(&event.delta);
+                }
+            }
+            ResponseStreamEvent::ResponseOutputTextDone(event) => {
+                if let Some(ContentPart::OutputText(text)) =
+                    self.part(event.output_index, event.content_index)
+                {
+                    text.text.clone_from(&event.text);
+                }
+            }
+            ResponseStreamEvent::ResponseOutputTextAnnotationAdded(event) => {
+                if let Some(ContentPart::OutputText(text)) =
+                    self.part(event.output_index, event.content_index)
+                {
+                    set(
+                        &mut text.annotations,
+                        event.annotation_index,
+                        event.annotation.clone(),
+                    );
+                }
+            }
+            ResponseStreamEvent::ResponseRefusalDelta(event) => {
+                if let Some(ContentPart::Refusal(refusal)) =
+                    self.part(event.output_index, event.content_index)
+                {
+                    refusal.refusal.push_str(&event.delta);
+                }
+            }
+            ResponseStreamEvent::ResponseRefusalDone(event) => {
+                if let Some(ContentPart::Refusal(refusal)) =
+                    self.part(event.output_index, event.content_index)
+                {
+                    refusal.refusal.clone_from(&event.refusal);
+                }
+            }
+            ResponseStreamEvent::ResponseFunctionCallArgumentsDelta(event) => {
+                if let Some(Item::FunctionCall(call)) = self.item(event.output_index) {
+                    call.arguments.push_str(&event.delta);
+                }
+            }
+            ResponseStreamEvent::ResponseFunctionCallArgumentsDone(event) => {
+                if let Some(Item::FunctionCall(call)) = self.item(event.output_index) {
+                    call.arguments.clone_from(&event.arguments);
+                }
+            }
+            ResponseStreamEvent::ResponseMcpCallArgumentsDelta(event) => {
+                if let Some(Item::McpCall(call)) = self.item(event.output_index) {
+                    call.arguments.push_str(&event.delta);
+                }
+            }
+            ResponseStreamEvent::ResponseMcpCallArgumentsDone(event) => {
+                if let Some(Item::McpCall(call)) = self.item(event.output_index) {
+                    call.arguments.clone_from(&event.arguments);
+                }
+            }
+            ResponseStreamEvent::ResponseCodeInterpreterCallCodeDelta(event) => {
+                if let Some(Item::CodeInterpreterCall(call)) = self.item(event.output_index) {
+                    call.code
+                        .get_or_insert_with(String::new)
+                        .push_str(&event.delta);
+                }
+            }
+            ResponseStreamEvent::ResponseCodeInterpreterCallCodeDone(event) => {
+                if let Some(Item::CodeInterpreterCall(call)) = self.item(event.output_index) {
+                    call.code = Some(event.code.clone());
+                }
+            }
+            ResponseStreamEvent::ResponseReasoningSummaryPartAdded(event)
+            | ResponseStreamEvent::ResponseReasoningSummaryPartDone(event) => {
+                if let Some(Item::Reasoning(reasoning)) = self.item(event.output_index) {
+                    set(
+                        &mut reasoning.summary,
+                        event.summary_index,
+                        event.part.clone(),
+                    );
+                }
+            }
+            ResponseStreamEvent::ResponseReasoningSummaryTextDelta(event) => {
+                if let Some(SummaryPart::SummaryText(summary)) =
+                    self.summary(event.output_index, event.summary_index)
+                {
+                    summary.text.push_str(&event.delta);
+                }
+            }
+            ResponseStreamEvent::ResponseReasoningSummaryTextDone(event) => {
+                if let Some(SummaryPart::SummaryText(summary)) =
+                    self.summary(event.output_index, event.summary_index)
+                {
+                    summary.text.clone_from(&event.text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn item(&mut self, output_index: u32) -> Option<&mut Item> {
+        self.response
+            .as_mut()?
+            .output
+            .get_mut(output_index as usize)
+    }
+
+    fn part(&mut self, output_index: u32, content_index: u32) -> Option<&mut ContentPart> {
+        match self.item(output_index)? {
+            Item::Message(InputMessage {
+                content: InputContent::Parts(parts),
+                ..
+            }) => parts.get_mut(content_index as usize),
+            _ => None,
+        }
+    }
+
+    fn summary(&mut self, output_index: u32, summary_index: u32) -> Option<&mut SummaryPart> {
+        match self.item(output_index)? {
+            Item::Reasoning(reasoning) => reasoning.summary.get_mut(summary_index as usize),
+            _ => None,
+        }
+    }
+}
+
+/// Replace the element at `index`, or append it.
+fn set<T>(values: &mut Vec<T>, index: u32, value: T) {
+    match values.get_mut(index as usize) {
+        Some(current) => *current = value,
+        None => values.push(value),
+    }
+}
+
+// end: Display of message contents and deltas
+
+impl From<&CompletionUsage> for TokenUsage {
+    fn from(usage: &CompletionUsage) -> Self {
+        Self {
+            input_tokens: usage.prompt_tokens,
+            output_tokens: usage.completion_tokens,
+            total_tokens: usage.total_tokens,
+            cached_tokens: usage
+                .prompt_tokens_details
+                .as_ref()
+                .and_then(|details| details.cached_tokens),
+            reasoning_tokens: usage
+                .completion_tokens_details
+                .as_ref()
+                .and_then(|details| details.reasoning_tokens),
+        }
+    }
+}
+
+impl CompletionUsage {
+    /// Prompt tokens served from the [prompt cache](https://platform.openai.com/docs/guides/prompt-caching),
+    /// 0 when not reported.
+    pub fn cached_tokens(&self) -> u32 {
+        self.prompt_tokens_details
+            .as_ref()
+            .and_then(|details| details.cached_tokens)
+            .unwrap_or(0)
+    }
+
+    /// Completion tokens used for reasoning, 0 when not reported.
+    pub fn reasoning_tokens(&self) -> u32 {
+        self.completion_tokens_details
+            .as_ref()
+            .and_then(|details| details.reasoning_tokens)
+            .unwrap_or(0)
+    }
+}
+
+impl ResponseUsage {
+    /// Input tokens served from the [prompt cache](https://platform.openai.com/docs/guides/prompt-caching).
+    pub fn cached_tokens(&self) -> u32 {
+        self.input_tokens_details.cached_tokens
+    }
+
+    /// Output tokens used for reasoning.
+    pub fn reasoning_tokens(&self) -> u32 {
+        self.output_tokens_details.reasoning_tokens
+    }
+}
+
+impl CreateChatCompletionRequest {
+    /// Move the deprecated `max_tokens` to `max_completion_tokens` when the model is a reasoning
+    /// model, see [ModelId::is_reasoning], as reasoning models reject `max_tokens`.
+    /// `max_completion_tokens`, when set, is kept.
+    ///
+    /// Requests are sent as built: call this to opt in, e.g. for code written for older models.
+    #[allow(deprecated)]
+    pub fn map_max_tokens(&mut self) {
+        if !ModelId::from(self.model.as_str()).is_reasoning() {
+            return;
+        }
+        if let Some(max_tokens) = self.max_tokens.take() {
+            self.max_completion_tokens.get_or_insert(max_tokens);
+        }
+    }
+
+    /// Normalize the request so that requests sharing instructions and tools share the longest
+    /// possible prefix, which the [prompt cache](https://platform.openai.com/docs/guides/prompt-caching)
+    /// can serve:
+    /// - system and developer messages are moved before the other messages, keeping their order,
+    /// - tools are sorted by name,
+    /// - the keys of the JSON schemas of the tools are sorted.
+    ///
+    /// Serialization of requests is deterministic: fields are serialized in declaration order,
+    /// and the keys of JSON values in their order, so normalized requests with the same system
+    /// prompt and tools serialize to the same prefix.
+    pub fn normalize_for_prompt_cache(&mut self) {
+        // stable: keeps the order of the conversation
+        self.messages.sort_by_key(|message| {
+            !matches!(
+                message,
+                ChatCompletionRequestMessage::System(_)
+                    | ChatCompletionRequestMessage::Developer(_)
+            )
+        });
+
+        if let Some(tools) = &mut self.tools {
+            tools.sort_by(|a, b| a.function.name.cmp(&b.function.name));
+            for tool in tools {
+                if let Some(parameters) = &mut tool.function.parameters {
+                    sort_keys(parameters);
+                }
+            }
+        }
+    }
+}
+
+/// Sort the keys of the objects of `value`, recursively, whatever the order of `serde_json::Map`.
+fn sort_keys(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = std::mem::take(map).into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (key, mut value) in entries {
+                sort_keys(&mut value);
+                map.insert(key, value);
+            }
+        }
+        serde_json::Value::Array(values) => values.iter_mut().for_each(sort_keys),
+        _ => {}
+    }
+}
+
+impl From<&ResponseUsage> for TokenUsage {
+    fn from(usage: &ResponseUsage) -> Self {
+        Self {
+            input_tokens: usage.input_tokens,
+            output_tokens: usage.output_tokens,
+            total_tokens: usage.total_tokens,
+            cached_tokens: Some(usage.input_tokens_details.cached_tokens),
+            reasoning_tokens: Some(usage.output_tokens_details.reasoning_tokens),
+        }
+    }
+}
+
+impl ResponseMetadata {
+    pub(crate) fn from_response(
+        status: reqwest::StatusCode,
+        headers: &reqwest::header::HeaderMap,
+    ) -> Self {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+        let retry_after = header("retry-after-ms")
+            .and_then(|ms| ms.parse::<f64>().ok())
+            .map(|ms| ms / 1000.0)
+            .or_else(|| header("retry-after").and_then(|secs| secs.parse::<f64>().ok()))
+            .filter(|secs| secs.is_finite() && *secs >= 0.0)
+            .map(Duration::from_secs_f64);
+
+        Self {
+            status: status.as_u16(),
+            request_id: header("x-request-id").map(ToString::to_string),
+            retry_after,
+            rate_limit: RateLimitHeaders {
+                limit_requests: header("x-ratelimit-limit-requests").and_then(|v| v.parse().ok()),
+                limit_tokens: header("x-ratelimit-limit-tokens").and_then(|v| v.parse().ok()),
+                remaining_requests: header("x-ratelimit-remaining-requests")
+                    .and_then(|v| v.parse().ok()),
+                remaining_tokens: header("x-ratelimit-remaining-tokens")
+                    .and_then(|v| v.parse().ok()),
+                reset_requests: header("x-ratelimit-reset-requests").and_then(parse_reset_duration),
+                reset_tokens: header("x-ratelimit-reset-tokens").and_then(parse_reset_duration),
+            },
+        }
+    }
+}
+
+/// Parse durations of rate limit reset headers, e.g. `1s`, `6m0s`, `20ms` or `1h2m3.5s`
+fn parse_reset_duration(value: &str) -> Option<Duration> {
+    let mut seconds = 0.0;
+    let mut number = String::new();
+    let mut chars = value.trim().chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() || c == '.' {
+            number.push(c);
+            continue;
+        }
+
+        let n: f64 = number.parse().ok()?;
+        number.clear();
+
+        seconds += match c {
+            'h' => n * 3600.0,
+            'm' if chars.peek() == Some(&'s') => {
+                chars.next();
+                n / 1000.0
+            }
+            'm' => n * 60.0,
+            's' => n,
+            _ => return None,
+        };
+    }
+
+    // bare number of seconds
+    if !number.is_empty() {
+        seconds += number.parse::<f64>().ok()?;
+    }
+
+    (seconds.is_finite() && !value.trim().is_empty()).then(|| Duration::from_secs_f64(seconds))
+}
+
 // start: types to multipart from
 
 impl AsyncTryFrom<CreateTranscriptionRequest> for reqwest::multipart::Form {
@@ -860,6 +2146,10 @@ impl AsyncTryFrom<CreateTranscriptionRequest> for reqwest::multipart::Form {
             }
         }
 
+        if let Some(stream) = request.stream {
+            form = form.text("stream", stream.to_string());
+        }
+
         Ok(form)
     }
 }
@@ -986,4 +2276,19 @@ impl AsyncTryFrom<AddUploadPartRequest> for reqwest::multipart::Form {
     }
 }
 
+impl AsyncTryFrom<MultipartForm> for reqwest::multipart::Form {
+    type Error = OpenAIError;
+
+    async fn try_from(request: MultipartForm) -> Result<Self, Self::Error> {
+        let mut form = reqwest::multipart::Form::new();
+        for (name, value) in request.parts {
+            form = match value {
+                MultipartValue::Text(text) => form.text(name, text),
+                MultipartValue::File(source) => form.part(name, create_file_part(source).await?),
+            };
+        }
+        Ok(form)
+    }
+}
+
 // end: types to multipart form
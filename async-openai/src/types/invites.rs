@@ -13,6 +13,7 @@ pub enum InviteStatus {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "InviteRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option))]
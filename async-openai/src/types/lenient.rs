@@ -0,0 +1,37 @@
+//! Deserialization helpers used by the `lenient` feature.
+//!
+//! OpenAI compatible servers (Ollama, vLLM, Groq, ...) tend to deviate slightly from
+//! OpenAI's schema: a `finish_reason` of `"eos"`, a missing `object` or `created`, a tool
+//! call without `type`. With the `lenient` feature such values are replaced by `None`
+//! or a default instead of failing the whole response.
+use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+
+/// Deserialize an optional value, yielding `None` when the value is not understood,
+/// e.g. an enum variant unknown to this library.
+pub(crate) fn deserialize_or_none<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    if value.is_null() {
+        return Ok(None);
+    }
+
+    match serde_json::from_value(value.clone()) {
+        Ok(value) => Ok(Some(value)),
+        Err(e) => {
+            tracing::warn!("lenient: ignoring unrecognized value {value}: {e}");
+            Ok(None)
+        }
+    }
+}
+
+/// Deserialize a value, yielding `T::default()` when the value is not understood.
+pub(crate) fn deserialize_or_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned + Default,
+{
+    Ok(deserialize_or_none(deserializer)?.unwrap_or_default())
+}
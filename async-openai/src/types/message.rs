@@ -8,6 +8,7 @@ use crate::error::OpenAIError;
 use super::{ImageDetail, ImageUrl};
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum MessageRole {
     #[default]
@@ -82,6 +83,7 @@ pub struct MessageObject {
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MessageAttachment {
     /// The ID of the file to attach to the message.
     pub file_id: String,
@@ -90,6 +92,7 @@ pub struct MessageAttachment {
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 #[serde(rename_all = "snake_case")]
 pub enum MessageAttachmentTool {
@@ -170,11 +173,13 @@ pub struct FilePath {
 
 /// References an image [File](https://platform.openai.com/docs/api-reference/files) in the content of a message.
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MessageContentImageFileObject {
     pub image_file: ImageFile,
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ImageFile {
     /// The [File](https://platform.openai.com/docs/api-reference/files) ID of the image in the message content. Set `purpose="vision"` when uploading the File if you need to later display the file content.
     pub file_id: String,
@@ -184,17 +189,20 @@ pub struct ImageFile {
 
 /// References an image URL in the content of a message.
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MessageContentImageUrlObject {
     pub image_url: ImageUrl,
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MessageRequestContentTextObject {
     /// Text content to be sent to the model
     pub text: String,
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum CreateMessageRequestContent {
     /// The text contents of the message.
@@ -204,6 +212,7 @@ pub enum CreateMessageRequestContent {
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 #[serde(rename_all = "snake_case")]
 pub enum MessageContentInput {
@@ -212,6 +221,7 @@ pub enum MessageContentInput {
     ImageUrl(MessageContentImageUrlObject),
 }
 #[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "CreateMessageRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
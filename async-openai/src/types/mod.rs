@@ -6,14 +6,18 @@ mod assistant_stream;
 mod audio;
 mod audit_log;
 mod batch;
+mod builder;
 mod chat;
 mod common;
 mod completion;
+mod conversation;
 mod embedding;
 mod file;
 mod fine_tuning;
 mod image;
 mod invites;
+#[cfg(feature = "lenient")]
+mod lenient;
 mod message;
 mod model;
 mod moderation;
@@ -24,6 +28,7 @@ mod projects;
 #[cfg_attr(docsrs, doc(cfg(feature = "realtime")))]
 #[cfg(feature = "realtime")]
 pub mod realtime;
+mod response;
 mod run;
 mod step;
 mod thread;
@@ -36,9 +41,11 @@ pub use assistant_stream::*;
 pub use audio::*;
 pub use audit_log::*;
 pub use batch::*;
+pub use builder::*;
 pub use chat::*;
 pub use common::*;
 pub use completion::*;
+pub use conversation::*;
 pub use embedding::*;
 pub use file::*;
 pub use fine_tuning::*;
@@ -51,6 +58,7 @@ pub use project_api_key::*;
 pub use project_service_account::*;
 pub use project_users::*;
 pub use projects::*;
+pub use response::*;
 pub use run::*;
 pub use step::*;
 pub use thread::*;
@@ -59,6 +67,7 @@ pub use users::*;
 pub use vector_store::*;
 
 mod impls;
+pub(crate) mod validate;
 use derive_builder::UninitializedFieldError;
 
 use crate::error::OpenAIError;
@@ -2,19 +2,37 @@ use serde::{Deserialize, Serialize};
 
 /// Describes an OpenAI model offering that can be used with the API.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct Model {
     /// The model identifier, which can be referenced in the API endpoints.
     pub id: String,
     /// The object type, which is always "model".
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub object: String,
     /// The Unix timestamp (in seconds) when the model was created.
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub created: u32,
     /// The organization that owns the model.
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub owned_by: String,
+
+    /// Fields which are not part of OpenAI's schema, e.g. added by OpenAI compatible servers.
+    /// Empty unless the `extra-fields` feature is enabled.
+    #[cfg_attr(feature = "extra-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "extra-fields"), serde(skip))]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct ListModelResponse {
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub object: String,
     pub data: Vec<Model>,
 }
@@ -25,3 +43,265 @@ pub struct DeleteModelResponse {
     pub object: String,
     pub deleted: bool,
 }
+
+/// Kind of input or output of a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Modality {
+    Text,
+    Image,
+    Audio,
+    /// Output of embedding models.
+    Embedding,
+}
+
+/// Limits and capabilities of a model, see [ModelId::info].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelInfo {
+    /// Maximum number of tokens of the prompt and the output together.
+    pub context_window: u32,
+    /// Maximum number of output tokens, including reasoning tokens. Zero for embedding models.
+    pub max_output_tokens: u32,
+    pub input_modalities: &'static [Modality],
+    pub output_modalities: &'static [Modality],
+}
+
+impl ModelInfo {
+    pub fn supports_input(&self, modality: Modality) -> bool {
+        self.input_modalities.contains(&modality)
+    }
+
+    pub fn supports_output(&self, modality: Modality) -> bool {
+        self.output_modalities.contains(&modality)
+    }
+}
+
+/// Identifier of a model: one of the well-known OpenAI models, which have a [ModelInfo], or any other model.
+///
+/// Converts from and into the `String` of the `model` field of requests:
+///
+/// ```
+/// use async_openai::types::{CreateChatCompletionRequestArgs, ModelId};
+///
+/// let request = CreateChatCompletionRequestArgs::default()
+///     .model(ModelId::Gpt4oMini)
+///     .build()
+///     .unwrap();
+///
+/// let model = ModelId::from(request.model.as_str());
+/// assert_eq!(model.context_window(), Some(128_000));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ModelId {
+    #[serde(rename = "gpt-5")]
+    Gpt5,
+    #[serde(rename = "gpt-5-mini")]
+    Gpt5Mini,
+    #[serde(rename = "gpt-5-nano")]
+    Gpt5Nano,
+    #[serde(rename = "gpt-4.1")]
+    Gpt41,
+    #[serde(rename = "gpt-4.1-mini")]
+    Gpt41Mini,
+    #[serde(rename = "gpt-4.1-nano")]
+    Gpt41Nano,
+    #[serde(rename = "gpt-4o")]
+    Gpt4o,
+    #[serde(rename = "gpt-4o-mini")]
+    Gpt4oMini,
+    #[serde(rename = "gpt-4o-audio-preview")]
+    Gpt4oAudioPreview,
+    #[serde(rename = "gpt-4-turbo")]
+    Gpt4Turbo,
+    #[serde(rename = "gpt-4")]
+    Gpt4,
+    #[serde(rename = "gpt-3.5-turbo")]
+    Gpt35Turbo,
+    #[serde(rename = "o1")]
+    O1,
+    #[serde(rename = "o1-mini")]
+    O1Mini,
+    #[serde(rename = "o3")]
+    O3,
+    #[serde(rename = "o3-mini")]
+    O3Mini,
+    #[serde(rename = "o4-mini")]
+    O4Mini,
+    #[serde(rename = "text-embedding-3-small")]
+    TextEmbedding3Small,
+    #[serde(rename = "text-embedding-3-large")]
+    TextEmbedding3Large,
+    #[serde(rename = "text-embedding-ada-002")]
+    TextEmbeddingAda002,
+    #[serde(untagged)]
+    Other(String),
+}
+
+const TEXT: &[Modality] = &[Modality::Text];
+const TEXT_IMAGE: &[Modality] = &[Modality::Text, Modality::Image];
+const TEXT_AUDIO: &[Modality] = &[Modality::Text, Modality::Audio];
+const EMBEDDING: &[Modality] = &[Modality::Embedding];
+
+impl ModelId {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ModelId::Gpt5 => "gpt-5",
+            ModelId::Gpt5Mini => "gpt-5-mini",
+            ModelId::Gpt5Nano => "gpt-5-nano",
+            ModelId::Gpt41 => "gpt-4.1",
+            ModelId::Gpt41Mini => "gpt-4.1-mini",
+            ModelId::Gpt41Nano => "gpt-4.1-nano",
+            ModelId::Gpt4o => "gpt-4o",
+            ModelId::Gpt4oMini => "gpt-4o-mini",
+            ModelId::Gpt4oAudioPreview => "gpt-4o-audio-preview",
+            ModelId::Gpt4Turbo => "gpt-4-turbo",
+            ModelId::Gpt4 => "gpt-4",
+            ModelId::Gpt35Turbo => "gpt-3.5-turbo",
+            ModelId::O1 => "o1",
+            ModelId::O1Mini => "o1-mini",
+            ModelId::O3 => "o3",
+            ModelId::O3Mini => "o3-mini",
+            ModelId::O4Mini => "o4-mini",
+            ModelId::TextEmbedding3Small => "text-embedding-3-small",
+            ModelId::TextEmbedding3Large => "text-embedding-3-large",
+            ModelId::TextEmbeddingAda002 => "text-embedding-ada-002",
+            ModelId::Other(model) => model,
+        }
+    }
+
+    /// Limits and capabilities of well-known models. Dated snapshots, e.g. `gpt-4o-2024-08-06`,
+    /// have the info of their model. `None` for other models.
+    pub fn info(&self) -> Option<ModelInfo> {
+        let (context_window, max_output_tokens, input_modalities, output_modalities) = match self {
+            ModelId::Gpt5 => (400_000, 128_000, TEXT_IMAGE, TEXT),
+            ModelId::Gpt5Mini => (400_000, 128_000, TEXT_IMAGE, TEXT),
+            ModelId::Gpt5Nano => (400_000, 128_000, TEXT_IMAGE, TEXT),
+            ModelId::Gpt41 => (1_047_576, 32_768, TEXT_IMAGE, TEXT),
+            ModelId::Gpt41Mini => (1_047_576, 32_768, TEXT_IMAGE, TEXT),
+            ModelId::Gpt41Nano => (1_047_576, 32_768, TEXT_IMAGE, TEXT),
+            ModelId::Gpt4o => (128_000, 16_384, TEXT_IMAGE, TEXT),
+            ModelId::Gpt4oMini => (128_000, 16_384, TEXT_IMAGE, TEXT),
+            ModelId::Gpt4oAudioPreview => (128_000, 16_384, TEXT_AUDIO, TEXT_AUDIO),
+            ModelId::Gpt4Turbo => (128_000, 4_096, TEXT_IMAGE, TEXT),
+            ModelId::Gpt4 => (8_192, 8_192, TEXT, TEXT),
+            ModelId::Gpt35Turbo => (16_385, 4_096, TEXT, TEXT),
+            ModelId::O1 => (200_000, 100_000, TEXT_IMAGE, TEXT),
+            ModelId::O1Mini => (128_000, 65_536, TEXT, TEXT),
+            ModelId::O3 => (200_000, 100_000, TEXT_IMAGE, TEXT),
+            ModelId::O3Mini => (200_000, 100_000, TEXT, TEXT),
+            ModelId::O4Mini => (200_000, 100_000, TEXT_IMAGE, TEXT),
+            ModelId::TextEmbedding3Small => (8_192, 0, TEXT, EMBEDDING),
+            ModelId::TextEmbedding3Large => (8_192, 0, TEXT, EMBEDDING),
+            ModelId::TextEmbeddingAda002 => (8_192, 0, TEXT, EMBEDDING),
+            ModelId::Other(_) => return self.snapshot_of()?.info(),
+        };
+
+        Some(ModelInfo {
+            context_window,
+            max_output_tokens,
+            input_modalities,
+            output_modalities,
+        })
+    }
+
+    /// See [ModelInfo::context_window].
+    pub fn context_window(&self) -> Option<u32> {
+        self.info().map(|info| info.context_window)
+    }
+
+    /// See [ModelInfo::max_output_tokens].
+    pub fn max_output_tokens(&self) -> Option<u32> {
+        self.info().map(|info| info.max_output_tokens)
+    }
+
+    /// Whether this is a well-known [reasoning model](https://platform.openai.com/docs/guides/reasoning),
+    /// of the o-series or GPT-5, or a snapshot of one. Reasoning models take `max_completion_tokens`
+    /// instead of `max_tokens`, and `reasoning_effort`.
+    pub fn is_reasoning(&self) -> bool {
+        match self {
+            ModelId::Gpt5
+            | ModelId::Gpt5Mini
+            | ModelId::Gpt5Nano
+            | ModelId::O1
+            | ModelId::O1Mini
+            | ModelId::O3
+            | ModelId::O3Mini
+            | ModelId::O4Mini => true,
+            ModelId::Other(_) => self.snapshot_of().is_some_and(|model| model.is_reasoning()),
+            _ => false,
+        }
+    }
+}
+
+impl ModelId {
+    /// The well-known model of a dated snapshot, e.g. [ModelId::Gpt4o] for `gpt-4o-2024-08-06`.
+    pub(crate) fn snapshot_of(&self) -> Option<ModelId> {
+        match ModelId::from(strip_date(self.as_str())?) {
+            ModelId::Other(_) => None,
+            model => Some(model),
+        }
+    }
+}
+
+/// `gpt-4o-2024-08-06` -> `gpt-4o`
+fn strip_date(model: &str) -> Option<&str> {
+    let split = model.len().checked_sub(11)?;
+    let date = model.get(split..)?;
+    let is_date = date.bytes().enumerate().all(|(i, b)| match i {
+        0 | 5 | 8 => b == b'-',
+        _ => b.is_ascii_digit(),
+    });
+
+    is_date.then(|| &model[..split])
+}
+
+impl From<&str> for ModelId {
+    fn from(model: &str) -> Self {
+        match model {
+            "gpt-5" => ModelId::Gpt5,
+            "gpt-5-mini" => ModelId::Gpt5Mini,
+            "gpt-5-nano" => ModelId::Gpt5Nano,
+            "gpt-4.1" => ModelId::Gpt41,
+            "gpt-4.1-mini" => ModelId::Gpt41Mini,
+            "gpt-4.1-nano" => ModelId::Gpt41Nano,
+            "gpt-4o" => ModelId::Gpt4o,
+            "gpt-4o-mini" => ModelId::Gpt4oMini,
+            "gpt-4o-audio-preview" => ModelId::Gpt4oAudioPreview,
+            "gpt-4-turbo" => ModelId::Gpt4Turbo,
+            "gpt-4" => ModelId::Gpt4,
+            "gpt-3.5-turbo" => ModelId::Gpt35Turbo,
+            "o1" => ModelId::O1,
+            "o1-mini" => ModelId::O1Mini,
+            "o3" => ModelId::O3,
+            "o3-mini" => ModelId::O3Mini,
+            "o4-mini" => ModelId::O4Mini,
+            "text-embedding-3-small" => ModelId::TextEmbedding3Small,
+            "text-embedding-3-large" => ModelId::TextEmbedding3Large,
+            "text-embedding-ada-002" => ModelId::TextEmbeddingAda002,
+            model => ModelId::Other(model.to_string()),
+        }
+    }
+}
+
+impl From<String> for ModelId {
+    fn from(model: String) -> Self {
+        match ModelId::from(model.as_str()) {
+            ModelId::Other(_) => ModelId::Other(model),
+            known => known,
+        }
+    }
+}
+
+impl From<ModelId> for String {
+    fn from(model: ModelId) -> Self {
+        match model {
+            ModelId::Other(model) => model,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for ModelId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
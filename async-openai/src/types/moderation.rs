@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use crate::error::OpenAIError;
 
 #[derive(Debug, Serialize, Clone, PartialEq, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum ModerationInput {
     /// A single string of text to classify for moderation
@@ -18,6 +19,7 @@ pub enum ModerationInput {
 
 /// Content part for multi-modal moderation input
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum ModerationContentPart {
     /// An object describing text to classify
@@ -37,12 +39,14 @@ pub enum ModerationContentPart {
 
 /// Image URL configuration for image moderation
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ModerationImageUrl {
     /// Either a URL of the image or the base64 encoded image data
     pub url: String,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Builder, PartialEq, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "CreateModerationRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -156,6 +160,10 @@ pub struct ContentModerationResult {
 
 /// Represents if a given text input is potentially harmful.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct CreateModerationResponse {
     /// The unique identifier for the moderation request.
     pub id: String,
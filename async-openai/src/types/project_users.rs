@@ -21,6 +21,7 @@ pub struct ProjectUser {
 
 /// `owner` or `member`
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ProjectUserRole {
     Owner,
@@ -33,11 +34,12 @@ pub struct ProjectUserListResponse {
     pub data: Vec<ProjectUser>,
     pub first_id: String,
     pub last_id: String,
-    pub has_more: String,
+    pub has_more: bool,
 }
 
 /// The project user create request payload.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "ProjectUserCreateRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option))]
@@ -51,6 +53,7 @@ pub struct ProjectUserCreateRequest {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "ProjectUserUpdateRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option))]
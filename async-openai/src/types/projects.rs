@@ -34,11 +34,12 @@ pub struct ProjectListResponse {
     pub data: Vec<Project>,
     pub first_id: String,
     pub last_id: String,
-    pub has_more: String,
+    pub has_more: bool,
 }
 
 /// The project create request payload.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "ProjectCreateRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option))]
@@ -51,6 +52,7 @@ pub struct ProjectCreateRequest {
 
 /// The project update request payload.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "ProjectUpdateRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option))]
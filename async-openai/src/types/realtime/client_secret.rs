@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+
+use super::session_resource::{AudioFormat, SessionResource, TurnDetection};
+
+/// Ephemeral key, to authenticate browser and mobile clients to the Realtime API
+/// instead of a standard API key.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClientSecret {
+    /// Ephemeral key usable in client environments.
+    pub value: String,
+    /// Timestamp for when the key expires, one minute after its creation by default.
+    pub expires_at: u64,
+}
+
+/// Configuration of the session to create, with its ephemeral key.
+pub type CreateRealtimeSessionRequest = SessionResource;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RealtimeSessionCreateResponse {
+    /// Unique identifier of the session.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// The object type, always `realtime.session`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object: Option<String>,
+
+    /// Ephemeral key returned by the API.
+    pub client_secret: ClientSecret,
+
+    /// Configuration of the session.
+    #[serde(flatten)]
+    pub session: SessionResource,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct InputAudioTranscription {
+    /// The model to use for transcription: `gpt-4o-transcribe`, `gpt-4o-mini-transcribe` or `whisper-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// The language of the input audio in ISO-639-1 format, e.g. `en`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+
+    /// Text to guide the style of the model, or to continue a previous audio segment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum NoiseReductionType {
+    /// For close-talking microphones such as headphones.
+    NearField,
+    /// For far-field microphones such as laptop or conference room microphones.
+    FarField,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InputAudioNoiseReduction {
+    pub r#type: NoiseReductionType,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CreateTranscriptionSessionRequest {
+    /// The set of modalities the model can respond with. To disable audio, set this to ["text"].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modalities: Option<Vec<String>>,
+
+    /// The format of input audio. Options are "pcm16", "g711_ulaw", or "g711_alaw".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_audio_format: Option<AudioFormat>,
+
+    /// Configuration for input audio transcription.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_audio_transcription: Option<InputAudioTranscription>,
+
+    /// Configuration for turn detection. Can be set to null to turn off.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub turn_detection: Option<TurnDetection>,
+
+    /// Configuration for noise reduction of the input audio, before it is sent to VAD and the model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_audio_noise_reduction: Option<InputAudioNoiseReduction>,
+
+    /// Items to include in the transcription, e.g. `item.input_audio_transcription.logprobs`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TranscriptionSessionCreateResponse {
+    /// Ephemeral key returned by the API.
+    pub client_secret: ClientSecret,
+
+    /// The set of modalities the model can respond with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modalities: Option<Vec<String>>,
+
+    /// The format of input audio.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_audio_format: Option<AudioFormat>,
+
+    /// Configuration of the transcription model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_audio_transcription: Option<InputAudioTranscription>,
+
+    /// Configuration for turn detection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub turn_detection: Option<TurnDetection>,
+}
@@ -1,4 +1,5 @@
 mod client_event;
+mod client_secret;
 mod content_part;
 mod conversation;
 mod error;
@@ -9,6 +10,7 @@ mod server_event;
 mod session_resource;
 
 pub use client_event::*;
+pub use client_secret::*;
 pub use content_part::*;
 pub use conversation::*;
 pub use error::*;
@@ -0,0 +1,1888 @@
+use std::collections::HashMap;
+
+use crate::{error::OpenAIError, runtime::BoxStream};
+use base64::{engine::general_purpose, Engine as _};
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use super::{validate, ImageDetail, ReasoningEffort, ServiceTier, ServiceTierResponse};
+
+/// An image input to the model, given by exactly one of `image_url` and `file_id`.
+/// Learn about [image inputs](https://platform.openai.com/docs/guides/vision).
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[builder(name = "InputImageArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
+pub struct InputImage {
+    /// The detail level of the image to be sent to the model. One of `high`, `low`, or `auto`. Defaults to `auto`.
+    #[serde(default)]
+    pub detail: ImageDetail,
+    /// The ID of the file to be sent to the model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<String>,
+    /// The URL of the image to be sent to the model. A fully qualified URL or base64 encoded image in a data URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
+}
+
+impl InputImage {
+    /// Image at `url`, which can also be a base64 encoded data URL.
+    pub fn from_url<S: Into<String>>(url: S) -> Self {
+        Self {
+            detail: ImageDetail::default(),
+            file_id: None,
+            image_url: Some(url.into()),
+        }
+    }
+
+    /// Image uploaded as the file `file_id`.
+    pub fn from_file_id<S: Into<String>>(file_id: S) -> Self {
+        Self {
+            detail: ImageDetail::default(),
+            file_id: Some(file_id.into()),
+            image_url: None,
+        }
+    }
+
+    /// Image with the content `bytes`, sent as a base64 encoded data URL.
+    /// `mime_type` is the format of the image, e.g. `image/png` or `image/jpeg`.
+    pub fn from_bytes(mime_type: &str, bytes: impl AsRef<[u8]>) -> Self {
+        Self::from_url(data_url(mime_type, bytes.as_ref()))
+    }
+
+    pub fn with_detail(mut self, detail: ImageDetail) -> Self {
+        self.detail = detail;
+        self
+    }
+}
+
+impl InputImageArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        validate::exactly_one(&[
+            ("image_url", validate::is_set(&self.image_url)),
+            ("file_id", validate::is_set(&self.file_id)),
+        ])
+    }
+}
+
+/// A file input to the model, given either by `file_id`, or by `filename` and `file_data`.
+/// Learn about [file inputs](https://platform.openai.com/docs/guides/pdf-files).
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[builder(name = "InputFileArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
+pub struct InputFile {
+    /// The ID of the file to be sent to the model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<String>,
+    /// The name of the file to be sent to the model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+    /// The content of the file to be sent to the model, as a base64 encoded data URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_data: Option<String>,
+}
+
+impl InputFile {
+    /// File uploaded as `file_id`.
+    pub fn from_file_id<S: Into<String>>(file_id: S) -> Self {
+        Self {
+            file_id: Some(file_id.into()),
+            filename: None,
+            file_data: None,
+        }
+    }
+
+    /// File `filename` with the content `file_data`, a base64 encoded data URL,
+    /// e.g. `data:application/pdf;base64,...`.
+    pub fn from_data<F: Into<String>, D: Into<String>>(filename: F, file_data: D) -> Self {
+        Self {
+            file_id: None,
+            filename: Some(filename.into()),
+            file_data: Some(file_data.into()),
+        }
+    }
+
+    /// File `filename` with the content `bytes`, sent as a base64 encoded data URL.
+    /// `mime_type` is the format of the file, e.g. `application/pdf`.
+    pub fn from_bytes<F: Into<String>>(
+        filename: F,
+        mime_type: &str,
+        bytes: impl AsRef<[u8]>,
+    ) -> Self {
+        Self::from_data(filename, data_url(mime_type, bytes.as_ref()))
+    }
+}
+
+fn data_url(mime_type: &str, bytes: &[u8]) -> String {
+    format!(
+        "data:{mime_type};base64,{}",
+        general_purpose::STANDARD.encode(bytes)
+    )
+}
+
+impl InputFileArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        let file_id = validate::is_set(&self.file_id);
+        let filename = validate::is_set(&self.filename);
+        let file_data = validate::is_set(&self.file_data);
+
+        if filename != file_data {
+            return Err(OpenAIError::InvalidArgument(
+                "filename and file_data must be set together".into(),
+            ));
+        }
+
+        validate::exactly_one(&[("file_id", file_id), ("filename and file_data", file_data)])
+    }
+}
+
+/// Text, image or file inputs to the model, used to generate a response.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum Input {
+    /// A text input to the model, equivalent to a text input with the `user` role.
+    Text(String),
+    /// A list of one or many input items to the model, containing different content types.
+    Items(Vec<Item>),
+}
+
+/// An item of the context of a response: an input of the model or an output it generated.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Item {
+    /// A message input to the model, or output by it.
+    Message(InputMessage),
+    /// A call to a function defined by you.
+    FunctionCall(FunctionToolCall),
+    /// The output of a function call, given back to the model.
+    FunctionCallOutput(FunctionCallOutput),
+    /// A call to the file search tool.
+    FileSearchCall(FileSearchToolCall),
+    /// A call to the web search tool.
+    WebSearchCall(WebSearchToolCall),
+    /// A call to the computer use tool.
+    ComputerCall(ComputerToolCall),
+    /// A call to the code interpreter tool.
+    CodeInterpreterCall(CodeInterpreterToolCall),
+    /// The tools listed by an MCP server.
+    McpListTools(McpListTools),
+    /// A call to a tool of an MCP server.
+    McpCall(McpToolCall),
+    /// A call to the image generation tool.
+    ImageGenerationCall(ImageGenerationToolCall),
+    /// A call to run a command on the local shell.
+    LocalShellCall(LocalShellToolCall),
+    /// The output of a local shell call, given back to the model.
+    LocalShellCallOutput(LocalShellCallOutput),
+    /// A request for your approval of a call to a tool of an MCP server.
+    McpApprovalRequest(McpApprovalRequest),
+    /// Your approval, or denial, of a call to a tool of an MCP server, given back to the model.
+    McpApprovalResponse(McpApprovalResponse),
+    /// The output of a computer call, given back to the model.
+    ComputerCallOutput(ComputerCallOutput),
+    /// The chain of thought of a reasoning model.
+    Reasoning(Reasoning),
+    /// A reference to an item of a previous response, by its ID.
+    ItemReference(ItemReference),
+}
+
+/// The role of a message. Instructions given with the `developer` or `system` role
+/// take precedence over instructions given with the `user` role.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum InputRole {
+    #[default]
+    User,
+    Assistant,
+    System,
+    Developer,
+}
+
+/// Status of an item, populated when items are returned via the API.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ItemStatus {
+    InProgress,
+    Completed,
+    Incomplete,
+}
+
+/// A message with a role, input to the model or output by it.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[builder(name = "InputMessageArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct InputMessage {
+    /// The unique ID of the message, populated when messages are returned via the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub role: InputRole,
+    pub content: InputContent,
+    /// The status of the message, populated when messages are returned via the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ItemStatus>,
+}
+
+/// Content of a message: a text, or a list of content parts of different types.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum InputContent {
+    Text(String),
+    Parts(Vec<ContentPart>),
+}
+
+/// A part of the content of a message.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    /// A text input to the model.
+    InputText(InputText),
+    /// An image input to the model.
+    InputImage(InputImage),
+    /// A file input to the model.
+    InputFile(InputFile),
+    /// A text output from the model.
+    OutputText(OutputText),
+    /// A refusal from the model.
+    Refusal(Refusal),
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct InputText {
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OutputText {
+    /// The text output from the model.
+    pub text: String,
+    /// The annotations of the text output, e.g. citations of files or URLs.
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+}
+
+/// An annotation of a text output of the model, e.g. a citation of a source.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Annotation {
+    /// A citation of a web page, e.g. found by web search.
+    UrlCitation(UrlCitationAnnotation),
+    /// A citation of a file, e.g. found by file search.
+    FileCitation(FileCitationAnnotation),
+    /// A path to a file, e.g. generated by code interpreter.
+    FilePath(FilePathAnnotation),
+    /// A citation of a file in a container, e.g. created by code interpreter.
+    ContainerFileCitation(ContainerFileCitationAnnotation),
+    /// Annotations of other types.
+    #[serde(untagged)]
+    Other(serde_json::Value),
+}
+
+/// A citation of a file in a text output.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct FileCitationAnnotation {
+    /// The ID of the file.
+    pub file_id: String,
+    /// The name of the file.
+    pub filename: String,
+    /// The index of the citation in the text.
+    pub index: u32,
+}
+
+/// A path to a file in a text output.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct FilePathAnnotation {
+    /// The ID of the file.
+    pub file_id: String,
+    /// The index of the path in the text.
+    pub index: u32,
+}
+
+/// A citation of a file in a container in a text output.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ContainerFileCitationAnnotation {
+    /// The ID of the container.
+    pub container_id: String,
+    /// The ID of the file.
+    pub file_id: String,
+    /// The name of the file.
+    pub filename: String,
+    /// The index of the first character of the citation in the text.
+    pub start_index: u32,
+    /// The index of the last character of the citation in the text.
+    pub end_index: u32,
+}
+
+/// A citation of a web page in a text output.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct UrlCitationAnnotation {
+    /// The URL of the web page.
+    pub url: String,
+    /// The title of the web page.
+    pub title: String,
+    /// The index of the first character of the citation in the text.
+    pub start_index: u32,
+    /// The index of the last character of the citation in the text.
+    pub end_index: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Refusal {
+    /// The refusal explanation from the model.
+    pub refusal: String,
+}
+
+/// A call to a function defined by you, see [function calling](https://platform.openai.com/docs/guides/function-calling).
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct FunctionToolCall {
+    /// The unique ID of the function call item.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The unique ID of the function call generated by the model,
+    /// referenced by the output of the call.
+    pub call_id: String,
+    /// The name of the function to call.
+    pub name: String,
+    /// The arguments to call the function with, as a JSON string.
+    pub arguments: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ItemStatus>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct FunctionCallOutput {
+    /// The unique ID of the function call output item.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The `call_id` of the function call.
+    pub call_id: String,
+    /// The output of the function call, usually a JSON string.
+    pub output: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ItemStatus>,
+}
+
+/// Status of a call to a built-in tool.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ToolCallStatus {
+    InProgress,
+    Searching,
+    Interpreting,
+    Generating,
+    Completed,
+    Incomplete,
+    Failed,
+}
+
+/// A call to the file search tool, see [file search](https://platform.openai.com/docs/guides/tools-file-search).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct FileSearchToolCall {
+    /// The unique ID of the file search call.
+    pub id: String,
+    /// The queries used to search for files.
+    pub queries: Vec<String>,
+    pub status: ToolCallStatus,
+    /// The results of the file search call, included when requested with `include`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub results: Option<Vec<FileSearchResult>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct FileSearchResult {
+    /// The unique ID of the file.
+    pub file_id: String,
+    /// The name of the file.
+    pub filename: String,
+    /// The relevance score of the file, between 0 and 1.
+    pub score: f32,
+    /// The text that was retrieved from the file.
+    pub text: String,
+    /// Key-value pairs attached to the file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attributes: Option<HashMap<String, serde_json::Value>>,
+}
+
+/// A call to the web search tool, see [web search](https://platform.openai.com/docs/guides/tools-web-search).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WebSearchToolCall {
+    /// The unique ID of the web search call.
+    pub id: String,
+    /// The action of the call, e.g. a search and its sources.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<WebSearchAction>,
+    pub status: ToolCallStatus,
+}
+
+/// An action of a web search call.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WebSearchAction {
+    /// Search the web for `query`.
+    Search {
+        query: String,
+        /// The sources used in the search, included with [Include::WebSearchCallActionSources].
+        #[serde(skip_serializing_if = "Option::is_none")]
+        sources: Option<Vec<WebSearchSource>>,
+    },
+    /// Open the web page at `url`, e.g. a search result.
+    OpenPage { url: String },
+    /// Search for `pattern` within the web page at `url`.
+    Find { url: String, pattern: String },
+}
+
+/// A source of a web search.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WebSearchSource {
+    Url { url: String },
+}
+
+/// A call to the code interpreter tool, see [code interpreter](https://platform.openai.com/docs/guides/tools-code-interpreter).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CodeInterpreterToolCall {
+    /// The unique ID of the code interpreter call.
+    pub id: String,
+    /// The ID of the container the code ran in.
+    pub container_id: String,
+    /// The code run, if any.
+    pub code: Option<String>,
+    /// The outputs of the code, included when requested with `include`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outputs: Option<Vec<CodeInterpreterCallOutput>>,
+    pub status: ToolCallStatus,
+}
+
+/// An output of code run by the code interpreter.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CodeInterpreterCallOutput {
+    /// The logs of the code.
+    Logs { logs: String },
+    /// An image output by the code.
+    Image { url: String },
+    /// Files written by the code.
+    Files { files: Vec<CodeInterpreterFile> },
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CodeInterpreterFile {
+    /// The ID of the file, to download it from its container.
+    pub file_id: String,
+    /// The MIME type of the file.
+    pub mime_type: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct McpListTools {
+    /// The unique ID of the list.
+    pub id: String,
+    /// The label of the MCP server.
+    pub server_label: String,
+    /// The tools available on the server.
+    pub tools: Vec<McpToolInfo>,
+    /// The error, if the server could not list its tools.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct McpToolInfo {
+    /// The name of the tool.
+    pub name: String,
+    /// The description of the tool.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The JSON schema describing the input of the tool.
+    pub input_schema: serde_json::Value,
+    /// Additional annotations about the tool.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct McpToolCall {
+    /// The unique ID of the tool call.
+    pub id: String,
+    /// The label of the MCP server running the tool.
+    pub server_label: String,
+    /// The name of the tool.
+    pub name: String,
+    /// The arguments passed to the tool, as a JSON string.
+    pub arguments: String,
+    /// The output of the tool call.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    /// The error of the tool call, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct McpApprovalRequest {
+    /// The unique ID of the approval request, referenced by its response.
+    pub id: String,
+    /// The label of the MCP server making the request.
+    pub server_label: String,
+    /// The name of the tool to run.
+    pub name: String,
+    /// The arguments for the tool, as a JSON string.
+    pub arguments: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct McpApprovalResponse {
+    /// The unique ID of the approval response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The ID of the approval request being answered.
+    pub approval_request_id: String,
+    /// Whether the request was approved.
+    pub approve: bool,
+    /// The reason for the decision.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// A call to the image generation tool, see [image generation](https://platform.openai.com/docs/guides/tools-image-generation).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ImageGenerationToolCall {
+    /// The unique ID of the image generation call.
+    pub id: String,
+    /// The generated image, base64 encoded.
+    pub result: Option<String>,
+    pub status: ToolCallStatus,
+}
+
+/// A call to run a command on the local shell, see [local shell](https://platform.openai.com/docs/guides/tools-local-shell).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct LocalShellToolCall {
+    /// The unique ID of the local shell call.
+    pub id: String,
+    /// The unique ID of the local shell call generated by the model.
+    pub call_id: String,
+    pub action: LocalShellAction,
+    pub status: ItemStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LocalShellAction {
+    /// Execute a command.
+    Exec(LocalShellExecAction),
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct LocalShellExecAction {
+    /// The command to run, as program and arguments.
+    pub command: Vec<String>,
+    /// Environment variables to set for the command.
+    pub env: HashMap<String, String>,
+    /// Timeout for the command, in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// The user to run the command as.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// The working directory to run the command in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_directory: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct LocalShellCallOutput {
+    /// The `id` of the local shell call.
+    pub id: String,
+    /// The output of the command, e.g. its combined stdout and stderr.
+    pub output: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ItemStatus>,
+}
+
+/// A call to the computer use tool, see [computer use](https://platform.openai.com/docs/guides/tools-computer-use).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ComputerToolCall {
+    /// The unique ID of the computer call.
+    pub id: String,
+    /// An identifier used when responding to the tool call with output.
+    pub call_id: String,
+    pub action: ComputerAction,
+    /// The pending safety checks for the computer call.
+    pub pending_safety_checks: Vec<SafetyCheck>,
+    pub status: ItemStatus,
+}
+
+/// An action to perform on the computer.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ComputerAction {
+    Click {
+        /// One of `left`, `right`, `wheel`, `back` or `forward`.
+        button: String,
+        x: i32,
+        y: i32,
+    },
+    DoubleClick {
+        x: i32,
+        y: i32,
+    },
+    Drag {
+        /// The path of the drag, from the start to the end point.
+        path: Vec<Coordinate>,
+    },
+    Keypress {
+        /// The combination of keys to press, e.g. `["CTRL", "C"]`.
+        keys: Vec<String>,
+    },
+    Move {
+        x: i32,
+        y: i32,
+    },
+    Screenshot,
+    Scroll {
+        x: i32,
+        y: i32,
+        scroll_x: i32,
+        scroll_y: i32,
+    },
+    Type {
+        text: String,
+    },
+    Wait,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Coordinate {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// A safety check of a computer call, to be acknowledged in its output to proceed.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SafetyCheck {
+    pub id: String,
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ComputerCallOutput {
+    /// The unique ID of the computer call output item.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The `call_id` of the computer call.
+    pub call_id: String,
+    /// A screenshot of the computer after the action.
+    pub output: ComputerScreenshot,
+    /// The safety checks of the computer call that were acknowledged.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acknowledged_safety_checks: Option<Vec<SafetyCheck>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ItemStatus>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename = "computer_screenshot")]
+pub struct ComputerScreenshot {
+    /// The URL of the screenshot image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
+    /// The ID of an uploaded file containing the screenshot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<String>,
+}
+
+/// The chain of thought of a reasoning model, to be given back to it in the next turns
+/// of a conversation when not using `previous_response_id`.
+///
+/// When responses are not stored, e.g. with zero data retention, the reasoning can only be
+/// given back encrypted: request it with [Include::ReasoningEncryptedContent].
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Reasoning {
+    /// The unique ID of the reasoning item.
+    pub id: String,
+    /// A summary of the reasoning, when requested with `reasoning.summary`.
+    pub summary: Vec<SummaryPart>,
+    /// The encrypted reasoning, included when requested with `include`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encrypted_content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ItemStatus>,
+}
+
+/// A part of a reasoning summary.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SummaryPart {
+    SummaryText(SummaryText),
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SummaryText {
+    /// A short summary of the reasoning used by the model.
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ItemReference {
+    /// The ID of the item to reference.
+    pub id: String,
+}
+
+/// A tool the model may call while generating a response.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Tool {
+    /// A function defined by you, see [function calling](https://platform.openai.com/docs/guides/function-calling).
+    Function(FunctionTool),
+    /// Search the contents of uploaded files, see [file search](https://platform.openai.com/docs/guides/tools-file-search).
+    FileSearch(FileSearchTool),
+    /// Search the Internet for sources related to the prompt, see [web search](https://platform.openai.com/docs/guides/tools-web-search).
+    WebSearch(WebSearchTool),
+    /// The preview version of the web search tool.
+    WebSearchPreview(WebSearchPreviewTool),
+    /// Control a virtual computer, see [computer use](https://platform.openai.com/docs/guides/tools-computer-use).
+    ComputerUsePreview(ComputerUsePreviewTool),
+    /// Run Python code in a sandboxed container, see [code interpreter](https://platform.openai.com/docs/guides/tools-code-interpreter).
+    CodeInterpreter(CodeInterpreterTool),
+    /// Call the tools of a remote [MCP](https://modelcontextprotocol.io) server, see [remote MCP](https://platform.openai.com/docs/guides/tools-remote-mcp).
+    Mcp(McpTool),
+    /// Generate or edit images with `gpt-image-1`, see [image generation](https://platform.openai.com/docs/guides/tools-image-generation).
+    ImageGeneration(ImageGenerationTool),
+    /// Run shell commands on your machine, see [local shell](https://platform.openai.com/docs/guides/tools-local-shell).
+    LocalShell,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[builder(name = "FunctionToolArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct FunctionTool {
+    /// The name of the function to call.
+    pub name: String,
+    /// A description of the function, used by the model to determine whether or not to call the function.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// A JSON schema object describing the parameters of the function.
+    pub parameters: serde_json::Value,
+    /// Whether to enforce strict parameter validation. Default `true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct FileSearchTool {
+    /// The IDs of the vector stores to search.
+    pub vector_store_ids: Vec<String>,
+    /// The maximum number of results to return. This number should be between 1 and 50 inclusive.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_num_results: Option<u32>,
+    /// A filter to apply based on file attributes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filters: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WebSearchTool {
+    /// Restrict the search to some domains.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filters: Option<WebSearchFilters>,
+    /// High level guidance for the amount of context window space to use for the search.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_context_size: Option<WebSearchContextSize>,
+    /// Approximate location of the user, to refine search results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_location: Option<WebSearchUserLocation>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WebSearchPreviewTool {
+    /// High level guidance for the amount of context window space to use for the search.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_context_size: Option<WebSearchContextSize>,
+    /// Approximate location of the user, to refine search results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_location: Option<WebSearchUserLocation>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WebSearchFilters {
+    /// Domains to search, up to 20, e.g. `openai.com`. Subdomains are included.
+    pub allowed_domains: Vec<String>,
+}
+
+/// Approximate location of the user of a web search.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename = "approximate")]
+pub struct WebSearchUserLocation {
+    /// Free text input for the city of the user, e.g. `San Francisco`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    /// The two-letter [ISO country code](https://en.wikipedia.org/wiki/ISO_3166-1) of the user, e.g. `US`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// Free text input for the region of the user, e.g. `California`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// The [IANA timezone](https://timeapi.io/documentation/iana-timezones) of the user, e.g. `America/Los_Angeles`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum WebSearchContextSize {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ComputerUsePreviewTool {
+    /// The width of the computer display.
+    pub display_width: u32,
+    /// The height of the computer display.
+    pub display_height: u32,
+    /// The type of computer environment to control, e.g. `browser`, `mac`, `windows` or `ubuntu`.
+    pub environment: String,
+}
+
+/// Additional output data to include in a response.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum Include {
+    /// The search results of file search calls.
+    #[serde(rename = "file_search_call.results")]
+    FileSearchCallResults,
+    /// The image URLs of input images of messages.
+    #[serde(rename = "message.input_image.image_url")]
+    MessageInputImageImageUrl,
+    /// The image URLs of the screenshots of computer call outputs.
+    #[serde(rename = "computer_call_output.output.image_url")]
+    ComputerCallOutputOutputImageUrl,
+    /// The encrypted reasoning of reasoning items, to use them in the next turns of a
+    /// conversation when the response is not stored, e.g. with zero data retention.
+    #[serde(rename = "reasoning.encrypted_content")]
+    ReasoningEncryptedContent,
+    /// The log probabilities of the tokens of output text.
+    #[serde(rename = "message.output_text.logprobs")]
+    MessageOutputTextLogprobs,
+    /// The outputs of code interpreter calls.
+    #[serde(rename = "code_interpreter_call.outputs")]
+    CodeInterpreterCallOutputs,
+    /// The sources of the searches of web search calls.
+    #[serde(rename = "web_search_call.action.sources")]
+    WebSearchCallActionSources,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CodeInterpreterTool {
+    /// The container to run the code in.
+    pub container: CodeInterpreterContainer,
+}
+
+/// The container of the code interpreter: an existing container, or a new one.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum CodeInterpreterContainer {
+    /// The ID of an existing container.
+    Id(String),
+    /// A new container, created or reused automatically.
+    Auto(AutoContainer),
+}
+
+impl Default for CodeInterpreterContainer {
+    fn default() -> Self {
+        Self::Auto(AutoContainer::default())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename = "auto")]
+pub struct AutoContainer {
+    /// The IDs of uploaded files to make available to the code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[builder(name = "McpToolArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct McpTool {
+    /// A label for the MCP server, used to identify it in tool calls.
+    pub server_label: String,
+    /// The URL of the MCP server.
+    pub server_url: String,
+    /// HTTP headers to send to the MCP server, e.g. for authentication.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<HashMap<String, String>>,
+    /// The tools of the server the model may call, all of them when not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_tools: Option<McpAllowedTools>,
+    /// Which tools require an approval before being called, all of them when not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_approval: Option<McpRequireApproval>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum McpAllowedTools {
+    /// The names of the allowed tools.
+    List(Vec<String>),
+    Filter(McpToolFilter),
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct McpToolFilter {
+    /// The names of the tools.
+    pub tool_names: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum McpRequireApproval {
+    /// Whether all the tools require an approval.
+    Setting(McpApprovalSetting),
+    /// The tools which always require an approval, and those which never do.
+    Filter(McpApprovalFilter),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum McpApprovalSetting {
+    Always,
+    Never,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct McpApprovalFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub always: Option<McpToolFilter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub never: Option<McpToolFilter>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[builder(name = "ImageGenerationToolArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ImageGenerationTool {
+    /// The image generation model to use. Default `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// The size of the generated image. Default `auto`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<ImageGenerationSize>,
+    /// The quality of the generated image. Default `auto`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<ImageGenerationQuality>,
+    /// The format of the generated image. Default `png`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<ImageGenerationOutputFormat>,
+    /// Compression level of `jpeg` and `webp` images, between 0 and 100. Default 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_compression: Option<u8>,
+    /// The background of the generated image. Default `auto`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<ImageGenerationBackground>,
+    /// The moderation level of the generated image. Default `auto`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moderation: Option<ImageGenerationModeration>,
+    /// The number of partial images to stream, between 0 and 3. Default 0.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial_images: Option<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ImageGenerationSize {
+    #[serde(rename = "auto")]
+    Auto,
+    #[serde(rename = "1024x1024")]
+    S1024x1024,
+    #[serde(rename = "1536x1024")]
+    S1536x1024,
+    #[serde(rename = "1024x1536")]
+    S1024x1536,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ImageGenerationQuality {
+    Auto,
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ImageGenerationOutputFormat {
+    Png,
+    Webp,
+    Jpeg,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ImageGenerationBackground {
+    Auto,
+    Transparent,
+    Opaque,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ImageGenerationModeration {
+    Auto,
+    Low,
+}
+
+/// A reference to a [prompt template](https://platform.openai.com/docs/guides/text#reusable-prompts)
+/// created in the dashboard, and the values of its variables.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[builder(name = "ResponsePromptArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ResponsePrompt {
+    /// The unique ID of the prompt template.
+    pub id: String,
+    /// The version of the prompt template, the current version by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Values substituted for the variables of the prompt template, by name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables: Option<HashMap<String, PromptVariable>>,
+}
+
+/// The value of a variable of a prompt template: a text, or an input content part
+/// like an image or a file.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum PromptVariable {
+    Text(String),
+    Content(ContentPart),
+}
+
+/// Configuration of the text output of the model.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TextConfig {
+    /// The format of the text output.
+    pub format: TextFormat,
+}
+
+/// The format of the text output of the model.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TextFormat {
+    /// Plain text, the default.
+    #[default]
+    Text,
+    /// JSON, the older JSON mode. The model must be instructed to produce JSON.
+    JsonObject,
+    /// JSON conforming to a schema, see [Structured Outputs](https://platform.openai.com/docs/guides/structured-outputs).
+    JsonSchema(TextJsonSchema),
+}
+
+/// A JSON Schema the text output conforms to. Unlike the `response_format` of chat
+/// completions, its fields are given alongside `type` in [TextFormat].
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TextJsonSchema {
+    /// The name of the format. Must be a-z, A-Z, 0-9, or contain underscores and dashes,
+    /// with a maximum length of 64.
+    pub name: String,
+    /// The schema of the output, described as a JSON Schema object.
+    pub schema: serde_json::Value,
+    /// A description of what the format is for, used by the model to determine how to respond.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether to enable strict schema adherence. Only a subset of JSON Schema is supported
+    /// when `strict` is true, see [crate::schema::strict].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+}
+
+/// Configuration options for [reasoning models](https://platform.openai.com/docs/guides/reasoning).
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ReasoningConfig {
+    /// Constrains effort on reasoning for reasoning models.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effort: Option<ReasoningEffort>,
+    /// A summary of the reasoning performed by the model, useful for debugging and understanding the model's reasoning process.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<ReasoningSummary>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningSummary {
+    Auto,
+    Concise,
+    Detailed,
+}
+
+/// The truncation strategy to use for the model response.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Truncation {
+    /// If the context of this response and previous ones exceeds the model's context window,
+    /// the model truncates the response by dropping input items in the middle of the conversation.
+    Auto,
+    /// If a model response will exceed the context window size for a model, the request fails with a 400 error.
+    Disabled,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[builder(name = "CreateResponseArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct CreateResponse {
+    /// Model ID used to generate the response, like `gpt-4o` or `o3`.
+    pub model: String,
+
+    /// Text, image, or file inputs to the model, used to generate a response.
+    pub input: Input,
+
+    /// Whether to run the model response in the background, for long-running requests
+    /// of reasoning models. Requires `store`, poll the response with
+    /// [crate::Responses::wait_for_completion] or [crate::Responses::retrieve].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<bool>,
+
+    /// Additional output data to include in the response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include: Option<Vec<Include>>,
+
+    /// Inserts a system (or developer) message as the first item in the model's context.
+    ///
+    /// When using along with `previous_response_id`, the instructions from a previous response
+    /// will not be carried over to the next response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+
+    /// An upper bound for the number of tokens that can be generated for a response,
+    /// including visible output tokens and reasoning tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+
+    /// Set of 16 key-value pairs that can be attached to an object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+
+    /// Whether to allow the model to run tool calls in parallel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallel_tool_calls: Option<bool>,
+
+    /// The unique ID of the previous response to the model. Use this to create multi-turn conversations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_response_id: Option<String>,
+
+    /// A prompt template created in the dashboard, with values for its variables, used
+    /// instead of inlining `instructions`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<ResponsePrompt>,
+
+    /// The conversation the response belongs to, see [crate::Conversations]. Its items are
+    /// prepended to `input`, and the input and output items of the response are added to it.
+    /// Cannot be used together with `previous_response_id`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conversation: Option<String>,
+
+    /// Configuration options for reasoning models.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<ReasoningConfig>,
+
+    /// The latency tier to use for processing the request, e.g. `flex` for lower prices
+    /// with slower responses. Defaults to the tier configured in the project settings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<ServiceTier>,
+
+    /// Whether to store the generated model response for later retrieval via API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+
+    /// If set to true, the model response data will be streamed to the client as it is
+    /// generated using server-sent events. Use [crate::Responses::create_stream].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+
+    /// What sampling temperature to use, between 0 and 2.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+
+    /// Configuration of the text output of the model, e.g. JSON conforming to a schema
+    /// with [Structured Outputs](https://platform.openai.com/docs/guides/structured-outputs).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<TextConfig>,
+
+    /// An array of tools the model may call while generating a response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Tool>>,
+
+    /// An alternative to sampling with temperature, called nucleus sampling.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+
+    /// The truncation strategy to use for the model response. Defaults to `disabled`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncation: Option<Truncation>,
+
+    /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+
+/// A response generated by the model.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "deny-unknown-fields", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
+pub struct Response {
+    /// Unique identifier for this response.
+    pub id: String,
+    /// The object type of this resource, always `response`.
+    pub object: String,
+    /// Unix timestamp (in seconds) of when this response was created.
+    pub created_at: u64,
+    /// The status of the response generation.
+    pub status: ResponseStatus,
+    /// Whether the response was run in the background.
+    pub background: Option<bool>,
+    /// The conversation the response belongs to.
+    pub conversation: Option<ResponseConversation>,
+    /// The error of a failed response.
+    pub error: Option<ResponseError>,
+    /// Why the response is incomplete.
+    pub incomplete_details: Option<IncompleteDetails>,
+    /// The system (or developer) message inserted as the first item in the model's context.
+    pub instructions: Option<String>,
+    /// An upper bound for the number of tokens that can be generated for the response.
+    pub max_output_tokens: Option<u32>,
+    /// Set of 16 key-value pairs attached to the response.
+    pub metadata: Option<HashMap<String, String>>,
+    /// Model ID used to generate the response.
+    pub model: String,
+    /// The content items generated by the model.
+    pub output: Vec<Item>,
+    /// Whether the model was allowed to run tool calls in parallel.
+    pub parallel_tool_calls: Option<bool>,
+    /// The unique ID of the previous response to the model.
+    pub previous_response_id: Option<String>,
+    /// The prompt template used, with the values of its variables.
+    pub prompt: Option<ResponsePrompt>,
+    /// Configuration options of reasoning models.
+    pub reasoning: Option<ReasoningConfig>,
+    /// The service tier used for processing the request.
+    pub service_tier: Option<ServiceTierResponse>,
+    /// Whether the response is stored for later retrieval via API.
+    pub store: Option<bool>,
+    /// The sampling temperature used.
+    pub temperature: Option<f32>,
+    /// Configuration of the text output of the model.
+    pub text: Option<TextConfig>,
+    /// The tools the model was allowed to call.
+    pub tools: Option<Vec<Tool>>,
+    /// The nucleus sampling parameter used.
+    pub top_p: Option<f32>,
+    /// The truncation strategy used.
+    pub truncation: Option<Truncation>,
+    /// Token usage of the response, including reasoning tokens.
+    pub usage: Option<ResponseUsage>,
+    /// The identifier of the end-user given in the request.
+    pub user: Option<String>,
+
+    /// Fields which are not part of OpenAI's schema, e.g. added by OpenAI compatible servers.
+    /// Empty unless the `extra-fields` feature is enabled.
+    #[cfg_attr(feature = "extra-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "extra-fields"), serde(skip))]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// The status of the response generation.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseStatus {
+    Completed,
+    Failed,
+    InProgress,
+    Incomplete,
+    Cancelled,
+    Queued,
+}
+
+impl ResponseStatus {
+    /// Whether the response is still being generated, i.e. `queued` or `in_progress`.
+    pub fn is_pending(&self) -> bool {
+        matches!(self, ResponseStatus::Queued | ResponseStatus::InProgress)
+    }
+}
+
+/// The error returned when the model fails to generate a response.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseError {
+    /// The error code, e.g. `server_error` or `rate_limit_exceeded`.
+    pub code: String,
+    /// A human-readable description of the error.
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct IncompleteDetails {
+    /// The reason why the response is incomplete, `max_output_tokens` or `content_filter`.
+    pub reason: String,
+}
+
+/// Token usage of a response.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ResponseUsage {
+    /// The number of input tokens.
+    pub input_tokens: u32,
+    /// A detailed breakdown of the input tokens.
+    pub input_tokens_details: InputTokensDetails,
+    /// The number of output tokens.
+    pub output_tokens: u32,
+    /// A detailed breakdown of the output tokens.
+    pub output_tokens_details: OutputTokensDetails,
+    /// The total number of tokens used.
+    pub total_tokens: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct InputTokensDetails {
+    /// The number of tokens retrieved from the [prompt cache](https://platform.openai.com/docs/guides/prompt-caching).
+    pub cached_tokens: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct OutputTokensDetails {
+    /// The number of reasoning tokens.
+    pub reasoning_tokens: u32,
+}
+
+/// The conversation of a [Response].
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct ResponseConversation {
+    /// The unique ID of the conversation.
+    pub id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct DeleteResponse {
+    pub id: String,
+    pub object: String,
+    pub deleted: bool,
+}
+
+/// A list of the input items of a response.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseItemList {
+    pub object: String,
+    pub data: Vec<Item>,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+    pub has_more: bool,
+}
+
+/// An event of a streamed response, see [streaming](https://platform.openai.com/docs/api-reference/responses-streaming).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type")]
+#[non_exhaustive]
+pub enum ResponseStreamEvent {
+    /// The response was created.
+    #[serde(rename = "response.created")]
+    ResponseCreated(ResponseEvent),
+    /// The response is in progress.
+    #[serde(rename = "response.in_progress")]
+    ResponseInProgress(ResponseEvent),
+    /// The response is complete.
+    #[serde(rename = "response.completed")]
+    ResponseCompleted(ResponseEvent),
+    /// The response failed.
+    #[serde(rename = "response.failed")]
+    ResponseFailed(ResponseEvent),
+    /// The response finished as incomplete, e.g. when `max_output_tokens` was reached.
+    #[serde(rename = "response.incomplete")]
+    ResponseIncomplete(ResponseEvent),
+    /// A new output item was added.
+    #[serde(rename = "response.output_item.added")]
+    ResponseOutputItemAdded(ResponseOutputItemEvent),
+    /// An output item is complete.
+    #[serde(rename = "response.output_item.done")]
+    ResponseOutputItemDone(ResponseOutputItemEvent),
+    /// A new content part was added to an output item.
+    #[serde(rename = "response.content_part.added")]
+    ResponseContentPartAdded(ResponseContentPartEvent),
+    /// A content part is complete.
+    #[serde(rename = "response.content_part.done")]
+    ResponseContentPartDone(ResponseContentPartEvent),
+    /// A delta of text output.
+    #[serde(rename = "response.output_text.delta")]
+    ResponseOutputTextDelta(ResponseTextDeltaEvent),
+    /// A text output is complete.
+    #[serde(rename = "response.output_text.done")]
+    ResponseOutputTextDone(ResponseTextDoneEvent),
+    /// An annotation was added to a text output.
+    #[serde(rename = "response.output_text.annotation.added")]
+    ResponseOutputTextAnnotationAdded(ResponseTextAnnotationEvent),
+    /// A delta of refusal text.
+    #[serde(rename = "response.refusal.delta")]
+    ResponseRefusalDelta(ResponseRefusalDeltaEvent),
+    /// A refusal text is complete.
+    #[serde(rename = "response.refusal.done")]
+    ResponseRefusalDone(ResponseRefusalDoneEvent),
+    /// A delta of the arguments of a function call.
+    #[serde(rename = "response.function_call_arguments.delta")]
+    ResponseFunctionCallArgumentsDelta(ResponseFunctionCallArgumentsDeltaEvent),
+    /// The arguments of a function call are complete.
+    #[serde(rename = "response.function_call_arguments.done")]
+    ResponseFunctionCallArgumentsDone(ResponseFunctionCallArgumentsDoneEvent),
+    /// A file search call was initiated.
+    #[serde(rename = "response.file_search_call.in_progress")]
+    ResponseFileSearchCallInProgress(ResponseToolCallEvent),
+    /// A file search call is searching.
+    #[serde(rename = "response.file_search_call.searching")]
+    ResponseFileSearchCallSearching(ResponseToolCallEvent),
+    /// A file search call is complete, with results.
+    #[serde(rename = "response.file_search_call.completed")]
+    ResponseFileSearchCallCompleted(ResponseToolCallEvent),
+    /// A web search call was initiated.
+    #[serde(rename = "response.web_search_call.in_progress")]
+    ResponseWebSearchCallInProgress(ResponseToolCallEvent),
+    /// A web search call is searching.
+    #[serde(rename = "response.web_search_call.searching")]
+    ResponseWebSearchCallSearching(ResponseToolCallEvent),
+    /// A web search call is complete.
+    #[serde(rename = "response.web_search_call.completed")]
+    ResponseWebSearchCallCompleted(ResponseToolCallEvent),
+    /// A code interpreter call was initiated.
+    #[serde(rename = "response.code_interpreter_call.in_progress")]
+    ResponseCodeInterpreterCallInProgress(ResponseToolCallEvent),
+    /// A code interpreter call is running code.
+    #[serde(rename = "response.code_interpreter_call.interpreting")]
+    ResponseCodeInterpreterCallInterpreting(ResponseToolCallEvent),
+    /// A code interpreter call is complete.
+    #[serde(rename = "response.code_interpreter_call.completed")]
+    ResponseCodeInterpreterCallCompleted(ResponseToolCallEvent),
+    /// A delta of the code of a code interpreter call.
+    #[serde(rename = "response.code_interpreter_call_code.delta")]
+    ResponseCodeInterpreterCallCodeDelta(ResponseCodeInterpreterCallCodeDeltaEvent),
+    /// The code of a code interpreter call is complete.
+    #[serde(rename = "response.code_interpreter_call_code.done")]
+    ResponseCodeInterpreterCallCodeDone(ResponseCodeInterpreterCallCodeDoneEvent),
+    /// An MCP tool call was initiated.
+    #[serde(rename = "response.mcp_call.in_progress")]
+    ResponseMcpCallInProgress(ResponseToolCallEvent),
+    /// An MCP tool call is complete.
+    #[serde(rename = "response.mcp_call.completed")]
+    ResponseMcpCallCompleted(ResponseToolCallEvent),
+    /// An MCP tool call failed.
+    #[serde(rename = "response.mcp_call.failed")]
+    ResponseMcpCallFailed(ResponseToolCallEvent),
+    /// A delta of the arguments of an MCP tool call.
+    #[serde(rename = "response.mcp_call_arguments.delta")]
+    ResponseMcpCallArgumentsDelta(ResponseFunctionCallArgumentsDeltaEvent),
+    /// The arguments of an MCP tool call are complete.
+    #[serde(rename = "response.mcp_call_arguments.done")]
+    ResponseMcpCallArgumentsDone(ResponseFunctionCallArgumentsDoneEvent),
+    /// The tools of an MCP server are being listed.
+    #[serde(rename = "response.mcp_list_tools.in_progress")]
+    ResponseMcpListToolsInProgress(ResponseToolCallEvent),
+    /// The tools of an MCP server are listed.
+    #[serde(rename = "response.mcp_list_tools.completed")]
+    ResponseMcpListToolsCompleted(ResponseToolCallEvent),
+    /// The tools of an MCP server could not be listed.
+    #[serde(rename = "response.mcp_list_tools.failed")]
+    ResponseMcpListToolsFailed(ResponseToolCallEvent),
+    /// An image generation call was initiated.
+    #[serde(rename = "response.image_generation_call.in_progress")]
+    ResponseImageGenerationCallInProgress(ResponseToolCallEvent),
+    /// An image generation call is generating the image.
+    #[serde(rename = "response.image_generation_call.generating")]
+    ResponseImageGenerationCallGenerating(ResponseToolCallEvent),
+    /// An image generation call is complete.
+    #[serde(rename = "response.image_generation_call.completed")]
+    ResponseImageGenerationCallCompleted(ResponseToolCallEvent),
+    /// A partial image is available while the image is generated.
+    #[serde(rename = "response.image_generation_call.partial_image")]
+    ResponseImageGenerationCallPartialImage(ResponseImageGenerationCallPartialImageEvent),
+    /// A new part was added to a reasoning summary.
+    #[serde(rename = "response.reasoning_summary_part.added")]
+    ResponseReasoningSummaryPartAdded(ResponseReasoningSummaryPartEvent),
+    /// A part of a reasoning summary is complete.
+    #[serde(rename = "response.reasoning_summary_part.done")]
+    ResponseReasoningSummaryPartDone(ResponseReasoningSummaryPartEvent),
+    /// A delta of reasoning summary text.
+    #[serde(rename = "response.reasoning_summary_text.delta")]
+    ResponseReasoningSummaryTextDelta(ResponseReasoningSummaryTextDeltaEvent),
+    /// A reasoning summary text is complete.
+    #[serde(rename = "response.reasoning_summary_text.done")]
+    ResponseReasoningSummaryTextDone(ResponseReasoningSummaryTextDoneEvent),
+    /// An error occurred.
+    #[serde(rename = "error")]
+    Error(ResponseErrorEvent),
+}
+
+/// An event carrying the response in its current state.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseEvent {
+    /// The sequence number of this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    pub response: Response,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseTextDeltaEvent {
+    /// The sequence number of this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    /// The ID of the output item that the text delta was added to.
+    pub item_id: String,
+    /// The index of the output item that the text delta was added to.
+    pub output_index: u32,
+    /// The index of the content part that the text delta was added to.
+    pub content_index: u32,
+    /// The text delta that was added.
+    pub delta: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseTextDoneEvent {
+    /// The sequence number of this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    /// The ID of the output item that the text content is finalized.
+    pub item_id: String,
+    /// The index of the output item that the text content is finalized.
+    pub output_index: u32,
+    /// The index of the content part that the text content is finalized.
+    pub content_index: u32,
+    /// The text content that is finalized.
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseOutputItemEvent {
+    /// The sequence number of this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    /// The index of the output item.
+    pub output_index: u32,
+    /// The output item, in its state when it was added or completed.
+    pub item: Item,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseContentPartEvent {
+    /// The sequence number of this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    /// The ID of the output item of the content part.
+    pub item_id: String,
+    /// The index of the output item of the content part.
+    pub output_index: u32,
+    /// The index of the content part.
+    pub content_index: u32,
+    /// The content part, in its state when it was added or completed.
+    pub part: ContentPart,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseTextAnnotationEvent {
+    /// The sequence number of this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    /// The ID of the output item that the annotation was added to.
+    pub item_id: String,
+    /// The index of the output item that the annotation was added to.
+    pub output_index: u32,
+    /// The index of the content part that the annotation was added to.
+    pub content_index: u32,
+    /// The index of the annotation in the text output.
+    pub annotation_index: u32,
+    /// The annotation, e.g. a file or URL citation.
+    pub annotation: Annotation,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseRefusalDeltaEvent {
+    /// The sequence number of this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    /// The ID of the output item that the refusal text was added to.
+    pub item_id: String,
+    /// The index of the output item that the refusal text was added to.
+    pub output_index: u32,
+    /// The index of the content part that the refusal text was added to.
+    pub content_index: u32,
+    /// The refusal text that was added.
+    pub delta: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseRefusalDoneEvent {
+    /// The sequence number of this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    /// The ID of the output item that the refusal text is finalized.
+    pub item_id: String,
+    /// The index of the output item that the refusal text is finalized.
+    pub output_index: u32,
+    /// The index of the content part that the refusal text is finalized.
+    pub content_index: u32,
+    /// The refusal text that is finalized.
+    pub refusal: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseFunctionCallArgumentsDeltaEvent {
+    /// The sequence number of this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    /// The ID of the function call item that the arguments delta was added to.
+    pub item_id: String,
+    /// The index of the function call item that the arguments delta was added to.
+    pub output_index: u32,
+    /// The arguments delta that was added.
+    pub delta: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseFunctionCallArgumentsDoneEvent {
+    /// The sequence number of this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    /// The ID of the function call item.
+    pub item_id: String,
+    /// The index of the function call item.
+    pub output_index: u32,
+    /// The function call arguments, a JSON string.
+    pub arguments: String,
+}
+
+/// Progress of a built-in tool call, e.g. a file search or a web search.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseToolCallEvent {
+    /// The sequence number of this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    /// The ID of the tool call item.
+    pub item_id: String,
+    /// The index of the tool call item.
+    pub output_index: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseCodeInterpreterCallCodeDeltaEvent {
+    /// The sequence number of this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    /// The ID of the code interpreter call item.
+    pub item_id: String,
+    /// The index of the code interpreter call item.
+    pub output_index: u32,
+    /// The code delta that was added.
+    pub delta: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseCodeInterpreterCallCodeDoneEvent {
+    /// The sequence number of this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    /// The ID of the code interpreter call item.
+    pub item_id: String,
+    /// The index of the code interpreter call item.
+    pub output_index: u32,
+    /// The code that is finalized.
+    pub code: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseImageGenerationCallPartialImageEvent {
+    /// The sequence number of this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    /// The ID of the image generation call item.
+    pub item_id: String,
+    /// The index of the image generation call item.
+    pub output_index: u32,
+    /// The index of the partial image, starting at 0.
+    pub partial_image_index: u32,
+    /// The partial image, base64 encoded.
+    pub partial_image_b64: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseReasoningSummaryPartEvent {
+    /// The sequence number of this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    /// The ID of the reasoning item of the summary part.
+    pub item_id: String,
+    /// The index of the reasoning item of the summary part.
+    pub output_index: u32,
+    /// The index of the summary part in the reasoning summary.
+    pub summary_index: u32,
+    /// The summary part, in its state when it was added or completed.
+    pub part: SummaryPart,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseReasoningSummaryTextDeltaEvent {
+    /// The sequence number of this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    /// The ID of the reasoning item that the summary text delta was added to.
+    pub item_id: String,
+    /// The index of the reasoning item that the summary text delta was added to.
+    pub output_index: u32,
+    /// The index of the summary part that the summary text delta was added to.
+    pub summary_index: u32,
+    /// The summary text delta that was added.
+    pub delta: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseReasoningSummaryTextDoneEvent {
+    /// The sequence number of this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    /// The ID of the reasoning item of the summary text.
+    pub item_id: String,
+    /// The index of the reasoning item of the summary text.
+    pub output_index: u32,
+    /// The index of the summary part of the summary text.
+    pub summary_index: u32,
+    /// The summary text that is finalized.
+    pub text: String,
+}
+
+/// An error which occurred while streaming the response.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseErrorEvent {
+    /// The sequence number of this event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    /// The error code.
+    pub code: Option<String>,
+    /// The error message.
+    pub message: String,
+    /// The parameter the error relates to.
+    pub param: Option<String>,
+}
+
+/// Parsed server side events stream of a response, ending with
+/// [ResponseStreamEvent::ResponseCompleted], [ResponseStreamEvent::ResponseIncomplete]
+/// or [ResponseStreamEvent::ResponseFailed].
+pub type ResponseStream = BoxStream<'static, Result<ResponseStreamEvent, OpenAIError>>;
+
+/// Assembles a [Response] from the events of a [ResponseStream], applying their deltas,
+/// e.g. to render the response while it is generated and keep the final object.
+///
+/// The final `response.completed`, `response.incomplete` or `response.failed` event replaces
+/// the assembled response with the one it carries, identical to the response of
+/// [crate::Responses::create]. See also [crate::traits::ResponseStreamExt::collect_final].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ResponseAccumulator {
+    pub(crate) response: Option<Response>,
+}
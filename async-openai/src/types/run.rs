@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use crate::{error::OpenAIError, types::FunctionCall};
 
 use super::{
-    AssistantTools, AssistantsApiResponseFormatOption, AssistantsApiToolChoiceOption,
+    validate, AssistantTools, AssistantsApiResponseFormatOption, AssistantsApiToolChoiceOption,
     CreateMessageRequest,
 };
 
@@ -86,6 +86,7 @@ pub struct RunObject {
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum TruncationObjectType {
     #[default]
@@ -95,6 +96,7 @@ pub enum TruncationObjectType {
 
 /// Thread Truncation Controls
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TruncationObject {
     /// The truncation strategy to use for the thread. The default is `auto`. If set to `last_messages`, the thread will be truncated to the n most recent messages in the thread. When set to `auto`, messages in the middle of the thread will be dropped to fit the context length of the model, `max_prompt_tokens`.
     pub r#type: TruncationObjectType,
@@ -179,11 +181,12 @@ pub struct RunCompletionUsage {
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "CreateRunRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateRunRequest {
     /// The ID of the [assistant](https://platform.openai.com/docs/api-reference/assistants) to use to execute this run.
     pub assistant_id: String,
@@ -248,6 +251,20 @@ pub struct CreateRunRequest {
     pub response_format: Option<AssistantsApiResponseFormatOption>,
 }
 
+impl CreateRunRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        validate::sampling(self.temperature.flatten(), self.top_p.flatten())?;
+        validate::min("max_prompt_tokens", self.max_prompt_tokens.flatten(), 256)?;
+        validate::min(
+            "max_completion_tokens",
+            self.max_completion_tokens.flatten(),
+            256,
+        )?;
+
+        Ok(())
+    }
+}
+
 #[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
 pub struct ModifyRunRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -272,6 +289,7 @@ pub struct SubmitToolOutputsRunRequest {
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "ToolsOutputsArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
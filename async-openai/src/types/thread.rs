@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use crate::error::OpenAIError;
 
 use super::{
-    AssistantToolResources, AssistantTools, AssistantsApiResponseFormatOption,
+    validate, AssistantToolResources, AssistantTools, AssistantsApiResponseFormatOption,
     AssistantsApiToolChoiceOption, CreateAssistantToolResources, CreateMessageRequest,
     TruncationObject,
 };
@@ -28,6 +28,7 @@ pub struct ThreadObject {
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "CreateThreadRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -64,11 +65,12 @@ pub struct DeleteThreadResponse {
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "CreateThreadAndRunRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateThreadAndRunRequest {
     /// The ID of the [assistant](https://platform.openai.com/docs/api-reference/assistants) to use to execute this run.
     pub assistant_id: String,
@@ -132,3 +134,17 @@ pub struct CreateThreadAndRunRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub response_format: Option<AssistantsApiResponseFormatOption>,
 }
+
+impl CreateThreadAndRunRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        validate::sampling(self.temperature.flatten(), self.top_p.flatten())?;
+        validate::min("max_prompt_tokens", self.max_prompt_tokens.flatten(), 256)?;
+        validate::min(
+            "max_completion_tokens",
+            self.max_completion_tokens.flatten(),
+            256,
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+//! Checks shared by the `validate` functions of request builders,
+//! so that documented constraints fail at `build()` rather than as an API error.
+use std::{cmp::Ordering, fmt::Display};
+
+use crate::error::OpenAIError;
+
+use super::{Modality, ModelId};
+
+/// Ensure that `value`, when set, lies within `min..=max`, which NaN does not.
+pub(crate) fn range<T>(name: &str, value: Option<T>, min: T, max: T) -> Result<(), OpenAIError>
+where
+    T: PartialOrd + Display,
+{
+    match value {
+        Some(value) if !(at_least(&value, &min) && at_least(&max, &value)) => {
+            Err(OpenAIError::InvalidArgument(format!(
+                "{name} must be between {min} and {max}, got {value}"
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Ensure that `value`, when set, is at least `min`, which NaN is not.
+pub(crate) fn min<T>(name: &str, value: Option<T>, min: T) -> Result<(), OpenAIError>
+where
+    T: PartialOrd + Display,
+{
+    match value {
+        Some(value) if !at_least(&value, &min) => Err(OpenAIError::InvalidArgument(format!(
+            "{name} must be at least {min}, got {value}"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Whether `value` compares greater than or equal to `min`, hence false for NaN.
+fn at_least<T: PartialOrd>(value: &T, min: &T) -> bool {
+    matches!(
+        value.partial_cmp(min),
+        Some(Ordering::Greater | Ordering::Equal)
+    )
+}
+
+/// Ensure that `temperature` is within 0..=2 and `top_p` within 0..=1.
+/// Setting both is allowed but discouraged by OpenAI, hence only logged.
+pub(crate) fn sampling(temperature: Option<f32>, top_p: Option<f32>) -> Result<(), OpenAIError> {
+    range("temperature", temperature, 0.0, 2.0)?;
+    range("top_p", top_p, 0.0, 1.0)?;
+
+    if temperature.is_some() && top_p.is_some() {
+        tracing::warn!("both temperature and top_p are set, it is recommended to alter only one");
+    }
+
+    Ok(())
+}
+
+/// Ensure that `stream_options` is not set on a request explicitly marked as non-streaming.
+///
+/// Builders leave an unset `stream` undecided, as it is set by `create_stream`,
+/// while `create` checks its request with `stream` defaulting to `Some(false)`.
+pub(crate) fn stream_options(
+    stream: Option<bool>,
+    stream_options: bool,
+) -> Result<(), OpenAIError> {
+    if stream_options && stream == Some(false) {
+        return Err(OpenAIError::InvalidArgument(
+            "stream_options can only be set when stream is true".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Whether a field of a builder was set to `Some`.
+pub(crate) fn is_set<T>(field: &Option<Option<T>>) -> bool {
+    matches!(field, Some(Some(_)))
+}
+
+/// Ensure that exactly one of the named alternatives is set.
+pub(crate) fn exactly_one(alternatives: &[(&str, bool)]) -> Result<(), OpenAIError> {
+    if alternatives.iter().filter(|(_, set)| *set).count() == 1 {
+        return Ok(());
+    }
+
+    let names: Vec<&str> = alternatives.iter().map(|(name, _)| *name).collect();
+    Err(OpenAIError::InvalidArgument(format!(
+        "exactly one of {} must be set",
+        names.join(", ")
+    )))
+}
+
+/// Ensure that `max_output_tokens` and the output `modalities` are supported by `model`,
+/// when it is a well-known [ModelId].
+pub(crate) fn model(
+    model: Option<&str>,
+    max_output_tokens: Option<u32>,
+    modalities: &[Modality],
+) -> Result<(), OpenAIError> {
+    let Some(model) = model.map(ModelId::from) else {
+        return Ok(());
+    };
+    let Some(info) = model.info() else {
+        return Ok(());
+    };
+
+    if let Some(max_output_tokens) = max_output_tokens {
+        if max_output_tokens > info.max_output_tokens {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "{model} supports at most {} output tokens, got {max_output_tokens}",
+                info.max_output_tokens
+            )));
+        }
+    }
+
+    if let Some(modality) = modalities
+        .iter()
+        .find(|modality| !info.supports_output(**modality))
+    {
+        return Err(OpenAIError::InvalidArgument(format!(
+            "{model} does not support {modality:?} output"
+        )));
+    }
+
+    Ok(())
+}
@@ -3,11 +3,12 @@ use std::collections::HashMap;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
-use crate::error::OpenAIError;
+use crate::{error::OpenAIError, runtime::BoxStream};
 
-use super::StaticChunkingStrategy;
+use super::{validate, StaticChunkingStrategy};
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "CreateVectorStoreRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -35,6 +36,7 @@ pub struct CreateVectorStoreRequest {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum VectorStoreChunkingStrategy {
     /// The default strategy. This strategy currently uses a `max_chunk_size_tokens` of `800` and `chunk_overlap_tokens` of `400`.
@@ -50,6 +52,7 @@ pub enum VectorStoreChunkingStrategy {
 
 /// Vector store expiration policy
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VectorStoreExpirationAfter {
     /// Anchor timestamp after which the expiration policy applies. Supported anchors: `last_active_at`.
     pub anchor: String,
@@ -122,6 +125,7 @@ pub struct DeleteVectorStoreResponse {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "UpdateVectorStoreRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -145,6 +149,10 @@ pub struct ListVectorStoreFilesResponse {
     pub has_more: bool,
 }
 
+/// Vector store files of a list, deserialized as they are received.
+pub type VectorStoreFileObjectStream =
+    BoxStream<'static, Result<VectorStoreFileObject, OpenAIError>>;
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
 pub struct VectorStoreFileObject {
     /// The identifier, which can be referenced in API endpoints.
@@ -201,6 +209,7 @@ pub enum VectorStoreFileObjectChunkingStrategy {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "CreateVectorStoreFileRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -223,6 +232,7 @@ pub struct DeleteVectorStoreFileResponse {
 }
 
 #[derive(Debug, Serialize, Default, Clone, Builder, PartialEq, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "CreateVectorStoreFileBatchRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -300,11 +310,12 @@ pub struct VectorStoreFileContentObject {
 }
 
 #[derive(Debug, Serialize, Default, Clone, Builder, PartialEq, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[builder(name = "VectorStoreSearchRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct VectorStoreSearchRequest {
     /// A query string for a search.
     pub query: VectorStoreSearchQuery,
@@ -326,7 +337,16 @@ pub struct VectorStoreSearchRequest {
     pub ranking_options: Option<RankingOptions>,
 }
 
+impl VectorStoreSearchRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        validate::range("max_num_results", self.max_num_results.flatten(), 1, 50)?;
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum VectorStoreSearchQuery {
     /// A single query to search for.
@@ -360,6 +380,7 @@ impl From<Vec<String>> for VectorStoreSearchQuery {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum VectorStoreSearchFilter {
     Comparison(ComparisonFilter),
@@ -380,6 +401,7 @@ impl From<CompoundFilter> for VectorStoreSearchFilter {
 
 /// A filter used to compare a specified attribute key to a given value using a defined comparison operation.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ComparisonFilter {
     /// Specifies the comparison operator: `eq`, `ne`, `gt`, `gte`, `lt`, `lte`.
     pub r#type: ComparisonType,
@@ -393,6 +415,7 @@ pub struct ComparisonFilter {
 
 /// Specifies the comparison operator: `eq`, `ne`, `gt`, `gte`, `lt`, `lte`.
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ComparisonType {
     Eq,
@@ -405,6 +428,7 @@ pub enum ComparisonType {
 
 /// The value to compare against the attribute key; supports string, number, or boolean types.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum AttributeValue {
     String(String),
@@ -438,6 +462,7 @@ impl From<&str> for AttributeValue {
 
 /// Ranking options for search.
 #[derive(Debug, Serialize, Default, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct RankingOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ranker: Option<Ranker>,
@@ -447,6 +472,7 @@ pub struct RankingOptions {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Ranker {
     #[serde(rename = "auto")]
     Auto,
@@ -456,6 +482,7 @@ pub enum Ranker {
 
 /// Combine multiple filters using `and` or `or`.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CompoundFilter {
     /// Type of operation: `and` or `or`.
     pub r#type: CompoundFilterType,
@@ -466,6 +493,7 @@ pub struct CompoundFilter {
 
 /// Type of operation: `and` or `or`.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum CompoundFilterType {
     And,
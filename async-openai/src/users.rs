@@ -3,6 +3,7 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::Paginated,
     types::{User, UserDeleteResponse, UserListResponse, UserRoleUpdateRequest},
     Client,
 };
@@ -28,6 +29,14 @@ impl<'c, C: Config> Users<'c, C> {
             .await
     }
 
+    /// Every page of [Users::list], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate<Q>(&self, query: &Q) -> Result<Paginated<C, UserListResponse>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(self.client, "/organization/users".into(), query)
+    }
+
     /// Modifies a user's role in the organization.
     #[crate::byot(T0 = std::fmt::Display, T1 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn modify(
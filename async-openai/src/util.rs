@@ -1,35 +1,42 @@
+#[cfg(not(target_family = "wasm"))]
 use std::path::Path;
 
 use reqwest::Body;
+#[cfg(not(target_family = "wasm"))]
 use tokio::fs::File;
-use tokio_util::codec::{BytesCodec, FramedRead};
+#[cfg(not(target_family = "wasm"))]
+use tokio_util::io::ReaderStream;
 
 use crate::error::OpenAIError;
 use crate::types::InputSource;
 
-pub(crate) async fn file_stream_body(source: InputSource) -> Result<Body, OpenAIError> {
-    let body = match source {
-        InputSource::Path { path } => {
-            let file = File::open(path)
-                .await
-                .map_err(|e| OpenAIError::FileReadError(e.to_string()))?;
-            let stream = FramedRead::new(file, BytesCodec::new());
-            Body::wrap_stream(stream)
-        }
-        _ => {
-            return Err(OpenAIError::FileReadError(
-                "Cannot create stream from non-file source".to_string(),
-            ))
-        }
-    };
-    Ok(body)
+/// Size of the chunks in which files are read from disk for uploads.
+#[cfg(not(target_family = "wasm"))]
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Body streaming the file at `path` from disk, so that uploads use constant memory,
+/// and its length, so that the multipart form still has a `Content-Length`.
+#[cfg(not(target_family = "wasm"))]
+async fn file_stream_body(path: &Path) -> Result<(Body, u64), OpenAIError> {
+    let file = File::open(path)
+        .await
+        .map_err(|e| OpenAIError::FileReadError(e.to_string()))?;
+    let length = file
+        .metadata()
+        .await
+        .map_err(|e| OpenAIError::FileReadError(e.to_string()))?
+        .len();
+
+    let stream = ReaderStream::with_capacity(file, UPLOAD_CHUNK_SIZE);
+    Ok((Body::wrap_stream(stream), length))
 }
 
 /// Creates the part for the given file for multipart upload.
 pub(crate) async fn create_file_part(
     source: InputSource,
 ) -> Result<reqwest::multipart::Part, OpenAIError> {
-    let (stream, file_name) = match source {
+    let (part, file_name) = match source {
+        #[cfg(not(target_family = "wasm"))]
         InputSource::Path { path } => {
             let file_name = path
                 .file_name()
@@ -43,16 +50,29 @@ pub(crate) async fn create_file_part(
                 .unwrap()
                 .to_string();
 
+            let (body, length) = file_stream_body(&path).await?;
             (
-                file_stream_body(InputSource::Path { path }).await?,
+                reqwest::multipart::Part::stream_with_length(body, length),
                 file_name,
             )
         }
-        InputSource::Bytes { filename, bytes } => (Body::from(bytes), filename),
-        InputSource::VecU8 { filename, vec } => (Body::from(vec), filename),
+        #[cfg(target_family = "wasm")]
+        InputSource::Path { path } => {
+            return Err(OpenAIError::FileReadError(format!(
+                "cannot read {} without a file system, use InputSource::Bytes instead",
+                path.display()
+            )))
+        }
+        InputSource::Bytes { filename, bytes } => (
+            reqwest::multipart::Part::stream(Body::from(bytes)),
+            filename,
+        ),
+        InputSource::VecU8 { filename, vec } => {
+            (reqwest::multipart::Part::stream(Body::from(vec)), filename)
+        }
     };
 
-    let file_part = reqwest::multipart::Part::stream(stream)
+    let file_part = part
         .file_name(file_name)
         .mime_str("application/octet-stream")
         .unwrap();
@@ -60,6 +80,7 @@ pub(crate) async fn create_file_part(
     Ok(file_part)
 }
 
+#[cfg(not(target_family = "wasm"))]
 pub(crate) fn create_all_dir<P: AsRef<Path>>(dir: P) -> Result<(), OpenAIError> {
     let exists = match Path::try_exists(dir.as_ref()) {
         Ok(exists) => exists,
@@ -3,6 +3,7 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::Paginated,
     types::{
         CreateVectorStoreFileBatchRequest, ListVectorStoreFilesResponse, VectorStoreFileBatchObject,
     },
@@ -87,4 +88,23 @@ impl<'c, C: Config> VectorStoreFileBatches<'c, C> {
             )
             .await
     }
+
+    /// Every page of [VectorStoreFileBatches::list], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate<Q>(
+        &self,
+        batch_id: &str,
+        query: &Q,
+    ) -> Result<Paginated<C, ListVectorStoreFilesResponse>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(
+            self.client,
+            format!(
+                "/vector_stores/{}/file_batches/{batch_id}/files",
+                &self.vector_store_id
+            ),
+            query,
+        )
+    }
 }
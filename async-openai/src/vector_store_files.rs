@@ -3,9 +3,10 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::Paginated,
     types::{
         CreateVectorStoreFileRequest, DeleteVectorStoreFileResponse, ListVectorStoreFilesResponse,
-        VectorStoreFileContentResponse, VectorStoreFileObject,
+        VectorStoreFileContentResponse, VectorStoreFileObject, VectorStoreFileObjectStream,
     },
     Client,
 };
@@ -79,6 +80,38 @@ impl<'c, C: Config> VectorStoreFiles<'c, C> {
             .await
     }
 
+    /// Every page of [VectorStoreFiles::list], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate<Q>(
+        &self,
+        query: &Q,
+    ) -> Result<Paginated<C, ListVectorStoreFilesResponse>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(
+            self.client,
+            format!("/vector_stores/{}/files", &self.vector_store_id),
+            query,
+        )
+    }
+
+    /// Same as [VectorStoreFiles::list], with the vector store files deserialized one by one
+    /// as the response is received, instead of after the whole response.
+    pub async fn list_stream<Q>(
+        &self,
+        query: &Q,
+    ) -> Result<VectorStoreFileObjectStream, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_list_stream(
+                &format!("/vector_stores/{}/files", &self.vector_store_id),
+                &query,
+            )
+            .await
+    }
+
     /// Retrieve the parsed contents of a vector store file.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve_file_content(
@@ -3,6 +3,7 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::Paginated,
     types::{
         CreateVectorStoreRequest, DeleteVectorStoreResponse, ListVectorStoresResponse,
         UpdateVectorStoreRequest, VectorStoreObject, VectorStoreSearchRequest,
@@ -57,6 +58,17 @@ impl<'c, C: Config> VectorStores<'c, C> {
         self.client.get_with_query("/vector_stores", &query).await
     }
 
+    /// Every page of [VectorStores::list], with the `after` cursor handled internally, see [Paginated].
+    pub fn paginate<Q>(
+        &self,
+        query: &Q,
+    ) -> Result<Paginated<C, ListVectorStoresResponse>, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Paginated::new(self.client, "/vector_stores".into(), query)
+    }
+
     /// Delete a vector store.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn delete(
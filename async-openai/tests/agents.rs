@@ -0,0 +1,92 @@
+//! Runs of agents against a mock transport.
+use async_openai::{
+    agents::{Agent, AgentStopReason},
+    testing::{MockResponse, MockTransport},
+    tools::{tool, ToolSet},
+    types::CreateResponse,
+};
+use reqwest::Method;
+use serde_json::json;
+
+/// Add two numbers.
+#[tool]
+async fn add(a: i64, b: i64) -> Result<i64, String> {
+    Ok(a + b)
+}
+
+fn completed(id: &str, output: serde_json::Value) -> serde_json::Value {
+    json!({
+        "type": "response.completed",
+        "response": {
+            "id": id,
+            "object": "response",
+            "created_at": 1741476542,
+            "status": "completed",
+            "model": "gpt-4.1",
+            "output": output,
+            "usage": {
+                "input_tokens": 10,
+                "input_tokens_details": {"cached_tokens": 0},
+                "output_tokens": 5,
+                "output_tokens_details": {"reasoning_tokens": 0},
+                "total_tokens": 15
+            }
+        }
+    })
+}
+
+#[tokio::test]
+async fn stored_run_sends_only_new_items() {
+    let mock = MockTransport::new();
+    mock.on(
+        Method::POST,
+        "/responses",
+        MockResponse::sse([completed(
+            "resp_1",
+            json!([
+                {"type": "reasoning", "id": "rs_1", "summary": []},
+                {
+                    "type": "function_call",
+                    "id": "fc_1",
+                    "call_id": "call_1",
+                    "name": "add",
+                    "arguments": "{\"a\":1,\"b\":2}",
+                    "status": "completed"
+                }
+            ]),
+        )]),
+    )
+    .on(
+        Method::POST,
+        "/responses",
+        MockResponse::sse([completed(
+            "resp_2",
+            json!([{
+                "type": "message",
+                "id": "msg_1",
+                "status": "completed",
+                "role": "assistant",
+                "content": [{"type": "output_text", "text": "3", "annotations": []}]
+            }]),
+        )]),
+    );
+
+    let client = mock.client();
+    let mut agent = Agent::new(&client, "gpt-4.1").with_tools(ToolSet::new().with_tool(AddTool));
+    let outcome = agent.run("What is 1 + 2?").finish().await.unwrap();
+
+    assert_eq!(outcome.reason, AgentStopReason::Answered);
+    assert_eq!(outcome.turns, 2);
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 2);
+    let second: CreateResponse = requests[1].json().unwrap();
+    assert_eq!(second.previous_response_id.as_deref(), Some("resp_1"));
+
+    // the stored response already has the reasoning and the function call
+    let input = serde_json::to_value(&second.input).unwrap();
+    assert_eq!(
+        input,
+        json!([{"type": "function_call_output", "call_id": "call_1", "output": "3"}])
+    );
+}
@@ -2,7 +2,12 @@
 //! The purpose of this test to make sure that all _byot methods compiles with custom types.
 use std::pin::Pin;
 
-use async_openai::{error::OpenAIError, Client};
+use async_openai::{
+    error::OpenAIError,
+    types::{AssistantStreamEventRaw, InputSource, MultipartForm},
+    Client,
+};
+use bytes::Bytes;
 use futures::Stream;
 use serde_json::{json, Value};
 
@@ -26,6 +31,25 @@ async fn test_byot_files() {
     let _r: Result<Value, OpenAIError> = client.files().list_byot([("limit", "2")]).await;
     let _r: Result<Value, OpenAIError> = client.files().retrieve_byot("file_id").await;
     let _r: Result<Value, OpenAIError> = client.files().delete_byot("file_id").await;
+    let _r: Result<Bytes, OpenAIError> = client.files().content_byot("file_id").await;
+}
+
+#[tokio::test]
+async fn test_byot_multipart_form() {
+    let client = Client::new();
+
+    let form = MultipartForm::new()
+        .file(
+            "file",
+            InputSource::Path {
+                path: "a.mp3".into(),
+            },
+        )
+        .text("model", "whisper-1");
+
+    let _r: Result<Value, OpenAIError> = client.files().create_byot(form.clone()).await;
+    let _r: Result<Value, OpenAIError> = client.audio().transcribe_byot(form.clone()).await;
+    let _r: Result<Value, OpenAIError> = client.images().create_edit_byot(form).await;
 }
 
 #[tokio::test]
@@ -98,6 +122,15 @@ async fn test_byot_audio() {
         .audio()
         .translate_verbose_json_byot(MyJson(json!({})))
         .await;
+    let _r: Result<MyStreamingType, OpenAIError> = client
+        .audio()
+        .transcribe_stream_byot(MyJson(json!({})))
+        .await;
+    let _r: Result<Bytes, OpenAIError> =
+        client.audio().transcribe_raw_byot(MyJson(json!({}))).await;
+    let _r: Result<Vec<u8>, OpenAIError> =
+        client.audio().translate_raw_byot(MyJson(json!({}))).await;
+    let _r: Result<Bytes, OpenAIError> = client.audio().speech_byot(json!({})).await;
 }
 
 #[tokio::test]
@@ -145,6 +178,9 @@ impl TryFrom<eventsource_stream::Event> for MyThreadJson {
 
 type MyThreadStreamingType = Pin<Box<dyn Stream<Item = Result<MyThreadJson, OpenAIError>> + Send>>;
 
+type RawThreadStreamingType =
+    Pin<Box<dyn Stream<Item = Result<AssistantStreamEventRaw, OpenAIError>> + Send>>;
+
 #[tokio::test]
 async fn test_byot_threads() {
     let client = Client::new();
@@ -152,6 +188,8 @@ async fn test_byot_threads() {
     let _r: Result<Value, OpenAIError> = client.threads().create_and_run_byot(json!({})).await;
     let _r: Result<MyThreadStreamingType, OpenAIError> =
         client.threads().create_and_run_stream_byot(json!({})).await;
+    let _r: Result<RawThreadStreamingType, OpenAIError> =
+        client.threads().create_and_run_stream_byot(json!({})).await;
     let _r: Result<Value, OpenAIError> = client.threads().create_byot(json!({})).await;
     let _r: Result<Value, OpenAIError> = client.threads().retrieve_byot("thread_id").await;
     let _r: Result<Value, OpenAIError> = client.threads().update_byot("thread_id", json!({})).await;
@@ -442,3 +480,68 @@ async fn test_byot_users() {
     let _r: Result<Value, OpenAIError> = client.users().retrieve_byot("user_id").await;
     let _r: Result<Value, OpenAIError> = client.users().delete_byot("user_id").await;
 }
+
+/// Every public method of an API group must have a `_byot` variant.
+#[test]
+fn test_byot_coverage() {
+    // modules which are not API groups
+    const SKIP: &[&str] = &[
+        "agents.rs",
+        "client.rs",
+        "concurrent.rs",
+        "config.rs",
+        "download.rs",
+        "error.rs",
+        "history.rs",
+        "lib.rs",
+        "realtime.rs",
+        "single_flight.rs",
+        "sink.rs",
+        "sse.rs",
+        "tools.rs",
+        "traits.rs",
+        "transport.rs",
+        "util.rs",
+    ];
+    // methods of API groups built on endpoints which have byot variants
+    const HELPERS: &[&str] = &[
+        "chat.rs: create_parsed",
+        "file.rs: content_jsonl",
+        "file.rs: content_stream",
+        "file.rs: list_stream",
+        "responses.rs: create_structured",
+        "responses.rs: create_with_tools",
+        "responses.rs: wait_for_completion",
+        "vector_store_files.rs: list_stream",
+    ];
+
+    let src = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let mut missing = vec![];
+
+    for entry in std::fs::read_dir(src).unwrap() {
+        let path = entry.unwrap().path();
+        let file_name = path.file_name().unwrap().to_str().unwrap().to_string();
+        if path.extension() != Some("rs".as_ref()) || SKIP.contains(&file_name.as_str()) {
+            continue;
+        }
+
+        let source = std::fs::read_to_string(&path).unwrap();
+        let mut byot = false;
+        for line in source.lines().map(str::trim) {
+            if line.starts_with("#[crate::byot") {
+                byot = true;
+            } else if let Some(signature) = line.strip_prefix("pub async fn ") {
+                let name = signature.split(['(', '<']).next().unwrap();
+                let method = format!("{file_name}: {name}");
+                if !byot && !HELPERS.contains(&method.as_str()) {
+                    missing.push(method);
+                }
+                byot = false;
+            } else if line.starts_with("fn ") || line.starts_with("pub fn ") {
+                byot = false;
+            }
+        }
+    }
+
+    assert!(missing.is_empty(), "methods without byot: {missing:?}");
+}
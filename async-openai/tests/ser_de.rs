@@ -1,6 +1,8 @@
+use async_openai::error::{ApiError, ApiErrorCode, ApiErrorType, OpenAIError};
 use async_openai::types::{
-    ChatCompletionRequestSystemMessageArgs, ChatCompletionRequestUserMessageArgs,
-    CreateChatCompletionRequest, CreateChatCompletionRequestArgs,
+    ChatCompletionModalities, ChatCompletionRequestSystemMessageArgs,
+    ChatCompletionRequestUserMessageArgs, ChatCompletionStreamOptions, CreateChatCompletionRequest,
+    CreateChatCompletionRequestArgs, Modality, ModelId, OwnedBuilder,
 };
 
 #[tokio::test]
@@ -26,3 +28,1443 @@ async fn chat_types_serde() {
     let deserialized: CreateChatCompletionRequest = serde_json::from_str(&serialized).unwrap();
     assert_eq!(request, deserialized);
 }
+
+#[test]
+fn api_error_codes_de() {
+    let error: ApiError = serde_json::from_str(
+        r#"{"message": "Rate limit reached", "type": "requests", "param": null, "code": "rate_limit_exceeded"}"#,
+    )
+    .unwrap();
+    assert_eq!(error.r#type, Some(ApiErrorType::Requests));
+    assert_eq!(error.code, Some(ApiErrorCode::RateLimitExceeded));
+
+    let error: ApiError = serde_json::from_str(
+        r#"{"message": "Something new", "type": "brand_new_error", "param": null, "code": "brand_new_code"}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        error.r#type,
+        Some(ApiErrorType::Other("brand_new_error".into()))
+    );
+    assert_eq!(
+        error.code,
+        Some(ApiErrorCode::Other("brand_new_code".into()))
+    );
+    assert_eq!(
+        error.to_string(),
+        "brand_new_error: Something new (code: brand_new_code)"
+    );
+}
+
+#[test]
+fn builder_validates_ranges() {
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .temperature(2.5)
+        .build();
+    assert!(matches!(request, Err(OpenAIError::InvalidArgument(_))));
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .top_logprobs(5)
+        .build();
+    assert!(matches!(request, Err(OpenAIError::InvalidArgument(_))));
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .top_p(f32::NAN)
+        .build();
+    assert!(matches!(request, Err(OpenAIError::InvalidArgument(_))));
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .stream(false)
+        .stream_options(ChatCompletionStreamOptions {
+            include_usage: true,
+        })
+        .build();
+    assert!(matches!(request, Err(OpenAIError::InvalidArgument(_))));
+}
+
+#[test]
+fn model_id() {
+    assert_eq!(ModelId::from("gpt-4o-mini"), ModelId::Gpt4oMini);
+    assert_eq!(String::from(ModelId::O3Mini), "o3-mini");
+    assert_eq!(
+        serde_json::from_str::<ModelId>(r#""ft:gpt-4o-mini:acme""#).unwrap(),
+        ModelId::Other("ft:gpt-4o-mini:acme".into())
+    );
+
+    let snapshot = ModelId::from("gpt-4o-2024-08-06");
+    assert_eq!(snapshot.to_string(), "gpt-4o-2024-08-06");
+    assert_eq!(snapshot.context_window(), Some(128_000));
+    assert_eq!(ModelId::from("ft:gpt-4o-mini:acme").info(), None);
+
+    let info = ModelId::Gpt4oAudioPreview.info().unwrap();
+    assert!(info.supports_output(Modality::Audio));
+    assert!(!ModelId::Gpt4o
+        .info()
+        .unwrap()
+        .supports_output(Modality::Audio));
+}
+
+#[test]
+#[allow(deprecated)]
+fn reasoning_max_tokens() {
+    use async_openai::types::{ChatCompletionRequestDeveloperMessageArgs, ReasoningEffort};
+
+    assert!(ModelId::from("o3-mini-2025-01-31").is_reasoning());
+    assert!(!ModelId::Gpt4o.is_reasoning());
+
+    let mut request = CreateChatCompletionRequestArgs::default()
+        .model(ModelId::O3Mini)
+        .messages([ChatCompletionRequestDeveloperMessageArgs::default()
+            .content("Answer in French")
+            .build()
+            .unwrap()
+            .into()])
+        .reasoning_effort(ReasoningEffort::Low)
+        .max_tokens(100u32)
+        .build()
+        .unwrap();
+    let mut gpt4o = CreateChatCompletionRequest {
+        model: ModelId::Gpt4o.into(),
+        ..request.clone()
+    };
+
+    request.map_max_tokens();
+    gpt4o.map_max_tokens();
+
+    let request = serde_json::to_value(&request).unwrap();
+    assert_eq!(request["max_completion_tokens"], 100);
+    assert!(request.get("max_tokens").is_none());
+    assert_eq!(request["reasoning_effort"], "low");
+    assert_eq!(request["messages"][0]["role"], "developer");
+    assert_eq!(gpt4o.max_tokens, Some(100));
+    assert_eq!(gpt4o.max_completion_tokens, None);
+}
+
+#[test]
+fn builder_validates_model_limits() {
+    let request = CreateChatCompletionRequestArgs::default()
+        .model(ModelId::Gpt4oMini)
+        .max_completion_tokens(100_000_u32)
+        .build();
+    assert!(matches!(request, Err(OpenAIError::InvalidArgument(_))));
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .modalities([
+            ChatCompletionModalities::Text,
+            ChatCompletionModalities::Audio,
+        ])
+        .build();
+    assert!(matches!(request, Err(OpenAIError::InvalidArgument(_))));
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("my-model")
+        .max_completion_tokens(100_000_u32)
+        .build();
+    assert!(request.is_ok());
+}
+
+#[test]
+fn owned_builders() {
+    let requests = ["gpt-4o", "gpt-4o-mini"]
+        .into_iter()
+        .map(|model| {
+            CreateChatCompletionRequestArgs::default()
+                .model(model)
+                .temperature(0.5)
+                .owned()
+        })
+        .map(|args| args.with(|args| args.n(2u8)).build())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(requests[1].model, "gpt-4o-mini");
+    assert_eq!(requests[1].temperature, Some(0.5));
+    assert_eq!(requests[1].n, Some(2));
+}
+
+#[cfg(feature = "lenient")]
+#[test]
+fn lenient_chat_completion_response_de() {
+    use async_openai::types::CreateChatCompletionResponse;
+
+    // e.g. as returned by an OpenAI compatible server
+    let response: CreateChatCompletionResponse = serde_json::from_str(
+        r#"{
+            "id": "chatcmpl-1",
+            "model": "llama3",
+            "service_tier": "on_demand",
+            "choices": [{
+                "message": {"role": "assistant", "content": "2"},
+                "finish_reason": "eos"
+            }],
+            "usage": {"prompt_tokens": 8, "completion_tokens": 1}
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(response.object, "");
+    assert_eq!(response.service_tier, None);
+    assert_eq!(response.choices[0].finish_reason, None);
+    assert_eq!(response.usage.unwrap().total_tokens, 0);
+}
+
+#[cfg(feature = "extra-fields")]
+#[test]
+fn extra_fields_de() {
+    use async_openai::types::CreateChatCompletionResponse;
+
+    // e.g. as returned by vLLM
+    let json = serde_json::json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 1,
+        "model": "llama3",
+        "prompt_logprobs": null,
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": "2", "reasoning_content": "1+1"},
+            "finish_reason": "stop",
+            "stop_reason": null
+        }],
+        "usage": {
+            "prompt_tokens": 8,
+            "completion_tokens": 1,
+            "total_tokens": 9,
+            "prompt_tokens_details": null,
+            "kv_cache_usage": 0.5
+        }
+    });
+
+    let response: CreateChatCompletionResponse = serde_json::from_value(json.clone()).unwrap();
+    assert!(response.extra.contains_key("prompt_logprobs"));
+    assert!(response.choices[0].extra.contains_key("stop_reason"));
+    assert_eq!(
+        response.choices[0].message.extra["reasoning_content"],
+        "1+1"
+    );
+    assert_eq!(
+        response.usage.as_ref().unwrap().extra["kv_cache_usage"],
+        0.5
+    );
+
+    let round_trip = serde_json::to_value(&response).unwrap();
+    assert_eq!(round_trip["usage"]["kv_cache_usage"], 0.5);
+}
+
+#[cfg(all(feature = "deny-unknown-fields", not(feature = "extra-fields")))]
+#[test]
+fn deny_unknown_fields_de() {
+    use async_openai::types::CreateEmbeddingResponse;
+
+    let mut json = serde_json::json!({
+        "object": "list",
+        "model": "text-embedding-3-small",
+        "data": [{"index": 0, "object": "embedding", "embedding": [0.5]}],
+        "usage": {"prompt_tokens": 1, "total_tokens": 1}
+    });
+    assert!(serde_json::from_value::<CreateEmbeddingResponse>(json.clone()).is_ok());
+
+    json["data"][0]["norm"] = 1.into();
+    assert!(serde_json::from_value::<CreateEmbeddingResponse>(json).is_err());
+}
+
+#[test]
+fn display_contents() {
+    use async_openai::types::{
+        ChatCompletionRequestMessageContentPartImage, ChatCompletionRequestUserMessageContent,
+        ChatCompletionRequestUserMessageContentPart, CreateChatCompletionStreamResponse, ImageUrl,
+    };
+
+    let content = ChatCompletionRequestUserMessageContent::Array(vec![
+        ChatCompletionRequestUserMessageContentPart::Text("Describe ".into()),
+        ChatCompletionRequestUserMessageContentPart::ImageUrl(
+            ChatCompletionRequestMessageContentPartImage {
+                image_url: ImageUrl::from("https://example.com/cat.png"),
+            },
+        ),
+        ChatCompletionRequestUserMessageContentPart::Text("this image".into()),
+    ]);
+    assert_eq!(content.to_string(), "Describe this image");
+
+    let chunks: Vec<CreateChatCompletionStreamResponse> = ["Hello", ", world"]
+        .iter()
+        .map(|content| {
+            serde_json::from_value(serde_json::json!({
+                "id": "chatcmpl-1",
+                "object": "chat.completion.chunk",
+                "created": 1,
+                "model": "gpt-4o",
+                "choices": [{"index": 0, "delta": {"content": content}, "finish_reason": null}]
+            }))
+            .unwrap()
+        })
+        .collect();
+    let text: String = chunks.iter().map(ToString::to_string).collect();
+    assert_eq!(text, "Hello, world");
+}
+
+#[test]
+fn input_image_and_file() {
+    use async_openai::types::{ImageDetail, InputFile, InputFileArgs, InputImage, InputImageArgs};
+
+    let image = InputImage::from_url("https://example.com/cat.png").with_detail(ImageDetail::Low);
+    assert_eq!(
+        serde_json::to_value(&image).unwrap(),
+        serde_json::json!({"detail": "low", "image_url": "https://example.com/cat.png"})
+    );
+    assert!(InputImageArgs::default().build().is_err());
+    assert!(InputImageArgs::default()
+        .image_url("https://example.com/cat.png")
+        .file_id("file-1")
+        .build()
+        .is_err());
+
+    let file = InputFile::from_data("a.pdf", "data:application/pdf;base64,AAAA");
+    assert_eq!(
+        serde_json::to_value(&file).unwrap(),
+        serde_json::json!({"filename": "a.pdf", "file_data": "data:application/pdf;base64,AAAA"})
+    );
+    assert_eq!(
+        InputFileArgs::default().file_id("file-1").build().unwrap(),
+        InputFile::from_file_id("file-1")
+    );
+    assert!(InputFileArgs::default().filename("a.pdf").build().is_err());
+    assert!(InputFileArgs::default()
+        .file_id("file-1")
+        .filename("a.pdf")
+        .file_data("data:application/pdf;base64,AAAA")
+        .build()
+        .is_err());
+}
+
+#[test]
+fn input_audio_from_bytes() {
+    use async_openai::types::{
+        ChatCompletionRequestMessageContentPartAudio, InputAudio, InputAudioFormat,
+    };
+
+    let audio = InputAudio::from_wav_bytes(b"RIFF");
+    assert_eq!(audio.data, "UklGRg==");
+    assert_eq!(audio.format, InputAudioFormat::Wav);
+    assert_eq!(
+        InputAudio::from_mp3_bytes(vec![0xff, 0xfb]).format,
+        InputAudioFormat::Mp3
+    );
+
+    let part = ChatCompletionRequestMessageContentPartAudio::from(audio);
+    assert_eq!(
+        serde_json::to_value(part).unwrap(),
+        serde_json::json!({"input_audio": {"data": "UklGRg==", "format": "wav"}})
+    );
+}
+
+#[test]
+fn user_content_merge() {
+    use async_openai::types::{
+        ChatCompletionRequestMessageContentPartImage, ChatCompletionRequestUserMessageContent,
+        ChatCompletionRequestUserMessageContentPart, ImageUrl,
+    };
+
+    let image = ChatCompletionRequestUserMessageContentPart::ImageUrl(
+        ChatCompletionRequestMessageContentPartImage {
+            image_url: ImageUrl::from("https://example.com/cat.png"),
+        },
+    );
+
+    let mut content = ChatCompletionRequestUserMessageContent::from("Context:");
+    content.append("first document");
+    content.append(vec![
+        image.clone(),
+        ChatCompletionRequestUserMessageContentPart::Text("".into()),
+        ChatCompletionRequestUserMessageContentPart::Text("second document".into()),
+    ]);
+    content.append("Question?");
+
+    assert_eq!(content.clone().into_parts().len(), 6);
+    assert_eq!(
+        content.merge_text("\n"),
+        ChatCompletionRequestUserMessageContent::Array(vec![
+            ChatCompletionRequestUserMessageContentPart::Text("Context:\nfirst document".into()),
+            image,
+            ChatCompletionRequestUserMessageContentPart::Text("second document\nQuestion?".into()),
+        ])
+    );
+
+    let content: ChatCompletionRequestUserMessageContent = ["a", "b"]
+        .map(|text| ChatCompletionRequestUserMessageContentPart::Text(text.into()))
+        .into_iter()
+        .collect();
+    assert_eq!(
+        content.merge_text(" "),
+        ChatCompletionRequestUserMessageContent::Text("a b".into())
+    );
+}
+
+#[test]
+fn normalize_for_prompt_cache() {
+    use async_openai::types::{
+        ChatCompletionRequestDeveloperMessageArgs, ChatCompletionRequestMessage,
+        ChatCompletionToolArgs, FunctionObjectArgs,
+    };
+
+    let tool = |name: &str| {
+        ChatCompletionToolArgs::default()
+            .function(
+                FunctionObjectArgs::default()
+                    .name(name)
+                    .parameters(serde_json::json!({"type": "object", "properties": {}}))
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()
+    };
+    let mut request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([
+            ChatCompletionRequestUserMessageArgs::default()
+                .content("hello")
+                .build()
+                .unwrap()
+                .into(),
+            ChatCompletionRequestSystemMessageArgs::default()
+                .content("be brief")
+                .build()
+                .unwrap()
+                .into(),
+            ChatCompletionRequestDeveloperMessageArgs::default()
+                .content("answer in French")
+                .build()
+                .unwrap()
+                .into(),
+        ])
+        .tools([tool("weather"), tool("time")])
+        .build()
+        .unwrap();
+
+    request.normalize_for_prompt_cache();
+
+    assert!(matches!(
+        request.messages[..],
+        [
+            ChatCompletionRequestMessage::System(_),
+            ChatCompletionRequestMessage::Developer(_),
+            ChatCompletionRequestMessage::User(_)
+        ]
+    ));
+    let tools = request.tools.as_ref().unwrap();
+    assert_eq!(tools[0].function.name, "time");
+    assert_eq!(tools[1].function.name, "weather");
+    assert!(serde_json::to_string(&tools[0])
+        .unwrap()
+        .contains(r#""parameters":{"properties":{},"type":"object"}"#));
+}
+
+#[test]
+fn chat_prediction() {
+    use async_openai::types::CompletionUsage;
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Rename the struct to Point")
+            .build()
+            .unwrap()
+            .into()])
+        .prediction("struct Pos { x: f32, y: f32 }")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&request).unwrap()["prediction"],
+        serde_json::json!({"type": "content", "content": "struct Pos { x: f32, y: f32 }"})
+    );
+
+    let usage: CompletionUsage = serde_json::from_value(serde_json::json!({
+        "prompt_tokens": 20,
+        "completion_tokens": 12,
+        "total_tokens": 32,
+        "completion_tokens_details": {
+            "accepted_prediction_tokens": 9,
+            "rejected_prediction_tokens": 1
+        }
+    }))
+    .unwrap();
+    let details = usage.completion_tokens_details.unwrap();
+    assert_eq!(details.accepted_prediction_tokens, Some(9));
+    assert_eq!(details.rejected_prediction_tokens, Some(1));
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn request_json_schema() {
+    use async_openai::types::CreateChatCompletionRequest;
+
+    let schema = serde_json::to_value(schemars::schema_for!(CreateChatCompletionRequest)).unwrap();
+    let properties = &schema["properties"];
+
+    assert!(properties["messages"].is_object());
+    assert!(properties["temperature"].is_object());
+    assert!(schema["required"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!("model")));
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn response_format_json_schema_for() {
+    use async_openai::types::ResponseFormat;
+
+    #[derive(schemars::JsonSchema)]
+    #[allow(dead_code)]
+    struct Weather {
+        city: String,
+        celsius: Option<f32>,
+    }
+
+    let format =
+        serde_json::to_value(ResponseFormat::json_schema_for::<Weather>().unwrap()).unwrap();
+    assert_eq!(format["type"], "json_schema");
+    assert_eq!(format["json_schema"]["name"], "Weather");
+    assert_eq!(format["json_schema"]["strict"], true);
+    let schema = &format["json_schema"]["schema"];
+    assert_eq!(schema["additionalProperties"], false);
+    assert_eq!(schema["required"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn image_b64_bytes() {
+    use async_openai::types::ImagesResponse;
+
+    let pixels: Vec<u8> = (0..=255).cycle().take(100_000).collect();
+    let b64 = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &pixels);
+    let response: ImagesResponse = serde_json::from_value(serde_json::json!({
+        "created": 1,
+        "data": [{"b64_json": b64}, {"url": "https://example.com/cat.png"}]
+    }))
+    .unwrap();
+
+    assert_eq!(response.data[0].as_bytes().unwrap(), pixels);
+    assert!(response.data[1].as_bytes().is_err());
+
+    let dir = std::env::temp_dir().join("async-openai-image-b64-bytes");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = response.data[0].save(&dir).await.unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), pixels);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn cursor_pages() {
+    use async_openai::{
+        pagination::CursorPage,
+        types::{ListFilesResponse, ProjectListResponse},
+    };
+
+    let projects: ProjectListResponse = serde_json::from_value(serde_json::json!({
+        "object": "list",
+        "data": [{
+            "id": "proj_abc",
+            "object": "organization.project",
+            "name": "Project example",
+            "created_at": 1711471533,
+            "archived_at": null,
+            "status": "active"
+        }],
+        "first_id": "proj_abc",
+        "last_id": "proj_abc",
+        "has_more": true
+    }))
+    .unwrap();
+
+    assert!(projects.has_more());
+    assert_eq!(projects.last_id(), Some("proj_abc"));
+    assert_eq!(projects.into_items()[0].name, "Project example");
+
+    // OpenAI compatible servers may list files without cursor
+    let files: ListFilesResponse =
+        serde_json::from_value(serde_json::json!({"object": "list", "data": []})).unwrap();
+
+    assert!(!files.has_more());
+    assert_eq!(files.last_id(), None);
+}
+
+#[test]
+fn stored_chat_completion_messages() {
+    use async_openai::{
+        pagination::CursorPage,
+        types::{ChatCompletionMessageList, ChatCompletionRequestUserMessageContentPart, Role},
+    };
+
+    let messages: ChatCompletionMessageList = serde_json::from_value(serde_json::json!({
+        "object": "list",
+        "data": [
+            {
+                "id": "chatcmpl-AyPNinnUqUDYo9SAdA52NobMflmj2-0",
+                "role": "user",
+                "content": "write a haiku about ai",
+                "name": null,
+                "content_parts": [{"type": "text", "text": "write a haiku about ai"}]
+            },
+            {
+                "id": "chatcmpl-AyPNinnUqUDYo9SAdA52NobMflmj2-1",
+                "role": "assistant",
+                "content": "Silent circuits hum",
+                "refusal": null,
+                "tool_calls": null
+            }
+        ],
+        "first_id": "chatcmpl-AyPNinnUqUDYo9SAdA52NobMflmj2-0",
+        "last_id": "chatcmpl-AyPNinnUqUDYo9SAdA52NobMflmj2-1",
+        "has_more": false
+    }))
+    .unwrap();
+
+    assert!(!messages.has_more());
+    assert_eq!(
+        messages.last_id(),
+        Some("chatcmpl-AyPNinnUqUDYo9SAdA52NobMflmj2-1")
+    );
+    let messages = messages.into_items();
+    assert!(matches!(
+        messages[0].content_parts.as_deref(),
+        Some([ChatCompletionRequestUserMessageContentPart::Text(_)])
+    ));
+    assert_eq!(messages[1].role, Role::Assistant);
+    assert_eq!(messages[1].content.as_deref(), Some("Silent circuits hum"));
+}
+
+#[test]
+fn response_stream_events() {
+    use async_openai::types::{
+        CreateResponse, CreateResponseArgs, InputMessage, InputRole, ResponseStreamEvent,
+    };
+
+    let request: CreateResponse = CreateResponseArgs::default()
+        .model("gpt-4o")
+        .input(vec![InputMessage::new(InputRole::User, "Say hello").into()])
+        .build()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({
+            "model": "gpt-4o",
+            "input": [{"type": "message", "role": "user", "content": "Say hello"}]
+        })
+    );
+
+    let events: Vec<ResponseStreamEvent> = [
+        r#"{"type": "response.created", "sequence_number": 0, "response": {"id": "resp_1", "object": "response", "created_at": 1741476542, "status": "in_progress", "model": "gpt-4o", "output": []}}"#,
+        r#"{"type": "response.output_text.delta", "sequence_number": 1, "item_id": "msg_1", "output_index": 0, "content_index": 0, "delta": "Hello"}"#,
+        r#"{"type": "response.output_text.delta", "sequence_number": 2, "item_id": "msg_1", "output_index": 0, "content_index": 0, "delta": ", world"}"#,
+        r#"{"type": "response.output_text.done", "sequence_number": 3, "item_id": "msg_1", "output_index": 0, "content_index": 0, "text": "Hello, world"}"#,
+    ]
+    .iter()
+    .map(|event| serde_json::from_str(event).unwrap())
+    .collect();
+
+    assert!(matches!(events[0], ResponseStreamEvent::ResponseCreated(_)));
+    let text: String = events.iter().map(ToString::to_string).collect();
+    assert_eq!(text, "Hello, world");
+}
+
+#[test]
+fn response_stream_events_round_trip() {
+    use async_openai::types::ResponseStreamEvent;
+
+    let events = [
+        serde_json::json!({"type": "response.incomplete", "sequence_number": 9, "response": {
+            "id": "resp_1", "object": "response", "created_at": 1741476542, "status": "incomplete",
+            "error": null, "incomplete_details": {"reason": "max_output_tokens"}, "instructions": null,
+            "max_output_tokens": 16, "metadata": {}, "model": "o3", "output": [], "parallel_tool_calls": true,
+            "previous_response_id": null, "reasoning": {"effort": "low"}, "store": true, "temperature": 1.0,
+            "tools": [], "top_p": 1.0, "truncation": "disabled", "user": null,
+            "usage": {"input_tokens": 12, "input_tokens_details": {"cached_tokens": 0}, "output_tokens": 16, "output_tokens_details": {"reasoning_tokens": 16}, "total_tokens": 28}
+        }}),
+        serde_json::json!({"type": "response.output_item.added", "sequence_number": 1, "output_index": 0, "item": {"type": "message", "id": "msg_1", "role": "assistant", "content": [], "status": "in_progress"}}),
+        serde_json::json!({"type": "response.content_part.added", "sequence_number": 2, "item_id": "msg_1", "output_index": 0, "content_index": 0, "part": {"type": "output_text", "text": "", "annotations": []}}),
+        serde_json::json!({"type": "response.content_part.done", "item_id": "msg_1", "output_index": 0, "content_index": 1, "part": {"type": "refusal", "refusal": "No"}}),
+        serde_json::json!({"type": "response.output_text.annotation.added", "item_id": "msg_1", "output_index": 0, "content_index": 0, "annotation_index": 0, "annotation": {"type": "url_citation", "url": "https://example.com", "title": "Example", "start_index": 0, "end_index": 5}}),
+        serde_json::json!({"type": "response.refusal.delta", "item_id": "msg_1", "output_index": 0, "content_index": 1, "delta": "No"}),
+        serde_json::json!({"type": "response.refusal.done", "item_id": "msg_1", "output_index": 0, "content_index": 1, "refusal": "No"}),
+        serde_json::json!({"type": "response.function_call_arguments.delta", "item_id": "fc_1", "output_index": 1, "delta": "{\"city\":"}),
+        serde_json::json!({"type": "response.function_call_arguments.done", "item_id": "fc_1", "output_index": 1, "arguments": "{\"city\":\"Paris\"}"}),
+        serde_json::json!({"type": "response.file_search_call.searching", "item_id": "fs_1", "output_index": 2}),
+        serde_json::json!({"type": "response.web_search_call.completed", "item_id": "ws_1", "output_index": 3}),
+        serde_json::json!({"type": "response.reasoning_summary_part.added", "item_id": "rs_1", "output_index": 0, "summary_index": 0, "part": {"type": "summary_text", "text": ""}}),
+        serde_json::json!({"type": "response.reasoning_summary_text.delta", "item_id": "rs_1", "output_index": 0, "summary_index": 0, "delta": "Thinking"}),
+        serde_json::json!({"type": "response.reasoning_summary_text.done", "item_id": "rs_1", "output_index": 0, "summary_index": 0, "text": "Thinking"}),
+        serde_json::json!({"type": "error", "code": "server_error", "message": "The server had an error", "param": null}),
+    ];
+
+    for event in events {
+        let deserialized: ResponseStreamEvent = serde_json::from_value(event.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&deserialized).unwrap(), event);
+    }
+}
+
+#[test]
+fn response_items_round_trip() {
+    use async_openai::types::{FunctionCallOutput, Item};
+
+    let items = [
+        serde_json::json!({"type": "function_call", "id": "fc_1", "call_id": "call_1", "name": "get_weather", "arguments": "{\"city\":\"Paris\"}", "status": "completed"}),
+        serde_json::json!({"type": "function_call_output", "call_id": "call_1", "output": "{\"temperature\":21}"}),
+        serde_json::json!({"type": "file_search_call", "id": "fs_1", "queries": ["weather"], "status": "completed", "results": [{"file_id": "file-1", "filename": "a.pdf", "score": 0.5, "text": "sunny", "attributes": {"year": 2025}}]}),
+        serde_json::json!({"type": "web_search_call", "id": "ws_1", "status": "searching"}),
+        serde_json::json!({"type": "computer_call", "id": "cu_1", "call_id": "call_2", "action": {"type": "drag", "path": [{"x": 0, "y": 0}, {"x": 10, "y": 20}]}, "pending_safety_checks": [], "status": "completed"}),
+        serde_json::json!({"type": "computer_call", "id": "cu_2", "call_id": "call_3", "action": {"type": "screenshot"}, "pending_safety_checks": [{"id": "sc_1", "code": "malicious_instructions", "message": "Check"}], "status": "in_progress"}),
+        serde_json::json!({"type": "computer_call_output", "call_id": "call_3", "output": {"type": "computer_screenshot", "image_url": "data:image/png;base64,AAAA"}}),
+        serde_json::json!({"type": "reasoning", "id": "rs_1", "summary": [{"type": "summary_text", "text": "Thinking"}]}),
+        serde_json::json!({"type": "item_reference", "id": "msg_1"}),
+    ];
+
+    for item in items {
+        let deserialized: Item = serde_json::from_value(item.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&deserialized).unwrap(), item);
+    }
+
+    let output: Item = FunctionCallOutput {
+        call_id: "call_1".into(),
+        output: "{}".into(),
+        ..Default::default()
+    }
+    .into();
+    assert!(matches!(output, Item::FunctionCallOutput(_)));
+}
+
+#[test]
+fn response_output_text() {
+    use async_openai::types::Response;
+
+    let response: Response = serde_json::from_value(serde_json::json!({
+        "id": "resp_1",
+        "object": "response",
+        "created_at": 1741476542,
+        "status": "completed",
+        "model": "gpt-4o",
+        "output": [
+            {"type": "reasoning", "id": "rs_1", "summary": []},
+            {"type": "message", "id": "msg_1", "role": "assistant", "status": "completed", "content": [
+                {"type": "output_text", "text": "Hello", "annotations": []},
+                {"type": "refusal", "refusal": "No"},
+            ]},
+            {"type": "function_call", "call_id": "call_1", "name": "f", "arguments": "{}"},
+            {"type": "message", "id": "msg_2", "role": "assistant", "content": [
+                {"type": "output_text", "text": ", world", "annotations": []},
+            ]},
+        ]
+    }))
+    .unwrap();
+
+    assert_eq!(response.output_text(), "Hello, world");
+}
+
+#[test]
+fn response_include() {
+    use async_openai::types::{CreateResponseArgs, Include};
+
+    let request = CreateResponseArgs::default()
+        .model("o3")
+        .input("Say hello")
+        .include([
+            Include::ReasoningEncryptedContent,
+            Include::FileSearchCallResults,
+        ])
+        .store(false)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({
+            "model": "o3",
+            "input": "Say hello",
+            "include": ["reasoning.encrypted_content", "file_search_call.results"],
+            "store": false
+        })
+    );
+}
+
+#[test]
+fn response_code_interpreter() {
+    use async_openai::types::{
+        AutoContainer, CodeInterpreterContainer, CodeInterpreterTool, Item, ResponseStreamEvent,
+        Tool,
+    };
+
+    let tools = [
+        Tool::CodeInterpreter(CodeInterpreterTool {
+            container: CodeInterpreterContainer::Auto(AutoContainer {
+                file_ids: Some(vec!["file-1".into()]),
+            }),
+        }),
+        Tool::CodeInterpreter(CodeInterpreterTool {
+            container: CodeInterpreterContainer::Id("cntr_1".into()),
+        }),
+    ];
+    assert_eq!(
+        serde_json::to_value(&tools).unwrap(),
+        serde_json::json!([
+            {"type": "code_interpreter", "container": {"type": "auto", "file_ids": ["file-1"]}},
+            {"type": "code_interpreter", "container": "cntr_1"}
+        ])
+    );
+
+    let call = serde_json::json!({
+        "type": "code_interpreter_call",
+        "id": "ci_1",
+        "container_id": "cntr_1",
+        "code": "print(6 * 7)",
+        "outputs": [
+            {"type": "logs", "logs": "42\n"},
+            {"type": "image", "url": "https://example.com/plot.png"},
+            {"type": "files", "files": [{"file_id": "cfile_1", "mime_type": "text/csv"}]}
+        ],
+        "status": "completed"
+    });
+    let item: Item = serde_json::from_value(call.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&item).unwrap(), call);
+
+    let event: ResponseStreamEvent = serde_json::from_value(serde_json::json!({
+        "type": "response.code_interpreter_call_code.delta",
+        "item_id": "ci_1",
+        "output_index": 0,
+        "delta": "print("
+    }))
+    .unwrap();
+    assert!(matches!(
+        event,
+        ResponseStreamEvent::ResponseCodeInterpreterCallCodeDelta(_)
+    ));
+}
+
+#[test]
+fn response_mcp() {
+    use async_openai::types::{
+        Item, McpAllowedTools, McpApprovalFilter, McpRequireApproval, McpToolArgs, McpToolFilter,
+        Tool,
+    };
+
+    let tool = Tool::Mcp(
+        McpToolArgs::default()
+            .server_label("deepwiki")
+            .server_url("https://mcp.deepwiki.com/mcp")
+            .allowed_tools(McpAllowedTools::List(vec!["ask_question".into()]))
+            .require_approval(McpRequireApproval::Filter(McpApprovalFilter {
+                never: Some(McpToolFilter {
+                    tool_names: vec!["ask_question".into()],
+                }),
+                ..Default::default()
+            }))
+            .build()
+            .unwrap(),
+    );
+    assert_eq!(
+        serde_json::to_value(&tool).unwrap(),
+        serde_json::json!({
+            "type": "mcp",
+            "server_label": "deepwiki",
+            "server_url": "https://mcp.deepwiki.com/mcp",
+            "allowed_tools": ["ask_question"],
+            "require_approval": {"never": {"tool_names": ["ask_question"]}}
+        })
+    );
+
+    let items = [
+        serde_json::json!({"type": "mcp_list_tools", "id": "mcpl_1", "server_label": "deepwiki", "tools": [{"name": "ask_question", "input_schema": {"type": "object"}}]}),
+        serde_json::json!({"type": "mcp_approval_request", "id": "mcpr_1", "server_label": "deepwiki", "name": "ask_question", "arguments": "{}"}),
+        serde_json::json!({"type": "mcp_approval_response", "approval_request_id": "mcpr_1", "approve": true}),
+        serde_json::json!({"type": "mcp_call", "id": "mcp_1", "server_label": "deepwiki", "name": "ask_question", "arguments": "{}", "output": "42"}),
+    ];
+    for item in items {
+        let deserialized: Item = serde_json::from_value(item.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&deserialized).unwrap(), item);
+    }
+}
+
+#[test]
+fn response_image_generation() {
+    use async_openai::types::{
+        ImageGenerationBackground, ImageGenerationOutputFormat, ImageGenerationSize,
+        ImageGenerationToolArgs, Item, ResponseStreamEvent, Tool,
+    };
+
+    let tool = Tool::ImageGeneration(
+        ImageGenerationToolArgs::default()
+            .size(ImageGenerationSize::S1536x1024)
+            .output_format(ImageGenerationOutputFormat::Webp)
+            .background(ImageGenerationBackground::Transparent)
+            .partial_images(2)
+            .build()
+            .unwrap(),
+    );
+    assert_eq!(
+        serde_json::to_value(&tool).unwrap(),
+        serde_json::json!({
+            "type": "image_generation",
+            "size": "1536x1024",
+            "output_format": "webp",
+            "background": "transparent",
+            "partial_images": 2
+        })
+    );
+
+    let item: Item = serde_json::from_value(serde_json::json!({
+        "type": "image_generation_call",
+        "id": "ig_1",
+        "result": "aGVsbG8=",
+        "status": "completed"
+    }))
+    .unwrap();
+    let Item::ImageGenerationCall(call) = item else {
+        panic!("not an image generation call");
+    };
+    assert_eq!(call.as_bytes().unwrap().as_ref(), b"hello");
+
+    let event: ResponseStreamEvent = serde_json::from_value(serde_json::json!({
+        "type": "response.image_generation_call.partial_image",
+        "item_id": "ig_1",
+        "output_index": 0,
+        "partial_image_index": 0,
+        "partial_image_b64": "aGVs"
+    }))
+    .unwrap();
+    let ResponseStreamEvent::ResponseImageGenerationCallPartialImage(partial) = event else {
+        panic!("not a partial image");
+    };
+    assert_eq!(partial.as_bytes().unwrap().as_ref(), b"hel");
+}
+
+#[test]
+fn response_local_shell() {
+    use async_openai::types::{Item, LocalShellCallOutput, Tool};
+
+    assert_eq!(
+        serde_json::to_value(Tool::LocalShell).unwrap(),
+        serde_json::json!({"type": "local_shell"})
+    );
+
+    let call = serde_json::json!({
+        "type": "local_shell_call",
+        "id": "lsh_1",
+        "call_id": "call_1",
+        "action": {"type": "exec", "command": ["ls", "-la"], "env": {"LANG": "C"}, "timeout_ms": 10000},
+        "status": "completed"
+    });
+    let item: Item = serde_json::from_value(call.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&item).unwrap(), call);
+
+    let output: Item = LocalShellCallOutput {
+        id: "call_1".into(),
+        output: "total 0".into(),
+        status: None,
+    }
+    .into();
+    assert_eq!(
+        serde_json::to_value(&output).unwrap(),
+        serde_json::json!({"type": "local_shell_call_output", "id": "call_1", "output": "total 0"})
+    );
+}
+
+#[test]
+fn response_request_settings() {
+    use async_openai::types::{CreateResponseArgs, ServiceTier};
+
+    let request = CreateResponseArgs::default()
+        .model("o3")
+        .input("Summarize the report")
+        .metadata([("user_id".to_string(), "u_1".to_string())])
+        .store(true)
+        .parallel_tool_calls(false)
+        .service_tier(ServiceTier::Flex)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({
+            "model": "o3",
+            "input": "Summarize the report",
+            "metadata": {"user_id": "u_1"},
+            "parallel_tool_calls": false,
+            "service_tier": "flex",
+            "store": true
+        })
+    );
+}
+
+#[test]
+fn response_into_input_items() {
+    use async_openai::types::{CreateResponseArgs, Input, InputMessage, InputRole, Item, Response};
+
+    let response: Response = serde_json::from_value(serde_json::json!({
+        "id": "resp_1",
+        "object": "response",
+        "created_at": 1741476542,
+        "status": "incomplete",
+        "model": "gpt-4.1",
+        "store": false,
+        "output": [
+            {"type": "function_call", "id": "fc_1", "call_id": "call_1", "name": "f", "arguments": "{}", "status": "completed"},
+            {"type": "message", "id": "msg_1", "status": "completed", "role": "assistant", "content": [{"type": "output_text", "text": "Done", "annotations": []}]},
+            {"type": "message", "id": "msg_2", "status": "in_progress", "role": "assistant", "content": []},
+        ]
+    }))
+    .unwrap();
+
+    let mut input: Vec<Item> = vec![InputMessage::new(InputRole::User, "Call f").into()];
+    input.extend(response.into_input_items());
+    let request = CreateResponseArgs::default()
+        .model("gpt-4.1")
+        .input(input)
+        .store(false)
+        .build()
+        .unwrap();
+
+    // the message in progress is skipped
+    let Input::Items(items) = &request.input else {
+        panic!("input is not a list of items");
+    };
+    assert_eq!(items.len(), 3);
+    assert!(matches!(&items[1], Item::FunctionCall(call) if call.call_id == "call_1"));
+    assert_eq!(
+        serde_json::to_value(&items[2]).unwrap()["content"][0]["text"],
+        "Done"
+    );
+}
+
+#[test]
+fn response_stateless_reasoning() {
+    use async_openai::types::{CreateResponseArgs, Input, InputMessage, InputRole, Item, Response};
+
+    let response: Response = serde_json::from_value(serde_json::json!({
+        "id": "resp_1",
+        "object": "response",
+        "created_at": 1741476542,
+        "status": "completed",
+        "model": "o3",
+        "store": false,
+        "output": [
+            {"type": "reasoning", "id": "rs_1", "summary": [], "encrypted_content": "gAAAAB"},
+            {"type": "function_call", "id": "fc_1", "call_id": "call_1", "name": "f", "arguments": "{}", "status": "completed"},
+        ]
+    }))
+    .unwrap();
+
+    let mut input: Vec<Item> = vec![InputMessage::new(InputRole::User, "Call f").into()];
+    input.extend(response.into_input_items());
+    let request = CreateResponseArgs::default()
+        .model("o3")
+        .input(input)
+        .store(false)
+        .build()
+        .unwrap();
+
+    let Input::Items(items) = &request.input else {
+        panic!("input is not a list of items");
+    };
+    assert_eq!(items.len(), 3);
+    assert_eq!(
+        serde_json::to_value(&items[1]).unwrap(),
+        serde_json::json!({"type": "reasoning", "id": "rs_1", "summary": [], "encrypted_content": "gAAAAB"})
+    );
+}
+
+#[test]
+fn conversation_items() {
+    use async_openai::types::{
+        Conversation, ConversationItemList, CreateConversationRequestArgs, InputMessage, InputRole,
+        Item,
+    };
+
+    let request = CreateConversationRequestArgs::default()
+        .items(vec![Item::from(InputMessage::new(
+            InputRole::User,
+            "Hello",
+        ))])
+        .metadata([("topic".to_string(), "demo".to_string())])
+        .build()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({
+            "items": [{"type": "message", "role": "user", "content": "Hello"}],
+            "metadata": {"topic": "demo"}
+        })
+    );
+
+    let conversation: Conversation = serde_json::from_value(serde_json::json!({
+        "id": "conv_1",
+        "object": "conversation",
+        "created_at": 1741900000,
+        "metadata": {"topic": "demo"}
+    }))
+    .unwrap();
+    assert_eq!(conversation.id, "conv_1");
+
+    let items: ConversationItemList = serde_json::from_value(serde_json::json!({
+        "object": "list",
+        "data": [{
+            "type": "message",
+            "id": "msg_1",
+            "status": "completed",
+            "role": "user",
+            "content": [{"type": "input_text", "text": "Hello"}]
+        }],
+        "first_id": "msg_1",
+        "last_id": "msg_1",
+        "has_more": false
+    }))
+    .unwrap();
+    assert!(
+        matches!(&items.data[0], Item::Message(message) if message.id.as_deref() == Some("msg_1"))
+    );
+}
+
+#[test]
+fn response_text_format() {
+    use async_openai::types::{
+        CreateResponseArgs, Response, TextConfig, TextFormat, TextJsonSchema,
+    };
+
+    let request = CreateResponseArgs::default()
+        .model("gpt-4o")
+        .input("Extract the event")
+        .text(TextConfig {
+            format: TextFormat::JsonSchema(TextJsonSchema {
+                name: "event".into(),
+                schema: serde_json::json!({"type": "object"}),
+                description: None,
+                strict: Some(true),
+            }),
+        })
+        .build()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&request).unwrap()["text"],
+        serde_json::json!({
+            "format": {"type": "json_schema", "name": "event", "schema": {"type": "object"}, "strict": true}
+        })
+    );
+
+    let response: Response = serde_json::from_value(serde_json::json!({
+        "id": "resp_1",
+        "object": "response",
+        "created_at": 1741476542,
+        "status": "completed",
+        "model": "gpt-4o",
+        "text": {"format": {"type": "text"}},
+        "output": [{
+            "type": "message",
+            "id": "msg_1",
+            "status": "completed",
+            "role": "assistant",
+            "content": [{"type": "refusal", "refusal": "I can't help with that."}]
+        }]
+    }))
+    .unwrap();
+    assert_eq!(response.text.as_ref().unwrap().format, TextFormat::Text);
+
+    let request = CreateResponseArgs::default()
+        .model("gpt-4o")
+        .input("List three colors as JSON")
+        .text(TextFormat::JsonObject)
+        .build()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&request).unwrap()["text"],
+        serde_json::json!({"format": {"type": "json_object"}})
+    );
+    assert_eq!(response.refusal(), Some("I can't help with that."));
+}
+
+#[test]
+fn response_prompt() {
+    use async_openai::types::{
+        CreateResponseArgs, InputImageArgs, PromptVariable, ResponsePromptArgs,
+    };
+
+    let request = CreateResponseArgs::default()
+        .model("gpt-4o")
+        .prompt(
+            ResponsePromptArgs::default()
+                .id("pmpt_1")
+                .version("2")
+                .variables([
+                    ("customer_name".to_string(), PromptVariable::from("Jane")),
+                    (
+                        "receipt".to_string(),
+                        InputImageArgs::default()
+                            .file_id("file_1")
+                            .build()
+                            .unwrap()
+                            .into(),
+                    ),
+                ])
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    let value = serde_json::to_value(&request).unwrap();
+    assert_eq!(value["prompt"]["id"], "pmpt_1");
+    assert_eq!(value["prompt"]["variables"]["customer_name"], "Jane");
+    assert_eq!(
+        value["prompt"]["variables"]["receipt"],
+        serde_json::json!({"type": "input_image", "detail": "auto", "file_id": "file_1"})
+    );
+
+    let variables: std::collections::HashMap<String, PromptVariable> =
+        serde_json::from_value(value["prompt"]["variables"].clone()).unwrap();
+    assert_eq!(
+        variables["customer_name"],
+        PromptVariable::Text("Jane".into())
+    );
+    assert!(matches!(variables["receipt"], PromptVariable::Content(_)));
+}
+
+#[test]
+fn response_web_search() {
+    use async_openai::types::{
+        Annotation, ContentPart, InputContent, Item, Response, Tool, WebSearchAction,
+        WebSearchContextSize, WebSearchFilters, WebSearchSource, WebSearchTool,
+        WebSearchUserLocation,
+    };
+
+    let tool = Tool::WebSearch(WebSearchTool {
+        filters: Some(WebSearchFilters {
+            allowed_domains: vec!["openai.com".into()],
+        }),
+        search_context_size: Some(WebSearchContextSize::Low),
+        user_location: Some(WebSearchUserLocation {
+            country: Some("US".into()),
+            ..Default::default()
+        }),
+    });
+    assert_eq!(
+        serde_json::to_value(&tool).unwrap(),
+        serde_json::json!({
+            "type": "web_search",
+            "filters": {"allowed_domains": ["openai.com"]},
+            "search_context_size": "low",
+            "user_location": {"type": "approximate", "country": "US"}
+        })
+    );
+
+    let response: Response = serde_json::from_value(serde_json::json!({
+        "id": "resp_1",
+        "object": "response",
+        "created_at": 1741476542,
+        "status": "completed",
+        "model": "gpt-4.1",
+        "output": [
+            {
+                "type": "web_search_call",
+                "id": "ws_1",
+                "status": "completed",
+                "action": {
+                    "type": "search",
+                    "query": "openai news",
+                    "sources": [{"type": "url", "url": "https://openai.com/news"}]
+                }
+            },
+            {
+                "type": "message",
+                "id": "msg_1",
+                "status": "completed",
+                "role": "assistant",
+                "content": [{
+                    "type": "output_text",
+                    "text": "OpenAI announced...",
+                    "annotations": [
+                        {"type": "url_citation", "url": "https://openai.com/news", "title": "News", "start_index": 0, "end_index": 19},
+                        {"type": "some_future_annotation", "index": 0}
+                    ]
+                }]
+            }
+        ]
+    }))
+    .unwrap();
+
+    let Item::WebSearchCall(call) = &response.output[0] else {
+        panic!("unexpected item {:?}", response.output[0]);
+    };
+    assert_eq!(
+        call.action,
+        Some(WebSearchAction::Search {
+            query: "openai news".into(),
+            sources: Some(vec![WebSearchSource::Url {
+                url: "https://openai.com/news".into()
+            }]),
+        })
+    );
+
+    let Item::Message(message) = &response.output[1] else {
+        panic!("unexpected item {:?}", response.output[1]);
+    };
+    let InputContent::Parts(parts) = &message.content else {
+        panic!("unexpected content {:?}", message.content);
+    };
+    let ContentPart::OutputText(text) = &parts[0] else {
+        panic!("unexpected part {:?}", parts[0]);
+    };
+    assert!(
+        matches!(&text.annotations[0], Annotation::UrlCitation(citation) if citation.title == "News")
+    );
+    assert!(matches!(&text.annotations[1], Annotation::Other(_)));
+}
+
+#[test]
+fn response_annotations() {
+    use async_openai::types::{Annotation, OutputText};
+
+    let json = serde_json::json!({
+        "text": "See the report and chart.",
+        "annotations": [
+            {"type": "file_citation", "file_id": "file_1", "filename": "report.pdf", "index": 14},
+            {"type": "file_path", "file_id": "file_2", "index": 24},
+            {"type": "container_file_citation", "container_id": "cntr_1", "file_id": "cfile_1", "filename": "chart.png", "start_index": 19, "end_index": 24},
+            {"type": "url_citation", "url": "https://example.com", "title": "Example", "start_index": 0, "end_index": 3}
+        ]
+    });
+
+    let text: OutputText = serde_json::from_value(json.clone()).unwrap();
+    assert!(
+        matches!(&text.annotations[0], Annotation::FileCitation(citation) if citation.filename == "report.pdf")
+    );
+    assert!(matches!(&text.annotations[1], Annotation::FilePath(path) if path.index == 24));
+    assert!(
+        matches!(&text.annotations[2], Annotation::ContainerFileCitation(citation) if citation.container_id == "cntr_1")
+    );
+    assert!(matches!(&text.annotations[3], Annotation::UrlCitation(_)));
+    assert_eq!(serde_json::to_value(&text).unwrap(), json);
+}
+
+#[test]
+fn response_input_from_bytes() {
+    use async_openai::types::{ImageDetail, InputFile, InputImage};
+
+    let image = InputImage::from_bytes("image/png", b"png").with_detail(ImageDetail::Low);
+    assert_eq!(
+        serde_json::to_value(&image).unwrap(),
+        serde_json::json!({"detail": "low", "image_url": "data:image/png;base64,cG5n"})
+    );
+
+    let file = InputFile::from_bytes("doc.pdf", "application/pdf", b"pdf".to_vec());
+    assert_eq!(
+        serde_json::to_value(&file).unwrap(),
+        serde_json::json!({"filename": "doc.pdf", "file_data": "data:application/pdf;base64,cGRm"})
+    );
+
+    // `detail` is optional
+    let image: InputImage =
+        serde_json::from_value(serde_json::json!({"file_id": "file_1"})).unwrap();
+    assert_eq!(image, InputImage::from_file_id("file_1"));
+}
+
+#[test]
+fn response_item_helpers() {
+    use async_openai::types::{CreateResponseArgs, Item};
+
+    let request = CreateResponseArgs::default()
+        .model("gpt-4o")
+        .input(vec![
+            Item::developer_text("Answer briefly."),
+            Item::user_text("What is in this image?"),
+            Item::image_url("https://example.com/cat.png"),
+            Item::assistant_text("A cat."),
+            Item::function_output("call_1", serde_json::json!({"temperature": 22})),
+            Item::function_output("call_2", "sunny"),
+        ])
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&request).unwrap()["input"],
+        serde_json::json!([
+            {"type": "message", "role": "developer", "content": "Answer briefly."},
+            {"type": "message", "role": "user", "content": "What is in this image?"},
+            {"type": "message", "role": "user", "content": [
+                {"type": "input_image", "detail": "auto", "image_url": "https://example.com/cat.png"}
+            ]},
+            {"type": "message", "role": "assistant", "content": "A cat."},
+            {"type": "function_call_output", "call_id": "call_1", "output": "{\"temperature\":22}"},
+            {"type": "function_call_output", "call_id": "call_2", "output": "sunny"},
+        ])
+    );
+}
+
+#[cfg(feature = "realtime")]
+#[test]
+fn realtime_client_secrets() {
+    use async_openai::types::realtime::{
+        AudioFormat, CreateTranscriptionSessionRequest, InputAudioTranscription,
+        RealtimeSessionCreateResponse, TranscriptionSessionCreateResponse,
+    };
+
+    let session: RealtimeSessionCreateResponse = serde_json::from_value(serde_json::json!({
+        "id": "sess_001",
+        "object": "realtime.session",
+        "model": "gpt-4o-realtime-preview",
+        "modalities": ["audio", "text"],
+        "instructions": "You are a friendly assistant.",
+        "voice": "alloy",
+        "input_audio_format": "pcm16",
+        "output_audio_format": "g711_ulaw",
+        "input_audio_transcription": null,
+        "turn_detection": null,
+        "tools": [],
+        "tool_choice": "none",
+        "temperature": 0.7,
+        "max_response_output_tokens": 200,
+        "client_secret": {"value": "ek_abc123", "expires_at": 1234567890}
+    }))
+    .unwrap();
+    assert_eq!(session.client_secret.value, "ek_abc123");
+    assert_eq!(
+        session.session.model.as_deref(),
+        Some("gpt-4o-realtime-preview")
+    );
+    assert!(matches!(
+        session.session.output_audio_format,
+        Some(AudioFormat::G711ULAW)
+    ));
+
+    let request = CreateTranscriptionSessionRequest {
+        input_audio_format: Some(AudioFormat::PCM16),
+        input_audio_transcription: Some(InputAudioTranscription {
+            model: Some("gpt-4o-transcribe".into()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({
+            "input_audio_format": "pcm16",
+            "input_audio_transcription": {"model": "gpt-4o-transcribe"}
+        })
+    );
+
+    let transcription: TranscriptionSessionCreateResponse =
+        serde_json::from_value(serde_json::json!({
+            "id": "sess_BBwZc7cFV3XizEyKGDCGL",
+            "object": "realtime.transcription_session",
+            "expires_at": 1742188264,
+            "modalities": ["audio", "text"],
+            "turn_detection": {"type": "server_vad", "threshold": 0.5, "prefix_padding_ms": 300, "silence_duration_ms": 200},
+            "input_audio_format": "pcm16",
+            "input_audio_transcription": {"model": "gpt-4o-transcribe", "language": null, "prompt": ""},
+            "client_secret": {"value": "ek_def456", "expires_at": 1742188264}
+        }))
+        .unwrap();
+    assert_eq!(transcription.client_secret.expires_at, 1742188264);
+}
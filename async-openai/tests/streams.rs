@@ -0,0 +1,400 @@
+//! Accumulation of streamed chat completions and responses.
+use async_openai::{
+    error::OpenAIError,
+    sink::{write_chat_text, write_response_text},
+    traits::{ChatCompletionStreamExt, ResponseStreamExt},
+    types::{
+        ChatCompletionStreamEvent, CreateChatCompletionStreamResponse, FinishReason, Response,
+        ResponseAccumulator, ResponseStreamEvent,
+    },
+};
+use futures::{stream::BoxStream, TryStreamExt};
+use serde_json::{json, Value};
+
+/// Stream of `items`, as returned by the client.
+fn stream<T: Send + 'static>(items: Vec<T>) -> BoxStream<'static, Result<T, OpenAIError>> {
+    Box::pin(futures::stream::iter(items.into_iter().map(Ok)))
+}
+
+/// Chunk of a chat completion stream with `choices`, and `usage` as in the last chunk when
+/// `stream_options.include_usage` is set.
+fn chunk(choices: Value, usage: Value) -> CreateChatCompletionStreamResponse {
+    serde_json::from_value(json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion.chunk",
+        "created": 1741476542,
+        "model": "gpt-4o",
+        "system_fingerprint": "fp_1",
+        "choices": choices,
+        "usage": usage,
+    }))
+    .unwrap()
+}
+
+/// Chunk with the `delta` of the choice `index`.
+fn delta(
+    index: u32,
+    delta: Value,
+    finish_reason: Option<&str>,
+) -> CreateChatCompletionStreamResponse {
+    chunk(
+        json!([{"index": index, "delta": delta, "finish_reason": finish_reason}]),
+        Value::Null,
+    )
+}
+
+/// Response of the Responses API with `status` and `output`.
+fn response(status: &str, output: Value) -> Value {
+    json!({
+        "id": "resp_1",
+        "object": "response",
+        "created_at": 1741476542,
+        "status": status,
+        "model": "gpt-4o",
+        "output": output,
+    })
+}
+
+#[tokio::test]
+async fn response_stream_collect_final() {
+    let message = json!({
+        "type": "message",
+        "id": "msg_1",
+        "status": "completed",
+        "role": "assistant",
+        "content": [{"type": "output_text", "text": "Hello, world", "annotations": []}]
+    });
+    let function_call = json!({
+        "type": "function_call",
+        "id": "fc_1",
+        "call_id": "call_1",
+        "name": "f",
+        "arguments": "{\"a\":1}",
+        "status": "completed"
+    });
+    let completed = response("completed", json!([message.clone(), function_call]));
+
+    let events: Vec<ResponseStreamEvent> = serde_json::from_value(json!([
+        {"type": "response.created", "response": response("in_progress", json!([]))},
+        {"type": "response.output_item.added", "output_index": 0, "item": {"type": "message", "id": "msg_1", "status": "in_progress", "role": "assistant", "content": []}},
+        {"type": "response.content_part.added", "item_id": "msg_1", "output_index": 0, "content_index": 0, "part": {"type": "output_text", "text": "", "annotations": []}},
+        {"type": "response.output_text.delta", "item_id": "msg_1", "output_index": 0, "content_index": 0, "delta": "Hello"},
+        {"type": "response.output_text.delta", "item_id": "msg_1", "output_index": 0, "content_index": 0, "delta": ", world"},
+        {"type": "response.output_item.done", "output_index": 0, "item": message},
+        {"type": "response.output_item.added", "output_index": 1, "item": {"type": "function_call", "id": "fc_1", "call_id": "call_1", "name": "f", "arguments": "", "status": "in_progress"}},
+        {"type": "response.function_call_arguments.delta", "item_id": "fc_1", "output_index": 1, "delta": "{\"a\":"},
+        {"type": "response.function_call_arguments.delta", "item_id": "fc_1", "output_index": 1, "delta": "1}"},
+        {"type": "response.completed", "response": completed},
+    ]))
+    .unwrap();
+
+    // deltas are applied before the final event
+    let mut accumulator = ResponseAccumulator::new();
+    for event in &events[..events.len() - 1] {
+        accumulator.apply(event);
+    }
+    let partial = accumulator.into_response().unwrap();
+    assert_eq!(partial.output_text(), "Hello, world");
+    assert_eq!(
+        serde_json::to_value(&partial.output[1]).unwrap()["arguments"],
+        "{\"a\":1}"
+    );
+
+    let expected: Response = serde_json::from_value(completed).unwrap();
+    let created = events[0].clone();
+    assert_eq!(stream(events).collect_final().await.unwrap(), expected);
+
+    assert!(stream(vec![created]).collect_final().await.is_err());
+}
+
+#[tokio::test]
+async fn write_response_deltas() {
+    let events: Vec<ResponseStreamEvent> = serde_json::from_value(json!([
+        {"type": "response.output_text.delta", "item_id": "msg_1", "output_index": 0, "content_index": 0, "delta": "Hello"},
+        {"type": "response.output_text.delta", "item_id": "msg_1", "output_index": 0, "content_index": 0, "delta": ", world"},
+        {"type": "response.completed", "response": response("completed", json!([]))},
+    ]))
+    .unwrap();
+
+    let mut text = vec![];
+    let response = write_response_text(stream(events), &mut text)
+        .await
+        .unwrap();
+
+    assert_eq!(text, b"Hello, world");
+    assert_eq!(response.id, "resp_1");
+
+    assert!(write_response_text(stream(vec![]), &mut text)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn chat_stream_accumulate() {
+    let chunks = vec![
+        delta(
+            0,
+            json!({"role": "assistant", "content": "Let me check"}),
+            None,
+        ),
+        delta(
+            0,
+            json!({"tool_calls": [{"index": 0, "id": "call_1", "type": "function", "function": {"name": "weather", "arguments": ""}}]}),
+            None,
+        ),
+        delta(
+            0,
+            json!({"tool_calls": [{"index": 0, "function": {"arguments": "{\"city\":"}}]}),
+            None,
+        ),
+        delta(
+            0,
+            json!({"tool_calls": [{"index": 0, "function": {"arguments": "\"Paris\"}"}}]}),
+            None,
+        ),
+        delta(
+            0,
+            json!({"tool_calls": [{"index": 1, "id": "call_2", "type": "function", "function": {"name": "time", "arguments": "{}"}}]}),
+            None,
+        ),
+        delta(0, json!({}), Some("tool_calls")),
+    ];
+
+    let events: Vec<ChatCompletionStreamEvent> =
+        stream(chunks).accumulate().try_collect().await.unwrap();
+
+    assert_eq!(
+        events[0],
+        ChatCompletionStreamEvent::TextDelta {
+            index: 0,
+            delta: "Let me check".into()
+        }
+    );
+    let ChatCompletionStreamEvent::ToolCallCompleted { tool_call, .. } = &events[1] else {
+        panic!("expected a tool call, got {:?}", events[1]);
+    };
+    assert_eq!(tool_call.id, "call_1");
+    assert_eq!(tool_call.function.name, "weather");
+    assert_eq!(tool_call.function.arguments, "{\"city\":\"Paris\"}");
+    assert!(matches!(
+        &events[2],
+        ChatCompletionStreamEvent::ToolCallCompleted { tool_call, .. } if tool_call.id == "call_2"
+    ));
+    assert_eq!(
+        events[3],
+        ChatCompletionStreamEvent::FinishReason {
+            index: 0,
+            reason: FinishReason::ToolCalls
+        }
+    );
+    let ChatCompletionStreamEvent::Completed { message, .. } = &events[4] else {
+        panic!("expected the message, got {:?}", events[4]);
+    };
+    assert_eq!(message.content.as_deref(), Some("Let me check"));
+    assert_eq!(message.tool_calls.as_ref().unwrap().len(), 2);
+    assert_eq!(events.len(), 5);
+}
+
+#[tokio::test]
+async fn chat_stream_collect_final() {
+    let chunks = vec![
+        delta(0, json!({"role": "assistant", "content": "Hello"}), None),
+        delta(0, json!({"content": " world"}), Some("stop")),
+        chunk(
+            json!([]),
+            json!({
+                "prompt_tokens": 2000,
+                "completion_tokens": 300,
+                "total_tokens": 2300,
+                "prompt_tokens_details": {"cached_tokens": 1024},
+                "completion_tokens_details": {"reasoning_tokens": 256}
+            }),
+        ),
+    ];
+
+    let response = stream(chunks.clone()).collect_final().await.unwrap();
+
+    assert_eq!(response.id, "chatcmpl-1");
+    assert_eq!(response.object, "chat.completion");
+    assert_eq!(response.system_fingerprint.as_deref(), Some("fp_1"));
+    assert_eq!(response.choices.len(), 1);
+    assert_eq!(
+        response.choices[0].message.content.as_deref(),
+        Some("Hello world")
+    );
+    assert_eq!(response.choices[0].finish_reason, Some(FinishReason::Stop));
+    let usage = response.usage.unwrap();
+    assert_eq!(usage.total_tokens, 2300);
+    assert_eq!(
+        usage.prompt_tokens_details.unwrap().cached_tokens,
+        Some(1024)
+    );
+    assert_eq!(
+        usage.completion_tokens_details.unwrap().reasoning_tokens,
+        Some(256)
+    );
+
+    let events: Vec<ChatCompletionStreamEvent> =
+        stream(chunks).accumulate().try_collect().await.unwrap();
+    assert!(matches!(
+        &events[2],
+        ChatCompletionStreamEvent::Usage(usage) if usage.prompt_tokens == 2000
+    ));
+
+    assert!(stream::<CreateChatCompletionStreamResponse>(vec![])
+        .collect_final()
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn chat_stream_audio() {
+    let chunks = vec![
+        delta(
+            0,
+            json!({"role": "assistant", "audio": {"id": "audio_1", "expires_at": 1741480142, "transcript": "Hello"}}),
+            None,
+        ),
+        delta(
+            0,
+            json!({"audio": {"data": "UklG", "transcript": " there"}}),
+            None,
+        ),
+        delta(0, json!({"audio": {"data": "RiQA"}}), None),
+    ];
+
+    let events: Vec<ChatCompletionStreamEvent> =
+        stream(chunks).accumulate().try_collect().await.unwrap();
+
+    assert_eq!(
+        events[..4],
+        [
+            ChatCompletionStreamEvent::AudioTranscriptDelta {
+                index: 0,
+                delta: "Hello".into()
+            },
+            ChatCompletionStreamEvent::AudioDelta {
+                index: 0,
+                data: "UklG".into()
+            },
+            ChatCompletionStreamEvent::AudioTranscriptDelta {
+                index: 0,
+                delta: " there".into()
+            },
+            ChatCompletionStreamEvent::AudioDelta {
+                index: 0,
+                data: "RiQA".into()
+            },
+        ]
+    );
+    let ChatCompletionStreamEvent::Completed { message, .. } = &events[4] else {
+        panic!("expected the message, got {:?}", events[4]);
+    };
+    let audio = message.audio.as_ref().unwrap();
+    assert_eq!(audio.id, "audio_1");
+    assert_eq!(audio.expires_at, 1741480142);
+    assert_eq!(audio.data, "UklGRiQA");
+    assert_eq!(audio.transcript, "Hello there");
+}
+
+#[tokio::test]
+async fn chat_stream_choices() {
+    // the deltas of the 2 choices of `n: 2` are interleaved
+    let chunks = vec![
+        delta(1, json!({"role": "assistant", "content": "Sure"}), None),
+        delta(0, json!({"role": "assistant", "content": "Let me"}), None),
+        delta(
+            1,
+            json!({"tool_calls": [{"index": 0, "id": "call_b", "type": "function", "function": {"name": "time", "arguments": "{"}}]}),
+            None,
+        ),
+        delta(
+            0,
+            json!({"tool_calls": [{"index": 0, "id": "call_a", "type": "function", "function": {"name": "weather", "arguments": "{"}}]}),
+            None,
+        ),
+        delta(
+            0,
+            json!({"tool_calls": [{"index": 0, "function": {"arguments": "}"}}]}),
+            None,
+        ),
+        delta(
+            1,
+            json!({"tool_calls": [{"index": 0, "function": {"arguments": "}"}}]}),
+            None,
+        ),
+        delta(0, json!({}), Some("tool_calls")),
+        delta(1, json!({}), Some("tool_calls")),
+    ];
+
+    let events: Vec<ChatCompletionStreamEvent> = stream(chunks.clone())
+        .accumulate()
+        .try_collect()
+        .await
+        .unwrap();
+    let tool_calls: Vec<(u32, &str, &str)> = events
+        .iter()
+        .filter_map(|event| match event {
+            ChatCompletionStreamEvent::ToolCallCompleted { index, tool_call } => Some((
+                *index,
+                tool_call.id.as_str(),
+                tool_call.function.arguments.as_str(),
+            )),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(tool_calls, [(0, "call_a", "{}"), (1, "call_b", "{}")]);
+
+    let response = stream(chunks).collect_final().await.unwrap();
+    assert_eq!(response.first_text(), Some("Let me"));
+    assert_eq!(response.texts(), ["Let me", "Sure"]);
+    assert_eq!(
+        response
+            .choice(1)
+            .unwrap()
+            .message
+            .tool_calls
+            .as_ref()
+            .unwrap()[0]
+            .id,
+        "call_b"
+    );
+}
+
+#[tokio::test]
+async fn write_chat_deltas_of_first_choice() {
+    let chunks = vec![
+        chunk(
+            json!([
+                {"index": 0, "delta": {"role": "assistant", "content": "Hello"}},
+                {"index": 1, "delta": {"role": "assistant", "content": "Hi"}},
+            ]),
+            Value::Null,
+        ),
+        chunk(
+            json!([
+                {"index": 0, "delta": {"content": ", world"}, "finish_reason": "stop"},
+                {"index": 1, "delta": {"content": " there"}, "finish_reason": "stop"},
+            ]),
+            Value::Null,
+        ),
+        chunk(
+            json!([]),
+            json!({"prompt_tokens": 5, "completion_tokens": 4, "total_tokens": 9}),
+        ),
+    ];
+
+    let mut text = vec![];
+    let response = write_chat_text(stream(chunks), &mut text).await.unwrap();
+
+    assert_eq!(text, b"Hello, world");
+    assert_eq!(response.id, "chatcmpl-1");
+    assert_eq!(response.choices.len(), 2);
+    assert_eq!(
+        response.choices[1].message.content.as_deref(),
+        Some("Hi there")
+    );
+    assert_eq!(response.usage.unwrap().total_tokens, 9);
+
+    assert!(write_chat_text(stream(vec![]), &mut text).await.is_err());
+}
@@ -0,0 +1,71 @@
+//! Rewriting JSON Schemas into strict mode.
+use async_openai::schema::strict;
+use serde_json::json;
+
+#[test]
+fn optional_properties_become_required_and_nullable() {
+    let schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Event",
+        "type": "object",
+        "properties": {
+            "name": { "type": "string", "default": "" },
+            "attendees": { "type": ["integer", "null"], "format": "uint32", "minimum": 0 },
+            "kind": { "$ref": "#/$defs/Kind", "description": "Kind of event" },
+            "date": { "type": "string", "format": "date" }
+        },
+        "required": ["name", "date"],
+        "$defs": {
+            "Kind": { "oneOf": [{ "const": "meeting" }, { "const": "call" }] }
+        }
+    });
+
+    let expected = json!({
+        "title": "Event",
+        "type": "object",
+        "properties": {
+            "name": { "type": "string" },
+            "attendees": { "type": ["integer", "null"], "minimum": 0 },
+            "kind": { "anyOf": [{ "$ref": "#/$defs/Kind" }, { "type": "null" }] },
+            "date": { "type": "string", "format": "date" }
+        },
+        "additionalProperties": false,
+        "$defs": {
+            "Kind": { "anyOf": [{ "const": "meeting" }, { "const": "call" }] }
+        }
+    });
+
+    let mut schema = strict(schema).unwrap();
+
+    // order of properties depends on serde_json's `preserve_order` feature
+    let mut required: Vec<String> =
+        serde_json::from_value(schema.as_object_mut().unwrap().remove("required").unwrap())
+            .unwrap();
+    required.sort();
+    assert_eq!(required, ["attendees", "date", "kind", "name"]);
+
+    assert_eq!(schema, expected);
+}
+
+#[test]
+fn unrepresentable_schemas_fail_with_location() {
+    let map = json!({
+        "type": "object",
+        "properties": {
+            "tags": { "type": "object", "additionalProperties": { "type": "string" } }
+        }
+    });
+    let error = strict(map).unwrap_err().to_string();
+    assert!(error.contains("#/properties/tags"), "{error}");
+
+    let any = json!({ "type": "object", "properties": { "value": true } });
+    assert!(strict(any).is_err());
+
+    let all_of = json!({
+        "type": "object",
+        "properties": { "a": { "allOf": [{ "type": "string" }, { "minLength": 1 }] } }
+    });
+    assert!(strict(all_of).is_err());
+
+    assert!(strict(json!({ "type": "string" })).is_err());
+}
@@ -0,0 +1,62 @@
+//! Rendering prompt templates.
+use async_openai::template::PromptTemplate;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[test]
+fn render_variables_and_partials() {
+    let signature = PromptTemplate::new("-- {{name}}").unwrap();
+    let template = PromptTemplate::new("Hello {{ user.name }}, \\{{literal}} {{> signature}}")
+        .unwrap()
+        .with_partial("signature", signature);
+
+    let rendered = template
+        .render(&json!({ "user": { "name": "{{name}}" }, "name": "Ada" }))
+        .unwrap();
+
+    // values are not expanded
+    assert_eq!(rendered, "Hello {{name}}, {{literal}} -- Ada");
+}
+
+#[test]
+fn render_errors() {
+    assert!(PromptTemplate::new("Hello {{name").is_err());
+    assert!(PromptTemplate::new("Hello {{}}").is_err());
+
+    let template = PromptTemplate::new("{{a}} {{> b}}").unwrap();
+    assert!(template.render(&json!({})).is_err());
+    assert!(template.render(&json!({ "a": 1 })).is_err());
+
+    let recursive = PromptTemplate::new("{{> r}}").unwrap();
+    let recursive = recursive.clone().with_partial("r", recursive);
+    assert!(recursive.render(&json!({})).is_err());
+}
+
+#[test]
+fn template_from_config() {
+    #[derive(Serialize, Deserialize)]
+    struct Config {
+        system: PromptTemplate,
+    }
+
+    #[derive(Serialize)]
+    struct Variables {
+        count: u32,
+    }
+
+    let config: Config =
+        serde_json::from_str(r#"{"system": "Answer in {{count}} words."}"#).unwrap();
+    let message = config
+        .system
+        .render_system(&Variables { count: 3 })
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(message).unwrap(),
+        json!({ "content": "Answer in 3 words." })
+    );
+    assert_eq!(
+        serde_json::to_string(&config).unwrap(),
+        r#"{"system":"Answer in {{count}} words."}"#
+    );
+}
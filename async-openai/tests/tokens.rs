@@ -0,0 +1,163 @@
+//! Estimates of prompt tokens.
+use async_openai::{
+    history::{fit_to_context, ChatHistory, TruncationStrategy},
+    tokens::{count_tokens, image_tokens, TokenCount, Tokenizer},
+    types::{
+        ChatCompletionRequestMessage, ChatCompletionRequestMessageContentPartImageArgs,
+        ChatCompletionRequestSystemMessage, ChatCompletionRequestUserMessage,
+        ChatCompletionRequestUserMessageArgs, ChatCompletionRequestUserMessageContent,
+        ChatCompletionRequestUserMessageContentPart, CreateChatCompletionRequestArgs,
+        CreateResponseArgs, EmbeddingInput, ImageDetail, ImageUrlArgs, InputMessage, InputRole,
+        Item, ModelId,
+    },
+    Client,
+};
+use base64::Engine;
+
+#[test]
+fn image_tile_accounting() {
+    // examples of https://platform.openai.com/docs/guides/vision#calculating-costs
+    assert_eq!(image_tokens(1024, 1024), 765);
+    assert_eq!(image_tokens(2048, 4096), 1105);
+    assert_eq!(image_tokens(256, 256), 255);
+}
+
+#[test]
+fn chat_request_token_count() {
+    let tokenizer = Tokenizer::for_model("gpt-4o").unwrap();
+
+    let text = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("hello")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+
+    // message overhead, content, reply priming
+    assert_eq!(text.token_count(&tokenizer), 3 + 1 + 3);
+
+    // header of a 1024x1024 PNG
+    let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    png.extend(1024u32.to_be_bytes());
+    png.extend(1024u32.to_be_bytes());
+    let url = format!(
+        "data:image/png;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(png)
+    );
+
+    let image = |detail: ImageDetail| {
+        ChatCompletionRequestMessageContentPartImageArgs::default()
+            .image_url(
+                ImageUrlArgs::default()
+                    .url(&url)
+                    .detail(detail)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()
+    };
+
+    let images: ChatCompletionRequestMessage = ChatCompletionRequestUserMessageArgs::default()
+        .content(ChatCompletionRequestUserMessageContent::Array(vec![
+            ChatCompletionRequestUserMessageContentPart::ImageUrl(image(ImageDetail::High)),
+            ChatCompletionRequestUserMessageContentPart::ImageUrl(image(ImageDetail::Low)),
+        ]))
+        .build()
+        .unwrap()
+        .into();
+
+    assert_eq!(images.token_count(&tokenizer), 3 + 765 + 85);
+}
+
+#[test]
+fn embedding_input_token_count() {
+    let tokenizer = Tokenizer::for_model("text-embedding-3-small").unwrap();
+
+    assert_eq!(
+        EmbeddingInput::IntegerArray(vec![1, 2, 3]).token_count(&tokenizer),
+        3
+    );
+    assert_eq!(
+        EmbeddingInput::StringArray(vec!["hello".into(), "hello".into()]).token_count(&tokenizer),
+        2
+    );
+}
+
+#[test]
+fn history_truncation() {
+    let tokenizer = Tokenizer::for_model("gpt-4o").unwrap();
+    let user = |text: &str| -> ChatCompletionRequestMessage {
+        ChatCompletionRequestUserMessage::from(text).into()
+    };
+
+    // each message takes 3 + 1 tokens, and the reply 3
+    let mut history = ChatHistory::new(tokenizer, 3 + 4 * 3);
+    history.push(ChatCompletionRequestSystemMessage::from("system"));
+    history.extend(["one", "two", "three"].map(user));
+
+    assert_eq!(
+        history.to_messages(),
+        [
+            ChatCompletionRequestSystemMessage::from("system").into(),
+            user("two"),
+            user("three")
+        ]
+    );
+
+    let history = history.with_strategy(TruncationStrategy::DropOldest);
+    assert_eq!(history.to_messages(), ["one", "two", "three"].map(user));
+    assert_eq!(history.messages().len(), 4);
+
+    assert!(ChatHistory::for_model(ModelId::Gpt4oMini, 16_384).is_ok());
+    assert!(ChatHistory::for_model("my-model", 1_000).is_err());
+}
+
+#[test]
+fn response_request_token_count() {
+    let text = CreateResponseArgs::default()
+        .model("gpt-4o")
+        .instructions("hello")
+        .input("hello")
+        .build()
+        .unwrap();
+
+    // instructions and input like messages, reply priming
+    assert_eq!(count_tokens("gpt-4o", &text).unwrap(), 4 + 4 + 3);
+
+    let items: Vec<Item> = vec![
+        InputMessage::new(InputRole::User, "hello").into(),
+        InputMessage::new(InputRole::Assistant, "hello").into(),
+    ];
+    assert_eq!(count_tokens("gpt-4o", &items).unwrap(), 4 + 4);
+}
+
+#[tokio::test]
+async fn fit_request_to_context() {
+    let user = |text: &str| -> ChatCompletionRequestMessage {
+        ChatCompletionRequestUserMessage::from(text).into()
+    };
+
+    // each message takes 3 + 1 tokens, and the reply 3
+    let mut request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4")
+        .max_completion_tokens(8_192 - 3 - 4 * 2_u32)
+        .messages(["one", "two", "three"].map(user))
+        .build()
+        .unwrap();
+
+    fit_to_context(&Client::new(), &mut request, TruncationStrategy::DropOldest)
+        .await
+        .unwrap();
+    assert_eq!(request.messages, ["two", "three"].map(user));
+
+    request.model = "my-model".into();
+    assert!(
+        fit_to_context(&Client::new(), &mut request, TruncationStrategy::DropOldest)
+            .await
+            .is_err()
+    );
+}
@@ -0,0 +1,215 @@
+//! Tools generated by the `#[tool]` attribute.
+use async_openai::{
+    tools::{tool, Tool, ToolParameters, ToolSet},
+    types::{
+        AssistantTools, ChatCompletionMessageToolCall, ChatCompletionRequestMessage,
+        ChatCompletionRequestToolMessageContent, ChatCompletionToolType, FunctionCall,
+        FunctionObjectArgs, FunctionToolCall, Item, RunToolCallObject, Tool as ResponseTool,
+    },
+};
+use serde_json::json;
+
+/// Add two numbers.
+///
+/// # Arguments
+///
+/// * `a` - The first number
+/// * `b` - The second number,
+///   defaults to one
+#[tool]
+async fn add(a: i64, b: Option<i64>) -> Result<i64, String> {
+    a.checked_add(b.unwrap_or(1))
+        .ok_or_else(|| "overflow".to_string())
+}
+
+/// Current time.
+#[tool(name = "now")]
+async fn current_time() -> Result<String, String> {
+    Ok("noon".into())
+}
+
+/// Multiply two numbers.
+#[tool]
+struct MultiplyNumbers {
+    /// The first number
+    a: i64,
+    /// The second number
+    b: i64,
+}
+
+fn tool_call(name: &str, arguments: &str) -> ChatCompletionMessageToolCall {
+    ChatCompletionMessageToolCall {
+        id: "call_1".into(),
+        r#type: ChatCompletionToolType::Function,
+        function: FunctionCall {
+            name: name.into(),
+            arguments: arguments.into(),
+        },
+    }
+}
+
+#[test]
+fn tool_definition() {
+    let definition = AddTool.definition();
+
+    assert_eq!(definition.name, "add");
+    assert_eq!(definition.description.as_deref(), Some("Add two numbers."));
+
+    let parameters = definition.parameters.unwrap();
+    assert_eq!(parameters["type"], "object");
+    assert_eq!(parameters["required"], json!(["a"]));
+    assert_eq!(
+        parameters["properties"]["b"]["description"],
+        "The second number, defaults to one"
+    );
+
+    assert_eq!(CurrentTimeTool.definition().name, "now");
+}
+
+#[tokio::test]
+async fn tool_dispatch() {
+    let tool_set = ToolSet::new().with_tool(AddTool).with_tool(CurrentTimeTool);
+
+    assert_eq!(tool_set.chat_tools().len(), 2);
+    assert_eq!(tool_set.call("add", r#"{"a": 2}"#).await.unwrap(), "3");
+    assert_eq!(tool_set.call("now", "").await.unwrap(), "noon");
+    assert!(tool_set.call("add", r#"{"a": "2"}"#).await.is_err());
+    assert!(tool_set
+        .call("add", &format!(r#"{{"a": {}}}"#, i64::MAX))
+        .await
+        .is_err());
+    assert!(tool_set.call("sub", "{}").await.is_err());
+
+    let messages = tool_set
+        .dispatch_all(&[
+            tool_call("add", r#"{"a": 1, "b": 2}"#),
+            tool_call("sub", "{}"),
+        ])
+        .await;
+
+    let contents: Vec<_> = messages
+        .into_iter()
+        .map(|message| match message {
+            ChatCompletionRequestMessage::Tool(message) => match message.content {
+                ChatCompletionRequestToolMessageContent::Text(text) => text,
+                content => panic!("unexpected content {content:?}"),
+            },
+            message => panic!("unexpected message {message:?}"),
+        })
+        .collect();
+
+    assert_eq!(contents[0], "3");
+    assert!(contents[1].starts_with("error: tool `sub` failed"));
+}
+
+#[tokio::test]
+async fn function_call_dispatch() {
+    let tool_set = ToolSet::new().with_tool(AddTool).with_fn(
+        FunctionObjectArgs::default()
+            .name("echo")
+            .description("Echo the arguments.")
+            .build()
+            .unwrap(),
+        |arguments| async move { Ok(arguments) },
+    );
+
+    let tools = tool_set.response_tools();
+    assert_eq!(tools.len(), 2);
+    match &tools[1] {
+        ResponseTool::Function(tool) => {
+            assert_eq!(tool.name, "echo");
+            assert_eq!(tool.parameters["type"], "object");
+        }
+        tool => panic!("unexpected tool {tool:?}"),
+    }
+
+    let function_call = |call_id: &str, name: &str, arguments: &str| FunctionToolCall {
+        id: None,
+        call_id: call_id.into(),
+        name: name.into(),
+        arguments: arguments.into(),
+        status: None,
+    };
+
+    let items = tool_set
+        .dispatch_function_calls(&[
+            function_call("call_1", "add", r#"{"a": 1}"#),
+            function_call("call_2", "echo", r#"{"text": "hi"}"#),
+            function_call("call_3", "sub", "{}"),
+        ])
+        .await;
+
+    let outputs: Vec<_> = items
+        .into_iter()
+        .map(|item| match item {
+            Item::FunctionCallOutput(output) => (output.call_id, output.output),
+            item => panic!("unexpected item {item:?}"),
+        })
+        .collect();
+
+    assert_eq!(outputs[0], ("call_1".to_string(), "2".to_string()));
+    assert_eq!(
+        outputs[1],
+        ("call_2".to_string(), r#"{"text": "hi"}"#.to_string())
+    );
+    assert_eq!(outputs[2].0, "call_3");
+    assert!(outputs[2].1.starts_with("error: tool `sub` failed"));
+}
+
+#[tokio::test]
+async fn tool_parameters_handler() {
+    let definition = MultiplyNumbers::definition();
+    assert_eq!(definition.name, "multiply_numbers");
+    assert_eq!(
+        definition.description.as_deref(),
+        Some("Multiply two numbers.")
+    );
+
+    let parameters = definition.parameters.unwrap();
+    assert_eq!(parameters["required"], json!(["a", "b"]));
+    assert_eq!(
+        parameters["properties"]["a"]["description"],
+        "The first number"
+    );
+    assert!(parameters.get("description").is_none());
+
+    let tool_set = ToolSet::new().with_handler(|numbers: MultiplyNumbers| async move {
+        numbers.a.checked_mul(numbers.b).ok_or("overflow")
+    });
+
+    assert_eq!(
+        tool_set
+            .call("multiply_numbers", r#"{"a": 2, "b": 3}"#)
+            .await
+            .unwrap(),
+        "6"
+    );
+    assert!(tool_set
+        .call("multiply_numbers", r#"{"a": 2}"#)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn run_tool_call_dispatch() {
+    let tool_set = ToolSet::new().with_tool(AddTool);
+
+    match &tool_set.assistant_tools()[..] {
+        [AssistantTools::Function(tool)] => assert_eq!(tool.function.name, "add"),
+        tools => panic!("unexpected tools {tools:?}"),
+    }
+
+    let outputs = tool_set
+        .dispatch_all(&[RunToolCallObject {
+            id: "call_1".into(),
+            r#type: "function".into(),
+            function: FunctionCall {
+                name: "add".into(),
+                arguments: r#"{"a": 4}"#.into(),
+            },
+        }])
+        .await;
+
+    assert_eq!(outputs[0].tool_call_id.as_deref(), Some("call_1"));
+    assert_eq!(outputs[0].output.as_deref(), Some("5"));
+}